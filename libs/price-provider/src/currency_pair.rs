@@ -11,9 +11,14 @@ use frame_support::{traits::Get, CloneNoBound, DebugNoBound, EqNoBound, PartialE
 use serde::{Deserialize, Serialize};
 
 use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
-use scale_info::{prelude::string::String, TypeInfo};
+use scale_info::{
+    prelude::{string::String, vec::Vec},
+    TypeInfo,
+};
 pub use utils::{BoundedString, BoundedStringConversionError, LikeString};
 
+use crate::namespace::{NamespacedSymbol, NamespacedSymbolError};
+
 /// Represents from/to currency symbol pair.
 /// Used to express price relationship between two currencies.
 /// Given some from/to pair price `N` should be considered as `1 x from = N x to`.
@@ -26,6 +31,16 @@ pub struct CurrencySymbolPair<From, To> {
     to: To,
 }
 
+/// Orientation of the found currency pair relative to the requested one.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PairOrientation {
+    /// The pair has the requested orientation.
+    Direct,
+    /// The pair is the swapped requested pair, so its price must be inverted.
+    Inverted,
+}
+
 /// Represents from/to currency pair built atop of two types returning `&'static str`.
 /// Used to express price relationship between two currencies.
 /// Given some from/to pair price `N` should be considered as `1 x from = N x to`.
@@ -35,6 +50,19 @@ pub struct StaticCurrencySymbolPair<From: Get<&'static str>, To: Get<&'static st
     _marker: PhantomData<(From, To)>,
 }
 
+/// Wraps `Get<CurrencySymbolPair<&'static str, &'static str>>` to be used where an optional bound pair is expected.
+/// Use `()` instead to express the absence of the bound pair.
+#[derive(Debug, TypeInfo)]
+#[scale_info(skip_type_params(P))]
+pub struct SomeCurrencySymbolPair<P: Get<CurrencySymbolPair<&'static str, &'static str>>> {
+    _marker: PhantomData<P>,
+}
+
+/// Plain representation of the currency pair as raw `from`/`to` symbol bytes.
+/// Has the same encoding as `CurrencySymbolPair<String, String>` and `BoundedCurrencySymbolPair<String, String, _>`
+/// while its type info is understood by any decoder.
+pub type PlainCurrencySymbolPair = (Vec<u8>, Vec<u8>);
+
 /// Stores `CurrencySymbolPair` and limits each of the symbols by the max length in bytes - `MaxSymBytesLen`.
 #[derive(TypeInfo, CloneNoBound, PartialEqNoBound, EqNoBound, DebugNoBound)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -74,6 +102,13 @@ impl<From: LikeString, To: LikeString> CurrencySymbolPair<From, To> {
         CurrencySymbolPair::new(from, (map)(to))
     }
 
+    /// Swaps `from` and `to` members returning the inverted pair, e.g. `USD/DOCK` for `DOCK/USD`.
+    pub fn swap(self) -> CurrencySymbolPair<To, From> {
+        let Self { from, to } = self;
+
+        CurrencySymbolPair::new(to, from)
+    }
+
     /// Translates given currency pair over `from` member and attempts to create a new `CurrencySymbolPair`.
     pub fn translate_over_from<R: LikeString, E, F: FnOnce(From) -> Result<R, E>>(
         self,
@@ -95,12 +130,91 @@ impl<From: LikeString, To: LikeString> CurrencySymbolPair<From, To> {
     }
 }
 
+impl<From: LikeString + AsRef<str>, To: LikeString + AsRef<str>> CurrencySymbolPair<From, To> {
+    /// Parses `from` member as a namespaced symbol, see [`crate::namespace`] for details.
+    pub fn namespaced_from(&self) -> Result<NamespacedSymbol<'_>, NamespacedSymbolError> {
+        NamespacedSymbol::parse(self.from.as_ref())
+    }
+
+    /// Parses `to` member as a namespaced symbol, see [`crate::namespace`] for details.
+    pub fn namespaced_to(&self) -> Result<NamespacedSymbol<'_>, NamespacedSymbolError> {
+        NamespacedSymbol::parse(self.to.as_ref())
+    }
+
+    /// Returns namespace of the pair which is the namespace of its `from` member.
+    pub fn namespace(&self) -> Result<Option<&str>, NamespacedSymbolError> {
+        self.namespaced_from().map(|from| from.namespace())
+    }
+
+    /// Returns the pair with namespaces stripped from both members.
+    pub fn logical_pair(
+        &self,
+    ) -> Result<CurrencySymbolPair<String, String>, NamespacedSymbolError> {
+        Ok(CurrencySymbolPair::new(
+            self.namespaced_from()?.symbol().into(),
+            self.namespaced_to()?.symbol().into(),
+        ))
+    }
+
+    /// Returns `true` if both pairs denote the same currencies regardless of their namespaces.
+    pub fn is_same_logical_pair<OtherFrom, OtherTo>(
+        &self,
+        other: &CurrencySymbolPair<OtherFrom, OtherTo>,
+    ) -> bool
+    where
+        OtherFrom: LikeString + AsRef<str>,
+        OtherTo: LikeString + AsRef<str>,
+    {
+        matches!(
+            (self.logical_pair(), other.logical_pair()),
+            (Ok(this), Ok(other)) if this == other
+        )
+    }
+}
+
+impl CurrencySymbolPair<String, String> {
+    /// Instantiates new `CurrencySymbolPair` placing `from` symbol into the given namespace,
+    /// e.g. `chainlink:BTC`/`USD`. The namespace denotes the source of the price.
+    pub fn namespaced(
+        namespace: Option<&str>,
+        from: &str,
+        to: &str,
+    ) -> Result<Self, NamespacedSymbolError> {
+        let from = NamespacedSymbol::new(namespace, from)?;
+        let to = NamespacedSymbol::new(None, to)?;
+
+        Ok(Self::new(from.to_raw(), to.to_raw()))
+    }
+}
+
 impl<S: LikeString> CurrencySymbolPair<S, S> {
     /// Maps given currency pair over `from`/`to` members and creates a new `CurrencySymbolPair`.
     pub fn map_pair<R: LikeString, F: FnMut(S) -> R>(self, mut map: F) -> CurrencySymbolPair<R, R> {
         self.map_over_from(&mut map).map_over_to(map)
     }
 
+    /// Returns `true` if the pair has canonical orientation, i.e. its `from` member isn't greater
+    /// than `to` lexicographically. Symmetric feeds can store only canonical pairs to halve the storage.
+    pub fn is_canonical(&self) -> bool
+    where
+        S: Ord,
+    {
+        self.from <= self.to
+    }
+
+    /// Returns the pair in canonical orientation along with the orientation of the original pair
+    /// relative to the canonical one.
+    pub fn into_canonical(self) -> (Self, PairOrientation)
+    where
+        S: Ord,
+    {
+        if self.is_canonical() {
+            (self, PairOrientation::Direct)
+        } else {
+            (self.swap(), PairOrientation::Inverted)
+        }
+    }
+
     /// Translates given currency pair over `from`/`to` members and attempts to create a new `CurrencySymbolPair`.
     pub fn translate_pair<R: LikeString, E, F: FnMut(S) -> Result<R, E>>(
         self,
@@ -133,6 +247,38 @@ impl<From: LikeString, To: LikeString, MaxSymBytesLen: Get<u32>> Encode
     }
 }
 
+impl<From, To, MaxSymBytesLen> BoundedCurrencySymbolPair<From, To, MaxSymBytesLen>
+where
+    From: LikeString + Decode + 'static,
+    To: LikeString + Decode + 'static,
+    MaxSymBytesLen: Get<u32>,
+{
+    /// Decodes the encoded `BoundedCurrencySymbolPair` without checking symbols against `MaxSymBytesLen`.
+    /// Returns an unbounded `CurrencySymbolPair`, so entries stored before `MaxSymBytesLen` was lowered
+    /// can still be read and cleaned up.
+    ///
+    /// **Must be used only inside of the migrations and pruning routines.**
+    pub fn decode_unchecked_len<I: codec::Input>(
+        input: &mut I,
+    ) -> Result<CurrencySymbolPair<From, To>, codec::Error> {
+        CurrencySymbolPair::<From, To>::decode(input)
+    }
+}
+
+impl<From, To, MaxSymBytesLen> BoundedCurrencySymbolPair<From, To, MaxSymBytesLen>
+where
+    From: LikeString,
+    To: LikeString,
+    MaxSymBytesLen: Get<u32>,
+{
+    /// Swaps `from` and `to` members returning the inverted pair, e.g. `USD/DOCK` for `DOCK/USD`.
+    pub fn swap(self) -> BoundedCurrencySymbolPair<To, From, MaxSymBytesLen> {
+        let CurrencySymbolPair { from, to } = self.0;
+
+        BoundedCurrencySymbolPair(CurrencySymbolPair { from: to, to: from })
+    }
+}
+
 impl<From, To, MaxSymBytesLen> Decode for BoundedCurrencySymbolPair<From, To, MaxSymBytesLen>
 where
     From: LikeString + Decode + 'static,
@@ -187,6 +333,22 @@ impl<FromTy: LikeString, To: LikeString> From<(FromTy, To)> for CurrencySymbolPa
     }
 }
 
+impl<FromTy: LikeString, To: LikeString> From<CurrencySymbolPair<FromTy, To>> for (FromTy, To) {
+    fn from(CurrencySymbolPair { from, to }: CurrencySymbolPair<FromTy, To>) -> Self {
+        (from, to)
+    }
+}
+
+impl<MaxSymBytesLen: Get<u32>> From<BoundedCurrencySymbolPair<String, String, MaxSymBytesLen>>
+    for PlainCurrencySymbolPair
+{
+    fn from(pair: BoundedCurrencySymbolPair<String, String, MaxSymBytesLen>) -> Self {
+        let (from, to) = CurrencySymbolPair::from(pair).into();
+
+        (String::into_bytes(from), String::into_bytes(to))
+    }
+}
+
 impl<From: Get<&'static str>, To: Get<&'static str>>
     Get<CurrencySymbolPair<&'static str, &'static str>> for StaticCurrencySymbolPair<From, To>
 {
@@ -195,6 +357,14 @@ impl<From: Get<&'static str>, To: Get<&'static str>>
     }
 }
 
+impl<P: Get<CurrencySymbolPair<&'static str, &'static str>>>
+    Get<Option<CurrencySymbolPair<&'static str, &'static str>>> for SomeCurrencySymbolPair<P>
+{
+    fn get() -> Option<CurrencySymbolPair<&'static str, &'static str>> {
+        Some(P::get())
+    }
+}
+
 impl<From, To> Display for CurrencySymbolPair<From, To>
 where
     From: LikeString + Display,
@@ -220,6 +390,24 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn price_record_codec_compatibility() {
+        let pair = CurrencySymbolPair::new("DOCK", "USD")
+            .map_pair(ToOwned::to_owned)
+            .checked_into::<BoundedCurrencySymbolPair<String, String, ConstU32<4>>>()
+            .unwrap();
+        let encoded = pair.encode();
+
+        assert_eq!(
+            price_record_codec::CurrencySymbolPair::<String, String>::decode(&mut &encoded[..]),
+            Ok(price_record_codec::CurrencySymbolPair::new("DOCK", "USD"))
+        );
+        assert_eq!(
+            price_record_codec::CurrencySymbolPair::<String, String>::new("DOCK", "USD").encode(),
+            encoded
+        );
+    }
+
     #[test]
     fn debug() {
         assert_eq!(
@@ -370,6 +558,27 @@ mod tests {
         assert_eq!(pair.map_pair(ToOwned::to_owned), decoded.into());
     }
 
+    #[test]
+    fn decode_unchecked_len() {
+        let pair = CurrencySymbolPair::new("ABCDEF", "X");
+        let encoded = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<6>>>()
+            .unwrap()
+            .encode();
+
+        assert!(
+            BoundedCurrencySymbolPair::<String, String, ConstU32<3>>::decode(&mut &encoded[..])
+                .is_err()
+        );
+        assert_eq!(
+            BoundedCurrencySymbolPair::<String, String, ConstU32<3>>::decode_unchecked_len(
+                &mut &encoded[..]
+            ),
+            Ok(pair.map_pair(ToOwned::to_owned))
+        );
+    }
+
     #[test]
     fn encode_decode_second_longer() {
         let pair = CurrencySymbolPair::new("X", "ABCDEF");
@@ -483,6 +692,85 @@ mod tests {
         assert_eq!(pair, decoded_pair.map_pair(A));
     }
 
+    #[test]
+    fn namespaced() {
+        let chainlink = CurrencySymbolPair::namespaced(Some("chainlink"), "BTC", "USD").unwrap();
+        let dex = CurrencySymbolPair::new("dex:BTC", "USD");
+        let plain = CurrencySymbolPair::new("BTC", "USD");
+
+        assert_eq!(
+            chainlink,
+            CurrencySymbolPair::new("chainlink:BTC".to_string(), "USD".to_string())
+        );
+        assert_eq!(chainlink.namespace(), Ok(Some("chainlink")));
+        assert_eq!(plain.namespace(), Ok(None));
+        assert_eq!(dex.namespaced_from().unwrap().symbol(), "BTC");
+        assert_eq!(
+            dex.logical_pair(),
+            Ok(CurrencySymbolPair::new(
+                "BTC".to_string(),
+                "USD".to_string()
+            ))
+        );
+        assert!(chainlink.is_same_logical_pair(&dex));
+        assert!(dex.is_same_logical_pair(&plain));
+        assert!(!dex.is_same_logical_pair(&CurrencySymbolPair::new("dex:ETH", "USD")));
+        assert!(!dex.is_same_logical_pair(&CurrencySymbolPair::new("a:b:BTC", "USD")));
+        assert!(chainlink != dex.map_pair(ToString::to_string));
+
+        assert_eq!(
+            CurrencySymbolPair::namespaced(Some(""), "BTC", "USD"),
+            Err(NamespacedSymbolError::InvalidNamespaceLen)
+        );
+        assert_eq!(
+            CurrencySymbolPair::namespaced(None, "BTC", "dex:USD"),
+            Err(NamespacedSymbolError::UnexpectedSeparator)
+        );
+    }
+
+    #[test]
+    fn plain_pair() {
+        let pair = CurrencySymbolPair::new("DOCK".to_string(), "USD".to_string());
+        let bounded: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().try_into().unwrap();
+        let plain = PlainCurrencySymbolPair::from(bounded.clone());
+
+        assert_eq!(plain, (b"DOCK".to_vec(), b"USD".to_vec()));
+        assert_eq!(plain.encode(), bounded.encode());
+        assert_eq!(plain.encode(), pair.encode());
+    }
+
+    #[test]
+    fn swap() {
+        let pair = CurrencySymbolPair::new("DOCK".to_string(), A("USD".to_owned()));
+        assert_eq!(
+            pair.clone().swap(),
+            CurrencySymbolPair::new(A("USD".to_owned()), "DOCK".to_string())
+        );
+        assert_eq!(pair.clone().swap().swap(), pair);
+
+        let bounded: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            CurrencySymbolPair::new("DOCK", "USD").try_into().unwrap();
+        assert_eq!(
+            CurrencySymbolPair::from(bounded.swap()),
+            CurrencySymbolPair::new("USD", "DOCK")
+        );
+
+        let dock_usd = CurrencySymbolPair::new("DOCK", "USD");
+        let usd_dock = CurrencySymbolPair::new("USD", "DOCK");
+        assert!(dock_usd.is_canonical());
+        assert!(!usd_dock.is_canonical());
+        assert!(CurrencySymbolPair::new("USD", "USD").is_canonical());
+        assert_eq!(
+            dock_usd.clone().into_canonical(),
+            (dock_usd.clone(), PairOrientation::Direct)
+        );
+        assert_eq!(
+            usd_dock.into_canonical(),
+            (dock_usd, PairOrientation::Inverted)
+        );
+    }
+
     #[test]
     fn static_types() {
         parameter_types! {
@@ -495,5 +783,10 @@ mod tests {
 
         let cur_pair = CurrencySymbolPair::<_, _>::new("DOCK", "USD");
         assert_eq!(DockUsdPair::get(), cur_pair);
+        assert_eq!(SomeCurrencySymbolPair::<DockUsdPair>::get(), Some(cur_pair));
+        assert_eq!(
+            <() as Get<Option<CurrencySymbolPair<&'static str, &'static str>>>>::get(),
+            None
+        );
     }
 }
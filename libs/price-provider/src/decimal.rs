@@ -0,0 +1,130 @@
+//! Exact conversions between `PriceRecord`s and `rust_decimal::Decimal`s used to display and accept
+//! human-entered prices without binary floating point artifacts. Available with the `decimal` feature only.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use rust_decimal::Decimal;
+
+use crate::PriceRecord;
+
+/// Max decimals representable by `Decimal`.
+pub const MAX_DECIMAL_SCALE: u8 = 28;
+
+/// Errors happening while converting `Decimal` into `PriceRecord`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DecimalConversionError {
+    /// The supplied string isn't a valid decimal or has more than `MAX_DECIMAL_SCALE` decimals.
+    InvalidDecimal,
+    /// Price amount can't be negative.
+    NegativeAmount,
+    /// Price amount without decimal point doesn't fit into `u64`.
+    AmountOverflow,
+}
+
+impl Display for DecimalConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidDecimal => f.write_str("Invalid decimal"),
+            Self::NegativeAmount => f.write_str("Price amount can't be negative"),
+            Self::AmountOverflow => f.write_str("Price amount doesn't fit into `u64`"),
+        }
+    }
+}
+
+impl std::error::Error for DecimalConversionError {}
+
+impl<T> PriceRecord<T> {
+    /// Converts the price into `Decimal`. Returns `None` if decimals exceed `MAX_DECIMAL_SCALE`.
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        Decimal::try_from_i128_with_scale(self.amount().into(), self.decimals()).ok()
+    }
+
+    /// Converts `Decimal` into the price record with the least decimals representing the same value,
+    /// e.g. `1.250` is stored as the amount `125` with `2` decimals.
+    pub fn from_decimal(value: Decimal, block_number: T) -> Result<Self, DecimalConversionError> {
+        if value.is_sign_negative() && !value.is_zero() {
+            return Err(DecimalConversionError::NegativeAmount);
+        }
+
+        let value = value.normalize();
+        let amount = value
+            .mantissa()
+            .try_into()
+            .map_err(|_| DecimalConversionError::AmountOverflow)?;
+        let decimals = value
+            .scale()
+            .try_into()
+            .map_err(|_| DecimalConversionError::InvalidDecimal)?;
+
+        Ok(Self::new(amount, decimals, block_number))
+    }
+
+    /// Parses a human-entered price like `0.0123` into the price record.
+    /// Values with more than `MAX_DECIMAL_SCALE` decimals are rejected instead of being rounded.
+    pub fn parse_decimal(value: &str, block_number: T) -> Result<Self, DecimalConversionError> {
+        let value = Decimal::from_str_exact(value.trim())
+            .map_err(|_| DecimalConversionError::InvalidDecimal)?;
+
+        Self::from_decimal(value, block_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_decimal() {
+        assert_eq!(
+            PriceRecord::new(1234, 3, 0)
+                .to_decimal()
+                .unwrap()
+                .to_string(),
+            "1.234"
+        );
+        assert_eq!(
+            PriceRecord::new(1, 28, 0).to_decimal().unwrap().to_string(),
+            "0.0000000000000000000000000001"
+        );
+        assert_eq!(PriceRecord::new(1, 29, 0).to_decimal(), None);
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 0, 0)
+                .to_decimal()
+                .unwrap()
+                .to_string(),
+            u64::MAX.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_decimal() {
+        assert_eq!(
+            PriceRecord::parse_decimal("0.1", 5),
+            Ok(PriceRecord::new(1, 1, 5))
+        );
+        // Unlike `0.1f64`, no binary float artifacts.
+        assert_eq!(
+            PriceRecord::parse_decimal(" 1.2500 ", 0),
+            Ok(PriceRecord::new(125, 2, 0))
+        );
+        assert_eq!(
+            PriceRecord::parse_decimal("100", 0),
+            Ok(PriceRecord::new(100, 0, 0))
+        );
+        assert_eq!(
+            PriceRecord::parse_decimal("-0.1", 0),
+            Err(DecimalConversionError::NegativeAmount)
+        );
+        assert_eq!(
+            PriceRecord::parse_decimal("18446744073709551616", 0),
+            Err(DecimalConversionError::AmountOverflow)
+        );
+        assert_eq!(
+            PriceRecord::parse_decimal("1.2.3", 0),
+            Err(DecimalConversionError::InvalidDecimal)
+        );
+        assert_eq!(
+            PriceRecord::parse_decimal("0.00000000000000000000000000001", 0),
+            Err(DecimalConversionError::InvalidDecimal)
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Exponentially-weighted moving average (EWMA) price computation.
+
+use crate::PriceRecord;
+use sp_core::U256;
+use sp_runtime::traits::{CheckedConversion, UniqueSaturatedInto};
+
+/// Fixed-point scale of the decay weights.
+const SCALE: u128 = 1_000_000_000_000_000_000;
+/// `ln(2)` scaled by `SCALE`.
+const LN_2: u128 = 693_147_180_559_945_309;
+
+/// Folds the record into the exponentially-weighted moving average `previous` having the given half-life in blocks.
+/// The previous average weighs `0.5^(elapsed / half_life)` where `elapsed` is the amount of blocks between
+/// the previous average and the record, so the records of the same block as the previous average don't move it,
+/// and a zero half-life makes the average follow the latest record. If there's no previous average,
+/// the record itself is returned.
+///
+/// The amounts are normalized to the max decimals of both records. If the resulting amount doesn't fit
+/// into `u64`, decimals get reduced truncating the extra digits. The resulting record has the block number
+/// of the supplied record.
+///
+/// Returns `None` if the record precedes the previous average or the arithmetic overflows.
+pub fn exponentially_weighted_average<BlockNumber>(
+    previous: Option<PriceRecord<BlockNumber>>,
+    record: PriceRecord<BlockNumber>,
+    half_life: u64,
+) -> Option<PriceRecord<BlockNumber>>
+where
+    BlockNumber: UniqueSaturatedInto<u64> + Copy,
+{
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return Some(record),
+    };
+    let block_of = |record: &PriceRecord<BlockNumber>| -> u64 {
+        record.block_number().unique_saturated_into()
+    };
+    let elapsed = block_of(&record).checked_sub(block_of(&previous))?;
+
+    let mut decimals = previous.decimals().max(record.decimals());
+    let normalize = |record: &PriceRecord<BlockNumber>| {
+        U256::from(10u8)
+            .checked_pow((decimals - record.decimals()).into())?
+            .checked_mul(record.amount().into())
+    };
+
+    let weight = decay(elapsed, half_life);
+    let mut amount = normalize(&previous)?
+        .checked_mul(weight.into())?
+        .checked_add(normalize(&record)?.checked_mul((SCALE - weight).into())?)?
+        / U256::from(SCALE);
+    while amount > U256::from(u64::MAX) {
+        amount /= U256::from(10u8);
+        decimals = decimals.checked_sub(1)?;
+    }
+
+    Some(PriceRecord::new(
+        amount.checked_into()?,
+        decimals.checked_into()?,
+        record.block_number(),
+    ))
+}
+
+/// Returns `0.5^(elapsed / half_life)` scaled by `SCALE`.
+/// The fractional halving is computed as `exp(-ln(2) * remainder / half_life)` using the Taylor series.
+fn decay(elapsed: u64, half_life: u64) -> u128 {
+    if half_life == 0 {
+        return 0;
+    }
+    let halvings = elapsed / half_life;
+    if halvings >= u128::BITS.into() {
+        return 0;
+    }
+
+    // `x < ln(2)`, so the terms decrease and all partial sums are positive.
+    let x = LN_2 * u128::from(elapsed % half_life) / u128::from(half_life);
+    let (mut term, mut sum) = (SCALE, SCALE);
+    for n in 1..=32u128 {
+        term = term * x / SCALE / n;
+        if term == 0 {
+            break;
+        }
+        sum = if n % 2 == 1 {
+            sum.saturating_sub(term)
+        } else {
+            sum.saturating_add(term)
+        };
+    }
+
+    sum >> halvings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decay, exponentially_weighted_average, SCALE};
+    use crate::PriceRecord;
+
+    #[test]
+    fn decay_halves_each_half_life() {
+        assert_eq!(decay(0, 10), SCALE);
+        assert_eq!(decay(10, 10), SCALE / 2);
+        assert_eq!(decay(30, 10), SCALE / 8);
+        assert_eq!(decay(1, 0), 0);
+        assert_eq!(decay(u64::MAX, 1), 0);
+        // `0.5^0.5 = 0.70710678...`
+        assert_eq!(decay(5, 10) / 1_000_000_000, 707_106_781);
+    }
+
+    #[test]
+    fn ewma() {
+        let first = PriceRecord::new(100, 0, 10u64);
+
+        assert_eq!(exponentially_weighted_average(None, first, 10), Some(first));
+        // Records of the same block don't move the average.
+        assert_eq!(
+            exponentially_weighted_average(Some(first), PriceRecord::new(200, 0, 10), 10),
+            Some(first)
+        );
+        // Half of the weight moves to the new record after a half-life.
+        assert_eq!(
+            exponentially_weighted_average(Some(first), PriceRecord::new(200, 0, 20), 10),
+            Some(PriceRecord::new(150, 0, 20))
+        );
+        // Amounts are normalized to the max decimals.
+        assert_eq!(
+            exponentially_weighted_average(Some(first), PriceRecord::new(2000, 1, 30), 10),
+            Some(PriceRecord::new(1750, 1, 30))
+        );
+        assert_eq!(
+            exponentially_weighted_average(Some(first), PriceRecord::new(200, 0, 11), 0),
+            Some(PriceRecord::new(200, 0, 11))
+        );
+        assert_eq!(
+            exponentially_weighted_average(Some(first), PriceRecord::new(200, 0, 9), 10),
+            None
+        );
+        assert_eq!(
+            exponentially_weighted_average(
+                Some(PriceRecord::new(u64::MAX, 0, 0u64)),
+                PriceRecord::new(u64::MAX, 18, 10),
+                10
+            ),
+            Some(PriceRecord::new(9_223_372_036_854_775_816, 0, 10))
+        );
+    }
+}
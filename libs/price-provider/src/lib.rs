@@ -5,12 +5,39 @@
 use frame_support::traits::Get;
 
 pub mod currency_pair;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod ewma;
+pub mod minor_units;
+pub mod namespace;
+pub mod outliers;
 pub mod price_record;
+pub mod twap;
 
-pub use currency_pair::{BoundedCurrencySymbolPair, CurrencySymbolPair, StaticCurrencySymbolPair};
-pub use price_record::PriceRecord;
+pub use currency_pair::{
+    BoundedCurrencySymbolPair, CurrencySymbolPair, PairOrientation, PlainCurrencySymbolPair,
+    SomeCurrencySymbolPair, StaticCurrencySymbolPair,
+};
+#[cfg(feature = "decimal")]
+pub use decimal::DecimalConversionError;
+pub use ewma::exponentially_weighted_average;
+pub use minor_units::{MinorUnitExponentList, MinorUnitExponents};
+pub use namespace::{NamespacedSymbol, NamespacedSymbolError};
+pub use outliers::outlier_indices;
+pub use price_record::{PriceRecord, PriceRecordBuilder, PriceRecordError, MAX_DECIMALS};
+pub use twap::time_weighted_average;
 pub use utils::{BoundedStringConversionError, LikeString};
 
+/// Commonly co-used traits and types along with the `utils` prelude.
+pub mod prelude {
+    pub use crate::{
+        BoundedCurrencySymbolPair, CurrencySymbolPair, ExponentiallyWeightedPriceProvider,
+        MinorUnitExponents, MinorUnitsPriceProvider, OptionalStaticPriceProvider, PriceProvider,
+        PriceRecord, RoutedPriceProvider, StaticPriceProvider, TimeWeightedPriceProvider,
+    };
+    pub use utils::prelude::*;
+}
+
 /// Trait to provide price of currency pairs.
 /// The raw price amount should be divided by 10^decimals and rounded to get price per 1 unit.
 pub trait PriceProvider<T: frame_system::Config> {
@@ -26,6 +53,106 @@ pub trait PriceProvider<T: frame_system::Config> {
         To: LikeString + 'static;
 }
 
+/// Trait to provide time-weighted average price (TWAP) of currency pairs.
+/// The raw price amount should be divided by 10^decimals and rounded to get price per 1 unit.
+pub trait TimeWeightedPriceProvider<T: frame_system::Config> {
+    type Error;
+
+    /// Get the time-weighted average price of the given currency pair over the last `window` blocks.
+    /// Returns the price record containing raw price amount, decimals, and the current block number.
+    fn pair_twap<From, To>(
+        currency_pair: CurrencySymbolPair<From, To>,
+        window: T::BlockNumber,
+    ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
+    where
+        From: LikeString + 'static,
+        To: LikeString + 'static;
+}
+
+/// Trait to provide exponentially-weighted moving average price (EWMA) of currency pairs.
+/// The raw price amount should be divided by 10^decimals and rounded to get price per 1 unit.
+pub trait ExponentiallyWeightedPriceProvider<T: frame_system::Config> {
+    type Error;
+
+    /// Get the exponentially-weighted moving average price of the given currency pair.
+    /// Returns the price record containing raw price amount, decimals, and the block number of the latest
+    /// price folded into the average.
+    fn pair_ewma<From, To>(
+        currency_pair: CurrencySymbolPair<From, To>,
+    ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
+    where
+        From: LikeString + 'static,
+        To: LikeString + 'static;
+}
+
+/// Trait to provide prices of currency pairs routed through an intermediate currency, e.g. the price of `BTC/EUR`
+/// derived from the prices of `BTC/USD` and `USD/EUR`. See [`PriceRecord::route_through`] for the decimals
+/// and block number of the resulting record.
+pub trait RoutedPriceProvider<T: frame_system::Config>: PriceProvider<T> {
+    /// Get the price of `from/to` currency pair composed of the latest `from/intermediate`
+    /// and `intermediate/to` prices. Returns `None` if either price is missing or the composed price
+    /// can't be represented.
+    fn route_price<From, Via, To>(
+        from: From,
+        intermediate: Via,
+        to: To,
+    ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
+    where
+        From: LikeString + 'static,
+        Via: LikeString + 'static,
+        To: LikeString + 'static,
+    {
+        let first = match Self::pair_price(CurrencySymbolPair::new(from, intermediate.clone()))? {
+            Some(price) => price,
+            None => return Ok(None),
+        };
+        let second = match Self::pair_price(CurrencySymbolPair::new(intermediate, to))? {
+            Some(price) => price,
+            None => return Ok(None),
+        };
+
+        Ok(first.route_through(second))
+    }
+}
+
+/// Trait to convert amounts of currencies into the smallest units of the quote currencies using the latest prices,
+/// e.g. `DOCK` into USD cents. `M` provides the minor unit exponents of the currency symbols.
+/// See [`PriceRecord::amount_in_minor_units`] for the rounding.
+pub trait MinorUnitsPriceProvider<T, M>: PriceProvider<T>
+where
+    T: frame_system::Config,
+    M: MinorUnitExponents,
+{
+    /// Get `major_amount` units of the `from` currency expressed in the smallest units of the `to` currency
+    /// using the latest price of the given currency pair. Returns `None` if the price is missing,
+    /// the minor unit exponent of `to` is unknown, or the result doesn't fit into `u128`.
+    fn amount_in_minor_units<From, To>(
+        currency_pair: CurrencySymbolPair<From, To>,
+        major_amount: u128,
+    ) -> Result<Option<u128>, Self::Error>
+    where
+        From: LikeString + 'static,
+        To: LikeString + AsRef<str> + 'static,
+    {
+        let (_, to): (From, To) = currency_pair.clone().into();
+        let exponent = match M::minor_unit_exponent(to.as_ref()) {
+            Some(exponent) => exponent,
+            None => return Ok(None),
+        };
+
+        Ok(Self::pair_price(currency_pair)?
+            .and_then(|price| price.amount_in_minor_units(major_amount, exponent)))
+    }
+}
+
+impl<T, M, PP> MinorUnitsPriceProvider<T, M> for PP
+where
+    T: frame_system::Config,
+    M: MinorUnitExponents,
+    PP: PriceProvider<T>,
+{
+}
+
 /// Trait to provide price of the bound currency pair.
 /// The raw price amount should be divided by 10^decimals and rounded to get price per 1 unit.
 pub trait StaticPriceProvider<T, P>
@@ -57,3 +184,36 @@ where
         Self::pair_price(<Self as StaticPriceProvider<T, P>>::pair())
     }
 }
+
+/// Trait to provide price of the optionally bound currency pair.
+/// Allows runtimes to be compiled without the bound pair (e.g. using `()` as `P`) instead of requiring a dummy symbol.
+/// The raw price amount should be divided by 10^decimals and rounded to get price per 1 unit.
+pub trait OptionalStaticPriceProvider<T, P>
+where
+    T: frame_system::Config,
+    P: Get<Option<CurrencySymbolPair<&'static str, &'static str>>>,
+{
+    type Error;
+
+    /// Get the latest price of the bound currency pair.
+    /// Returns `None` if there's no bound pair or its price isn't set.
+    fn price() -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>;
+
+    /// Returns underlying bound pair to provide a price for, if it exists.
+    fn pair() -> Option<CurrencySymbolPair<&'static str, &'static str>> {
+        P::get()
+    }
+}
+
+impl<T, P, PP> OptionalStaticPriceProvider<T, P> for PP
+where
+    T: frame_system::Config,
+    P: Get<Option<CurrencySymbolPair<&'static str, &'static str>>>,
+    PP: PriceProvider<T>,
+{
+    type Error = PP::Error;
+
+    fn price() -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error> {
+        <Self as OptionalStaticPriceProvider<T, P>>::pair().map_or(Ok(None), Self::pair_price)
+    }
+}
@@ -0,0 +1,57 @@
+//! Conversion of currency amounts into the smallest units of the quote currency, e.g. `DOCK` into USD cents.
+//!
+//! Downstream code used to scale by the price decimals and by the minor unit exponent separately,
+//! often in the wrong order, truncating the result. [`PriceRecord::amount_in_minor_units`] applies both scalings
+//! at once, rounding down only the final result.
+
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+
+#[cfg(doc)]
+use crate::PriceRecord;
+
+/// Registry of the minor unit exponents of the currency symbols, e.g. `2` for `USD` (cents).
+pub trait MinorUnitExponents {
+    /// Returns the exponent of the smallest unit of the currency symbol, so one unit of the currency
+    /// equals `10^exponent` smallest units. Returns `None` if the symbol is unknown.
+    fn minor_unit_exponent(symbol: &str) -> Option<u8>;
+}
+
+/// Doesn't know any symbol.
+impl MinorUnitExponents for () {
+    fn minor_unit_exponent(_symbol: &str) -> Option<u8> {
+        None
+    }
+}
+
+/// Looks up the exponents in the static list of `(symbol, exponent)` pairs, e.g. declared via `parameter_types!`.
+pub struct MinorUnitExponentList<L>(PhantomData<L>);
+
+impl<L: Get<&'static [(&'static str, u8)]>> MinorUnitExponents for MinorUnitExponentList<L> {
+    fn minor_unit_exponent(symbol: &str) -> Option<u8> {
+        L::get()
+            .iter()
+            .find(|(listed, _)| *listed == symbol)
+            .map(|(_, exponent)| *exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::parameter_types;
+
+    parameter_types! {
+        pub const Exponents: &'static [(&'static str, u8)] = &[("USD", 2), ("DOCK", 6)];
+    }
+
+    #[test]
+    fn exponent_list() {
+        type List = MinorUnitExponentList<Exponents>;
+
+        assert_eq!(List::minor_unit_exponent("USD"), Some(2));
+        assert_eq!(List::minor_unit_exponent("DOCK"), Some(6));
+        assert_eq!(List::minor_unit_exponent("usd"), None);
+        assert_eq!(<()>::minor_unit_exponent("USD"), None);
+    }
+}
@@ -0,0 +1,147 @@
+//! Namespaced currency symbols like `chainlink:BTC` or `dex:DOCK` which allow the same logical pair
+//! provided by different sources to coexist.
+
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use scale_info::prelude::string::String;
+
+/// Separates the namespace from the symbol.
+pub const NAMESPACE_SEPARATOR: char = ':';
+
+/// Max length of the namespace in bytes.
+pub const MAX_NAMESPACE_BYTES_LEN: usize = 16;
+
+/// Errors happening while parsing or building a namespaced symbol.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NamespacedSymbolError {
+    /// Namespace is empty or exceeds `MAX_NAMESPACE_BYTES_LEN`.
+    InvalidNamespaceLen,
+    /// Symbol is empty.
+    EmptySymbol,
+    /// Namespace or symbol contains the `NAMESPACE_SEPARATOR`.
+    UnexpectedSeparator,
+}
+
+/// Currency symbol optionally prefixed by the namespace denoting its source.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NamespacedSymbol<'a> {
+    namespace: Option<&'a str>,
+    symbol: &'a str,
+}
+
+impl<'a> NamespacedSymbol<'a> {
+    /// Instantiates new `NamespacedSymbol` validating its components.
+    pub fn new(namespace: Option<&'a str>, symbol: &'a str) -> Result<Self, NamespacedSymbolError> {
+        if let Some(namespace) = namespace {
+            if namespace.is_empty() || namespace.len() > MAX_NAMESPACE_BYTES_LEN {
+                return Err(NamespacedSymbolError::InvalidNamespaceLen);
+            }
+            if namespace.contains(NAMESPACE_SEPARATOR) {
+                return Err(NamespacedSymbolError::UnexpectedSeparator);
+            }
+        }
+        if symbol.is_empty() {
+            return Err(NamespacedSymbolError::EmptySymbol);
+        }
+        if symbol.contains(NAMESPACE_SEPARATOR) {
+            return Err(NamespacedSymbolError::UnexpectedSeparator);
+        }
+
+        Ok(Self { namespace, symbol })
+    }
+
+    /// Parses `namespace:symbol` or plain `symbol`.
+    pub fn parse(raw: &'a str) -> Result<Self, NamespacedSymbolError> {
+        match raw.split_once(NAMESPACE_SEPARATOR) {
+            Some((namespace, symbol)) => Self::new(Some(namespace), symbol),
+            None => Self::new(None, raw),
+        }
+    }
+
+    /// Returns the namespace if any.
+    pub fn namespace(&self) -> Option<&'a str> {
+        self.namespace
+    }
+
+    /// Returns the symbol without the namespace.
+    pub fn symbol(&self) -> &'a str {
+        self.symbol
+    }
+
+    /// Returns a copy of the symbol placed in the given namespace.
+    pub fn with_namespace(
+        &self,
+        namespace: Option<&'a str>,
+    ) -> Result<Self, NamespacedSymbolError> {
+        Self::new(namespace, self.symbol)
+    }
+
+    /// Formats the symbol as `namespace:symbol` or plain `symbol`.
+    pub fn to_raw(&self) -> String {
+        use core::fmt::Write;
+
+        let mut raw = String::new();
+        let _ = write!(raw, "{}", self);
+
+        raw
+    }
+}
+
+impl Display for NamespacedSymbol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Some(namespace) = self.namespace {
+            write!(f, "{}{}", namespace, NAMESPACE_SEPARATOR)?;
+        }
+
+        f.write_str(self.symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format() {
+        let symbol = NamespacedSymbol::parse("chainlink:BTC").unwrap();
+        assert_eq!(symbol.namespace(), Some("chainlink"));
+        assert_eq!(symbol.symbol(), "BTC");
+        assert_eq!(symbol.to_raw(), "chainlink:BTC");
+
+        let symbol = NamespacedSymbol::parse("DOCK").unwrap();
+        assert_eq!(symbol.namespace(), None);
+        assert_eq!(symbol.symbol(), "DOCK");
+        assert_eq!(symbol.to_raw(), "DOCK");
+        assert_eq!(
+            symbol.with_namespace(Some("dex")).unwrap().to_raw(),
+            "dex:DOCK"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            NamespacedSymbol::parse(":BTC"),
+            Err(NamespacedSymbolError::InvalidNamespaceLen)
+        );
+        assert_eq!(
+            NamespacedSymbol::parse("averyveryverylongnamespace:BTC"),
+            Err(NamespacedSymbolError::InvalidNamespaceLen)
+        );
+        assert_eq!(
+            NamespacedSymbol::parse("dex:"),
+            Err(NamespacedSymbolError::EmptySymbol)
+        );
+        assert_eq!(
+            NamespacedSymbol::parse(""),
+            Err(NamespacedSymbolError::EmptySymbol)
+        );
+        assert_eq!(
+            NamespacedSymbol::parse("dex:a:BTC"),
+            Err(NamespacedSymbolError::UnexpectedSeparator)
+        );
+        assert_eq!(
+            NamespacedSymbol::new(Some("a:b"), "BTC"),
+            Err(NamespacedSymbolError::UnexpectedSeparator)
+        );
+    }
+}
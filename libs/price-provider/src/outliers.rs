@@ -0,0 +1,108 @@
+//! Median absolute deviation (MAD) based outlier detection used to discard byzantine submissions
+//! before they're aggregated.
+
+use crate::PriceRecord;
+use sp_core::U256;
+use sp_std::prelude::*;
+
+/// Returns indices of the records deviating from the median value by more than `max_deviations`
+/// median absolute deviations. Values are compared after being normalized to the common decimals.
+///
+/// If more than a half of the records have the same value, MAD is zero, so every record having a different
+/// value is an outlier. Returns an empty vec if there're less than three records since the median of
+/// two values can't tell which one is wrong.
+pub fn outlier_indices<T>(records: &[PriceRecord<T>], max_deviations: u32) -> Vec<usize> {
+    if records.len() < 3 {
+        return Vec::new();
+    }
+
+    let decimals = records
+        .iter()
+        .map(PriceRecord::decimals)
+        .max()
+        .unwrap_or_default();
+    let values: Vec<_> = records
+        .iter()
+        .map(|record| normalized_value(record, decimals))
+        .collect();
+
+    let median_value = median(values.clone());
+    let deviations: Vec<_> = values
+        .into_iter()
+        .map(|value| abs_diff(value, median_value))
+        .collect();
+    let max_deviation = median(deviations.clone()).saturating_mul(max_deviations.into());
+
+    deviations
+        .into_iter()
+        .enumerate()
+        .filter(|(_, deviation)| *deviation > max_deviation)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Scales the record amount up to the given decimals saturating at `U256::MAX`.
+fn normalized_value<T>(record: &PriceRecord<T>, decimals: u32) -> U256 {
+    U256::from(10u8)
+        .checked_pow((decimals - record.decimals()).into())
+        .and_then(|multiplier| multiplier.checked_mul(record.amount().into()))
+        .unwrap_or(U256::MAX)
+}
+
+/// Returns the median of the supplied non-empty values rounded down.
+fn median(mut values: Vec<U256>) -> U256 {
+    values.sort_unstable();
+
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        let (lower, upper) = (values[mid - 1], values[mid]);
+
+        lower + (upper - lower) / 2
+    } else {
+        values[mid]
+    }
+}
+
+/// Returns the absolute difference of two values.
+fn abs_diff(a: U256, b: U256) -> U256 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::outlier_indices;
+    use crate::PriceRecord;
+
+    #[test]
+    fn outliers() {
+        let records = |amounts: &[(u64, u8)]| -> Vec<_> {
+            amounts
+                .iter()
+                .map(|&(amount, decimals)| PriceRecord::new(amount, decimals, 0))
+                .collect()
+        };
+
+        assert!(outlier_indices(&records(&[(1, 0), (1_000, 0)]), 3).is_empty());
+        // Median is 1.00, MAD is 0.01.
+        assert_eq!(
+            outlier_indices(
+                &records(&[(100, 2), (99, 2), (101, 2), (102, 2), (150, 2), (1, 0)]),
+                3
+            ),
+            vec![4]
+        );
+        assert_eq!(
+            outlier_indices(&records(&[(100, 2), (10, 1), (101, 2), (1, 0)]), 0),
+            vec![2]
+        );
+        assert!(outlier_indices(&records(&[(100, 2), (104, 2), (96, 2)]), 1).is_empty());
+        assert_eq!(
+            outlier_indices(&records(&[(u64::MAX, 255), (1, 0), (1, 0)]), 10),
+            vec![0]
+        );
+    }
+}
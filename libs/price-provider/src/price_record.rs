@@ -2,11 +2,17 @@
 use serde::{Deserialize, Serialize};
 
 use codec::{Decode, Encode, MaxEncodedLen};
+use core::cmp::Ordering;
 use scale_info::TypeInfo;
 use sp_core::U256;
 use sp_runtime::traits::CheckedConversion;
 use sp_std::prelude::*;
 
+/// Max decimals of the price records built via `PriceRecordBuilder` or produced by the rescaling helpers.
+/// `10^77` is the greatest power of ten fitting into `U256`, so conversions like [`PriceRecord::price_per_unit`]
+/// can be performed for any valid record.
+pub const MAX_DECIMALS: u8 = 77;
+
 /// Stores price amount with specified decimals and block number when this record was created.
 #[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Hash, Debug, MaxEncodedLen)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -17,6 +23,9 @@ pub struct PriceRecord<T> {
     decimals: u8,
     /// Block number when this record was published.
     block_number: T,
+    /// Half-width of the confidence interval in the raw amount units, i.e. the price is expected to be within
+    /// `amount ± confidence`. `None` if the uncertainty is unknown.
+    confidence: Option<u64>,
 }
 
 impl<T> PriceRecord<T> {
@@ -25,14 +34,22 @@ impl<T> PriceRecord<T> {
     /// - `amount` - raw price amount. This value should be divided by 10^decimals to get a price per 1 unit.
     /// - `decimals` - value representing precision. Used to allow storing decimal value as an integer.
     /// - `block_number` - block number when this record was published.
+    ///
+    /// The record has no confidence interval, see [`Self::with_confidence`].
     pub const fn new(amount: u64, decimals: u8, block_number: T) -> Self {
         Self {
             amount,
             decimals,
             block_number,
+            confidence: None,
         }
     }
 
+    /// Sets the half-width of the confidence interval in the raw amount units, `None` removes it.
+    pub fn with_confidence(self, confidence: Option<u64>) -> Self {
+        Self { confidence, ..self }
+    }
+
     /// Returns raw price amount. This value should be divided by 10^decimals to get a price per 1 unit.
     pub const fn amount(&self) -> u64 {
         self.amount
@@ -51,6 +68,12 @@ impl<T> PriceRecord<T> {
         self.block_number
     }
 
+    /// Returns half-width of the confidence interval in the raw amount units, i.e. the price is expected
+    /// to be within `amount ± confidence`. `None` if the uncertainty is unknown.
+    pub const fn confidence(&self) -> Option<u64> {
+        self.confidence
+    }
+
     /// Returns price per given amount of units.
     ///
     /// The input value will be converted to `U256` and the output price will be created from `U256`.
@@ -71,8 +94,12 @@ impl<T> PriceRecord<T> {
     }
 
     /// Attempts to increase decimals amount for the given price record.
+    /// Returns `None` if the resulting decimals exceed `MAX_DECIMALS`.
     pub fn inc_decimals(mut self, decimals: u8) -> Option<Self> {
-        self.decimals = self.decimals.checked_add(decimals)?;
+        self.decimals = self
+            .decimals
+            .checked_add(decimals)
+            .filter(|decimals| *decimals <= MAX_DECIMALS)?;
 
         Some(self)
     }
@@ -83,13 +110,338 @@ impl<T> PriceRecord<T> {
 
         Some(self)
     }
+
+    /// Reduces decimals of the given price record to `max_decimals` truncating the extra digits of the amount,
+    /// so the amount may become zero. The confidence interval is rounded up, so it isn't narrowed.
+    /// Records having at most `max_decimals` decimals are returned as is.
+    pub fn truncated_to_decimals(self, max_decimals: u8) -> Self {
+        let diff = match self.decimals.checked_sub(max_decimals) {
+            Some(diff) if diff > 0 => diff,
+            _ => return self,
+        };
+        let amount = 10u64
+            .checked_pow(diff.into())
+            .map_or(0, |divisor| self.amount / divisor);
+
+        Self {
+            amount,
+            decimals: max_decimals,
+            confidence: Self::confidence_scaled_down(self.confidence, diff),
+            ..self
+        }
+    }
+
+    /// Attempts to rescale the given price record to retain `digits` significant digits of the amount,
+    /// adjusting decimals accordingly. Extra digits are truncated, while the confidence interval is rounded up
+    /// and saturates at `u64::MAX`.
+    ///
+    /// Records with zero amount are returned as is.
+    /// Returns `None` if `digits` is zero or the resulting amount/decimals can't be represented.
+    pub fn rescaled_to_significant_digits(self, digits: u8) -> Option<Self> {
+        if digits == 0 {
+            return None;
+        } else if self.amount == 0 {
+            return Some(self);
+        }
+
+        let current_digits = Self::significant_digits(self.amount);
+
+        if current_digits > digits {
+            let diff = current_digits - digits;
+            let amount = self.amount / 10u64.checked_pow(diff.into())?;
+
+            Some(Self {
+                amount,
+                confidence: Self::confidence_scaled_down(self.confidence, diff),
+                ..self.dec_decimals(diff)?
+            })
+        } else {
+            let diff = digits - current_digits;
+            let multiplier = 10u64.checked_pow(diff.into())?;
+            let amount = self.amount.checked_mul(multiplier)?;
+
+            Some(Self {
+                amount,
+                confidence: self
+                    .confidence
+                    .map(|confidence| confidence.saturating_mul(multiplier)),
+                ..self.inc_decimals(diff)?
+            })
+        }
+    }
+
+    /// Returns the inverse price `1 / price` with the given decimals keeping the block number,
+    /// e.g. the price of `USD/DOCK` derived from the price of `DOCK/USD`. Extra digits are truncated.
+    /// The inverse record has no confidence interval.
+    ///
+    /// Returns `None` if the amount is zero, `decimals` exceed `MAX_DECIMALS`, or the inverse amount is zero
+    /// or doesn't fit into `u64`.
+    pub fn inverse(self, decimals: u8) -> Option<Self> {
+        if decimals > MAX_DECIMALS {
+            return None;
+        }
+        let numerator =
+            U256::from(10u8).checked_pow((self.decimals() + u32::from(decimals)).into())?;
+        let amount: u64 = numerator.checked_div(self.amount.into())?.checked_into()?;
+
+        (amount > 0).then_some(Self {
+            amount,
+            decimals,
+            confidence: None,
+            ..self
+        })
+    }
+
+    /// Returns the price of `A/C` routed through the intermediate currency `B` using `self` as the price of `A/B`
+    /// and `other` as the price of `B/C`, e.g. the price of `BTC/EUR` derived from `BTC/USD` and `USD/EUR`.
+    /// The resulting record has the sum of decimals of both records, the lower block number of them,
+    /// and no confidence interval.
+    /// If the resulting amount doesn't fit into `u64` or the decimals exceed `MAX_DECIMALS`,
+    /// decimals get reduced truncating the extra digits.
+    ///
+    /// Returns `None` if either amount is zero or the resulting price can't be represented.
+    pub fn route_through(self, other: Self) -> Option<Self>
+    where
+        T: Ord,
+    {
+        let mut amount = U256::from(self.amount).checked_mul(other.amount.into())?;
+        let mut decimals = u32::from(self.decimals) + u32::from(other.decimals);
+        while amount > U256::from(u64::MAX) || decimals > MAX_DECIMALS.into() {
+            amount /= U256::from(10u8);
+            decimals = decimals.checked_sub(1)?;
+        }
+        let amount: u64 = amount.checked_into()?;
+
+        (amount > 0).then(|| Self {
+            amount,
+            decimals: decimals as u8,
+            block_number: self.block_number.min(other.block_number),
+            confidence: None,
+        })
+    }
+
+    /// Converts `major_amount` units of the currency being valued into the smallest units of the quote currency,
+    /// one unit of which equals `10^minor_unit_exponent` smallest units, e.g. `2` for USD cents.
+    /// The result is rounded down. Returns `None` if it doesn't fit into `u128`.
+    pub fn amount_in_minor_units(
+        &self,
+        major_amount: u128,
+        minor_unit_exponent: u8,
+    ) -> Option<u128> {
+        let amount = U256::from(self.amount).checked_mul(major_amount.into())?;
+        let amount = if minor_unit_exponent >= self.decimals {
+            amount.checked_mul(
+                U256::from(10u8).checked_pow((minor_unit_exponent - self.decimals).into())?,
+            )?
+        } else {
+            // Amount is less than `2^192`, so it's rounded down to zero if the divisor overflows.
+            U256::from(10u8)
+                .checked_pow((self.decimals - minor_unit_exponent).into())
+                .map_or_else(U256::zero, |divisor| amount / divisor)
+        };
+
+        amount.checked_into()
+    }
+
+    /// Compares values of the records normalized to the common decimals, i.e. `10` with `1` decimal
+    /// equals `100` with `2` decimals. Block numbers aren't compared.
+    pub fn cmp_by_value(&self, other: &Self) -> Ordering {
+        // Zero amount is zero with any decimals, so it can't be scaled past the other amount.
+        if self.amount == 0 || other.amount == 0 {
+            return self.amount.cmp(&other.amount);
+        }
+        // Scales amount having less decimals up to the max decimals.
+        // If the scaled amount overflows `u128`, it's greater than any non-zero `u64` amount.
+        let scale = |amount: u64, diff: u8| {
+            10u128
+                .checked_pow(diff.into())
+                .and_then(|multiplier| u128::from(amount).checked_mul(multiplier))
+        };
+
+        match self.decimals.cmp(&other.decimals) {
+            Ordering::Equal => self.amount.cmp(&other.amount),
+            Ordering::Less => scale(self.amount, other.decimals - self.decimals)
+                .map_or(Ordering::Greater, |amount| amount.cmp(&other.amount.into())),
+            Ordering::Greater => scale(other.amount, self.decimals - other.decimals)
+                .map_or(Ordering::Less, |other_amount| {
+                    u128::from(self.amount).cmp(&other_amount)
+                }),
+        }
+    }
+
+    /// Returns the record with the lower value, see [`Self::cmp_by_value`].
+    /// Returns `self` if the values are equal.
+    pub fn min_by_value(self, other: Self) -> Self {
+        match self.cmp_by_value(&other) {
+            Ordering::Greater => other,
+            _ => self,
+        }
+    }
+
+    /// Returns the record with the greater value, see [`Self::cmp_by_value`].
+    /// Returns `other` if the values are equal.
+    pub fn max_by_value(self, other: Self) -> Self {
+        match self.cmp_by_value(&other) {
+            Ordering::Greater => self,
+            _ => other,
+        }
+    }
+
+    /// Divides the confidence interval by `10^diff` rounding up.
+    fn confidence_scaled_down(confidence: Option<u64>, diff: u8) -> Option<u64> {
+        confidence.map(|confidence| match 10u64.checked_pow(diff.into()) {
+            Some(divisor) => confidence / divisor + u64::from(confidence % divisor != 0),
+            None => u64::from(confidence != 0),
+        })
+    }
+
+    /// Returns amount of decimal digits in the supplied non-zero amount.
+    fn significant_digits(mut amount: u64) -> u8 {
+        let mut digits = 0;
+        while amount > 0 {
+            amount /= 10;
+            digits += 1;
+        }
+
+        digits
+    }
+}
+
+/// Orders records by their values normalized to the common decimals, see [`PriceRecord::cmp_by_value`].
+/// Records having equal values are ordered by decimals, then by block numbers and then by confidence intervals,
+/// so the ordering is consistent with `Eq`.
+impl<T: Ord> Ord for PriceRecord<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_by_value(other)
+            .then_with(|| self.decimals.cmp(&other.decimals))
+            .then_with(|| self.block_number.cmp(&other.block_number))
+            .then_with(|| self.confidence.cmp(&other.confidence))
+    }
+}
+
+impl<T: Ord> PartialOrd for PriceRecord<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Errors happening on `PriceRecord` construction via `PriceRecordBuilder`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PriceRecordError {
+    /// Price amount is zero while it's not explicitly allowed.
+    ZeroAmount,
+    /// Decimals amount exceeds max allowed.
+    TooManyDecimals,
+    /// Block number is lower than the previous record's block number.
+    NonMonotonicBlockNumber,
+}
+
+/// Builds `PriceRecord` enforcing its invariants:
+/// - amount is non-zero unless explicitly allowed
+/// - decimals don't exceed configured max, which can't be greater than `MAX_DECIMALS`
+/// - block number isn't lower than the previous record's block number, if it was supplied
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceRecordBuilder<T> {
+    record: PriceRecord<T>,
+    allow_zero_amount: bool,
+    max_decimals: u8,
+    previous_block_number: Option<T>,
+}
+
+impl<T> PriceRecordBuilder<T> {
+    /// Instantiates new `PriceRecordBuilder` with the given amount, decimals and block number.
+    /// By default, zero amount isn't allowed and decimals are limited by `MAX_DECIMALS`.
+    pub const fn new(amount: u64, decimals: u8, block_number: T) -> Self {
+        Self {
+            record: PriceRecord::new(amount, decimals, block_number),
+            allow_zero_amount: false,
+            max_decimals: MAX_DECIMALS,
+            previous_block_number: None,
+        }
+    }
+
+    /// Allows building a record with zero amount.
+    pub fn allow_zero_amount(mut self) -> Self {
+        self.allow_zero_amount = true;
+
+        self
+    }
+
+    /// Sets max allowed decimals amount. Values exceeding `MAX_DECIMALS` are lowered to it.
+    pub fn max_decimals(mut self, max_decimals: u8) -> Self {
+        self.max_decimals = max_decimals.min(MAX_DECIMALS);
+
+        self
+    }
+
+    /// Sets half-width of the confidence interval in the raw amount units, see [`PriceRecord::confidence`].
+    pub fn confidence(mut self, confidence: Option<u64>) -> Self {
+        self.record.confidence = confidence;
+
+        self
+    }
+
+    /// Sets previous record for the same pair, so the built record's block number can't be lower than its one.
+    pub fn with_previous(mut self, previous: Option<PriceRecord<T>>) -> Self {
+        self.previous_block_number = previous.map(|PriceRecord { block_number, .. }| block_number);
+
+        self
+    }
+
+    /// Validates invariants and builds `PriceRecord`.
+    pub fn build(self) -> Result<PriceRecord<T>, PriceRecordError>
+    where
+        T: PartialOrd,
+    {
+        let Self {
+            record,
+            allow_zero_amount,
+            max_decimals,
+            previous_block_number,
+        } = self;
+
+        if record.amount == 0 && !allow_zero_amount {
+            Err(PriceRecordError::ZeroAmount)
+        } else if record.decimals > max_decimals {
+            Err(PriceRecordError::TooManyDecimals)
+        } else if matches!(previous_block_number, Some(previous) if record.block_number < previous)
+        {
+            Err(PriceRecordError::NonMonotonicBlockNumber)
+        } else {
+            Ok(record)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use sp_core::U256;
 
-    use crate::PriceRecord;
+    use crate::{PriceRecord, PriceRecordBuilder, PriceRecordError, MAX_DECIMALS};
+
+    #[test]
+    fn price_record_codec_compatibility() {
+        use codec::{Decode, Encode};
+
+        let record = PriceRecord::new(12345, 6, 7u32);
+        let encoded = record.encode();
+        assert_eq!(
+            price_record_codec::PriceRecord::<u32>::decode(&mut &encoded[..]),
+            Ok(price_record_codec::PriceRecord::new(12345, 6, 7))
+        );
+        assert_eq!(
+            price_record_codec::PriceRecord::new(12345, 6, 7u32).encode(),
+            encoded
+        );
+
+        let record = record.with_confidence(Some(89));
+        assert_eq!(
+            price_record_codec::PriceRecord::new(12345, 6, 7u32)
+                .with_confidence(Some(89))
+                .encode(),
+            record.encode()
+        );
+    }
 
     #[test]
     fn getters() {
@@ -98,6 +450,8 @@ mod tests {
         assert_eq!(rec.amount(), 12345);
         assert_eq!(rec.decimals(), 6);
         assert_eq!(rec.block_number(), 7);
+        assert_eq!(rec.confidence(), None);
+        assert_eq!(rec.with_confidence(Some(3)).confidence(), Some(3));
     }
 
     #[test]
@@ -127,9 +481,59 @@ mod tests {
         assert_eq!(standard_price.price_per_unit(32u64), Some(394u32));
     }
 
+    #[test]
+    fn ordering() {
+        use core::cmp::Ordering;
+
+        let a = PriceRecord::new(10, 1, 5);
+        let b = PriceRecord::new(100, 2, 3);
+        let c = PriceRecord::new(101, 2, 1);
+        let large = PriceRecord::new(2, 0, 0);
+        let tiny = PriceRecord::new(u64::MAX, 255, 0);
+
+        assert_eq!(a.cmp_by_value(&b), Ordering::Equal);
+        assert_eq!(a.cmp_by_value(&c), Ordering::Less);
+        assert_eq!(c.cmp_by_value(&a), Ordering::Greater);
+        assert_eq!(tiny.cmp_by_value(&large), Ordering::Less);
+        assert_eq!(large.cmp_by_value(&tiny), Ordering::Greater);
+        // Zero amount isn't scaled past the amounts having much more decimals.
+        let zero = PriceRecord::new(0, 0, 0);
+        assert_eq!(
+            zero.cmp_by_value(&PriceRecord::new(5, 50, 0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            PriceRecord::new(5, 50, 0).cmp_by_value(&zero),
+            Ordering::Greater
+        );
+        assert_eq!(
+            zero.cmp_by_value(&PriceRecord::new(0, 255, 0)),
+            Ordering::Equal
+        );
+
+        assert_eq!(a.min_by_value(b), a);
+        assert_eq!(a.max_by_value(b), b);
+        assert_eq!(c.min_by_value(a), a);
+        assert_eq!(a.max_by_value(c), c);
+
+        // Equal values are ordered by decimals and then by block numbers.
+        assert!(a < b);
+        assert!(b < c);
+        assert!(PriceRecord::new(10, 1, 4) < a);
+        assert!(a < a.with_confidence(Some(0)));
+
+        let mut records = vec![c, tiny, b, large, a];
+        records.sort();
+        assert_eq!(records, vec![tiny, a, b, c, large]);
+    }
+
     #[test]
     fn decimals() {
         assert_eq!(PriceRecord::new(12345, 255, 7).inc_decimals(1), None);
+        assert_eq!(
+            PriceRecord::new(12345, MAX_DECIMALS, 7).inc_decimals(1),
+            None
+        );
         assert_eq!(PriceRecord::new(12345, 0, 7).dec_decimals(1), None);
         assert_eq!(
             PriceRecord::new(12345, 15, 7).inc_decimals(15),
@@ -140,4 +544,257 @@ mod tests {
             Some(PriceRecord::new(12345, 0, 7))
         );
     }
+
+    #[test]
+    fn truncated_to_decimals() {
+        assert_eq!(
+            PriceRecord::new(12345, 4, 7).truncated_to_decimals(2),
+            PriceRecord::new(123, 2, 7)
+        );
+        assert_eq!(
+            PriceRecord::new(12345, 4, 7).truncated_to_decimals(4),
+            PriceRecord::new(12345, 4, 7)
+        );
+        assert_eq!(
+            PriceRecord::new(12345, 4, 7).truncated_to_decimals(6),
+            PriceRecord::new(12345, 4, 7)
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 255, 7).truncated_to_decimals(MAX_DECIMALS),
+            PriceRecord::new(0, MAX_DECIMALS, 7)
+        );
+        // Any valid record can be converted.
+        assert_eq!(
+            PriceRecord::new(u64::MAX, MAX_DECIMALS, 7).price_per_unit(1u8),
+            Some(0u8)
+        );
+    }
+
+    #[test]
+    fn confidence_rescaling() {
+        let record = PriceRecord::new(12345, 4, 7).with_confidence(Some(101));
+
+        assert_eq!(
+            record.truncated_to_decimals(2),
+            PriceRecord::new(123, 2, 7).with_confidence(Some(2))
+        );
+        assert_eq!(
+            record.truncated_to_decimals(0),
+            PriceRecord::new(1, 0, 7).with_confidence(Some(1))
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 255, 7)
+                .with_confidence(Some(5))
+                .truncated_to_decimals(MAX_DECIMALS),
+            PriceRecord::new(0, MAX_DECIMALS, 7).with_confidence(Some(1))
+        );
+        assert_eq!(
+            record.rescaled_to_significant_digits(3),
+            Some(PriceRecord::new(123, 2, 7).with_confidence(Some(2)))
+        );
+        assert_eq!(
+            record.rescaled_to_significant_digits(7),
+            Some(PriceRecord::new(1234500, 6, 7).with_confidence(Some(10100)))
+        );
+        assert_eq!(
+            PriceRecord::new(1, 0, 7)
+                .with_confidence(Some(u64::MAX))
+                .rescaled_to_significant_digits(2),
+            Some(PriceRecord::new(10, 1, 7).with_confidence(Some(u64::MAX)))
+        );
+        // Derived records don't keep the confidence.
+        assert_eq!(
+            PriceRecord::new(25, 2, 5)
+                .with_confidence(Some(1))
+                .inverse(0),
+            Some(PriceRecord::new(4, 0, 5))
+        );
+        assert_eq!(
+            PriceRecord::new(25, 1, 5)
+                .with_confidence(Some(1))
+                .route_through(PriceRecord::new(92, 2, 3).with_confidence(Some(1))),
+            Some(PriceRecord::new(2300, 3, 3))
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        // 1 / 0.25 = 4
+        assert_eq!(
+            PriceRecord::new(25, 2, 5).inverse(0),
+            Some(PriceRecord::new(4, 0, 5))
+        );
+        // 1 / 3 = 0.333
+        assert_eq!(
+            PriceRecord::new(3, 0, 5).inverse(3),
+            Some(PriceRecord::new(333, 3, 5))
+        );
+        assert_eq!(PriceRecord::new(0, 0, 5).inverse(3), None);
+        assert_eq!(PriceRecord::new(3, 0, 5).inverse(0), None);
+        assert_eq!(PriceRecord::new(1, 0, 5).inverse(20), None);
+        assert_eq!(PriceRecord::new(1, 255, 5).inverse(255), None);
+        assert_eq!(PriceRecord::new(1, 0, 5).inverse(MAX_DECIMALS + 1), None);
+    }
+
+    #[test]
+    fn route_through() {
+        // 2.5 * 0.92 = 2.3
+        assert_eq!(
+            PriceRecord::new(25, 1, 5).route_through(PriceRecord::new(92, 2, 3)),
+            Some(PriceRecord::new(2300, 3, 3))
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 2, 5).route_through(PriceRecord::new(10, 1, 7)),
+            Some(PriceRecord::new(u64::MAX, 2, 5))
+        );
+        assert_eq!(
+            PriceRecord::new(1, 200, 5).route_through(PriceRecord::new(100, 200, 7)),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(1_200_000, 60, 5).route_through(PriceRecord::new(10, 20, 7)),
+            Some(PriceRecord::new(12_000, MAX_DECIMALS, 5))
+        );
+        assert_eq!(
+            PriceRecord::new(0, 0, 5).route_through(PriceRecord::new(1, 0, 7)),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 0, 5).route_through(PriceRecord::new(u64::MAX, 0, 7)),
+            None
+        );
+    }
+
+    #[test]
+    fn amount_in_minor_units() {
+        // 10 units at 0.25 = 2.50 = 250 cents.
+        assert_eq!(
+            PriceRecord::new(25, 2, 0).amount_in_minor_units(10, 2),
+            Some(250)
+        );
+        // 3 units at 1.23456 = 3.70368, rounded down to 370 cents.
+        assert_eq!(
+            PriceRecord::new(123_456, 5, 0).amount_in_minor_units(3, 2),
+            Some(370)
+        );
+        // 2 units at 0.5 = 1 = 1_000_000 units having 6 decimals.
+        assert_eq!(
+            PriceRecord::new(5, 1, 0).amount_in_minor_units(2, 6),
+            Some(1_000_000)
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 255, 0).amount_in_minor_units(u128::MAX, 0),
+            Some(0)
+        );
+        assert_eq!(
+            PriceRecord::new(2, 0, 0).amount_in_minor_units(u128::MAX, 0),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(1, 0, 0).amount_in_minor_units(1, 255),
+            None
+        );
+    }
+
+    #[test]
+    fn rescaled_to_significant_digits() {
+        assert_eq!(
+            PriceRecord::new(123456, 6, 7).rescaled_to_significant_digits(3),
+            Some(PriceRecord::new(123, 3, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(123999, 6, 7).rescaled_to_significant_digits(3),
+            Some(PriceRecord::new(123, 3, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(12, 1, 7).rescaled_to_significant_digits(5),
+            Some(PriceRecord::new(12000, 4, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(12345, 2, 7).rescaled_to_significant_digits(5),
+            Some(PriceRecord::new(12345, 2, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(0, 2, 7).rescaled_to_significant_digits(5),
+            Some(PriceRecord::new(0, 2, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 19, 7).rescaled_to_significant_digits(1),
+            Some(PriceRecord::new(1, 0, 7))
+        );
+        assert_eq!(
+            PriceRecord::new(u64::MAX, 0, 7).rescaled_to_significant_digits(3),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(1, 0, 7).rescaled_to_significant_digits(21),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(1, 250, 7).rescaled_to_significant_digits(10),
+            None
+        );
+        assert_eq!(
+            PriceRecord::new(1, 0, 7).rescaled_to_significant_digits(0),
+            None
+        );
+    }
+
+    #[test]
+    fn builder() {
+        assert_eq!(
+            PriceRecordBuilder::new(100, 2, 5).build(),
+            Ok(PriceRecord::new(100, 2, 5))
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(0, 2, 5).build(),
+            Err(PriceRecordError::ZeroAmount)
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(0, 2, 5).allow_zero_amount().build(),
+            Ok(PriceRecord::new(0, 2, 5))
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 19, 5).max_decimals(18).build(),
+            Err(PriceRecordError::TooManyDecimals)
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 18, 5).max_decimals(18).build(),
+            Ok(PriceRecord::new(100, 18, 5))
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, MAX_DECIMALS + 1, 5).build(),
+            Err(PriceRecordError::TooManyDecimals)
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, MAX_DECIMALS + 1, 5)
+                .max_decimals(u8::MAX)
+                .build(),
+            Err(PriceRecordError::TooManyDecimals)
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 2, 5)
+                .with_previous(Some(PriceRecord::new(10, 1, 6)))
+                .build(),
+            Err(PriceRecordError::NonMonotonicBlockNumber)
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 2, 5)
+                .with_previous(Some(PriceRecord::new(10, 1, 5)))
+                .build(),
+            Ok(PriceRecord::new(100, 2, 5))
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 2, 5)
+                .with_previous(None)
+                .build(),
+            Ok(PriceRecord::new(100, 2, 5))
+        );
+        assert_eq!(
+            PriceRecordBuilder::new(100, 2, 5)
+                .confidence(Some(3))
+                .build(),
+            Ok(PriceRecord::new(100, 2, 5).with_confidence(Some(3)))
+        );
+    }
 }
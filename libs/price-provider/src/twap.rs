@@ -0,0 +1,127 @@
+//! Time-weighted average price (TWAP) computation over the price history.
+
+use crate::PriceRecord;
+use sp_core::U256;
+use sp_runtime::traits::{CheckedConversion, UniqueSaturatedInto};
+
+/// Computes the time-weighted average price over blocks `start..end` using the records sorted by block number.
+/// Each record's price is considered valid from its block until the block of the next record, so the last
+/// record at or before `start` weighs in as well. Blocks preceding the first record aren't covered by any price
+/// and thus ignored. If `start == end`, the latest record at or before `end` is returned with the block `end`.
+///
+/// The amounts are normalized to the max decimals of the weighed records. If the resulting amount doesn't fit
+/// into `u64`, decimals get reduced truncating the extra digits. The resulting record has the block number `end`.
+///
+/// Returns `None` if `start > end`, there're no records at or before `end`, or the arithmetic overflows.
+pub fn time_weighted_average<BlockNumber>(
+    records: &[PriceRecord<BlockNumber>],
+    start: BlockNumber,
+    end: BlockNumber,
+) -> Option<PriceRecord<BlockNumber>>
+where
+    BlockNumber: UniqueSaturatedInto<u64> + Copy,
+{
+    let block_of = |record: &PriceRecord<BlockNumber>| -> u64 {
+        record.block_number().unique_saturated_into()
+    };
+    let (from, to): (u64, u64) = (start.unique_saturated_into(), end.unique_saturated_into());
+    if from > to {
+        return None;
+    }
+
+    // The last record at or before `start` and all records within `start..=end`.
+    let first_idx = records
+        .partition_point(|record| block_of(record) <= from)
+        .saturating_sub(1);
+    let end_idx = records.partition_point(|record| block_of(record) <= to);
+    let records = records.get(first_idx..end_idx)?;
+
+    let mut decimals = records.iter().map(PriceRecord::decimals).max()?;
+    let normalize = |record: &PriceRecord<BlockNumber>| {
+        U256::from(10u8)
+            .checked_pow((decimals - record.decimals()).into())?
+            .checked_mul(record.amount().into())
+    };
+
+    let (mut weighted_sum, mut total_blocks) = (U256::zero(), 0u64);
+    for (idx, record) in records.iter().enumerate() {
+        let valid_from = block_of(record).max(from);
+        let valid_until = records.get(idx + 1).map_or(to, block_of);
+        let blocks = valid_until.saturating_sub(valid_from);
+
+        weighted_sum = weighted_sum.checked_add(normalize(record)?.checked_mul(blocks.into())?)?;
+        total_blocks += blocks;
+    }
+
+    let mut amount = if total_blocks == 0 {
+        normalize(records.last()?)?
+    } else {
+        weighted_sum / U256::from(total_blocks)
+    };
+    while amount > U256::from(u64::MAX) {
+        amount /= U256::from(10u8);
+        decimals = decimals.checked_sub(1)?;
+    }
+
+    Some(PriceRecord::new(
+        amount.checked_into()?,
+        decimals.checked_into()?,
+        end,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::time_weighted_average;
+    use crate::PriceRecord;
+
+    #[test]
+    fn twap() {
+        let history = [
+            PriceRecord::new(10, 0, 10u64),
+            PriceRecord::new(200, 1, 20),
+            PriceRecord::new(40, 0, 40),
+        ];
+
+        assert_eq!(time_weighted_average(&history, 0, 9), None);
+        assert_eq!(time_weighted_average(&history, 20, 10), None);
+        assert_eq!(time_weighted_average::<u64>(&[], 0, 10), None);
+        // Blocks before the first record are ignored.
+        assert_eq!(
+            time_weighted_average(&history, 0, 20),
+            Some(PriceRecord::new(100, 1, 20))
+        );
+        // 10 blocks of 10.0 and 10 blocks of 20.0.
+        assert_eq!(
+            time_weighted_average(&history, 10, 30),
+            Some(PriceRecord::new(150, 1, 30))
+        );
+        // 5 blocks of 10.0, 20 blocks of 20.0 and 10 blocks of 40.0.
+        assert_eq!(
+            time_weighted_average(&history, 15, 50),
+            Some(PriceRecord::new(242, 1, 50))
+        );
+        // The record preceding the window is valid for its whole length.
+        assert_eq!(
+            time_weighted_average(&history, 25, 35),
+            Some(PriceRecord::new(200, 1, 35))
+        );
+        assert_eq!(
+            time_weighted_average(&history, 45, 45),
+            Some(PriceRecord::new(40, 0, 45))
+        );
+
+        let extreme = [
+            PriceRecord::new(u64::MAX, 0, 0u64),
+            PriceRecord::new(1, 1, 1),
+        ];
+        assert_eq!(
+            time_weighted_average(&extreme, 0, 1),
+            Some(PriceRecord::new(u64::MAX, 0, 1))
+        );
+        assert_eq!(
+            time_weighted_average(&extreme, 0, 2),
+            Some(PriceRecord::new(u64::MAX / 2, 0, 2))
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! SCALE codecs of the price feed data for the embedded verifiers and WASM front-ends.
+//!
+//! Types have the same encoding as `price_provider::PriceRecord` and `price_provider::CurrencySymbolPair`
+//! (including `BoundedCurrencySymbolPair` which is encoded as a plain pair of strings), so the values read from
+//! the `dock-price-feed` storage, events or runtime API responses can be decoded without depending on Substrate.
+//! Depends only on `parity-scale-codec` and optionally `serde`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use codec::{Decode, Encode, MaxEncodedLen};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Price amount with specified decimals and the number of the block it was published at.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Hash, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PriceRecord<BlockNumber> {
+    /// Raw price amount. This value should be divided by 10^decimals to get a price per 1 unit.
+    pub amount: u64,
+    /// Represents precision. Used to allow storing decimal value as an integer.
+    pub decimals: u8,
+    /// Block number when this record was published.
+    pub block_number: BlockNumber,
+    /// Half-width of the confidence interval in the raw amount units. `None` if the uncertainty is unknown.
+    pub confidence: Option<u64>,
+}
+
+impl<BlockNumber> PriceRecord<BlockNumber> {
+    /// Constructs new `PriceRecord` with the given amount, decimals and block number without confidence interval.
+    pub const fn new(amount: u64, decimals: u8, block_number: BlockNumber) -> Self {
+        Self {
+            amount,
+            decimals,
+            block_number,
+            confidence: None,
+        }
+    }
+
+    /// Sets the half-width of the confidence interval in the raw amount units, `None` removes it.
+    pub fn with_confidence(self, confidence: Option<u64>) -> Self {
+        Self { confidence, ..self }
+    }
+}
+
+/// From/to currency symbol pair. Given some from/to pair price `N` should be considered as `1 x from = N x to`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencySymbolPair<From = String, To = String> {
+    /// Represents currency being valued.
+    pub from: From,
+    /// Used as a unit to express price.
+    pub to: To,
+}
+
+impl<From, To> CurrencySymbolPair<From, To> {
+    /// Instantiates new `CurrencySymbolPair` with the given symbols.
+    pub fn new(from: impl Into<From>, to: impl Into<To>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CurrencySymbolPair, PriceRecord};
+    use codec::{Decode, Encode, MaxEncodedLen};
+
+    #[test]
+    fn codec() {
+        let record = PriceRecord::new(1_234, 3, 5u32);
+        assert_eq!(
+            record.encode(),
+            [&1_234u64.encode()[..], &[3], &5u32.encode()[..], &[0]].concat()
+        );
+        assert_eq!(
+            record.with_confidence(Some(7)).encode(),
+            [&record.encode()[..13], &[1], &7u64.encode()[..]].concat()
+        );
+        assert_eq!(PriceRecord::<u32>::max_encoded_len(), 8 + 1 + 4 + 1 + 8);
+
+        let pair = CurrencySymbolPair::<String, String>::new("DOCK", "USD");
+        assert_eq!(pair.encode(), ("DOCK", "USD").encode());
+        assert_eq!(
+            CurrencySymbolPair::decode(&mut &pair.encode()[..]),
+            Ok(pair)
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use core::ops::{Deref, DerefMut};
+use frame_support::{
+    traits::Get, BoundedVec, CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+/// Queue holding up to `MaxLen` latest items in the order they were pushed.
+/// Pushing into the full queue evicts the oldest item.
+///
+/// Encoded the same way as `BoundedVec<T, MaxLen>`, so it can replace one in storage without a migration.
+#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, DebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(MaxLen))]
+pub struct BoundedFifo<T, MaxLen: Get<u32>>(BoundedVec<T, MaxLen>)
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug;
+
+impl<T, MaxLen> BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    /// Instantiates an empty queue.
+    pub fn new() -> Self {
+        Self(BoundedVec::default())
+    }
+
+    /// Appends the item evicting the oldest one if the queue is full. Returns the evicted item.
+    /// If `MaxLen` is `0`, nothing gets stored and the supplied item is returned back.
+    pub fn push(&mut self, item: T) -> Option<T> {
+        let max_len = MaxLen::get() as usize;
+        if max_len == 0 {
+            return Some(item);
+        }
+
+        let evicted = (self.0.len() >= max_len).then(|| self.0.remove(0));
+        // Can't fail since there's a free slot now.
+        let _ = self.0.try_push(item);
+
+        evicted
+    }
+
+    /// Returns the oldest item.
+    pub fn oldest(&self) -> Option<&T> {
+        self.0.first()
+    }
+
+    /// Returns the latest pushed item.
+    pub fn latest(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    /// Returns `true` if the next push will evict the oldest item.
+    pub fn is_full(&self) -> bool {
+        self.0.len() >= MaxLen::get() as usize
+    }
+
+    /// Retains only the items satisfying the predicate preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
+    /// Evicts the oldest items, so at most `len` latest items are left. Returns the amount of the evicted items.
+    pub fn keep_latest(&mut self, len: usize) -> usize {
+        let excess = self.0.len().saturating_sub(len);
+        let mut idx = 0;
+        self.0.retain(|_| {
+            idx += 1;
+            idx > excess
+        });
+
+        excess
+    }
+
+    /// Consumes self and returns the items from the oldest to the latest.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0.into_inner()
+    }
+}
+
+impl<T, MaxLen> Default for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, MaxLen> Deref for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0[..]
+    }
+}
+
+/// Allows to modify the items in place, the length can't be changed this way.
+impl<T, MaxLen> DerefMut for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0[..]
+    }
+}
+
+impl<T, MaxLen> TryFrom<Vec<T>> for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    type Error = ();
+
+    /// Returns `Err` if there're more than `MaxLen` items.
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        BoundedVec::try_from(items).map(Self)
+    }
+}
+
+impl<T, MaxLen> From<BoundedVec<T, MaxLen>> for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    fn from(items: BoundedVec<T, MaxLen>) -> Self {
+        Self(items)
+    }
+}
+
+impl<T, MaxLen> From<BoundedFifo<T, MaxLen>> for BoundedVec<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug,
+    MaxLen: Get<u32>,
+{
+    fn from(BoundedFifo(items): BoundedFifo<T, MaxLen>) -> Self {
+        items
+    }
+}
+
+impl<T, MaxLen> Encode for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug + Encode,
+    MaxLen: Get<u32>,
+{
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<O: codec::Output + ?Sized>(&self, dest: &mut O) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl<T, MaxLen> EncodeLike<BoundedVec<T, MaxLen>> for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug + Encode,
+    MaxLen: Get<u32>,
+{
+}
+
+impl<T, MaxLen> Decode for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug + Decode,
+    MaxLen: Get<u32>,
+{
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        BoundedVec::decode(input).map(Self)
+    }
+}
+
+impl<T, MaxLen> MaxEncodedLen for BoundedFifo<T, MaxLen>
+where
+    T: Clone + PartialEq + Eq + core::fmt::Debug + MaxEncodedLen,
+    MaxLen: Get<u32>,
+{
+    fn max_encoded_len() -> usize {
+        BoundedVec::<T, MaxLen>::max_encoded_len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codec::{Decode, Encode, MaxEncodedLen};
+    use frame_support::BoundedVec;
+    use sp_runtime::traits::ConstU32;
+
+    use super::BoundedFifo;
+
+    #[test]
+    fn push_evicts_oldest() {
+        let mut fifo = BoundedFifo::<u8, ConstU32<3>>::new();
+        assert!(fifo.is_empty());
+
+        assert_eq!(fifo.push(1), None);
+        assert_eq!(fifo.push(2), None);
+        assert!(!fifo.is_full());
+        assert_eq!(fifo.push(3), None);
+        assert!(fifo.is_full());
+        assert_eq!(fifo.push(4), Some(1));
+        assert_eq!(fifo.oldest(), Some(&2));
+        assert_eq!(fifo.latest(), Some(&4));
+        assert_eq!(fifo.into_inner(), vec![2, 3, 4]);
+
+        let mut empty = BoundedFifo::<u8, ConstU32<0>>::new();
+        assert_eq!(empty.push(1), Some(1));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn keep_latest() {
+        let mut fifo = BoundedFifo::<u8, ConstU32<3>>::try_from(vec![1, 2, 3]).unwrap();
+
+        assert_eq!(fifo.keep_latest(3), 0);
+        assert_eq!(fifo.keep_latest(1), 2);
+        assert_eq!(fifo.clone().into_inner(), vec![3]);
+        assert_eq!(fifo.keep_latest(0), 1);
+        assert!(fifo.is_empty());
+    }
+
+    #[test]
+    fn codec() {
+        let fifo = BoundedFifo::<u8, ConstU32<3>>::try_from(vec![1, 2]).unwrap();
+        let bounded_vec = BoundedVec::<u8, ConstU32<3>>::try_from(vec![1, 2]).unwrap();
+
+        assert_eq!(fifo.encode(), bounded_vec.encode());
+        assert_eq!(
+            BoundedFifo::<u8, ConstU32<3>>::decode(&mut &fifo.encode()[..]).unwrap(),
+            fifo
+        );
+        assert!(BoundedFifo::<u8, ConstU32<1>>::decode(&mut &fifo.encode()[..]).is_err());
+        assert!(BoundedFifo::<u8, ConstU32<1>>::try_from(vec![1, 2]).is_err());
+        assert_eq!(
+            BoundedFifo::<u8, ConstU32<3>>::max_encoded_len(),
+            BoundedVec::<u8, ConstU32<3>>::max_encoded_len()
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use core::{fmt::Display, ops::Deref};
+use frame_support::{
+    traits::Get, BoundedVec, CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound,
+};
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use codec::{Decode, Encode, EncodeLike, MaxEncodedLen};
+use scale_info::TypeInfo;
+
+use crate::{BoundedString, BoundedStringConversionError};
+
+/// Validated UTF-8 string stored inside of `BoundedVec<u8, MaxBytesLen>`.
+/// Has the same encoding as `String` and `BoundedString<MaxBytesLen, String>`, so can be used as a drop-in
+/// storage replacement with the max encoded length guaranteed by the underlying `BoundedVec`.
+#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, DebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(MaxBytesLen))]
+pub struct BoundedUtf8Bytes<MaxBytesLen: Get<u32>>(BoundedVec<u8, MaxBytesLen>);
+
+impl<MaxBytesLen: Get<u32>> BoundedUtf8Bytes<MaxBytesLen> {
+    /// Instantiates `Self` if byte size of the provided string doesn't exceed `MaxBytesLen`.
+    pub fn new(str: &str) -> Result<Self, BoundedStringConversionError> {
+        str.as_bytes()
+            .to_vec()
+            .try_into()
+            .map(Self)
+            .map_err(|_| BoundedStringConversionError::InvalidStringByteLen)
+    }
+
+    /// Returns underlying bytes as `&str`.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: bytes are validated to be UTF-8 on construction and decoding.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+
+    /// Returns underlying UTF-8 bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consumes self and returns underlying `BoundedVec<u8, MaxBytesLen>` value.
+    pub fn into_inner(self) -> BoundedVec<u8, MaxBytesLen> {
+        self.0
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> Deref for BoundedUtf8Bytes<MaxBytesLen> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> Default for BoundedUtf8Bytes<MaxBytesLen> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> TryFrom<String> for BoundedUtf8Bytes<MaxBytesLen> {
+    type Error = BoundedStringConversionError;
+
+    fn try_from(str: String) -> Result<Self, Self::Error> {
+        str.into_bytes()
+            .try_into()
+            .map(Self)
+            .map_err(|_| BoundedStringConversionError::InvalidStringByteLen)
+    }
+}
+
+impl<'a, MaxBytesLen: Get<u32>> TryFrom<&'a str> for BoundedUtf8Bytes<MaxBytesLen> {
+    type Error = BoundedStringConversionError;
+
+    fn try_from(str: &'a str) -> Result<Self, Self::Error> {
+        Self::new(str)
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> From<BoundedUtf8Bytes<MaxBytesLen>> for String {
+    fn from(BoundedUtf8Bytes(bytes): BoundedUtf8Bytes<MaxBytesLen>) -> Self {
+        // SAFETY: bytes are validated to be UTF-8 on construction and decoding.
+        unsafe { String::from_utf8_unchecked(bytes.into_inner()) }
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> From<BoundedString<MaxBytesLen, String>>
+    for BoundedUtf8Bytes<MaxBytesLen>
+{
+    fn from(str: BoundedString<MaxBytesLen, String>) -> Self {
+        Self::try_from(str.into_inner())
+            .expect("Both types share the same byte limit, so this conversion can't fail")
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> From<BoundedUtf8Bytes<MaxBytesLen>>
+    for BoundedString<MaxBytesLen, String>
+{
+    fn from(bytes: BoundedUtf8Bytes<MaxBytesLen>) -> Self {
+        BoundedString::new(bytes.into())
+            .expect("Both types share the same byte limit, so this conversion can't fail")
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> PartialOrd for BoundedUtf8Bytes<MaxBytesLen> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> Ord for BoundedUtf8Bytes<MaxBytesLen> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> Display for BoundedUtf8Bytes<MaxBytesLen> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> Encode for BoundedUtf8Bytes<MaxBytesLen> {
+    fn encode_to<T: codec::Output + ?Sized>(&self, dest: &mut T) {
+        self.0.encode_to(dest)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        self.0.encode()
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> EncodeLike<String> for BoundedUtf8Bytes<MaxBytesLen> {}
+
+impl<MaxBytesLen: Get<u32>> Decode for BoundedUtf8Bytes<MaxBytesLen> {
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let bytes = BoundedVec::<u8, MaxBytesLen>::decode(input)?;
+        core::str::from_utf8(&bytes).map_err(|_| "Invalid UTF-8 string bytes")?;
+
+        Ok(Self(bytes))
+    }
+}
+
+impl<MaxBytesLen: Get<u32>> MaxEncodedLen for BoundedUtf8Bytes<MaxBytesLen> {
+    fn max_encoded_len() -> usize {
+        BoundedVec::<u8, MaxBytesLen>::max_encoded_len()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<MaxBytesLen: Get<u32>> Serialize for BoundedUtf8Bytes<MaxBytesLen> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, MaxBytesLen: Get<u32>> Deserialize<'de> for BoundedUtf8Bytes<MaxBytesLen> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let str = String::deserialize(deserializer)?;
+
+        Self::try_from(str).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codec::{Decode, Encode, MaxEncodedLen};
+    use sp_runtime::traits::ConstU32;
+
+    use crate::{BoundedString, BoundedStringConversionError, BoundedUtf8Bytes};
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn serde() {
+        let serialized =
+            serde_json::to_string(&BoundedUtf8Bytes::<ConstU32<10>>::new("ABC").unwrap()).unwrap();
+        assert_eq!(serialized, "\"ABC\"");
+
+        let deserialized: BoundedUtf8Bytes<ConstU32<3>> = serde_json::from_str("\"CDE\"").unwrap();
+        assert_eq!(deserialized.as_str(), "CDE");
+
+        assert!(serde_json::from_str::<'_, BoundedUtf8Bytes<ConstU32<2>>>("\"CDE\"").is_err());
+    }
+
+    #[test]
+    fn workflow() {
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<3>>::new("ABDE"),
+            Err(BoundedStringConversionError::InvalidStringByteLen)
+        );
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<3>>::new("🦅"),
+            Err(BoundedStringConversionError::InvalidStringByteLen)
+        );
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<4>>::new("🦅").unwrap().as_str(),
+            "🦅"
+        );
+        assert_eq!(
+            &BoundedUtf8Bytes::<ConstU32<4>>::new("ABDE").unwrap()[1..3],
+            "BD"
+        );
+        assert_eq!(
+            String::from(BoundedUtf8Bytes::<ConstU32<4>>::try_from("ABDE".to_string()).unwrap()),
+            "ABDE"
+        );
+    }
+
+    #[test]
+    fn encode_decode() {
+        let bytes = BoundedUtf8Bytes::<ConstU32<5>>::new("ABCDE").unwrap();
+        let str = BoundedString::<ConstU32<5>>::new("ABCDE".to_string()).unwrap();
+
+        assert_eq!(bytes.encode(), str.encode());
+        assert_eq!(bytes.encode(), "ABCDE".to_string().encode());
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<5>>::decode(&mut &str.encode()[..]).unwrap(),
+            bytes
+        );
+        assert!(BoundedUtf8Bytes::<ConstU32<4>>::decode(&mut &str.encode()[..]).is_err());
+        assert!(
+            BoundedUtf8Bytes::<ConstU32<4>>::decode(&mut &vec![0xffu8, 0xfe].encode()[..]).is_err()
+        );
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<10>>::max_encoded_len(),
+            BoundedString::<ConstU32<10>>::max_encoded_len()
+        );
+        assert_eq!(
+            BoundedUtf8Bytes::<ConstU32<1000>>::max_encoded_len(),
+            BoundedString::<ConstU32<1000>>::max_encoded_len()
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        let str = BoundedString::<ConstU32<5>>::new("ABCDE".to_string()).unwrap();
+        let bytes: BoundedUtf8Bytes<ConstU32<5>> = str.clone().into();
+
+        assert_eq!(bytes.as_str(), "ABCDE");
+        assert_eq!(BoundedString::from(bytes), str);
+    }
+}
@@ -1,4 +1,10 @@
-use sp_runtime::DispatchResult;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+    traits::Get, BoundedVec, CloneNoBound, DebugNoBound, DefaultNoBound, EqNoBound,
+    PartialEqNoBound,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchError, DispatchResult};
 
 /// Identity-related operations.
 pub trait Identity {
@@ -15,6 +21,135 @@ pub trait Identity {
 
     /// Adds justification for the underlying identity.
     fn verify(&mut self, justification: Self::Justification) -> DispatchResult;
+
+    /// Returns amount of justifications accumulated by the underlying identity.
+    fn justification_count(&self) -> u32 {
+        self.verified().into()
+    }
+
+    /// Returns amount of justifications required for the underlying identity to become verified.
+    fn quorum(&self) -> u32 {
+        1
+    }
+
+    /// Returns reasons of the previous revocations of the underlying identity, oldest first.
+    /// The history is bounded by the implementation's storage limits.
+    fn revocation_history(&self) -> &[RevocationReason] {
+        &[]
+    }
+}
+
+/// Describes why an identity was removed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum RevocationReason {
+    /// Identity was removed by its owner.
+    Voluntary,
+    /// Identity was revoked to comply with the regulatory requirements.
+    Compliance,
+    /// Identity was revoked due to the misbehaviour of its owner.
+    Misconduct,
+}
+
+/// Identity which becomes verified once it accumulates `Quorum` justifications from distinct verifiers.
+/// Each justification is a pair of the verifier and the proof supplied by it.
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    DebugNoBound,
+    DefaultNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(Quorum, MaxJustifications))]
+#[codec(mel_bound(Info: MaxEncodedLen, Verifier: MaxEncodedLen, Proof: MaxEncodedLen))]
+pub struct QuorumIdentity<Info, Verifier, Proof, Quorum, MaxJustifications>
+where
+    Info: Clone + PartialEq + Eq + core::fmt::Debug + Default,
+    Verifier: Clone + PartialEq + Eq + core::fmt::Debug,
+    Proof: Clone + PartialEq + Eq + core::fmt::Debug,
+    Quorum: Get<u32>,
+    MaxJustifications: Get<u32>,
+{
+    /// Identity information.
+    pub info: Info,
+    /// Justifications supplied by distinct verifiers.
+    justifications: BoundedVec<(Verifier, Proof), MaxJustifications>,
+    #[codec(skip)]
+    _marker: core::marker::PhantomData<Quorum>,
+}
+
+impl<Info, Verifier, Proof, Quorum, MaxJustifications>
+    QuorumIdentity<Info, Verifier, Proof, Quorum, MaxJustifications>
+where
+    Info: Clone + PartialEq + Eq + core::fmt::Debug + Default,
+    Verifier: Clone + PartialEq + Eq + core::fmt::Debug,
+    Proof: Clone + PartialEq + Eq + core::fmt::Debug,
+    Quorum: Get<u32>,
+    MaxJustifications: Get<u32>,
+{
+    /// Instantiates new unverified identity with the supplied information.
+    pub fn new(info: Info) -> Self {
+        Self {
+            info,
+            justifications: Default::default(),
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns justifications accumulated so far.
+    pub fn justifications(&self) -> &[(Verifier, Proof)] {
+        &self.justifications
+    }
+
+    /// Returns `true` if the given verifier already justified the underlying identity.
+    pub fn justified_by(&self, verifier: &Verifier) -> bool {
+        self.justifications.iter().any(|(who, _)| who == verifier)
+    }
+}
+
+impl<Info, Verifier, Proof, Quorum, MaxJustifications> Identity
+    for QuorumIdentity<Info, Verifier, Proof, Quorum, MaxJustifications>
+where
+    Info: Clone + PartialEq + Eq + core::fmt::Debug + Default,
+    Verifier: Clone + PartialEq + Eq + core::fmt::Debug + Default,
+    Proof: Clone + PartialEq + Eq + core::fmt::Debug + Default,
+    Quorum: Get<u32>,
+    MaxJustifications: Get<u32>,
+{
+    type Info = Info;
+    type Justification = (Verifier, Proof);
+
+    fn verified(&self) -> bool {
+        self.justification_count() >= self.quorum()
+    }
+
+    fn info(&self) -> Self::Info {
+        self.info.clone()
+    }
+
+    fn verify(&mut self, (verifier, proof): Self::Justification) -> DispatchResult {
+        if self.justified_by(&verifier) {
+            return Err(DispatchError::Other(
+                "Identity is already justified by this verifier",
+            ));
+        }
+
+        self.justifications
+            .try_push((verifier, proof))
+            .map_err(|_| DispatchError::Other("Too many justifications"))
+    }
+
+    fn justification_count(&self) -> u32 {
+        self.justifications.len() as u32
+    }
+
+    fn quorum(&self) -> u32 {
+        Quorum::get()
+    }
 }
 
 /// Provides methods to retrieve an account's identity.
@@ -26,6 +161,117 @@ pub trait IdentityProvider<T: frame_system::Config> {
     fn identity(who: &T::AccountId) -> Option<Self::Identity>;
 }
 
+/// Identity returned by one of two identity providers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum EitherIdentity<A, B> {
+    /// Identity returned by the first provider.
+    Left(A),
+    /// Identity returned by the second provider.
+    Right(B),
+}
+
+impl<A, B> Identity for EitherIdentity<A, B>
+where
+    A: Identity,
+    B: Identity<Info = A::Info, Justification = A::Justification>,
+{
+    type Info = A::Info;
+    type Justification = A::Justification;
+
+    fn verified(&self) -> bool {
+        match self {
+            Self::Left(identity) => identity.verified(),
+            Self::Right(identity) => identity.verified(),
+        }
+    }
+
+    fn info(&self) -> Self::Info {
+        match self {
+            Self::Left(identity) => identity.info(),
+            Self::Right(identity) => identity.info(),
+        }
+    }
+
+    fn verify(&mut self, justification: Self::Justification) -> DispatchResult {
+        match self {
+            Self::Left(identity) => identity.verify(justification),
+            Self::Right(identity) => identity.verify(justification),
+        }
+    }
+
+    fn justification_count(&self) -> u32 {
+        match self {
+            Self::Left(identity) => identity.justification_count(),
+            Self::Right(identity) => identity.justification_count(),
+        }
+    }
+
+    fn quorum(&self) -> u32 {
+        match self {
+            Self::Left(identity) => identity.quorum(),
+            Self::Right(identity) => identity.quorum(),
+        }
+    }
+
+    fn revocation_history(&self) -> &[RevocationReason] {
+        match self {
+            Self::Left(identity) => identity.revocation_history(),
+            Self::Right(identity) => identity.revocation_history(),
+        }
+    }
+}
+
+/// Queries identity from `A` and falls back to `B` if `A` doesn't have an identity for the account.
+/// Allows to combine several identity backends (e.g. on-chain identity pallet and DID-backed provider)
+/// behind a single `IdentityProvider`.
+pub struct EitherIdentityProvider<A, B>(core::marker::PhantomData<(A, B)>);
+
+impl<T, A, B> IdentityProvider<T> for EitherIdentityProvider<A, B>
+where
+    T: frame_system::Config,
+    A: IdentityProvider<T>,
+    B: IdentityProvider<T>,
+    B::Identity: Identity<
+        Info = <A::Identity as Identity>::Info,
+        Justification = <A::Identity as Identity>::Justification,
+    >,
+{
+    type Identity = EitherIdentity<A::Identity, B::Identity>;
+
+    fn identity(who: &T::AccountId) -> Option<Self::Identity> {
+        A::identity(who)
+            .map(EitherIdentity::Left)
+            .or_else(|| B::identity(who).map(EitherIdentity::Right))
+    }
+}
+
+/// Same as `EitherIdentityProvider<A, B>`.
+impl<T, A, B> IdentityProvider<T> for (A, B)
+where
+    T: frame_system::Config,
+    EitherIdentityProvider<A, B>: IdentityProvider<T>,
+{
+    type Identity = <EitherIdentityProvider<A, B> as IdentityProvider<T>>::Identity;
+
+    fn identity(who: &T::AccountId) -> Option<Self::Identity> {
+        EitherIdentityProvider::<A, B>::identity(who)
+    }
+}
+
+/// Same as `EitherIdentityProvider<A, EitherIdentityProvider<B, C>>`.
+impl<T, A, B, C> IdentityProvider<T> for (A, B, C)
+where
+    T: frame_system::Config,
+    EitherIdentityProvider<A, EitherIdentityProvider<B, C>>: IdentityProvider<T>,
+{
+    type Identity =
+        <EitherIdentityProvider<A, EitherIdentityProvider<B, C>> as IdentityProvider<T>>::Identity;
+
+    fn identity(who: &T::AccountId) -> Option<Self::Identity> {
+        EitherIdentityProvider::<A, EitherIdentityProvider<B, C>>::identity(who)
+    }
+}
+
 /// Provides methods to set an account's identity.
 pub trait IdentitySetter<T: frame_system::Config>: IdentityProvider<T> {
     /// Attempts to set identity for the account.
@@ -42,4 +288,67 @@ pub trait IdentitySetter<T: frame_system::Config>: IdentityProvider<T> {
 
     /// Attempts to remove identity of the account.
     fn remove_identity(who: &T::AccountId) -> DispatchResult;
+
+    /// Attempts to remove identity of the account recording the supplied reason.
+    /// By default, the reason is discarded and the identity is removed using `remove_identity`.
+    fn remove_identity_with_reason(who: &T::AccountId, reason: RevocationReason) -> DispatchResult {
+        let _ = reason;
+
+        Self::remove_identity(who)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EitherIdentity, Identity, QuorumIdentity};
+    use sp_runtime::{traits::ConstU32, DispatchError};
+
+    type TestIdentity = QuorumIdentity<u8, u64, (), ConstU32<2>, ConstU32<3>>;
+
+    #[test]
+    fn quorum_identity() {
+        let mut identity = TestIdentity::new(1);
+        assert_eq!(identity.info(), 1);
+        assert_eq!(identity.quorum(), 2);
+        assert_eq!(identity.justification_count(), 0);
+        assert!(!identity.verified());
+
+        identity.verify((10, ())).unwrap();
+        assert_eq!(identity.justification_count(), 1);
+        assert!(!identity.verified());
+
+        assert_eq!(
+            identity.verify((10, ())),
+            Err(DispatchError::Other(
+                "Identity is already justified by this verifier"
+            ))
+        );
+        assert_eq!(identity.justification_count(), 1);
+
+        identity.verify((11, ())).unwrap();
+        assert_eq!(identity.justification_count(), 2);
+        assert!(identity.verified());
+        assert!(identity.justified_by(&11));
+
+        identity.verify((12, ())).unwrap();
+        assert_eq!(
+            identity.verify((13, ())),
+            Err(DispatchError::Other("Too many justifications"))
+        );
+        assert_eq!(identity.justifications().len(), 3);
+    }
+
+    #[test]
+    fn either_identity() {
+        let mut identity: EitherIdentity<TestIdentity, TestIdentity> =
+            EitherIdentity::Right(TestIdentity::new(2));
+        assert_eq!(identity.info(), 2);
+        assert!(!identity.verified());
+
+        identity.verify((10, ())).unwrap();
+        identity.verify((11, ())).unwrap();
+        assert_eq!(identity.justification_count(), 2);
+        assert!(identity.verified());
+        assert!(matches!(identity, EitherIdentity::Right(_)));
+    }
 }
@@ -3,10 +3,29 @@
 
 extern crate alloc;
 
+pub mod bounded_fifo;
 pub mod bounded_string;
+pub mod bounded_utf8_bytes;
 pub mod div_ceil;
 pub mod identity_provider;
+pub mod paged;
+pub mod safe_mode;
+pub mod slashing;
 
+pub use bounded_fifo::*;
 pub use bounded_string::*;
+pub use bounded_utf8_bytes::*;
 pub use div_ceil::*;
 pub use identity_provider::*;
+pub use paged::*;
+pub use safe_mode::*;
+pub use slashing::*;
+
+/// Commonly co-used traits and types.
+pub mod prelude {
+    pub use crate::{
+        BoundedFifo, BoundedString, BoundedStringConversionError, BoundedUtf8Bytes, CheckedDivCeil,
+        DivCeil, EitherIdentityProvider, Identity, IdentityProvider, LikeString, PageCursor, Paged,
+        RevocationReason, SafeMode, SlashBeneficiary, SlashHandler,
+    };
+}
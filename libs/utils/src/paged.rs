@@ -0,0 +1,56 @@
+use codec::{Decode, Encode};
+use frame_support::storage::PrefixIterator;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::prelude::*;
+
+/// Opaque cursor allowing to resume the paginated iteration.
+/// Contains the raw storage key of the last returned item.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PageCursor(pub Vec<u8>);
+
+/// Single page of the items along with the cursor pointing to the next page.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Paged<T> {
+    /// Items of the page.
+    pub items: Vec<T>,
+    /// Cursor of the next page. `None` means that there are no more items.
+    /// The next page may be empty if the amount of the remaining items was equal to the page limit.
+    pub next: Option<PageCursor>,
+}
+
+impl<T> Default for Paged<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            next: None,
+        }
+    }
+}
+
+impl<T> Paged<T> {
+    /// Takes up to `limit` items from the supplied storage iterator.
+    pub fn from_prefix_iterator<OnRemoval>(
+        mut iter: PrefixIterator<T, OnRemoval>,
+        limit: u32,
+    ) -> Self {
+        let items: Vec<_> = iter.by_ref().take(limit as usize).collect();
+        let next = (!items.is_empty() && items.len() == limit as usize)
+            .then(|| PageCursor(iter.last_raw_key().to_vec()));
+
+        Self { items, next }
+    }
+
+    /// Maps items of the page using the supplied function preserving the cursor.
+    pub fn map<R, F: FnMut(T) -> R>(self, map: F) -> Paged<R> {
+        let Self { items, next } = self;
+
+        Paged {
+            items: items.into_iter().map(map).collect(),
+            next,
+        }
+    }
+}
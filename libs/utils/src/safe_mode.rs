@@ -0,0 +1,26 @@
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+
+/// Reports whether the chain is in safe mode, i.e. only a restricted set of the state-changing
+/// transactions is allowed. Pallets can consult it to reject their own transactions with explicit errors
+/// while keeping read paths functional.
+pub trait SafeMode {
+    /// Returns `true` if the chain is in safe mode.
+    fn is_entered() -> bool;
+}
+
+/// The chain never enters safe mode.
+impl SafeMode for () {
+    fn is_entered() -> bool {
+        false
+    }
+}
+
+/// Safe mode is entered when `Entered` returns `true`, e.g. a storage-backed parameter controlled by governance.
+pub struct SafeModeIf<Entered>(PhantomData<Entered>);
+
+impl<Entered: Get<bool>> SafeMode for SafeModeIf<Entered> {
+    fn is_entered() -> bool {
+        Entered::get()
+    }
+}
@@ -0,0 +1,71 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::{Currency, OnUnbalanced, ReservableCurrency};
+use scale_info::TypeInfo;
+
+/// Destination of the slashed funds.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum SlashBeneficiary<AccountId> {
+    /// Slashed funds are passed to the default handler of the slasher (e.g. treasury).
+    Default,
+    /// Slashed funds are deposited to the given account.
+    Account(AccountId),
+    /// Slashed funds are burned.
+    Burn,
+}
+
+impl<AccountId> Default for SlashBeneficiary<AccountId> {
+    fn default() -> Self {
+        Self::Default
+    }
+}
+
+/// Applies economic penalties to the accounts.
+pub trait SlashHandler<AccountId> {
+    /// Slashed amount.
+    type Balance;
+    /// Describes why the slash happened.
+    type Reason;
+
+    /// Slashes up to `amount` from the reserved balance of `who` for the given `reason`
+    /// and routes slashed funds to the `beneficiary`.
+    /// Returns the amount that couldn't be slashed.
+    fn slash_reserved(
+        who: &AccountId,
+        amount: Self::Balance,
+        reason: Self::Reason,
+        beneficiary: SlashBeneficiary<AccountId>,
+    ) -> Self::Balance;
+}
+
+/// Slashes reserved balance of `Currency`.
+/// Funds slashed for the `SlashBeneficiary::Default` are passed to `DefaultBeneficiary`.
+pub struct ReservedBalanceSlasher<Currency, DefaultBeneficiary, Reason = ()>(
+    PhantomData<(Currency, DefaultBeneficiary, Reason)>,
+);
+
+impl<AccountId, C, D, Reason> SlashHandler<AccountId> for ReservedBalanceSlasher<C, D, Reason>
+where
+    C: ReservableCurrency<AccountId>,
+    D: OnUnbalanced<<C as Currency<AccountId>>::NegativeImbalance>,
+{
+    type Balance = <C as Currency<AccountId>>::Balance;
+    type Reason = Reason;
+
+    fn slash_reserved(
+        who: &AccountId,
+        amount: Self::Balance,
+        _reason: Reason,
+        beneficiary: SlashBeneficiary<AccountId>,
+    ) -> Self::Balance {
+        let (imbalance, remainder) = C::slash_reserved(who, amount);
+
+        match beneficiary {
+            SlashBeneficiary::Default => D::on_unbalanced(imbalance),
+            SlashBeneficiary::Account(dest) => C::resolve_creating(&dest, imbalance),
+            SlashBeneficiary::Burn => drop(imbalance),
+        }
+
+        remainder
+    }
+}
@@ -0,0 +1,119 @@
+//! Reader of the price updates written by `dock-price-feed` into the offchain index.
+//! Requires the node to be running with `--enable-offchain-indexing`.
+
+use codec::{Decode, Encode};
+use dock_price_feed::{
+    offchain_index::{entry_key, latest_key, pair_hash, OffchainPriceEntry},
+    CurrencySymbolPair, LikeString,
+};
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+
+/// Reads price updates from the node's persistent offchain storage.
+#[derive(Clone)]
+pub struct OffchainPriceReader<S> {
+    storage: S,
+}
+
+impl<S: OffchainStorage> OffchainPriceReader<S> {
+    /// Instantiates new `OffchainPriceReader` using supplied offchain storage.
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Returns the latest indexed price update for the given pair.
+    pub fn latest<From, To, BlockNumber>(
+        &self,
+        pair: &CurrencySymbolPair<From, To>,
+    ) -> Option<OffchainPriceEntry<BlockNumber>>
+    where
+        From: LikeString,
+        To: LikeString,
+        BlockNumber: Encode + Decode,
+    {
+        let pair_hash = pair_hash(pair);
+        let block_number = self
+            .storage
+            .get(STORAGE_PREFIX, &latest_key(&pair_hash))
+            .and_then(|encoded| BlockNumber::decode(&mut &encoded[..]).ok())?;
+
+        self.entry(&pair_hash, &block_number)
+    }
+
+    /// Returns price update for the given pair indexed in the block with the supplied number.
+    pub fn at<From, To, BlockNumber>(
+        &self,
+        pair: &CurrencySymbolPair<From, To>,
+        block_number: &BlockNumber,
+    ) -> Option<OffchainPriceEntry<BlockNumber>>
+    where
+        From: LikeString,
+        To: LikeString,
+        BlockNumber: Encode + Decode,
+    {
+        self.entry(&pair_hash(pair), block_number)
+    }
+
+    fn entry<BlockNumber: Encode + Decode>(
+        &self,
+        pair_hash: &[u8; 32],
+        block_number: &BlockNumber,
+    ) -> Option<OffchainPriceEntry<BlockNumber>> {
+        self.storage
+            .get(STORAGE_PREFIX, &entry_key(pair_hash, block_number))
+            .and_then(|encoded| Decode::decode(&mut &encoded[..]).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dock_price_feed::PriceRecord;
+    use sp_core::offchain::storage::InMemOffchainStorage;
+
+    #[test]
+    fn read_entries() {
+        let mut storage = InMemOffchainStorage::default();
+        let pair = CurrencySymbolPair::new("DOCK", "USD");
+        let pair_hash = pair_hash(&pair);
+
+        for block_number in [1u32, 5] {
+            let entry = OffchainPriceEntry::new(
+                pair_hash,
+                &PriceRecord::new(block_number as u64 * 10, 2, block_number),
+            );
+
+            storage.set(
+                STORAGE_PREFIX,
+                &entry_key(&pair_hash, &block_number),
+                &entry.encode(),
+            );
+            storage.set(
+                STORAGE_PREFIX,
+                &latest_key(&pair_hash),
+                &block_number.encode(),
+            );
+        }
+
+        let reader = OffchainPriceReader::new(storage);
+
+        assert_eq!(
+            reader
+                .latest::<_, _, u32>(&pair)
+                .map(|entry| entry.record()),
+            Some(PriceRecord::new(50, 2, 5))
+        );
+        assert_eq!(
+            reader.at(&pair, &1u32).map(|entry| entry.record()),
+            Some(PriceRecord::new(10, 2, 1))
+        );
+        assert_eq!(reader.at(&pair, &2u32), None);
+        assert_eq!(
+            reader.latest::<_, _, u32>(&CurrencySymbolPair::new("DOCK", "EUR")),
+            None
+        );
+        assert_eq!(
+            reader.latest::<_, _, u32>(&pair.map_pair(ToOwned::to_owned)),
+            reader.at(&CurrencySymbolPair::new("DOCK", "USD"), &5u32)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Typed client of the `dock-price-feed` RPC with generic parameters pre-filled for the Dock node.
+
+use core::time::Duration;
+use dock_price_feed::{CurrencySymbolPair, PriceRecord, PriceStatus};
+use futures::{future::try_join_all, stream, Stream};
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::HttpClientBuilder,
+    types::error::{CallError, METHOD_NOT_FOUND_CODE},
+    ws_client::WsClientBuilder,
+};
+use sp_core::H256;
+
+pub use dock_price_feed_rpc::PriceFeedApiClient;
+pub use jsonrpsee::{core::Error, http_client::HttpClient, ws_client::WsClient};
+
+/// Hash of the block used by the node.
+pub type BlockHash = H256;
+/// Number of the block used by the node.
+pub type BlockNumber = u32;
+
+/// Client of the price feed RPC.
+/// Wraps any `jsonrpsee` client, use `connect_ws`/`connect_http` to instantiate it from the node's url.
+#[derive(Clone, Debug)]
+pub struct PriceFeedClient<C> {
+    client: C,
+}
+
+impl PriceFeedClient<WsClient> {
+    /// Connects to the node using websocket transport.
+    pub async fn connect_ws(url: &str) -> Result<Self, Error> {
+        WsClientBuilder::default().build(url).await.map(Self::new)
+    }
+}
+
+impl PriceFeedClient<HttpClient> {
+    /// Instantiates client using http transport.
+    pub fn connect_http(url: &str) -> Result<Self, Error> {
+        HttpClientBuilder::default().build(url).map(Self::new)
+    }
+}
+
+impl<C> PriceFeedClient<C>
+where
+    C: ClientT + Send + Sync,
+{
+    /// Instantiates new `PriceFeedClient` over the supplied `jsonrpsee` client.
+    pub fn new(client: C) -> Self {
+        Self { client }
+    }
+
+    /// Returns a reference to the underlying `jsonrpsee` client.
+    pub fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns the price of the supplied currency pair if it's present.
+    /// If `at` isn't supplied, the best block is used.
+    pub async fn price(
+        &self,
+        currency_pair: CurrencySymbolPair<String, String>,
+        at: Option<BlockHash>,
+    ) -> Result<Option<PriceRecord<BlockNumber>>, Error> {
+        PriceFeedApiClient::<BlockHash, BlockNumber>::price(&self.client, at, currency_pair).await
+    }
+
+    /// Returns the price of the supplied currency pair along with its staleness if it's present.
+    /// If `at` isn't supplied, the best block is used.
+    pub async fn price_status(
+        &self,
+        currency_pair: CurrencySymbolPair<String, String>,
+        at: Option<BlockHash>,
+    ) -> Result<Option<PriceStatus<BlockNumber>>, Error> {
+        PriceFeedApiClient::<BlockHash, BlockNumber>::price_status(&self.client, at, currency_pair)
+            .await
+    }
+
+    /// Returns prices of the supplied currency pairs in the same order using a single `price_feed_prices` request.
+    /// Nodes which don't provide it are sent concurrent `price_feed_price` requests instead,
+    /// all of them are made against the same block if `at` is supplied.
+    pub async fn prices<I>(
+        &self,
+        currency_pairs: I,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<Option<PriceRecord<BlockNumber>>>, Error>
+    where
+        I: IntoIterator<Item = CurrencySymbolPair<String, String>>,
+    {
+        let currency_pairs: Vec<_> = currency_pairs.into_iter().collect();
+
+        match PriceFeedApiClient::<BlockHash, BlockNumber>::prices(
+            &self.client,
+            at,
+            currency_pairs.clone(),
+        )
+        .await
+        {
+            Err(Error::Call(CallError::Custom(error))) if error.code() == METHOD_NOT_FOUND_CODE => {
+                try_join_all(
+                    currency_pairs
+                        .into_iter()
+                        .map(|currency_pair| self.price(currency_pair, at)),
+                )
+                .await
+            }
+            result => result,
+        }
+    }
+
+    /// Returns a stream of price updates for the supplied currency pair.
+    ///
+    /// The best block price is polled every `poll_interval` and an item is produced each time the observed
+    /// price record changes (including the first observation), so it works over any transport and with the nodes
+    /// which don't enable subscriptions. Websocket clients can use `PriceFeedApiClient::subscribe_price`
+    /// to receive finalized updates instead.
+    pub fn subscribe_price(
+        &self,
+        currency_pair: CurrencySymbolPair<String, String>,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<Option<PriceRecord<BlockNumber>>, Error>> + '_ {
+        stream::unfold(
+            (None, true),
+            move |(last, first): (Option<PriceRecord<BlockNumber>>, bool)| {
+                let currency_pair = currency_pair.clone();
+
+                async move {
+                    loop {
+                        if !first {
+                            tokio::time::sleep(poll_interval).await;
+                        }
+
+                        match self.price(currency_pair.clone(), None).await {
+                            Ok(price) if first || price != last => {
+                                return Some((Ok(price), (price, false)))
+                            }
+                            Ok(_) => continue,
+                            Err(err) => return Some((Err(err), (last, false))),
+                        }
+                    }
+                }
+            },
+        )
+    }
+}
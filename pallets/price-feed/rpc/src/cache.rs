@@ -0,0 +1,180 @@
+//! Node-side LRU cache of the prices read by the RPC server.
+
+use dock_price_feed::{CurrencySymbolPair, PriceRecord};
+use lru::LruCache;
+use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+/// Prometheus metrics of the price cache.
+#[derive(Clone)]
+pub struct PriceCacheMetrics {
+    hits: Counter<U64>,
+    misses: Counter<U64>,
+}
+
+impl PriceCacheMetrics {
+    /// Registers the metrics in the supplied registry.
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            hits: register(
+                Counter::new(
+                    "dock_price_feed_rpc_cache_hits",
+                    "Number of the price reads served from the RPC cache",
+                )?,
+                registry,
+            )?,
+            misses: register(
+                Counter::new(
+                    "dock_price_feed_rpc_cache_misses",
+                    "Number of the price reads which required a runtime API call",
+                )?,
+                registry,
+            )?,
+        })
+    }
+}
+
+/// LRU cache of the prices keyed by the block hash and the currency pair.
+/// Prices at the given block never change, so cached entries are never invalidated and only get evicted
+/// once the cache reaches its capacity.
+pub struct PriceCache<BlockHash: Hash + Eq, Number> {
+    entries: Mutex<
+        LruCache<(BlockHash, CurrencySymbolPair<String, String>), Option<PriceRecord<Number>>>,
+    >,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    metrics: Option<PriceCacheMetrics>,
+}
+
+impl<BlockHash: Hash + Eq, Number: Copy> PriceCache<BlockHash, Number> {
+    /// Instantiates new `PriceCache` holding up to `capacity` prices.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+            hits: Default::default(),
+            misses: Default::default(),
+            metrics: None,
+        }
+    }
+
+    /// Reports hits and misses to the supplied Prometheus metrics.
+    pub fn with_metrics(mut self, metrics: PriceCacheMetrics) -> Self {
+        self.metrics = Some(metrics);
+
+        self
+    }
+
+    /// Returns the cached price of the currency pair at the given block or reads it using `read`
+    /// and caches the result. Errors aren't cached.
+    pub fn get_or_try_insert_with<E>(
+        &self,
+        at: BlockHash,
+        pair: CurrencySymbolPair<String, String>,
+        read: impl FnOnce(&CurrencySymbolPair<String, String>) -> Result<Option<PriceRecord<Number>>, E>,
+    ) -> Result<Option<PriceRecord<Number>>, E> {
+        let key = (at, pair);
+        if let Some(price) = self.entries.lock().expect("Poisoned lock").get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            if let Some(metrics) = &self.metrics {
+                metrics.hits.inc();
+            }
+
+            return Ok(*price);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.misses.inc();
+        }
+
+        // The lock isn't held during the read, so concurrent misses of the same key may read it twice.
+        let price = read(&key.1)?;
+        self.entries.lock().expect("Poisoned lock").put(key, price);
+
+        Ok(price)
+    }
+
+    /// Returns the share of the reads served from the cache or `None` if nothing was read yet.
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let total = hits.saturating_add(self.misses.load(Ordering::Relaxed));
+
+        (total > 0).then(|| hits as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caches_by_block_and_pair() {
+        let cache = PriceCache::<u8, u32>::new(2);
+        let pair = |from: &str| CurrencySymbolPair::new(from.to_string(), "USD".to_string());
+        let record = PriceRecord::new(10, 2, 1);
+        let read = |_: &CurrencySymbolPair<String, String>| Ok::<_, ()>(Some(record));
+        let unreachable =
+            |_: &CurrencySymbolPair<String, String>| -> Result<_, ()> { panic!("Must be cached") };
+
+        assert_eq!(cache.hit_rate(), None);
+        assert_eq!(
+            cache.get_or_try_insert_with(1, pair("DOCK"), read),
+            Ok(Some(record))
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(1, pair("DOCK"), unreachable),
+            Ok(Some(record))
+        );
+        assert_eq!(cache.hit_rate(), Some(0.5));
+
+        // Other blocks and pairs are cached separately.
+        assert_eq!(
+            cache.get_or_try_insert_with(2, pair("DOCK"), |_| Ok::<_, ()>(None)),
+            Ok(None)
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(2, pair("DOCK"), unreachable),
+            Ok(None)
+        );
+
+        // Errors aren't cached.
+        assert_eq!(
+            cache.get_or_try_insert_with(1, pair("BTC"), |_| Err(())),
+            Err(())
+        );
+        assert_eq!(
+            cache.get_or_try_insert_with(1, pair("BTC"), read),
+            Ok(Some(record))
+        );
+
+        // The least recently used entry got evicted.
+        assert_eq!(
+            cache.get_or_try_insert_with(1, pair("DOCK"), |_| Ok::<_, ()>(None)),
+            Ok(None)
+        );
+        assert_eq!(cache.hit_rate(), Some(2.0 / 7.0));
+    }
+
+    #[test]
+    fn reports_metrics() {
+        let registry = Registry::new();
+        let metrics = PriceCacheMetrics::register(&registry).unwrap();
+        let cache = PriceCache::<u8, u32>::new(1).with_metrics(metrics.clone());
+        let pair = CurrencySymbolPair::new("DOCK".to_string(), "USD".to_string());
+
+        for _ in 0..3 {
+            cache
+                .get_or_try_insert_with(1, pair.clone(), |_| Ok::<_, ()>(None))
+                .unwrap();
+        }
+
+        assert_eq!(metrics.hits.get(), 2);
+        assert_eq!(metrics.misses.get(), 1);
+    }
+}
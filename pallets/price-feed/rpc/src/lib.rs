@@ -1,16 +1,86 @@
 use core::fmt::Debug;
 pub use dock_price_feed::runtime_api::PriceFeedApi as PriceFeedRuntimeApi;
-use dock_price_feed::{CurrencySymbolPair, PriceRecord};
+use dock_price_feed::{
+    CurrencySymbolPair, PageCursor, Paged, PriceRecord, PriceStatus, MAX_PAGE_LIMIT,
+};
+use futures::{future, FutureExt, StreamExt};
 use jsonrpsee::{
     core::{async_trait, Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
     types::{error::CallError, ErrorObject},
+    PendingSubscription,
 };
-use sp_api::{NumberFor, ProvideRuntimeApi};
+use sc_client_api::{BlockchainEvents, ProofProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiError, ApiExt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::{traits::SpawnNamed, Bytes};
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
+pub mod cache;
+pub mod limits;
+pub mod source;
+pub mod telemetry;
+pub mod version;
+pub use cache::{PriceCache, PriceCacheMetrics};
+pub use limits::PriceFeedLimits;
+pub use source::{AsyncPriceSource, PriceAt, RuntimePriceSource};
+pub use telemetry::PriceFeedTelemetry;
+use version::{ensure_extended_queries, price_feed_api_version, supports_extended_queries};
+
+/// Code of the error returned for subscriptions to the server which wasn't supplied with the executor,
+/// see [`PriceFeed::with_subscriptions`].
+pub const SUBSCRIPTIONS_DISABLED_ERROR_CODE: i32 = 4;
+
+/// Price record along with the proof of its presence in the storage of the given block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceWithProof<BlockHash, Number> {
+    /// Hash of the block the proof was built for.
+    pub at: BlockHash,
+    /// Price record of the currency pair.
+    pub record: PriceRecord<Number>,
+    /// Storage key of the price record.
+    pub key: Bytes,
+    /// Storage proof nodes which can be verified against the state root of the block.
+    pub proof: Vec<Bytes>,
+}
+
+/// Price record of the currency pair proven by the [`PricesWithProof::proof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenPrice<Number> {
+    /// Price record of the currency pair.
+    pub record: PriceRecord<Number>,
+    /// Storage key of the price record.
+    pub key: Bytes,
+}
+
+/// Price records of multiple currency pairs along with the single proof of their presence
+/// in the storage of the given block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricesWithProof<BlockHash, Number> {
+    /// Hash of the block the proof was built for.
+    pub at: BlockHash,
+    /// Price records of the currency pairs in the requested order. `None` if the price isn't present.
+    pub prices: Vec<Option<ProvenPrice<Number>>>,
+    /// Storage proof nodes covering all present prices which can be verified against the state root of the block.
+    /// Trie nodes shared by multiple records are included only once.
+    pub proof: Vec<Bytes>,
+}
+
+/// Currency pair having a price along with its latest price record if requested.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListedPair<Number> {
+    /// Currency pair.
+    pub pair: CurrencySymbolPair<String, String>,
+    /// Latest price record of the currency pair, `None` unless requested.
+    pub record: Option<PriceRecord<Number>>,
+}
+
 #[rpc(server, client)]
 pub trait PriceFeedApi<BlockHash, Number> {
     /// Returns the price of the supplied currency pair if it's present.
@@ -20,6 +90,77 @@ pub trait PriceFeedApi<BlockHash, Number> {
         at: Option<BlockHash>,
         currency_pair: CurrencySymbolPair<String, String>,
     ) -> RpcResult<Option<PriceRecord<Number>>>;
+
+    /// Returns the price of the supplied currency pair along with the storage proof if the price is present.
+    /// The proof allows light clients to verify the price without trusting the RPC node.
+    /// Fails if the runtime at the requested block provides only the `price` method.
+    #[method(name = "price_feed_priceWithProof")]
+    async fn price_with_proof(
+        &self,
+        at: Option<BlockHash>,
+        currency_pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Option<PriceWithProof<BlockHash, Number>>>;
+
+    /// Returns prices of the supplied currency pairs along with the single storage proof covering all of them.
+    /// Unlike multiple `price_feed_priceWithProof` calls, shared trie nodes are transferred only once.
+    /// Fails if the runtime at the requested block provides only the `price` method.
+    #[method(name = "price_feed_pricesWithProof")]
+    async fn prices_with_proof(
+        &self,
+        at: Option<BlockHash>,
+        currency_pairs: Vec<CurrencySymbolPair<String, String>>,
+    ) -> RpcResult<PricesWithProof<BlockHash, Number>>;
+
+    /// Returns the price of the supplied currency pair along with its staleness if the price is present.
+    /// Fails if the runtime at the requested block provides only the `price` method.
+    #[method(name = "price_feed_priceStatus")]
+    async fn price_status(
+        &self,
+        at: Option<BlockHash>,
+        currency_pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Option<PriceStatus<Number>>>;
+
+    /// Returns prices of the supplied currency pairs in the requested order.
+    /// Runtimes which don't provide the batch query are called once per pair.
+    #[method(name = "price_feed_prices")]
+    async fn prices(
+        &self,
+        at: Option<BlockHash>,
+        currency_pairs: Vec<CurrencySymbolPair<String, String>>,
+    ) -> RpcResult<Vec<Option<PriceRecord<Number>>>>;
+
+    /// Returns the latest price records of the supplied currency pair ordered by block number.
+    /// Fails if the runtime at the requested block doesn't provide the price history.
+    #[method(name = "price_feed_priceHistory")]
+    async fn price_history(
+        &self,
+        at: Option<BlockHash>,
+        currency_pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Vec<PriceRecord<Number>>>;
+
+    /// Returns a page of the currency pairs having a price starting after the supplied cursor,
+    /// along with their latest price records if `with_prices` is `true`.
+    /// The page holds up to `limit` pairs, capped by `max_batch_len` of the limits and the runtime's page limit.
+    /// Fails if the runtime at the requested block provides only the `price` method.
+    #[method(name = "price_feed_listPairs")]
+    async fn list_pairs(
+        &self,
+        at: Option<BlockHash>,
+        with_prices: bool,
+        cursor: Option<PageCursor>,
+        limit: Option<u32>,
+    ) -> RpcResult<Paged<ListedPair<Number>>>;
+
+    /// Pushes the price record of the supplied currency pair each time it's updated by a finalized block,
+    /// i.e. the block's `PriceSet` event (or the throttled update) gets finalized.
+    /// Records are compared at the consecutive finality notifications, so multiple updates finalized at once
+    /// are pushed as the latest one. Removed prices aren't pushed.
+    #[subscription(
+        name = "price_feed_subscribePrice" => "price_feed_price",
+        unsubscribe = "price_feed_unsubscribePrice",
+        item = PriceRecord<Number>
+    )]
+    fn subscribe_price(&self, currency_pair: CurrencySymbolPair<String, String>);
 }
 
 #[derive(Debug, Clone)]
@@ -37,27 +178,86 @@ impl<T: Debug> From<RuntimeError<T>> for JsonRpseeError {
     }
 }
 
+/// Reads prices of the supplied currency pairs using the batch runtime API call if the runtime supports it,
+/// falling back to the per-pair calls otherwise.
+fn read_prices<Block, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    pairs: &[CurrencySymbolPair<String, String>],
+) -> Result<Vec<Option<PriceRecord<NumberFor<Block>>>>, ApiError>
+where
+    Block: BlockT,
+    Api: ApiExt<Block> + PriceFeedRuntimeApi<Block, NumberFor<Block>>,
+{
+    if supports_extended_queries(price_feed_api_version(api, at)?) {
+        api.prices(at, pairs.to_vec())
+    } else {
+        pairs
+            .iter()
+            .map(|pair| api.price(at, pair.clone()))
+            .collect()
+    }
+}
+
 /// A struct that implements the [`PriceFeedApi`].
-pub struct PriceFeed<C, P> {
+pub struct PriceFeed<C, P: BlockT> {
     client: Arc<C>,
+    limits: PriceFeedLimits,
+    telemetry: Option<PriceFeedTelemetry>,
+    cache: Option<Arc<PriceCache<P::Hash, NumberFor<P>>>>,
+    executor: Option<Arc<dyn SpawnNamed>>,
     _marker: std::marker::PhantomData<P>,
 }
 
-impl<C, P> PriceFeed<C, P> {
+impl<C, P: BlockT> PriceFeed<C, P> {
     /// Create new `PriceFeed` with the given reference to the client.
-    pub fn new(client: Arc<C>) -> Self {
+    /// Requests exceeding the supplied `limits` are rejected without calling the runtime API.
+    pub fn new(client: Arc<C>, limits: PriceFeedLimits) -> Self {
         PriceFeed {
             client,
+            limits,
+            telemetry: None,
+            cache: None,
+            executor: None,
             _marker: Default::default(),
         }
     }
+
+    /// Reports observed prices to the telemetry using supplied `PriceFeedTelemetry`.
+    pub fn with_telemetry(mut self, telemetry: PriceFeedTelemetry) -> Self {
+        self.telemetry = Some(telemetry);
+
+        self
+    }
+
+    /// Serves repeated `price_feed_price` reads at the same block from the supplied cache
+    /// instead of calling the runtime API. The cache can be shared between multiple RPC servers.
+    pub fn with_cache(mut self, cache: Arc<PriceCache<P::Hash, NumberFor<P>>>) -> Self {
+        self.cache = Some(cache);
+
+        self
+    }
+
+    /// Enables `price_feed_subscribePrice` spawning the subscription tasks using the supplied executor.
+    /// Subscriptions are rejected otherwise.
+    pub fn with_subscriptions(mut self, executor: Arc<dyn SpawnNamed>) -> Self {
+        self.executor = Some(executor);
+
+        self
+    }
 }
 
 #[async_trait]
 impl<C, Block> PriceFeedApiServer<<Block as BlockT>::Hash, NumberFor<Block>> for PriceFeed<C, Block>
 where
     Block: BlockT,
-    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C: Send
+        + Sync
+        + 'static
+        + ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + ProofProvider<Block>
+        + BlockchainEvents<Block>,
     C::Api: PriceFeedRuntimeApi<Block, NumberFor<Block>>,
 {
     async fn price(
@@ -65,14 +265,259 @@ where
         at: Option<<Block as BlockT>::Hash>,
         pair: CurrencySymbolPair<String, String>,
     ) -> RpcResult<Option<PriceRecord<NumberFor<Block>>>> {
+        self.limits.check_pair(&pair)?;
+        let api = self.client.runtime_api();
+        let info = self.client.info();
+
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or(info.best_hash);
+        let read = |pair: &CurrencySymbolPair<String, String>| {
+            api.price(&BlockId::hash(at_hash), pair.clone())
+                .map_err(RuntimeError)
+                .map_err(JsonRpseeError::from)
+        };
+
+        let price = match &self.cache {
+            Some(cache) => cache.get_or_try_insert_with(at_hash, pair.clone(), read)?,
+            None => read(&pair)?,
+        };
+
+        if let (Some(telemetry), Some(record)) = (&self.telemetry, &price) {
+            telemetry.observe(&pair, record, info.best_number);
+        }
+
+        Ok(price)
+    }
+    async fn price_with_proof(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Option<PriceWithProof<<Block as BlockT>::Hash, NumberFor<Block>>>> {
+        self.limits.check_pair(&pair)?;
         let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at = BlockId::hash(at_hash);
+        ensure_extended_queries(&*api, &at, "price_feed_priceWithProof")?;
 
-        let at = BlockId::hash(at.unwrap_or_else(||
+        let record = match api
+            .price(&at, pair.clone())
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?
+        {
+            Some(record) => record,
+            None => return Ok(None),
+        };
+        let key = match api
+            .price_storage_key(&at, pair)
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?
+        {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let proof = self
+            .client
+            .read_proof(&at, &mut core::iter::once(&key[..]))
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?;
+
+        Ok(Some(PriceWithProof {
+            at: at_hash,
+            record,
+            key: key.into(),
+            proof: proof.into_iter_nodes().map(Into::into).collect(),
+        }))
+    }
+
+    async fn prices_with_proof(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        pairs: Vec<CurrencySymbolPair<String, String>>,
+    ) -> RpcResult<PricesWithProof<<Block as BlockT>::Hash, NumberFor<Block>>> {
+        self.limits.check_pairs(&pairs)?;
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+        let at = BlockId::hash(at_hash);
+        ensure_extended_queries(&*api, &at, "price_feed_pricesWithProof")?;
+
+        let records = read_prices(&*api, &at, &pairs)
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?;
+        let prices = pairs
+            .into_iter()
+            .zip(records)
+            .map(|(pair, record)| {
+                let record = match record {
+                    Some(record) => record,
+                    None => return Ok(None),
+                };
+
+                Ok::<_, ApiError>(api.price_storage_key(&at, pair)?.map(|key| ProvenPrice {
+                    record,
+                    key: key.into(),
+                }))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?;
+
+        let proof = self
+            .client
+            .read_proof(
+                &at,
+                &mut prices.iter().flatten().map(|price| &price.key[..]),
+            )
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)?;
+
+        Ok(PricesWithProof {
+            at: at_hash,
+            prices,
+            proof: proof.into_iter_nodes().map(Into::into).collect(),
+        })
+    }
+
+    async fn price_status(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Option<PriceStatus<NumberFor<Block>>>> {
+        self.limits.check_pair(&pair)?;
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(
             // If the block hash is not supplied assume the best block.
-            self.client.info().best_hash));
+            || self.client.info().best_hash,
+        ));
+        ensure_extended_queries(&*api, &at, "price_feed_priceStatus")?;
 
-        api.price(&at, pair)
+        api.price_status(&at, pair)
             .map_err(RuntimeError)
-            .map_err(Into::into)
+            .map_err(JsonRpseeError::from)
+    }
+
+    async fn prices(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        pairs: Vec<CurrencySymbolPair<String, String>>,
+    ) -> RpcResult<Vec<Option<PriceRecord<NumberFor<Block>>>>> {
+        self.limits.check_pairs(&pairs)?;
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(
+            // If the block hash is not supplied assume the best block.
+            || self.client.info().best_hash,
+        ));
+
+        read_prices(&*api, &at, &pairs)
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)
+    }
+
+    async fn price_history(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        pair: CurrencySymbolPair<String, String>,
+    ) -> RpcResult<Vec<PriceRecord<NumberFor<Block>>>> {
+        self.limits.check_pair(&pair)?;
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(
+            // If the block hash is not supplied assume the best block.
+            || self.client.info().best_hash,
+        ));
+
+        ensure_extended_queries(&*api, &at, "price_feed_priceHistory")?;
+
+        api.price_history(&at, pair)
+            .map_err(RuntimeError)
+            .map_err(JsonRpseeError::from)
+    }
+
+    async fn list_pairs(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        with_prices: bool,
+        cursor: Option<PageCursor>,
+        limit: Option<u32>,
+    ) -> RpcResult<Paged<ListedPair<NumberFor<Block>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(
+            // If the block hash is not supplied assume the best block.
+            || self.client.info().best_hash,
+        ));
+        ensure_extended_queries(&*api, &at, "price_feed_listPairs")?;
+        let limit = limit
+            .unwrap_or(MAX_PAGE_LIMIT)
+            .min(self.limits.max_batch_len);
+
+        if with_prices {
+            api.all_prices(&at, cursor, limit).map(|page| {
+                page.map(|(pair, record)| ListedPair {
+                    pair,
+                    record: Some(record),
+                })
+            })
+        } else {
+            api.pairs(&at, cursor, limit)
+                .map(|page| page.map(|pair| ListedPair { pair, record: None }))
+        }
+        .map_err(RuntimeError)
+        .map_err(JsonRpseeError::from)
+    }
+
+    fn subscribe_price(
+        &self,
+        pending: PendingSubscription,
+        pair: CurrencySymbolPair<String, String>,
+    ) {
+        if let Err(JsonRpseeError::Call(error)) = self.limits.check_pair(&pair) {
+            pending.reject(error);
+            return;
+        }
+        let executor = match &self.executor {
+            Some(executor) => executor.clone(),
+            None => {
+                pending.reject(ErrorObject::owned(
+                    SUBSCRIPTIONS_DISABLED_ERROR_CODE,
+                    "Subscriptions are disabled",
+                    None::<()>,
+                ));
+                return;
+            }
+        };
+        let mut sink = match pending.accept() {
+            Some(sink) => sink,
+            None => return,
+        };
+
+        let client = self.client.clone();
+        let mut last = None;
+        let updates = self
+            .client
+            .finality_notification_stream()
+            .filter_map(move |notification| {
+                // Failed reads are skipped, so the update gets pushed on the next finalized block.
+                let record = client
+                    .runtime_api()
+                    .price(&BlockId::hash(notification.hash), pair.clone())
+                    .ok()
+                    .flatten();
+                if record.is_none() || record == last {
+                    return future::ready(None);
+                }
+                last = record.clone();
+
+                future::ready(record)
+            });
+
+        executor.spawn(
+            "price-feed-rpc-subscription",
+            Some("rpc"),
+            async move {
+                sink.pipe_from_stream(updates.boxed()).await;
+            }
+            .boxed(),
+        );
     }
 }
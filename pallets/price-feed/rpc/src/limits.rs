@@ -0,0 +1,132 @@
+//! Limits applied to the RPC requests before calling the runtime API.
+
+use dock_price_feed::CurrencySymbolPair;
+use jsonrpsee::{
+    core::Error as JsonRpseeError,
+    types::{error::CallError, ErrorObject},
+};
+
+/// Code of the error returned for requests exceeding the limits.
+pub const LIMIT_EXCEEDED_ERROR_CODE: i32 = 2;
+
+/// Limits of the RPC requests. Should match the runtime configuration, e.g. `MaxSymbolBytesLen` of the pallet,
+/// so requests which can't succeed are rejected without calling the runtime API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceFeedLimits {
+    /// Max length of the currency symbol in bytes.
+    pub max_symbol_bytes_len: u32,
+    /// Max amount of the currency pairs requested at once.
+    pub max_batch_len: u32,
+}
+
+impl Default for PriceFeedLimits {
+    /// Doesn't limit anything.
+    fn default() -> Self {
+        Self {
+            max_symbol_bytes_len: u32::MAX,
+            max_batch_len: u32::MAX,
+        }
+    }
+}
+
+impl PriceFeedLimits {
+    /// Instantiates new `PriceFeedLimits` with the given max length of the currency symbol in bytes.
+    pub fn new(max_symbol_bytes_len: u32) -> Self {
+        Self {
+            max_symbol_bytes_len,
+            ..Default::default()
+        }
+    }
+
+    /// Limits the amount of the currency pairs requested at once.
+    pub fn with_max_batch_len(mut self, max_batch_len: u32) -> Self {
+        self.max_batch_len = max_batch_len;
+
+        self
+    }
+
+    /// Returns `Err` if there're more than `max_batch_len` currency pairs or either of them exceeds the limits.
+    pub fn check_pairs(
+        &self,
+        pairs: &[CurrencySymbolPair<String, String>],
+    ) -> Result<(), JsonRpseeError> {
+        if pairs.len() > self.max_batch_len as usize {
+            return Err(limit_exceeded(format!(
+                "{} currency pairs requested while the limit is {}",
+                pairs.len(),
+                self.max_batch_len
+            )));
+        }
+
+        pairs.iter().try_for_each(|pair| self.check_pair(pair))
+    }
+
+    /// Returns `Err` if either symbol of the currency pair exceeds `max_symbol_bytes_len`.
+    pub fn check_pair(
+        &self,
+        pair: &CurrencySymbolPair<String, String>,
+    ) -> Result<(), JsonRpseeError> {
+        pair.clone()
+            .translate_pair(|symbol| {
+                if symbol.len() > self.max_symbol_bytes_len as usize {
+                    Err(symbol)
+                } else {
+                    Ok(symbol)
+                }
+            })
+            .map(drop)
+            .map_err(|symbol| {
+                limit_exceeded(format!(
+                    "Symbol `{}` exceeds {} bytes",
+                    symbol, self.max_symbol_bytes_len
+                ))
+            })
+    }
+}
+
+fn limit_exceeded(data: String) -> JsonRpseeError {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        LIMIT_EXCEEDED_ERROR_CODE,
+        "Limit exceeded",
+        Some(data),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PriceFeedLimits;
+    use dock_price_feed::CurrencySymbolPair;
+
+    #[test]
+    fn check_pair() {
+        let pair = |from: &str, to: &str| CurrencySymbolPair::new(from.to_owned(), to.to_owned());
+        let limits = PriceFeedLimits::new(4);
+
+        assert!(limits.check_pair(&pair("DOCK", "USD")).is_ok());
+        assert!(limits.check_pair(&pair("DOCKS", "USD")).is_err());
+        assert!(limits.check_pair(&pair("DOCK", "USDTS")).is_err());
+        assert!(PriceFeedLimits::default()
+            .check_pair(&pair("DOCKS", "USDTS"))
+            .is_ok());
+    }
+
+    #[test]
+    fn check_pairs() {
+        let pair = |from: &str, to: &str| CurrencySymbolPair::new(from.to_owned(), to.to_owned());
+        let limits = PriceFeedLimits::new(4).with_max_batch_len(2);
+
+        assert!(limits.check_pairs(&[]).is_ok());
+        assert!(limits
+            .check_pairs(&[pair("DOCK", "USD"), pair("DOT", "USD")])
+            .is_ok());
+        assert!(limits
+            .check_pairs(&[pair("DOCK", "USD"), pair("DOCKS", "USD")])
+            .is_err());
+        assert!(limits
+            .check_pairs(&[pair("DOCK", "USD"), pair("DOT", "USD"), pair("KSM", "USD")])
+            .is_err());
+        assert!(PriceFeedLimits::default()
+            .check_pairs(&vec![pair("DOCK", "USD"); 100])
+            .is_ok());
+    }
+}
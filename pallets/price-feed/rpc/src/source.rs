@@ -0,0 +1,189 @@
+//! Node-side access to the prices for the services which don't use the RPC server.
+
+use crate::{PriceCache, PriceFeedRuntimeApi};
+use dock_price_feed::{CurrencySymbolPair, PriceRecord};
+use futures::{channel::oneshot, FutureExt};
+use jsonrpsee::core::async_trait;
+use sp_api::{ApiError, NumberFor, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::traits::SpawnNamed;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+/// Block to read prices at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PriceAt {
+    /// The best known block.
+    Best,
+    /// The latest finalized block.
+    Finalized,
+}
+
+/// Asynchronous source of the currency pair prices.
+#[async_trait]
+pub trait AsyncPriceSource<Number> {
+    /// Error happening on the price retrieval.
+    type Error;
+
+    /// Returns the price of the supplied currency pair at the given block if it's present.
+    async fn price(
+        &self,
+        pair: CurrencySymbolPair<String, String>,
+        at: PriceAt,
+    ) -> Result<Option<PriceRecord<Number>>, Self::Error>;
+
+    /// Returns the price of the supplied currency pair at the best block if it's present.
+    async fn best_price(
+        &self,
+        pair: CurrencySymbolPair<String, String>,
+    ) -> Result<Option<PriceRecord<Number>>, Self::Error> {
+        self.price(pair, PriceAt::Best).await
+    }
+
+    /// Returns the price of the supplied currency pair at the finalized block if it's present.
+    async fn finalized_price(
+        &self,
+        pair: CurrencySymbolPair<String, String>,
+    ) -> Result<Option<PriceRecord<Number>>, Self::Error> {
+        self.price(pair, PriceAt::Finalized).await
+    }
+}
+
+/// Implements [`AsyncPriceSource`] by calling the runtime API of the given client.
+/// Prices are cached per block in the supplied [`PriceCache`], so the runtime API is called at most once per pair
+/// for each new best/finalized block. Runtime API calls are blocking, so they're performed by the blocking tasks
+/// spawned using the supplied executor.
+pub struct RuntimePriceSource<C, Block: BlockT> {
+    client: Arc<C>,
+    cache: Arc<PriceCache<Block::Hash, NumberFor<Block>>>,
+    executor: Arc<dyn SpawnNamed>,
+}
+
+impl<C, Block: BlockT> RuntimePriceSource<C, Block> {
+    /// Create new `RuntimePriceSource` with the given reference to the client.
+    /// The cache can be shared with the RPC server, see [`crate::PriceFeed::with_cache`].
+    pub fn new(
+        client: Arc<C>,
+        cache: Arc<PriceCache<Block::Hash, NumberFor<Block>>>,
+        executor: Arc<dyn SpawnNamed>,
+    ) -> Self {
+        Self {
+            client,
+            cache,
+            executor,
+        }
+    }
+}
+
+#[async_trait]
+impl<C, Block> AsyncPriceSource<NumberFor<Block>> for RuntimePriceSource<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: PriceFeedRuntimeApi<Block, NumberFor<Block>>,
+{
+    type Error = ApiError;
+
+    async fn price(
+        &self,
+        pair: CurrencySymbolPair<String, String>,
+        at: PriceAt,
+    ) -> Result<Option<PriceRecord<NumberFor<Block>>>, Self::Error> {
+        let info = self.client.info();
+        let hashes = (info.best_hash, info.finalized_hash);
+        let (client, cache) = (self.client.clone(), self.cache.clone());
+        let (sender, receiver) = oneshot::channel();
+
+        self.executor.spawn_blocking(
+            "price-feed-price-source",
+            Some("rpc"),
+            async move {
+                let price = cached_read(&cache, at, hashes, pair, |hash, pair| {
+                    client
+                        .runtime_api()
+                        .price(&BlockId::hash(hash), pair.clone())
+                });
+                // The receiver is dropped if the request was cancelled.
+                let _ = sender.send(price);
+            }
+            .boxed(),
+        );
+
+        receiver
+            .await
+            .map_err(|_| ApiError::Application("Price read task was cancelled".into()))?
+    }
+}
+
+/// Reads the price of the currency pair at the block selected from the supplied best and finalized block hashes
+/// using `read`, unless it's already cached for the selected block.
+fn cached_read<Hash, Number, E>(
+    cache: &PriceCache<Hash, Number>,
+    at: PriceAt,
+    (best, finalized): (Hash, Hash),
+    pair: CurrencySymbolPair<String, String>,
+    read: impl FnOnce(
+        Hash,
+        &CurrencySymbolPair<String, String>,
+    ) -> Result<Option<PriceRecord<Number>>, E>,
+) -> Result<Option<PriceRecord<Number>>, E>
+where
+    Hash: std::hash::Hash + Eq + Copy,
+    Number: Copy,
+{
+    let hash = match at {
+        PriceAt::Best => best,
+        PriceAt::Finalized => finalized,
+    };
+
+    cache.get_or_try_insert_with(hash, pair, |pair| read(hash, pair))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_and_finalized_reads_are_invalidated_separately() {
+        let cache = PriceCache::<u8, u32>::new(8);
+        let pair = CurrencySymbolPair::new("DOCK".to_string(), "USD".to_string());
+        let mut reads = Vec::new();
+        let mut read = |at, hashes| {
+            cached_read(&cache, at, hashes, pair.clone(), |hash, _| {
+                reads.push(hash);
+
+                Ok::<_, ()>(Some(PriceRecord::new(10, 2, hash.into())))
+            })
+        };
+
+        assert_eq!(
+            read(PriceAt::Best, (2, 1)),
+            Ok(Some(PriceRecord::new(10, 2, 2)))
+        );
+        assert_eq!(
+            read(PriceAt::Finalized, (2, 1)),
+            Ok(Some(PriceRecord::new(10, 2, 1)))
+        );
+        assert_eq!(
+            read(PriceAt::Best, (2, 1)),
+            Ok(Some(PriceRecord::new(10, 2, 2)))
+        );
+
+        // The new best block invalidates only the best price.
+        assert_eq!(
+            read(PriceAt::Best, (3, 1)),
+            Ok(Some(PriceRecord::new(10, 2, 3)))
+        );
+        assert_eq!(
+            read(PriceAt::Finalized, (3, 1)),
+            Ok(Some(PriceRecord::new(10, 2, 1)))
+        );
+
+        // The finalized block already read as the best one is served from the cache.
+        assert_eq!(
+            read(PriceAt::Finalized, (3, 2)),
+            Ok(Some(PriceRecord::new(10, 2, 2)))
+        );
+        assert_eq!(reads, vec![2, 1, 3]);
+    }
+}
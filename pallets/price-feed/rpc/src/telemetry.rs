@@ -0,0 +1,76 @@
+//! Reporting of the prices observed by the RPC server to the substrate telemetry.
+
+use dock_price_feed::{CurrencySymbolPair, PriceRecord};
+use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
+use sp_runtime::traits::{AtLeast32BitUnsigned, UniqueSaturatedInto};
+use std::{collections::HashMap, sync::Mutex};
+
+/// Sends telemetry messages when new prices get observed or observed prices go stale.
+pub struct PriceFeedTelemetry {
+    handle: TelemetryHandle,
+    stale_after: u32,
+    /// Last observed price block number along with the staleness flag per currency pair.
+    last_observed: Mutex<HashMap<String, (u64, bool)>>,
+}
+
+impl PriceFeedTelemetry {
+    /// Instantiates new `PriceFeedTelemetry` using supplied telemetry handle.
+    /// Prices that weren't updated for more than `stale_after` blocks will be reported as stale.
+    pub fn new(handle: TelemetryHandle, stale_after: u32) -> Self {
+        Self {
+            handle,
+            stale_after,
+            last_observed: Default::default(),
+        }
+    }
+
+    /// Records price of the supplied currency pair observed at the block with the given number.
+    /// Emits `price_feed.price_updated` message if the price record was updated since the last observation
+    /// and `price_feed.price_stale` once the record becomes older than `stale_after` blocks.
+    pub fn observe<Number>(
+        &self,
+        pair: &CurrencySymbolPair<String, String>,
+        record: &PriceRecord<Number>,
+        at: Number,
+    ) where
+        Number: AtLeast32BitUnsigned + Copy,
+    {
+        let pair = pair.to_string();
+        let updated_at: u64 = record.block_number().unique_saturated_into();
+        let age: u64 = at
+            .saturating_sub(record.block_number())
+            .unique_saturated_into();
+        let stale = age > self.stale_after as u64;
+
+        let mut last_observed = self.last_observed.lock().expect("Poisoned lock");
+        let (last_updated_at, reported_stale) = last_observed
+            .get(&pair)
+            .copied()
+            .unwrap_or((u64::MAX, false));
+
+        if last_updated_at != updated_at {
+            telemetry!(
+                Some(self.handle.clone());
+                SUBSTRATE_INFO;
+                "price_feed.price_updated";
+                "pair" => &pair,
+                "amount" => record.amount(),
+                "decimals" => record.decimals(),
+                "block_number" => updated_at,
+            );
+        }
+
+        if stale && (!reported_stale || last_updated_at != updated_at) {
+            telemetry!(
+                Some(self.handle.clone());
+                SUBSTRATE_INFO;
+                "price_feed.price_stale";
+                "pair" => &pair,
+                "block_number" => updated_at,
+                "age" => age,
+            );
+        }
+
+        last_observed.insert(pair, (updated_at, stale));
+    }
+}
@@ -0,0 +1,96 @@
+//! Negotiation of the `PriceFeedApi` version implemented by the runtime.
+//!
+//! During rolling upgrades the RPC node may serve blocks executed by the runtimes built before the newer methods
+//! were introduced, so the version is checked at the requested block and the older runtimes are served using the
+//! methods they provide.
+
+use crate::{PriceFeedRuntimeApi, RuntimeError};
+pub use dock_price_feed::runtime_api::EXTENDED_QUERIES_API_VERSION;
+use jsonrpsee::{
+    core::Error as JsonRpseeError,
+    types::{error::CallError, ErrorObject},
+};
+use sp_api::{ApiError, ApiExt, NumberFor};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+/// Code of the error returned for requests which can't be served by the runtime at the requested block.
+pub const UNSUPPORTED_BY_RUNTIME_ERROR_CODE: i32 = 3;
+
+/// Returns the version of `PriceFeedApi` implemented by the runtime at the given block,
+/// `None` if the runtime doesn't implement it.
+pub fn price_feed_api_version<Block, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+) -> Result<Option<u32>, ApiError>
+where
+    Block: BlockT,
+    Api: ApiExt<Block>,
+{
+    api.api_version::<dyn PriceFeedRuntimeApi<Block, NumberFor<Block>>>(at)
+}
+
+/// Returns `true` if the runtime implementing the given `PriceFeedApi` version provides
+/// the methods other than `price`.
+pub fn supports_extended_queries(version: Option<u32>) -> bool {
+    version.map_or(false, |version| version >= EXTENDED_QUERIES_API_VERSION)
+}
+
+/// Fails with [`UNSUPPORTED_BY_RUNTIME_ERROR_CODE`] unless the runtime at the given block provides
+/// the methods other than `price`, which are required to serve the given RPC method.
+pub(crate) fn ensure_extended_queries<Block, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    method: &str,
+) -> Result<(), JsonRpseeError>
+where
+    Block: BlockT,
+    Api: ApiExt<Block>,
+{
+    let version = price_feed_api_version(api, at)
+        .map_err(RuntimeError)
+        .map_err(JsonRpseeError::from)?;
+
+    if supports_extended_queries(version) {
+        Ok(())
+    } else {
+        Err(unsupported_by_runtime(method, version))
+    }
+}
+
+pub(crate) fn unsupported_by_runtime(method: &str, version: Option<u32>) -> JsonRpseeError {
+    let data = match version {
+        Some(version) => format!(
+            "`{}` requires `PriceFeedApi` version {} while the runtime implements version {}",
+            method, EXTENDED_QUERIES_API_VERSION, version
+        ),
+        None => format!(
+            "`{}` requires `PriceFeedApi` which isn't implemented by the runtime",
+            method
+        ),
+    };
+
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+        UNSUPPORTED_BY_RUNTIME_ERROR_CODE,
+        "Unsupported by runtime",
+        Some(data),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{supports_extended_queries, EXTENDED_QUERIES_API_VERSION};
+
+    #[test]
+    fn extended_queries_support() {
+        assert!(!supports_extended_queries(None));
+        assert!(!supports_extended_queries(Some(
+            EXTENDED_QUERIES_API_VERSION - 1
+        )));
+        assert!(supports_extended_queries(Some(
+            EXTENDED_QUERIES_API_VERSION
+        )));
+        assert!(supports_extended_queries(Some(
+            EXTENDED_QUERIES_API_VERSION + 1
+        )));
+    }
+}
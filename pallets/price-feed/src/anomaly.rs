@@ -0,0 +1,175 @@
+//! Offchain detection of the price anomalies.
+//!
+//! Nodes running offchain workers compare the latest price record of every currency pair against the reference
+//! price fetched via HTTP from `{base_url}/{from}/{to}`, where `base_url` is read from the persistent offchain storage
+//! under `REFERENCE_API_KEY`. The reference API is expected to respond with a plain decimal price, e.g. `1.2345`.
+//! If the prices diverge by more than `Config::AnomalyThreshold`, a `report_anomaly` transaction is submitted via
+//! `Config::AnomalyReportSubmitter`. Reports are only accepted from the operators of the currency pair, so the
+//! reference price can't be forged by an arbitrary account. Use [`SignedAnomalyReportSubmitter`] to sign them with
+//! the operator keys from the node's keystore.
+
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer};
+use price_provider::{CurrencySymbolPair, PriceRecord};
+use scale_info::{
+    prelude::string::{String, ToString},
+    TypeInfo,
+};
+use sp_runtime::{
+    offchain::{http, Duration},
+    traits::IdentifyAccount,
+    Perbill, RuntimeAppPublic,
+};
+use sp_std::prelude::*;
+
+use crate::{Call, Config, Pallet};
+
+/// Key of the reference API base URL in the persistent offchain storage.
+pub const REFERENCE_API_KEY: &[u8] = b"dock-price-feed::reference-api";
+
+/// Reference API request timeout in milliseconds.
+pub const REFERENCE_API_TIMEOUT_MILLIS: u64 = 2_000;
+
+/// Report of the divergence between the on-chain price record and the reference price.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct AnomalyReport<BlockNumber> {
+    /// Currency pair which price diverged.
+    pub pair: CurrencySymbolPair<String, String>,
+    /// Block number of the on-chain price record compared against the reference price.
+    pub record_block_number: BlockNumber,
+    /// Raw reference price amount.
+    pub reference_amount: u64,
+    /// Reference price decimals.
+    pub reference_decimals: u8,
+}
+
+/// Returns relative divergence of the price record from the reference price, saturated to 100%.
+/// Returns `None` if the reference amount is zero or amounts can't be brought to the same decimals.
+pub fn divergence<T>(
+    record: &PriceRecord<T>,
+    reference_amount: u64,
+    reference_decimals: u8,
+) -> Option<Perbill> {
+    let decimals = record.decimals().max(reference_decimals.into());
+    let scale = |amount: u64, amount_decimals: u32| -> Option<u128> {
+        10u128
+            .checked_pow(decimals - amount_decimals)?
+            .checked_mul(amount.into())
+    };
+
+    let amount = scale(record.amount(), record.decimals())?;
+    let reference = scale(reference_amount, reference_decimals.into())?;
+    if reference == 0 {
+        return None;
+    }
+
+    let diff = amount.max(reference) - amount.min(reference);
+
+    Some(Perbill::from_rational(diff.min(reference), reference))
+}
+
+/// Parses plain decimal price (e.g. `1.2345`) into a raw amount and decimals.
+pub fn parse_decimal_price(bytes: &[u8]) -> Option<(u64, u8)> {
+    let str = core::str::from_utf8(bytes).ok()?.trim();
+    let (int, frac) = str.split_once('.').unwrap_or((str, ""));
+    if int.is_empty() || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let decimals = frac.len().try_into().ok()?;
+    let amount = int
+        .bytes()
+        .chain(frac.bytes())
+        .try_fold(0u64, |acc, digit| {
+            acc.checked_mul(10)?.checked_add((digit - b'0').into())
+        })?;
+
+    Some((amount, decimals))
+}
+
+/// Fetches the reference price of the given pair using the supplied base URL.
+pub fn fetch_reference_price(
+    base_url: &str,
+    pair: &CurrencySymbolPair<String, String>,
+) -> Result<(u64, u8), http::Error> {
+    let mut url = String::from(base_url.trim_end_matches('/'));
+    url.push('/');
+    url.push_str(&pair.to_string());
+    let deadline =
+        sp_io::offchain::timestamp().add(Duration::from_millis(REFERENCE_API_TIMEOUT_MILLIS));
+
+    let pending = http::Request::get(&url)
+        .deadline(deadline)
+        .send()
+        .map_err(|_| http::Error::IoError)?;
+    let response = pending
+        .try_wait(deadline)
+        .map_err(|_| http::Error::DeadlineReached)??;
+    if response.code != 200 {
+        return Err(http::Error::Unknown);
+    }
+
+    let body = response.body().collect::<Vec<u8>>();
+
+    parse_decimal_price(&body).ok_or(http::Error::Unknown)
+}
+
+/// Submits anomaly reports produced by the offchain workers.
+pub trait AnomalyReportSubmitter<T: Config> {
+    /// Returns `true` if the node is able to submit reports, so the reference prices are worth fetching.
+    fn can_submit() -> bool;
+
+    /// Submits the anomaly report. Returns `Err` if nothing was submitted.
+    fn submit_report(report: AnomalyReport<T::BlockNumber>) -> Result<(), ()>;
+}
+
+/// Doesn't submit any reports, so the reference prices aren't fetched.
+impl<T: Config> AnomalyReportSubmitter<T> for () {
+    fn can_submit() -> bool {
+        false
+    }
+
+    fn submit_report(_report: AnomalyReport<T::BlockNumber>) -> Result<(), ()> {
+        Err(())
+    }
+}
+
+/// Submits `report_anomaly` transactions signed by every key of `AuthorityId` type found in the node's keystore
+/// which account is an operator of the reported currency pair.
+pub struct SignedAnomalyReportSubmitter<AuthorityId>(PhantomData<AuthorityId>);
+
+impl<T, AuthorityId> AnomalyReportSubmitter<T> for SignedAnomalyReportSubmitter<AuthorityId>
+where
+    T: Config + CreateSignedTransaction<Call<T>>,
+    AuthorityId: AppCrypto<T::Public, T::Signature>,
+{
+    fn can_submit() -> bool {
+        Signer::<T, AuthorityId>::all_accounts().can_sign()
+    }
+
+    fn submit_report(report: AnomalyReport<T::BlockNumber>) -> Result<(), ()> {
+        let operators: Vec<T::Public> = AuthorityId::RuntimeAppPublic::all()
+            .into_iter()
+            .map(|key| -> T::Public { AuthorityId::GenericPublic::from(key).into() })
+            .filter(|public| {
+                Pallet::<T>::is_operator(report.pair.clone(), &public.clone().into_account())
+            })
+            .collect();
+        if operators.is_empty() {
+            return Err(());
+        }
+
+        let results = Signer::<T, AuthorityId>::all_accounts()
+            .with_filter(operators)
+            .send_signed_transaction(|_| Call::report_anomaly {
+                report: report.clone(),
+            });
+
+        if results.iter().any(|(_, result)| result.is_ok()) {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
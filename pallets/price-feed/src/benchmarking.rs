@@ -0,0 +1,541 @@
+//! Price feed pallet benchmarking.
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::StorageValue;
+use frame_system::RawOrigin;
+use migrations::v1::{ContractConfigStore, LastPriceUpdateAt, Price, PriceUpdateFreq};
+use sp_core::H160;
+use sp_runtime::traits::{One, TrailingZeroInput};
+use sp_std::vec;
+
+use crate::Pallet as PriceFeedModule;
+
+const SEED: u32 = 0;
+
+/// Max encoded size of the residual V1 `ContractConfigStore` value.
+const MAX_CONTRACT_CONFIG_BYTES: u32 = 16_384;
+
+/// Returns a currency pair with both symbols of `MaxSymbolBytesLen` bytes.
+fn max_pair<T: Config>() -> CurrencySymbolPair<String, String> {
+    let len = T::MaxSymbolBytesLen::get() as usize;
+
+    CurrencySymbolPair::new("A".repeat(len), "B".repeat(len))
+}
+
+fn stored_max_pair<T: Config>() -> BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen> {
+    max_pair::<T>().try_into().unwrap()
+}
+
+/// Returns a distinct currency pair with both symbols of `MaxSymbolBytesLen` bytes for each index.
+fn indexed_pair<T: Config>(
+    i: u32,
+) -> BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen> {
+    let len = T::MaxSymbolBytesLen::get();
+    let from: String = (0..len)
+        .map(|pos| (b'A' + (i / 26u32.saturating_pow(pos) % 26) as u8) as char)
+        .collect();
+
+    CurrencySymbolPair::new(from, "B".repeat(len as usize))
+        .try_into()
+        .unwrap()
+}
+
+fn add_operator<T: Config>(operator: &T::AccountId) {
+    PriceFeedModule::<T>::add_operator(RawOrigin::Root.into(), max_pair::<T>(), operator.clone())
+        .unwrap();
+}
+
+/// Adds operators to `max_pair` until it has `MaxOperatorsPerPair - 1` of them,
+/// so the operator set is accessed at its max size.
+fn populate_operators<T: Config>() {
+    let present = PriceFeedModule::<T>::operators_of(stored_max_pair::<T>()).len() as u32;
+    for i in present..T::MaxOperatorsPerPair::get().saturating_sub(1) {
+        add_operator::<T>(&account("populated", i, SEED));
+    }
+}
+
+/// Funds the account and requests the operator role of the allowed `max_pair` on its behalf.
+fn request_operator_role<T: Config>(account: &T::AccountId) {
+    T::Currency::make_free_balance_be(
+        account,
+        T::Currency::minimum_balance().saturating_add(T::OperatorRequestDeposit::get()),
+    );
+    PriceFeedModule::<T>::allow_pair(RawOrigin::Root.into(), max_pair::<T>()).unwrap();
+    PriceFeedModule::<T>::request_operator_role(
+        RawOrigin::Signed(account.clone()).into(),
+        max_pair::<T>(),
+    )
+    .unwrap();
+}
+
+/// Allows, sets bounds and fills the price history of the `max_pair`, so the price submission
+/// performs all checks and shifts the history.
+fn populate_price_state<T: Config>(operator: &T::AccountId) {
+    PriceFeedModule::<T>::allow_pair(RawOrigin::Root.into(), max_pair::<T>()).unwrap();
+    PriceFeedModule::<T>::set_price_bounds(
+        RawOrigin::Root.into(),
+        max_pair::<T>(),
+        PriceBounds::new(1, u64::MAX, 0),
+    )
+    .unwrap();
+    for _ in 0..T::MaxPriceHistoryLen::get() {
+        PriceFeedModule::<T>::set_price(
+            RawOrigin::Signed(operator.clone()).into(),
+            max_pair::<T>(),
+            1_000,
+            2,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+}
+
+/// Fills `PendingOperatorRemovals` with `p` removals of the other operators.
+fn populate_pending_removals<T: Config>(p: u32) {
+    let pending: Vec<_> = (0..p)
+        .map(|i| PendingOperatorRemoval {
+            pair: stored_max_pair::<T>(),
+            operator: account("pending", i, SEED),
+            at: T::BlockNumber::one(),
+        })
+        .collect();
+
+    PendingOperatorRemovals::<T>::put(BoundedVec::try_from(pending).unwrap());
+}
+
+/// Returns the current block number of `BlockNumberProvider`.
+fn current_block<T: Config>() -> u64 {
+    T::BlockNumberProvider::current_block_number().unique_saturated_into()
+}
+
+/// Returns a scheme having the commit phase at the current block.
+fn commit_phase_scheme<T: Config>() -> CommitRevealScheme {
+    let scheme = CommitRevealScheme::new(u32::MAX, u32::MAX - 1, 1).unwrap();
+    assert!(scheme.phase_at(current_block::<T>()).1 == CommitRevealPhase::Commit);
+
+    scheme
+}
+
+/// Returns a scheme having the reveal phase at the current block.
+fn reveal_phase_scheme<T: Config>() -> CommitRevealScheme {
+    if current_block::<T>() == 0 {
+        frame_system::Pallet::<T>::set_block_number(T::BlockNumber::one());
+    }
+    let offset = (current_block::<T>() % u64::from(u32::MAX)) as u32;
+    let scheme = CommitRevealScheme::new(u32::MAX, offset, 1).unwrap();
+    assert!(scheme.phase_at(current_block::<T>()).1 == CommitRevealPhase::Reveal);
+
+    scheme
+}
+
+/// Stores commitments of `c` operators for the current round of the scheme.
+/// If `revealing` is supplied, its commitment to `price` is stored last.
+fn populate_commitments<T: Config>(
+    scheme: CommitRevealScheme,
+    c: u32,
+    revealing: Option<(&T::AccountId, u64, u8, &CommitmentSalt)>,
+) {
+    let stored_pair = stored_max_pair::<T>();
+    let (round, _) = scheme.phase_at(current_block::<T>());
+    let others = c.saturating_sub(revealing.is_some() as u32);
+    let mut commitments: Vec<_> = (0..others)
+        .map(|i| PriceCommitment {
+            operator: account("committer", i, SEED),
+            commitment: T::Hashing::hash_of(&i),
+            revealed: Some((1_000, 2)),
+        })
+        .collect();
+    if let Some((operator, price, decimals, salt)) = revealing {
+        commitments.push(PriceCommitment {
+            operator: operator.clone(),
+            commitment: commit_reveal::price_commitment::<T::Hashing, _, _>(
+                &stored_pair,
+                round,
+                operator,
+                price,
+                decimals,
+                salt,
+            ),
+            revealed: None,
+        });
+    }
+
+    CommitRevealSchemes::<T>::insert(&stored_pair, scheme);
+    PriceCommitments::<T>::insert(
+        &stored_pair,
+        (round, BoundedVec::try_from(commitments).unwrap()),
+    );
+}
+
+/// Populates V1 storage with a `ContractConfigStore` value having ABI of `bytes` length.
+fn populate_v1<T: Config>(bytes: u32) {
+    ContractConfigStore::put(migrations::v1::ContractConfig {
+        query_price_abi_encoded: vec![0; bytes as usize],
+        ..Default::default()
+    });
+    Price::put(1);
+    LastPriceUpdateAt::<T>::put(T::BlockNumber::one());
+    PriceUpdateFreq::put(1);
+    StorageVersion::<T>::put(Releases::V1SinglePair);
+}
+
+benchmarks! {
+    set_price {
+        let caller: T::AccountId = whitelisted_caller();
+        add_operator::<T>(&caller);
+        populate_operators::<T>();
+        populate_price_state::<T>(&caller);
+        let source = SourceTag::new(&"a".repeat(MAX_SOURCE_TAG_BYTES_LEN as usize)).unwrap();
+    }: _(RawOrigin::Signed(caller), max_pair::<T>(), 2_000, 2, Some(10), Some(source))
+    verify {
+        assert_eq!(
+            PriceFeedModule::<T>::price(&stored_max_pair::<T>()).map(|record| record.amount()),
+            Some(2_000)
+        );
+        assert!(FeedStates::<T>::get(&stored_max_pair::<T>()).source.is_some());
+    }
+
+    add_operator {
+        let operator: T::AccountId = account("operator", 0, SEED);
+        PriceFeedModule::<T>::allow_pair(RawOrigin::Root.into(), max_pair::<T>()).unwrap();
+        populate_operators::<T>();
+    }: _(RawOrigin::Root, max_pair::<T>(), operator.clone())
+    verify {
+        assert!(PriceFeedModule::<T>::is_pair_operator(&stored_max_pair::<T>(), &operator));
+    }
+
+    remove_operator {
+        let p in 0 .. T::MaxPendingOperatorRemovals::get().saturating_sub(1);
+
+        let operator: T::AccountId = account("operator", 0, SEED);
+        add_operator::<T>(&operator);
+        populate_operators::<T>();
+        populate_pending_removals::<T>(p);
+    }: _(RawOrigin::Root, max_pair::<T>(), operator.clone())
+    verify {
+        assert!(
+            !PriceFeedModule::<T>::is_pair_operator(&stored_max_pair::<T>(), &operator)
+                || PendingOperatorRemovals::<T>::get().len() as u32 == p + 1
+        );
+    }
+
+    set_emit_events_every_n_updates {
+    }: _(RawOrigin::Root, max_pair::<T>(), 10)
+
+    set_price_bounds {
+    }: _(RawOrigin::Root, max_pair::<T>(), PriceBounds::new(1, u64::MAX, 0))
+    verify {
+        assert!(FeedStates::<T>::get(&stored_max_pair::<T>()).bounds.is_some());
+    }
+
+    set_feed_spec_hash {
+    }: _(RawOrigin::Root, max_pair::<T>(), Some(T::Hashing::hash_of(&0u32)))
+    verify {
+        assert!(FeedSpecHashes::<T>::contains_key(&stored_max_pair::<T>()));
+    }
+
+    set_required_identity_level {
+    }: _(RawOrigin::Root, max_pair::<T>(), 1)
+    verify {
+        assert_eq!(FeedStates::<T>::get(&stored_max_pair::<T>()).required_identity_level, 1);
+    }
+
+    report_anomaly {
+        let operator: T::AccountId = account("operator", 0, SEED);
+        add_operator::<T>(&operator);
+        PriceFeedModule::<T>::set_price(
+            RawOrigin::Signed(operator.clone()).into(),
+            max_pair::<T>(),
+            1,
+            0,
+            None,
+            None,
+        )
+        .unwrap();
+        let record = PriceFeedModule::<T>::price(&stored_max_pair::<T>()).unwrap();
+        let report = AnomalyReport {
+            pair: max_pair::<T>(),
+            record_block_number: record.block_number(),
+            reference_amount: u64::MAX,
+            reference_decimals: 0,
+        };
+    }: _(RawOrigin::Signed(operator), report)
+
+    resume_pair {
+        let state = FeedState {
+            paused: true,
+            ..Default::default()
+        };
+        FeedStates::<T>::insert(&stored_max_pair::<T>(), state);
+        let origin = T::PauseOrigin::successful_origin();
+    }: _<T::Origin>(origin, max_pair::<T>())
+    verify {
+        assert!(!FeedStates::<T>::get(&stored_max_pair::<T>()).paused);
+    }
+
+    pause_pair {
+        let origin = T::PauseOrigin::successful_origin();
+    }: _<T::Origin>(origin, max_pair::<T>())
+    verify {
+        assert!(FeedStates::<T>::get(&stored_max_pair::<T>()).paused);
+    }
+
+    pause_all_pairs {
+        let origin = T::PauseOrigin::successful_origin();
+    }: _<T::Origin>(origin)
+    verify {
+        assert!(AllPairsPaused::<T>::get());
+    }
+
+    resume_all_pairs {
+        AllPairsPaused::<T>::put(true);
+        let origin = T::PauseOrigin::successful_origin();
+    }: _<T::Origin>(origin)
+    verify {
+        assert!(!AllPairsPaused::<T>::get());
+    }
+
+    note_local_time {
+    }: _(RawOrigin::None, u64::MAX)
+    verify {
+        assert!(LocalTime::<T>::get().is_some());
+    }
+
+    set_submission_window {
+    }: _(RawOrigin::Root, max_pair::<T>(), SubmissionWindow::new(10, 0, 5))
+    verify {
+        assert!(FeedStates::<T>::get(&stored_max_pair::<T>()).submission_window.is_some());
+    }
+
+    set_contract_config {
+        let call_data = || {
+            vec![0; evm_contract::MAX_CALL_DATA_BYTES_LEN as usize]
+                .try_into()
+                .unwrap()
+        };
+        let config = ContractConfig {
+            address: H160::repeat_byte(1),
+            query_aggregator_abi_encoded: Some(call_data()),
+            query_price_abi_encoded: call_data(),
+            decimals: 8,
+            update_period: 1,
+        };
+    }: _(RawOrigin::Root, max_pair::<T>(), Some(config))
+    verify {
+        assert!(ContractConfigs::<T>::contains_key(&stored_max_pair::<T>()));
+    }
+
+    remove_operator_immediately {
+        let p in 0 .. T::MaxPendingOperatorRemovals::get();
+
+        let operator: T::AccountId = account("operator", 0, SEED);
+        add_operator::<T>(&operator);
+        populate_operators::<T>();
+        populate_pending_removals::<T>(p);
+        let origin = T::EmergencyOrigin::successful_origin();
+    }: _<T::Origin>(origin, max_pair::<T>(), operator.clone())
+    verify {
+        assert!(!PriceFeedModule::<T>::is_pair_operator(&stored_max_pair::<T>(), &operator));
+    }
+
+    set_http_source {
+        let source = HttpSource {
+            url: "a"
+                .repeat(http_source::MAX_HTTP_SOURCE_URL_BYTES_LEN as usize)
+                .as_str()
+                .try_into()
+                .unwrap(),
+            json_path: "a"
+                .repeat(http_source::MAX_JSON_PATH_BYTES_LEN as usize)
+                .as_str()
+                .try_into()
+                .unwrap(),
+            decimals: 8,
+        };
+    }: _(RawOrigin::Root, max_pair::<T>(), Some(source))
+    verify {
+        assert!(HttpSources::<T>::contains_key(&stored_max_pair::<T>()));
+    }
+
+    set_commit_reveal_scheme {
+        populate_commitments::<T>(
+            commit_phase_scheme::<T>(),
+            T::MaxCommitmentsPerRound::get(),
+            None,
+        );
+    }: _(RawOrigin::Root, max_pair::<T>(), CommitRevealScheme::new(10, 5, 5))
+    verify {
+        assert_eq!(
+            PendingCommitRevealSchemes::<T>::get(&stored_max_pair::<T>()),
+            Some(CommitRevealScheme::new(10, 5, 5))
+        );
+    }
+
+    commit_price {
+        let c in 0 .. T::MaxCommitmentsPerRound::get().saturating_sub(1);
+
+        let caller: T::AccountId = whitelisted_caller();
+        add_operator::<T>(&caller);
+        populate_commitments::<T>(commit_phase_scheme::<T>(), c, None);
+    }: _(RawOrigin::Signed(caller), max_pair::<T>(), T::Hashing::hash_of(&0u32))
+    verify {
+        let (_, commitments) = PriceCommitments::<T>::get(&stored_max_pair::<T>()).unwrap();
+        assert_eq!(commitments.len() as u32, c + 1);
+    }
+
+    reveal_price {
+        let c in 1 .. T::MaxCommitmentsPerRound::get();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let salt = [1; 32];
+        populate_commitments::<T>(
+            reveal_phase_scheme::<T>(),
+            c,
+            Some((&caller, 1_000, 2, &salt)),
+        );
+    }: _(RawOrigin::Signed(caller), max_pair::<T>(), 1_000, 2, salt)
+    verify {
+        let (_, commitments) = PriceCommitments::<T>::get(&stored_max_pair::<T>()).unwrap();
+        assert!(commitments.iter().all(|commitment| commitment.revealed.is_some()));
+    }
+
+    set_price_access_restricted {
+    }: _(RawOrigin::Root, max_pair::<T>(), true)
+    verify {
+        assert!(RestrictedPriceAccess::<T>::get(&stored_max_pair::<T>()));
+    }
+
+    set_price_consumer {
+        let consumer = T::PriceConsumer::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+    }: _(RawOrigin::Root, max_pair::<T>(), consumer.clone(), true)
+    verify {
+        assert!(PriceConsumers::<T>::get(&stored_max_pair::<T>(), &consumer));
+    }
+
+    force_set_price {
+        let caller: T::AccountId = whitelisted_caller();
+        add_operator::<T>(&caller);
+        populate_price_state::<T>(&caller);
+    }: _(RawOrigin::Root, max_pair::<T>(), 2_000, 2)
+    verify {
+        assert_eq!(
+            PriceFeedModule::<T>::price(&stored_max_pair::<T>()).map(|record| record.amount()),
+            Some(2_000)
+        );
+    }
+
+    force_clear_price {
+        let caller: T::AccountId = whitelisted_caller();
+        add_operator::<T>(&caller);
+        populate_price_state::<T>(&caller);
+    }: _(RawOrigin::Root, max_pair::<T>())
+    verify {
+        assert!(PriceFeedModule::<T>::price(&stored_max_pair::<T>()).is_none());
+        assert!(PriceHistory::<T>::get(&stored_max_pair::<T>()).is_empty());
+    }
+
+    allow_pair {
+    }: _(RawOrigin::Root, max_pair::<T>())
+    verify {
+        assert!(AllowedPairs::<T>::contains_key(&stored_max_pair::<T>()));
+    }
+
+    disallow_pair {
+        PriceFeedModule::<T>::allow_pair(RawOrigin::Root.into(), max_pair::<T>()).unwrap();
+    }: _(RawOrigin::Root, max_pair::<T>())
+    verify {
+        assert_eq!(AllowedPairsCount::<T>::get(), 0);
+    }
+
+    claim_rewards {
+        let caller: T::AccountId = whitelisted_caller();
+        let payout = T::MaxRewardPayout::get();
+        T::Currency::make_free_balance_be(
+            &PriceFeedModule::<T>::reward_pot(),
+            T::Currency::minimum_balance().saturating_add(payout),
+        );
+        ClaimableRewards::<T>::insert(&caller, payout.saturating_add(payout));
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert_eq!(ClaimableRewards::<T>::get(&caller), payout);
+    }
+
+    request_operator_role {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(
+            &caller,
+            T::Currency::minimum_balance().saturating_add(T::OperatorRequestDeposit::get()),
+        );
+        PriceFeedModule::<T>::allow_pair(RawOrigin::Root.into(), max_pair::<T>()).unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), max_pair::<T>())
+    verify {
+        assert!(OperatorRequests::<T>::contains_key(&stored_max_pair::<T>(), &caller));
+    }
+
+    approve_operator {
+        let operator: T::AccountId = account("operator", 0, SEED);
+        request_operator_role::<T>(&operator);
+        populate_operators::<T>();
+        let origin = T::ApproveOrigin::successful_origin();
+    }: _<T::Origin>(origin, max_pair::<T>(), operator.clone())
+    verify {
+        assert!(PriceFeedModule::<T>::is_pair_operator(&stored_max_pair::<T>(), &operator));
+    }
+
+    reject_operator {
+        let operator: T::AccountId = account("operator", 0, SEED);
+        request_operator_role::<T>(&operator);
+        let origin = T::ApproveOrigin::successful_origin();
+    }: _<T::Origin>(origin, max_pair::<T>(), operator.clone())
+    verify {
+        assert!(!OperatorRequests::<T>::contains_key(&stored_max_pair::<T>(), &operator));
+    }
+
+    migrate_to_v2 {
+        let b in 0 .. MAX_CONTRACT_CONFIG_BYTES;
+
+        populate_v1::<T>(b);
+    }: {
+        migrations::v1::migrate_to_v2::<T>();
+    }
+    verify {
+        assert!(ContractConfigStore::get().is_none());
+        assert!(Price::get().is_none());
+        assert!(StorageVersion::<T>::get() == Releases::V2MultiPair);
+    }
+
+    update_price_root {
+        let p in 1 .. T::MaxPricedPairs::get();
+
+        for i in 0..p {
+            Prices::<T>::insert(indexed_pair::<T>(i), PriceRecord::new(1_000, 2, T::BlockNumber::one()));
+        }
+        PricedPairsCount::<T>::put(p);
+        PricesChanged::<T>::put(true);
+    }: {
+        PriceFeedModule::<T>::update_price_root(2u32.into());
+    }
+    verify {
+        assert!(PriceRoot::<T>::get().is_some());
+        assert!(!PricesChanged::<T>::get());
+    }
+
+    set_retention_tier {
+        let history: Vec<_> = (0..T::MaxPriceHistoryLen::get())
+            .map(|i| PriceRecord::new(1_000, 2, i.into()))
+            .collect();
+        PriceHistory::<T>::insert(&stored_max_pair::<T>(), BoundedFifo::try_from(history).unwrap());
+    }: _(RawOrigin::Root, max_pair::<T>(), RetentionTier::Ephemeral)
+    verify {
+        assert_eq!(FeedStates::<T>::get(&stored_max_pair::<T>()).retention, RetentionTier::Ephemeral);
+    }
+
+    impl_benchmark_test_suite!(
+        PriceFeedModule,
+        crate::mock::new_test_ext(),
+        crate::mock::Test
+    );
+}
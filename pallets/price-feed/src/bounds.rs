@@ -0,0 +1,62 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::cmp::Ordering;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Absolute bounds for the price amount of the currency pair.
+/// Both `min_amount` and `max_amount` are inclusive and expressed using `decimals`, so they can be compared
+/// against price records having any decimals.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PriceBounds {
+    /// Min allowed raw price amount.
+    pub min_amount: u64,
+    /// Max allowed raw price amount.
+    pub max_amount: u64,
+    /// Decimals of the `min_amount` and `max_amount`.
+    pub decimals: u8,
+}
+
+impl PriceBounds {
+    /// Instantiates new `PriceBounds`. Returns `None` if `min_amount` exceeds `max_amount`.
+    pub fn new(min_amount: u64, max_amount: u64, decimals: u8) -> Option<Self> {
+        (min_amount <= max_amount).then_some(Self {
+            min_amount,
+            max_amount,
+            decimals,
+        })
+    }
+
+    /// Returns `true` if `min_amount` doesn't exceed `max_amount`.
+    pub fn is_valid(&self) -> bool {
+        self.min_amount <= self.max_amount
+    }
+
+    /// Returns `true` if the given raw amount with decimals lies within bounds.
+    pub fn contains(&self, amount: u64, decimals: u8) -> bool {
+        cmp_amounts(amount, decimals, self.min_amount, self.decimals) != Ordering::Less
+            && cmp_amounts(amount, decimals, self.max_amount, self.decimals) != Ordering::Greater
+    }
+}
+
+/// Compares two raw amounts having different decimals.
+fn cmp_amounts(a: u64, a_decimals: u8, b: u64, b_decimals: u8) -> Ordering {
+    // Scales amount having less decimals up to the max decimals.
+    // If the scaled amount overflows `u128`, it's greater than any `u64` amount.
+    let scale = |amount: u64, diff: u8| {
+        10u128
+            .checked_pow(diff.into())
+            .and_then(|multiplier| (amount as u128).checked_mul(multiplier))
+    };
+
+    match a_decimals.cmp(&b_decimals) {
+        Ordering::Equal => a.cmp(&b),
+        Ordering::Less => {
+            scale(a, b_decimals - a_decimals).map_or(Ordering::Greater, |a| a.cmp(&b.into()))
+        }
+        Ordering::Greater => {
+            scale(b, a_decimals - b_decimals).map_or(Ordering::Less, |b| u128::from(a).cmp(&b))
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Two-phase price submission preventing operators from mirroring each other's prices.
+//!
+//! Root enables the scheme per currency pair via `set_commit_reveal_scheme`. Rounds last `period` blocks each starting
+//! from the block `0`. During the commit phase of a round, operators submit only a hash of their price via
+//! `commit_price`, see [`price_commitment`]. During the following reveal phase, they submit the price along with
+//! the salt via `reveal_price`, which is accepted only if it matches the commitment. Once the reveal phase ends,
+//! the median of the revealed prices becomes the pair's price at the beginning of the next block, and operators
+//! which committed but didn't reveal are reported to `Config::OnUnrevealedCommitment`. Rounds are scheduled for
+//! the aggregation at their [`CommitRevealScheme::reveal_deadline`] once committed to, so only the due rounds are
+//! visited.
+//!
+//! Since nobody sees the prices before the commit phase ends, lazy operators can't copy the first visible submission.
+//! `set_price` is rejected for the pairs using the scheme.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Hash;
+use sp_std::prelude::*;
+
+/// Salt mixed into the price commitment, so the committed price can't be brute-forced.
+pub type CommitmentSalt = [u8; 32];
+
+/// Timing of the commit and reveal phases of each round.
+/// Round `n` covers blocks `n * period..(n + 1) * period`. Its commit phase covers the first `commit_length` blocks
+/// of the round, and its reveal phase covers the following `reveal_length` blocks.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CommitRevealScheme {
+    /// Length of the round in blocks.
+    pub period: u32,
+    /// Length of the commit phase in blocks.
+    pub commit_length: u32,
+    /// Length of the reveal phase in blocks.
+    pub reveal_length: u32,
+}
+
+/// Phase of the commit-reveal round.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommitRevealPhase {
+    /// Operators submit commitments.
+    Commit,
+    /// Operators reveal committed prices.
+    Reveal,
+    /// The round is closed for submissions.
+    Closed,
+}
+
+impl CommitRevealScheme {
+    /// Instantiates new `CommitRevealScheme`. Returns `None` if either phase is empty or they don't fit into the round.
+    pub fn new(period: u32, commit_length: u32, reveal_length: u32) -> Option<Self> {
+        let scheme = Self {
+            period,
+            commit_length,
+            reveal_length,
+        };
+
+        scheme.is_valid().then_some(scheme)
+    }
+
+    /// Returns `true` if both phases are non-empty and fit into the round.
+    pub fn is_valid(&self) -> bool {
+        self.commit_length > 0
+            && self.reveal_length > 0
+            && self
+                .commit_length
+                .checked_add(self.reveal_length)
+                .map_or(false, |end| end <= self.period)
+    }
+
+    /// Returns index of the round containing the given block along with the round's phase at this block.
+    pub fn phase_at(&self, block_number: u64) -> (u64, CommitRevealPhase) {
+        let (round, offset) = match (
+            block_number.checked_div(self.period.into()),
+            block_number.checked_rem(self.period.into()),
+        ) {
+            (Some(round), Some(offset)) => (round, offset),
+            _ => return (0, CommitRevealPhase::Closed),
+        };
+
+        let phase = if offset < self.commit_length.into() {
+            CommitRevealPhase::Commit
+        } else if offset < u64::from(self.commit_length) + u64::from(self.reveal_length) {
+            CommitRevealPhase::Reveal
+        } else {
+            CommitRevealPhase::Closed
+        };
+
+        (round, phase)
+    }
+
+    /// Returns the first block after the reveal phase of the given round, at which the round is over.
+    pub fn reveal_deadline(&self, round: u64) -> u64 {
+        round
+            .saturating_mul(self.period.into())
+            .saturating_add(self.commit_length.into())
+            .saturating_add(self.reveal_length.into())
+    }
+
+    /// Returns `true` if the reveal phase of the given round is over at the given block.
+    pub fn is_round_over(&self, round: u64, block_number: u64) -> bool {
+        let (current_round, phase) = self.phase_at(block_number);
+
+        round < current_round || (round == current_round && phase == CommitRevealPhase::Closed)
+    }
+}
+
+/// Commitment of the operator to the price, see [`price_commitment`].
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PriceCommitment<AccountId, Hash> {
+    /// Operator which submitted the commitment.
+    pub operator: AccountId,
+    /// Hash of the committed price.
+    pub commitment: Hash,
+    /// Revealed raw price amount and decimals.
+    pub revealed: Option<(u64, u8)>,
+}
+
+/// Handles operators which committed to a price but didn't reveal it within the reveal phase.
+pub trait OnUnrevealedCommitment<Pair, AccountId> {
+    /// Penalizes the operator of the currency pair, e.g. by slashing its bond or removing it.
+    /// Returns the consumed weight.
+    fn on_unrevealed_commitment(pair: &Pair, operator: &AccountId, round: u64) -> Weight;
+}
+
+/// Doesn't penalize anybody besides emitting `CommitmentNotRevealed` events.
+impl<Pair, AccountId> OnUnrevealedCommitment<Pair, AccountId> for () {
+    fn on_unrevealed_commitment(_pair: &Pair, _operator: &AccountId, _round: u64) -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Computes the commitment of the operator to the price of the currency pair for the given round.
+/// The pair and the operator are committed as well, so the commitment can't be replayed by the other operators
+/// or in the following rounds.
+pub fn price_commitment<H: Hash, Pair: Encode, AccountId: Encode>(
+    pair: &Pair,
+    round: u64,
+    operator: &AccountId,
+    price: u64,
+    decimals: u8,
+    salt: &CommitmentSalt,
+) -> H::Output {
+    H::hash_of(&(pair, round, operator, price, decimals, salt))
+}
+
+/// Returns median of the prices normalized to their max decimals. If the amount of prices is even,
+/// the mean of the two middle prices is returned. If the resulting amount doesn't fit into `u64`,
+/// decimals get reduced truncating the extra digits. Returns `None` if there're no prices or the arithmetic overflows.
+pub fn median_price(prices: &[(u64, u8)]) -> Option<(u64, u8)> {
+    let mut decimals = prices.iter().map(|(_, decimals)| *decimals).max()?;
+    let mut amounts = prices
+        .iter()
+        .map(|(amount, price_decimals)| {
+            10u128
+                .checked_pow((decimals - price_decimals).into())?
+                .checked_mul((*amount).into())
+        })
+        .collect::<Option<Vec<_>>>()?;
+    amounts.sort_unstable();
+
+    let mid = amounts.len() / 2;
+    let mut amount = if amounts.len() % 2 == 0 {
+        let (lower, upper) = (amounts[mid - 1], amounts[mid]);
+
+        lower / 2 + upper / 2 + (lower % 2 + upper % 2) / 2
+    } else {
+        amounts[mid]
+    };
+    while amount > u64::MAX.into() {
+        amount /= 10;
+        decimals = decimals.checked_sub(1)?;
+    }
+
+    Some((amount as u64, decimals))
+}
+
+/// Returns half-width of the confidence interval of the median price, i.e. the max distance between the median
+/// and the prices in the median decimals, so the interval widens as the operators disagree. The distance
+/// is rounded up. Returns `None` if there're less than two prices or the arithmetic overflows.
+pub fn median_confidence(prices: &[(u64, u8)], (median, decimals): (u64, u8)) -> Option<u64> {
+    if prices.len() < 2 {
+        return None;
+    }
+    // Compares the prices and the median normalized to their max decimals.
+    let max_decimals = prices
+        .iter()
+        .map(|(_, decimals)| *decimals)
+        .max()?
+        .max(decimals);
+    let normalize = |amount: u64, decimals: u8| {
+        10u128
+            .checked_pow((max_decimals - decimals).into())?
+            .checked_mul(amount.into())
+    };
+
+    let median = normalize(median, decimals)?;
+    let mut distance = 0;
+    for (amount, decimals) in prices {
+        distance = distance.max(normalize(*amount, *decimals)?.abs_diff(median));
+    }
+    let divisor = 10u128.checked_pow((max_decimals - decimals).into())?;
+
+    (distance / divisor + u128::from(distance % divisor != 0))
+        .try_into()
+        .ok()
+}
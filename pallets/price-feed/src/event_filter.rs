@@ -0,0 +1,103 @@
+//! Runtime-configurable set of the deposited events.
+
+use bitflags::bitflags;
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+
+use crate::{Config, Event};
+
+bitflags! {
+    /// Kinds of the events which can be suppressed by `Config::DepositedEvents`.
+    /// `PriceSet`, `AllPairsPaused` and `AllPairsResumed` aren't listed since they're always deposited.
+    ///
+    /// Example: `pub const DepositedEvents: EventKinds = EventKinds::all().difference(EventKinds::OPERATOR_ADDED);`
+    #[derive(Encode, Decode, TypeInfo)]
+    pub struct EventKinds: u64 {
+        const OPERATOR_ADDED = 1 << 0;
+        const OPERATOR_REMOVED = 1 << 1;
+        const EVENT_SAMPLING_SET = 1 << 2;
+        const PRICE_BOUNDS_SET = 1 << 3;
+        const FEED_SPEC_HASH_SET = 1 << 4;
+        const ANOMALY_REPORTED = 1 << 5;
+        const PAIR_PAUSED = 1 << 6;
+        const PAIR_RESUMED = 1 << 7;
+        const PRICE_SOURCE_RECORDED = 1 << 8;
+        const REQUIRED_IDENTITY_LEVEL_SET = 1 << 9;
+        const SUBMISSION_WINDOW_SET = 1 << 10;
+        const HTTP_SOURCE_SET = 1 << 11;
+        const OPERATOR_REMOVAL_SCHEDULED = 1 << 12;
+        const CONTRACT_CONFIG_SET = 1 << 13;
+        const CONTRACT_PRICE_READ = 1 << 14;
+        const COMMIT_REVEAL_SCHEME_SET = 1 << 15;
+        const PRICE_COMMITTED = 1 << 16;
+        const PRICE_REVEALED = 1 << 17;
+        const COMMITMENT_NOT_REVEALED = 1 << 18;
+        const REVEALED_PRICE_SET = 1 << 19;
+        const OPERATOR_INACTIVE = 1 << 20;
+        const PRICE_ACCESS_RESTRICTION_SET = 1 << 21;
+        const PRICE_CONSUMER_SET = 1 << 22;
+        const PRICE_FORCE_SET = 1 << 23;
+        const PRICE_CLEARED = 1 << 24;
+        const PAIR_ALLOWED = 1 << 25;
+        const PAIR_DISALLOWED = 1 << 26;
+        const REWARDS_CLAIMED = 1 << 27;
+        const COMMIT_REVEAL_SCHEME_SCHEDULED = 1 << 28;
+        const OPERATOR_ROLE_REQUESTED = 1 << 29;
+        const OPERATOR_REQUEST_REJECTED = 1 << 30;
+        const SUBMISSION_REJECTED_AS_OUTLIER = 1 << 31;
+        const RETENTION_TIER_SET = 1 << 32;
+    }
+}
+
+impl<T: Config> Event<T> {
+    /// Returns the kind of the event or `None` if the event can't be suppressed.
+    pub fn kind(&self) -> Option<EventKinds> {
+        let kind = match self {
+            Event::OperatorAdded { .. } => EventKinds::OPERATOR_ADDED,
+            Event::OperatorRemoved { .. } => EventKinds::OPERATOR_REMOVED,
+            Event::PriceSet { .. } => return None,
+            Event::EventSamplingSet { .. } => EventKinds::EVENT_SAMPLING_SET,
+            Event::PriceBoundsSet { .. } => EventKinds::PRICE_BOUNDS_SET,
+            Event::FeedSpecHashSet { .. } => EventKinds::FEED_SPEC_HASH_SET,
+            Event::AnomalyReported { .. } => EventKinds::ANOMALY_REPORTED,
+            Event::PairPaused { .. } => EventKinds::PAIR_PAUSED,
+            Event::PairResumed { .. } => EventKinds::PAIR_RESUMED,
+            Event::PriceSourceRecorded { .. } => EventKinds::PRICE_SOURCE_RECORDED,
+            Event::RequiredIdentityLevelSet { .. } => EventKinds::REQUIRED_IDENTITY_LEVEL_SET,
+            Event::SubmissionWindowSet { .. } => EventKinds::SUBMISSION_WINDOW_SET,
+            Event::HttpSourceSet { .. } => EventKinds::HTTP_SOURCE_SET,
+            Event::OperatorRemovalScheduled { .. } => EventKinds::OPERATOR_REMOVAL_SCHEDULED,
+            Event::ContractConfigSet { .. } => EventKinds::CONTRACT_CONFIG_SET,
+            Event::ContractPriceRead { .. } => EventKinds::CONTRACT_PRICE_READ,
+            Event::CommitRevealSchemeSet { .. } => EventKinds::COMMIT_REVEAL_SCHEME_SET,
+            Event::PriceCommitted { .. } => EventKinds::PRICE_COMMITTED,
+            Event::PriceRevealed { .. } => EventKinds::PRICE_REVEALED,
+            Event::CommitmentNotRevealed { .. } => EventKinds::COMMITMENT_NOT_REVEALED,
+            Event::RevealedPriceSet { .. } => EventKinds::REVEALED_PRICE_SET,
+            Event::OperatorInactive { .. } => EventKinds::OPERATOR_INACTIVE,
+            Event::PriceAccessRestrictionSet { .. } => EventKinds::PRICE_ACCESS_RESTRICTION_SET,
+            Event::PriceConsumerSet { .. } => EventKinds::PRICE_CONSUMER_SET,
+            Event::PriceForceSet { .. } => EventKinds::PRICE_FORCE_SET,
+            Event::PriceCleared { .. } => EventKinds::PRICE_CLEARED,
+            Event::PairAllowed { .. } => EventKinds::PAIR_ALLOWED,
+            Event::PairDisallowed { .. } => EventKinds::PAIR_DISALLOWED,
+            Event::RewardsClaimed { .. } => EventKinds::REWARDS_CLAIMED,
+            Event::CommitRevealSchemeScheduled { .. } => EventKinds::COMMIT_REVEAL_SCHEME_SCHEDULED,
+            Event::OperatorRoleRequested { .. } => EventKinds::OPERATOR_ROLE_REQUESTED,
+            Event::OperatorRequestRejected { .. } => EventKinds::OPERATOR_REQUEST_REJECTED,
+            Event::SubmissionRejectedAsOutlier { .. } => EventKinds::SUBMISSION_REJECTED_AS_OUTLIER,
+            Event::RetentionTierSet { .. } => EventKinds::RETENTION_TIER_SET,
+            Event::AllPairsPaused | Event::AllPairsResumed => return None,
+            Event::__Ignore { .. } => return None,
+        };
+
+        Some(kind)
+    }
+
+    /// Returns `true` if the event must be deposited according to `Config::DepositedEvents`.
+    pub fn is_enabled(&self) -> bool {
+        self.kind()
+            .map_or(true, |kind| T::DepositedEvents::get().contains(kind))
+    }
+}
@@ -0,0 +1,173 @@
+//! Prices read from Chainlink-style EVM aggregator contracts.
+//!
+//! Root configures a [`ContractConfig`] per currency pair via `set_contract_config`. Every `update_period` blocks,
+//! `on_initialize` calls `query_price_abi_encoded` (`latestRoundData` at the time of writing) on the configured contract
+//! through `Config::EvmCaller`, decodes the returned `answer`, and stores it as the pair's price.
+//! `on_initialize` visits at most `Config::MaxContractVisitsPerBlock` configs per block continuing from the last
+//! visited one, so with more configs a due price is read once its config is visited in the following blocks.
+//! If `query_aggregator_abi_encoded` is set, the configured address is treated as a proxy which is queried for
+//! the aggregator address first, as it was done by the single-pair V1 storage.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, weights::Weight, BoundedVec};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, U256};
+use sp_runtime::traits::CheckedConversion;
+use sp_std::prelude::*;
+
+/// Max length of the encoded contract call data in bytes: a selector followed by two words.
+pub const MAX_CALL_DATA_BYTES_LEN: u32 = 4 + 32 * 2;
+
+/// Length of the ABI word in bytes.
+const WORD_LEN: usize = 32;
+
+/// Amount of words returned by `latestRoundData`: `(uint80 roundId, int256 answer, uint256 startedAt,
+/// uint256 updatedAt, uint80 answeredInRound)`.
+const LATEST_ROUND_DATA_WORDS: usize = 5;
+
+/// Encoded contract call data.
+pub type CallData = BoundedVec<u8, ConstU32<MAX_CALL_DATA_BYTES_LEN>>;
+
+/// Configuration of the EVM contract providing the price of a currency pair.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractConfig {
+    /// Address of the aggregator contract, or of the proxy contract if `query_aggregator_abi_encoded` is set.
+    pub address: H160,
+    /// Encoded call of the proxy contract method returning the aggregator address, e.g. `aggregator()`.
+    pub query_aggregator_abi_encoded: Option<CallData>,
+    /// Encoded call of the aggregator contract method returning the price, e.g. `latestRoundData()`.
+    /// The method must return `latestRoundData`-compatible ABI with the price being the second word.
+    pub query_price_abi_encoded: CallData,
+    /// Decimals of the answer returned by the aggregator, e.g. `8` for Chainlink USD feeds.
+    pub decimals: u8,
+    /// Price is read every `update_period` blocks. Must be greater than zero.
+    pub update_period: u32,
+}
+
+/// Reasons why the price can't be read from the contract.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EvmPriceError {
+    /// Call of the proxy contract failed.
+    AggregatorCallFailed,
+    /// Proxy contract returned malformed aggregator address.
+    InvalidAggregatorAddress,
+    /// Call of the aggregator contract failed.
+    PriceCallFailed,
+    /// Aggregator contract returned malformed round data.
+    InvalidRoundData,
+    /// Aggregator contract returned non-positive answer.
+    NonPositiveAnswer,
+}
+
+impl ContractConfig {
+    /// Returns `true` if the config can be used to read prices.
+    pub fn is_valid(&self) -> bool {
+        self.update_period > 0
+    }
+
+    /// Returns `true` if the price should be read at the given block.
+    pub fn is_due(&self, block_number: u64) -> bool {
+        block_number
+            .checked_rem(self.update_period.into())
+            .map_or(false, |offset| offset == 0)
+    }
+
+    /// Returns `true` if the price should have been read at any block after `after` up to `until` inclusive,
+    /// i.e. the config visited at `after` the last time is due by `until`.
+    pub fn is_due_between(&self, after: u64, until: u64) -> bool {
+        let period = u64::from(self.update_period);
+
+        until
+            .checked_div(period)
+            .zip(after.checked_div(period))
+            .map_or(false, |(until, after)| until > after)
+    }
+
+    /// Reads the price from the contract using `Caller` returning raw price amount and decimals
+    /// along with the weight consumed by the calls.
+    pub fn read_price<Caller: EvmCaller>(&self) -> (Result<(u64, u8), EvmPriceError>, Weight) {
+        let mut weight = Weight::zero();
+        let mut call = |target, input: &CallData| {
+            let (output, call_weight) = Caller::call(target, input);
+            weight = weight.saturating_add(call_weight);
+
+            output
+        };
+
+        let price = (|| {
+            let aggregator = match &self.query_aggregator_abi_encoded {
+                Some(query) => call(self.address, query)
+                    .ok_or(EvmPriceError::AggregatorCallFailed)
+                    .and_then(|output| {
+                        decode_address(&output).ok_or(EvmPriceError::InvalidAggregatorAddress)
+                    })?,
+                None => self.address,
+            };
+            let output = call(aggregator, &self.query_price_abi_encoded)
+                .ok_or(EvmPriceError::PriceCallFailed)?;
+            let answer = decode_latest_round_answer(&output)?;
+
+            answer_to_price(answer, self.decimals).ok_or(EvmPriceError::NonPositiveAnswer)
+        })();
+
+        (price, weight)
+    }
+}
+
+/// Calls EVM contracts on behalf of the pallet.
+pub trait EvmCaller {
+    /// Calls the contract at `target` with the given input without committing any state changes.
+    /// Returns the call output, or `None` if the call failed, along with the consumed weight.
+    fn call(target: H160, input: &[u8]) -> (Option<Vec<u8>>, Weight);
+}
+
+/// Fails every call, so no prices are read from the contracts.
+impl EvmCaller for () {
+    fn call(_target: H160, _input: &[u8]) -> (Option<Vec<u8>>, Weight) {
+        (None, Weight::zero())
+    }
+}
+
+/// Decodes an address returned as a single ABI word.
+pub fn decode_address(output: &[u8]) -> Option<H160> {
+    let word = output.get(..WORD_LEN)?;
+    let (padding, address) = word.split_at(WORD_LEN - H160::len_bytes());
+
+    padding
+        .iter()
+        .all(|byte| *byte == 0)
+        .then(|| H160::from_slice(address))
+}
+
+/// Decodes `answer` of the `latestRoundData` output. Returns `NonPositiveAnswer` if the answer
+/// is negative or zero.
+pub fn decode_latest_round_answer(output: &[u8]) -> Result<U256, EvmPriceError> {
+    if output.len() < WORD_LEN * LATEST_ROUND_DATA_WORDS {
+        return Err(EvmPriceError::InvalidRoundData);
+    }
+
+    // `int256` in two's complement, so the set highest bit denotes a negative number.
+    let answer = U256::from_big_endian(&output[WORD_LEN..WORD_LEN * 2]);
+    if answer.is_zero() || answer.bit(255) {
+        Err(EvmPriceError::NonPositiveAnswer)
+    } else {
+        Ok(answer)
+    }
+}
+
+/// Converts the answer with the given decimals into the raw price amount and decimals.
+/// If the answer doesn't fit into `u64`, decimals get reduced truncating the extra digits.
+/// Returns `None` if the answer is zero or too large to be represented.
+pub fn answer_to_price(mut answer: U256, mut decimals: u8) -> Option<(u64, u8)> {
+    while answer > U256::from(u64::MAX) {
+        answer /= U256::from(10u8);
+        decimals = decimals.checked_sub(1)?;
+    }
+
+    let amount: u64 = answer.checked_into()?;
+
+    (amount > 0).then_some((amount, decimals))
+}
@@ -0,0 +1,87 @@
+//! Adapter allowing to drive `pallet-transaction-payment` fee multiplier by the price of the `GAS` pseudo-currency.
+
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use pallet_transaction_payment::{Multiplier, MultiplierUpdate};
+use price_provider::{CurrencySymbolPair, PriceProvider, PriceRecord};
+use sp_runtime::{traits::Convert, FixedPointNumber, Perquintill};
+
+use crate::{Config, Pallet};
+
+/// Symbol of the pseudo-currency representing the gas price.
+pub const GAS_SYMBOL: &str = "GAS";
+
+/// Provides `GAS` pseudo-currency symbol.
+pub struct GasSymbol;
+
+impl Get<&'static str> for GasSymbol {
+    fn get() -> &'static str {
+        GAS_SYMBOL
+    }
+}
+
+/// Produces next fee multiplier using the price of the `GAS/NativeSym` pair.
+/// Given `GAS/NativeSym` pair price `N`, the next fee multiplier will be equal to `N`.
+///
+/// In case if the price isn't set or can't be represented as a `Multiplier`, previous multiplier is used.
+/// Resulting multiplier is never lower than `MinMultiplier`.
+pub struct NextFeeMultiplierProvider<T, NativeSym, MinMultiplier>(
+    PhantomData<(T, NativeSym, MinMultiplier)>,
+);
+
+impl<T, NativeSym, MinMultiplier> NextFeeMultiplierProvider<T, NativeSym, MinMultiplier>
+where
+    T: Config,
+    NativeSym: Get<&'static str>,
+{
+    /// Returns `GAS/NativeSym` pair.
+    pub fn pair() -> CurrencySymbolPair<&'static str, &'static str> {
+        CurrencySymbolPair::new(GAS_SYMBOL, NativeSym::get())
+    }
+
+    /// Converts supplied price record to the `Multiplier`.
+    pub fn multiplier_from_record(record: &PriceRecord<T::BlockNumber>) -> Option<Multiplier> {
+        let divisor = 10u128.checked_pow(record.decimals())?;
+
+        Multiplier::checked_from_rational(record.amount(), divisor)
+    }
+}
+
+impl<T, NativeSym, MinMultiplier> Convert<Multiplier, Multiplier>
+    for NextFeeMultiplierProvider<T, NativeSym, MinMultiplier>
+where
+    T: Config,
+    NativeSym: Get<&'static str>,
+    MinMultiplier: Get<Multiplier>,
+{
+    fn convert(previous: Multiplier) -> Multiplier {
+        Pallet::<T>::pair_price(Self::pair())
+            .ok()
+            .flatten()
+            .and_then(|record| Self::multiplier_from_record(&record))
+            .unwrap_or(previous)
+            .max(MinMultiplier::get())
+    }
+}
+
+impl<T, NativeSym, MinMultiplier> MultiplierUpdate
+    for NextFeeMultiplierProvider<T, NativeSym, MinMultiplier>
+where
+    T: Config,
+    NativeSym: Get<&'static str>,
+    MinMultiplier: Get<Multiplier>,
+{
+    fn min() -> Multiplier {
+        MinMultiplier::get()
+    }
+
+    /// The multiplier doesn't depend on the block fullness.
+    fn target() -> Perquintill {
+        Default::default()
+    }
+
+    /// The multiplier doesn't depend on the block fullness.
+    fn variability() -> Multiplier {
+        Default::default()
+    }
+}
@@ -0,0 +1,46 @@
+use codec::{Decode, Encode};
+use price_provider::CurrencySymbolPair;
+use scale_info::{prelude::string::String, TypeInfo};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Hash;
+use sp_std::prelude::*;
+
+/// Method used to aggregate prices observed from the different sources.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum Aggregation {
+    /// Median of the observed prices.
+    Median,
+    /// Arithmetic mean of the observed prices.
+    Mean,
+    /// The most recently observed price.
+    Latest,
+}
+
+/// Machine-readable specification of the currency pair price feed.
+/// Only the hash of the SCALE-encoded spec is stored on-chain, so off-chain consumers can verify that they
+/// honor the same spec governance approved.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeedSpec<AccountId> {
+    /// Currency pair this feed provides the price for.
+    pub pair: CurrencySymbolPair<String, String>,
+    /// Identifiers of the sources prices are observed from.
+    pub sources: Vec<String>,
+    /// Method used to aggregate prices observed from the sources.
+    pub aggregation: Aggregation,
+    /// Expected interval between price updates in milliseconds.
+    pub update_frequency_millis: u64,
+    /// Operators submitting prices for the pair.
+    pub operators: Vec<AccountId>,
+}
+
+impl<AccountId: Encode> FeedSpec<AccountId> {
+    /// Returns hash of the SCALE-encoded spec computed using the supplied hasher.
+    pub fn hash<H: Hash>(&self) -> H::Output {
+        H::hash_of(self)
+    }
+}
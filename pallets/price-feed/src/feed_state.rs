@@ -0,0 +1,125 @@
+//! Per-pair state read and updated on each price submission.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::ConstU32;
+use price_provider::{exponentially_weighted_average, PriceRecord};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::{UniqueSaturatedFrom, UniqueSaturatedInto};
+use utils::BoundedUtf8Bytes;
+
+use crate::{PriceBounds, SubmissionWindow};
+
+/// Max length of the price source tag in bytes.
+pub const MAX_SOURCE_TAG_BYTES_LEN: u32 = 32;
+
+/// Tag of the price source provided by the operator, e.g. `binance` or `kraken-avg`.
+pub type SourceTag = BoundedUtf8Bytes<ConstU32<MAX_SOURCE_TAG_BYTES_LEN>>;
+
+/// Importance of the currency pair determining how many latest price records are kept in `PriceHistory`.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RetentionTier {
+    /// Keeps up to `Config::MaxPriceHistoryLen` records.
+    Critical,
+    /// Keeps up to `Config::StandardPriceHistoryLen` records.
+    Standard,
+    /// Keeps up to `Config::EphemeralPriceHistoryLen` records.
+    Ephemeral,
+}
+
+impl Default for RetentionTier {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+/// Configuration and round state of a single currency pair.
+/// Stored as a single value, so the price submission performs one read and one write of it
+/// instead of touching a separate storage item per field.
+///
+/// The default value denotes a pair without any configuration and isn't kept in storage.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, Default, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeedState {
+    /// Price updates are paused pending governance review.
+    pub paused: bool,
+    /// Absolute price amount bounds. Prices of the pairs without bounds aren't restricted.
+    pub bounds: Option<PriceBounds>,
+    /// Identity level required from the operators. `0` means that any operator is accepted.
+    pub required_identity_level: u32,
+    /// Amount of price updates per each emitted `PriceSet` event. `0` or `1` means every update.
+    pub emit_events_every_n_updates: u32,
+    /// Amount of price updates happened since the last emitted `PriceSet` event.
+    pub updates_since_last_event: u32,
+    /// Time of the latest price update according to `Config::FeedTime`.
+    pub updated_at: Option<u64>,
+    /// Source tag of the latest price update if provided by the operator.
+    pub source: Option<SourceTag>,
+    /// Window of each round during which the operators can submit prices.
+    /// Prices of the pairs without the window can be submitted at any block.
+    pub submission_window: Option<SubmissionWindow>,
+    /// Exponentially-weighted moving average of the prices updated on each price update,
+    /// see `Config::EwmaHalfLife`. The block number is the one of the latest folded price.
+    pub ewma: Option<PriceRecord<u64>>,
+    /// Retention tier of the price history. Pairs are `Standard` unless tagged otherwise.
+    pub retention: RetentionTier,
+}
+
+impl FeedState {
+    /// Returns `true` if the state doesn't hold any value, so it can be removed from storage.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Records a price update and returns `true` if `PriceSet` event should be emitted.
+    pub fn record_update(&mut self) -> bool {
+        if self.emit_events_every_n_updates <= 1 {
+            return true;
+        }
+
+        self.updates_since_last_event = self.updates_since_last_event.saturating_add(1);
+        if self.updates_since_last_event >= self.emit_events_every_n_updates {
+            self.updates_since_last_event = 0;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Folds the price record into `ewma` using the given half-life in blocks,
+    /// see [`price_provider::exponentially_weighted_average`]. The average restarts from the record
+    /// if it can't be computed, e.g. once the record precedes the average.
+    pub fn update_ewma<BlockNumber>(&mut self, record: PriceRecord<BlockNumber>, half_life: u32)
+    where
+        BlockNumber: UniqueSaturatedInto<u64> + Copy,
+    {
+        let record = map_block_number(record, UniqueSaturatedInto::unique_saturated_into);
+
+        self.ewma =
+            exponentially_weighted_average(self.ewma, record, half_life.into()).or(Some(record));
+    }
+
+    /// Returns the exponentially-weighted moving average of the prices with the block number of `BlockNumber` type.
+    pub fn ewma_record<BlockNumber>(&self) -> Option<PriceRecord<BlockNumber>>
+    where
+        BlockNumber: UniqueSaturatedFrom<u64>,
+    {
+        self.ewma
+            .map(|ewma| map_block_number(ewma, BlockNumber::unique_saturated_from))
+    }
+}
+
+/// Converts the block number of the price record keeping its amount and decimals.
+fn map_block_number<From: Copy, To>(
+    record: PriceRecord<From>,
+    f: impl FnOnce(From) -> To,
+) -> PriceRecord<To> {
+    PriceRecord::new(
+        record.amount(),
+        record.decimals() as u8,
+        f(record.block_number()),
+    )
+}
@@ -0,0 +1,43 @@
+//! Time sources used to measure the age of the prices.
+
+use core::marker::PhantomData;
+use frame_support::traits::UnixTime;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+use crate::{Config, LocalTime};
+
+/// Provides the current time used to measure the age of the prices.
+/// Units are defined by the implementation (e.g. blocks or milliseconds).
+pub trait FeedTime {
+    /// Returns the current time or `None` if it's unknown.
+    fn now() -> Option<u64>;
+}
+
+/// Measures time in blocks using the current block number.
+pub struct BlockNumberFeedTime<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> FeedTime for BlockNumberFeedTime<T> {
+    fn now() -> Option<u64> {
+        Some(<frame_system::Pallet<T>>::block_number().unique_saturated_into())
+    }
+}
+
+/// Measures time in milliseconds since the unix epoch using the supplied `UnixTime` implementation,
+/// e.g. `pallet_timestamp`.
+pub struct UnixFeedTime<U>(PhantomData<U>);
+
+impl<U: UnixTime> FeedTime for UnixFeedTime<U> {
+    fn now() -> Option<u64> {
+        Some(U::now().as_millis().unique_saturated_into())
+    }
+}
+
+/// Measures time in milliseconds since the unix epoch using the node's local clock noted via inherent.
+/// See [`crate::inherent`] for details.
+pub struct LocalFeedTime<T>(PhantomData<T>);
+
+impl<T: Config> FeedTime for LocalFeedTime<T> {
+    fn now() -> Option<u64> {
+        <LocalTime<T>>::get().map(|(_, now)| now)
+    }
+}
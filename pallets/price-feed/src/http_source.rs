@@ -0,0 +1,314 @@
+//! Prices pulled from HTTP endpoints by the offchain workers.
+//!
+//! Root configures an [`HttpSource`] per currency pair via `set_http_source`. Nodes running offchain workers fetch
+//! the source `url`, extract the price located at `json_path` from the JSON response, and submit `set_price`
+//! transactions via `Config::HttpPriceSubmitter`. Use [`SignedHttpPriceSubmitter`] to sign them with the operator
+//! keys from the node's keystore, so operators only need to run a node instead of a custom bot.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::ConstU32;
+use frame_system::offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer};
+use price_provider::CurrencySymbolPair;
+use scale_info::{prelude::string::String, TypeInfo};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+    offchain::{http, Duration},
+    traits::IdentifyAccount,
+    RuntimeAppPublic,
+};
+use sp_std::prelude::*;
+use utils::BoundedUtf8Bytes;
+
+use crate::{anomaly::parse_decimal_price, Call, Config, Pallet};
+
+/// Max length of the HTTP source URL in bytes.
+pub const MAX_HTTP_SOURCE_URL_BYTES_LEN: u32 = 256;
+
+/// Max length of the JSON path in bytes.
+pub const MAX_JSON_PATH_BYTES_LEN: u32 = 64;
+
+/// HTTP source request timeout in milliseconds.
+pub const HTTP_SOURCE_TIMEOUT_MILLIS: u64 = 2_000;
+
+/// Max nesting of the JSON values traversed while looking up the price.
+const MAX_JSON_DEPTH: u32 = 32;
+
+/// HTTP endpoint providing the price of a currency pair.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct HttpSource {
+    /// URL responding to `GET` requests with JSON containing the price.
+    pub url: BoundedUtf8Bytes<ConstU32<MAX_HTTP_SOURCE_URL_BYTES_LEN>>,
+    /// Dot-separated path of the price within the response, e.g. `data.prices.0.usd`.
+    /// Segments are object keys or array indices. The price can be a JSON number or a string
+    /// holding a plain decimal price. An empty path denotes the whole response.
+    pub json_path: BoundedUtf8Bytes<ConstU32<MAX_JSON_PATH_BYTES_LEN>>,
+    /// Decimals of the submitted price. Extra digits of the fetched price are truncated.
+    pub decimals: u8,
+}
+
+impl HttpSource {
+    /// Extracts the price amount with the source decimals from the JSON response.
+    pub fn extract_price(&self, body: &[u8]) -> Option<u64> {
+        let value = json_value_at(body, &self.json_path)?;
+        let value = value
+            .strip_prefix(b"\"")
+            .and_then(|value| value.strip_suffix(b"\""))
+            .unwrap_or(value);
+        let (amount, decimals) = parse_decimal_price(value)?;
+
+        if decimals > self.decimals {
+            Some(amount / 10u64.checked_pow((decimals - self.decimals).into())?)
+        } else {
+            amount.checked_mul(10u64.checked_pow((self.decimals - decimals).into())?)
+        }
+    }
+
+    /// Fetches the price amount with the source decimals.
+    pub fn fetch_price(&self) -> Result<u64, http::Error> {
+        let deadline =
+            sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_SOURCE_TIMEOUT_MILLIS));
+
+        let pending = http::Request::get(self.url.as_str())
+            .deadline(deadline)
+            .send()
+            .map_err(|_| http::Error::IoError)?;
+        let response = pending
+            .try_wait(deadline)
+            .map_err(|_| http::Error::DeadlineReached)??;
+        if response.code != 200 {
+            return Err(http::Error::Unknown);
+        }
+
+        let body = response.body().collect::<Vec<u8>>();
+
+        self.extract_price(&body).ok_or(http::Error::Unknown)
+    }
+}
+
+/// Submits prices fetched from the HTTP sources by the offchain workers.
+pub trait HttpPriceSubmitter<T: Config> {
+    /// Returns `true` if the node is able to submit prices, so the sources are worth fetching.
+    fn can_submit() -> bool;
+
+    /// Submits the price of the currency pair. Returns `Err` if nothing was submitted.
+    fn submit_price(
+        currency_pair: CurrencySymbolPair<String, String>,
+        price: u64,
+        decimals: u8,
+    ) -> Result<(), ()>;
+}
+
+/// Doesn't submit any prices, so the HTTP sources aren't fetched.
+impl<T: Config> HttpPriceSubmitter<T> for () {
+    fn can_submit() -> bool {
+        false
+    }
+
+    fn submit_price(
+        _currency_pair: CurrencySymbolPair<String, String>,
+        _price: u64,
+        _decimals: u8,
+    ) -> Result<(), ()> {
+        Err(())
+    }
+}
+
+/// Submits `set_price` transactions signed by every key of `AuthorityId` type found in the node's keystore
+/// which account is an operator of the currency pair.
+pub struct SignedHttpPriceSubmitter<AuthorityId>(PhantomData<AuthorityId>);
+
+impl<T, AuthorityId> HttpPriceSubmitter<T> for SignedHttpPriceSubmitter<AuthorityId>
+where
+    T: Config + CreateSignedTransaction<Call<T>>,
+    AuthorityId: AppCrypto<T::Public, T::Signature>,
+{
+    fn can_submit() -> bool {
+        Signer::<T, AuthorityId>::all_accounts().can_sign()
+    }
+
+    fn submit_price(
+        currency_pair: CurrencySymbolPair<String, String>,
+        price: u64,
+        decimals: u8,
+    ) -> Result<(), ()> {
+        let operators: Vec<T::Public> = AuthorityId::RuntimeAppPublic::all()
+            .into_iter()
+            .map(|key| -> T::Public { AuthorityId::GenericPublic::from(key).into() })
+            .filter(|public| {
+                Pallet::<T>::is_operator(currency_pair.clone(), &public.clone().into_account())
+            })
+            .collect();
+        if operators.is_empty() {
+            return Err(());
+        }
+
+        let results = Signer::<T, AuthorityId>::all_accounts()
+            .with_filter(operators)
+            .send_signed_transaction(|_| Call::set_price {
+                currency_pair: currency_pair.clone(),
+                price,
+                decimals,
+                confidence: None,
+                source: None,
+            });
+
+        if results.iter().any(|(_, result)| result.is_ok()) {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Returns raw bytes of the JSON value located at the dot-separated `path`,
+/// e.g. `"1.23"` (including quotes) for `{"data":{"price":"1.23"}}` and `data.price`.
+/// Returns `None` if the value doesn't exist or the JSON is malformed along the way.
+pub fn json_value_at<'a>(json: &'a [u8], path: &str) -> Option<&'a [u8]> {
+    let mut cursor = JsonCursor { json, pos: 0 };
+
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        cursor.skip_whitespace();
+        match cursor.peek()? {
+            b'{' => cursor.enter_object_field(segment)?,
+            b'[' => cursor.enter_array_item(segment.parse().ok()?)?,
+            _ => return None,
+        }
+    }
+
+    cursor.skip_whitespace();
+    let start = cursor.pos;
+    cursor.skip_value(0)?;
+
+    json.get(start..cursor.pos)
+}
+
+/// Minimal JSON traversal without decoding the values.
+struct JsonCursor<'a> {
+    json: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonCursor<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.pos += 1;
+
+        Some(byte)
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.next()? == byte).then_some(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\r' | b'\n')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Returns raw string contents with escapes left intact.
+    fn string(&mut self) -> Option<&'a [u8]> {
+        self.expect(b'"')?;
+        let start = self.pos;
+
+        loop {
+            match self.next()? {
+                b'\\' => {
+                    self.next()?;
+                }
+                b'"' => return self.json.get(start..self.pos - 1),
+                _ => {}
+            }
+        }
+    }
+
+    /// Moves to the value of the `key` field of the object at the cursor.
+    fn enter_object_field(&mut self, key: &str) -> Option<()> {
+        self.expect(b'{')?;
+
+        loop {
+            self.skip_whitespace();
+            let field = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            if field == key.as_bytes() {
+                return Some(());
+            }
+
+            self.skip_value(0)?;
+            self.skip_whitespace();
+            self.expect(b',')?;
+        }
+    }
+
+    /// Moves to the item with the given index of the array at the cursor.
+    fn enter_array_item(&mut self, index: usize) -> Option<()> {
+        self.expect(b'[')?;
+
+        for _ in 0..index {
+            self.skip_whitespace();
+            self.skip_value(0)?;
+            self.skip_whitespace();
+            self.expect(b',')?;
+        }
+        self.skip_whitespace();
+
+        (self.peek()? != b']').then_some(())
+    }
+
+    /// Moves past the value at the cursor.
+    fn skip_value(&mut self, depth: u32) -> Option<()> {
+        if depth > MAX_JSON_DEPTH {
+            return None;
+        }
+
+        match self.peek()? {
+            b'"' => self.string().map(drop),
+            open @ (b'{' | b'[') => {
+                let close = if open == b'{' { b'}' } else { b']' };
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek()? == close {
+                    self.pos += 1;
+                    return Some(());
+                }
+
+                loop {
+                    if open == b'{' {
+                        self.string()?;
+                        self.skip_whitespace();
+                        self.expect(b':')?;
+                        self.skip_whitespace();
+                    }
+                    self.skip_value(depth + 1)?;
+                    self.skip_whitespace();
+
+                    match self.next()? {
+                        b',' => self.skip_whitespace(),
+                        byte if byte == close => return Some(()),
+                        _ => return None,
+                    }
+                }
+            }
+            _ => {
+                let start = self.pos;
+                while !matches!(
+                    self.peek(),
+                    None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n')
+                ) {
+                    self.pos += 1;
+                }
+
+                (self.pos > start).then_some(())
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Periodic detection of the operators which stopped submitting prices.
+//!
+//! Every `Config::InactivityCheckPeriod` blocks, `on_initialize` starts a sweep over `OperatorLastSubmission`
+//! which visits at most `Config::MaxInactivityChecksPerBlock` entries per block, continuing from the last visited one
+//! in the following blocks. The sweep reports the operators which became inactive after the block the previous sweep
+//! was started at, up to the block it was started at itself, so each operator is reported once per inactivity.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::{One, Saturating};
+
+/// Ongoing sweep over `OperatorLastSubmission` reporting the operators which became inactive at the blocks
+/// after `after` up to `until` inclusive.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct InactivityCheck<Pair, AccountId, BlockNumber> {
+    /// Block the previous sweep was started at.
+    pub after: BlockNumber,
+    /// Block the sweep was started at.
+    pub until: BlockNumber,
+    /// Currency pair and operator of the last visited entry, if any.
+    pub last_visited: Option<(Pair, AccountId)>,
+}
+
+impl<Pair, AccountId, BlockNumber> InactivityCheck<Pair, AccountId, BlockNumber>
+where
+    BlockNumber: Saturating + One + PartialOrd + Copy,
+{
+    /// Returns `true` if the operator which submitted at `last_submission` became inactive within the checked blocks,
+    /// i.e. didn't submit for `timeout` blocks after it.
+    pub fn became_inactive(&self, last_submission: BlockNumber, timeout: BlockNumber) -> bool {
+        let inactive_at = last_submission
+            .saturating_add(timeout)
+            .saturating_add(One::one());
+
+        self.after < inactive_at && inactive_at <= self.until
+    }
+}
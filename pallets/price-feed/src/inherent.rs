@@ -0,0 +1,58 @@
+//! Inherent carrying the node's local clock.
+//! Allows the runtime to compare the time of the latest price update with the wall-clock time rather than
+//! only with block numbers, which is important when block production stalls.
+
+use sp_inherents::InherentIdentifier;
+
+/// Identifier of the node's local clock inherent.
+pub const INHERENT_IDENTIFIER: InherentIdentifier = *b"dockpfcl";
+
+/// Milliseconds elapsed since the unix epoch according to the node's local clock.
+pub type InherentType = u64;
+
+/// Provides the node's local clock to the runtime.
+#[cfg(feature = "std")]
+pub struct InherentDataProvider(InherentType);
+
+#[cfg(feature = "std")]
+impl InherentDataProvider {
+    /// Instantiates `InherentDataProvider` using supplied milliseconds since the unix epoch.
+    pub fn new(now: InherentType) -> Self {
+        Self(now)
+    }
+
+    /// Instantiates `InherentDataProvider` using current system time.
+    pub fn from_system_time() -> Self {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Current time is always after unix epoch; qed")
+            .as_millis();
+
+        Self(now as InherentType)
+    }
+
+    /// Returns the local clock value provided to the runtime.
+    pub fn local_time(&self) -> InherentType {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl sp_inherents::InherentDataProvider for InherentDataProvider {
+    fn provide_inherent_data(
+        &self,
+        inherent_data: &mut sp_inherents::InherentData,
+    ) -> Result<(), sp_inherents::Error> {
+        inherent_data.put_data(INHERENT_IDENTIFIER, &self.0)
+    }
+
+    async fn try_handle_error(
+        &self,
+        _: &InherentIdentifier,
+        _: &[u8],
+    ) -> Option<Result<(), sp_inherents::Error>> {
+        // Local clock inherent never produces errors.
+        None
+    }
+}
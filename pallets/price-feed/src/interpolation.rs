@@ -0,0 +1,87 @@
+//! Linear interpolation of the prices between the records kept in `PriceHistory`.
+
+use codec::{Decode, Encode};
+use price_provider::PriceRecord;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::U256;
+use sp_runtime::traits::{CheckedConversion, UniqueSaturatedInto};
+
+/// Price of the currency pair at some block.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum InterpolatedPrice<BlockNumber> {
+    /// The price was recorded at the requested block.
+    Exact(PriceRecord<BlockNumber>),
+    /// The price was interpolated between the two surrounding records.
+    /// The record's block number is the requested block.
+    Interpolated(PriceRecord<BlockNumber>),
+}
+
+impl<BlockNumber> InterpolatedPrice<BlockNumber> {
+    /// Returns the underlying price record.
+    pub fn record(&self) -> &PriceRecord<BlockNumber> {
+        match self {
+            Self::Exact(record) | Self::Interpolated(record) => record,
+        }
+    }
+
+    /// Returns `true` if the price was interpolated.
+    pub fn is_interpolated(&self) -> bool {
+        matches!(self, Self::Interpolated(_))
+    }
+}
+
+/// Linearly interpolates the price at `block_number` lying between the blocks of `before` and `after`.
+/// The amounts are normalized to the max decimals of the records. If the resulting amount doesn't fit into `u64`,
+/// decimals get reduced truncating the extra digits.
+///
+/// Returns `None` if `block_number` lies outside of the records' blocks or the decimals can't be normalized.
+pub fn interpolate<BlockNumber>(
+    before: &PriceRecord<BlockNumber>,
+    after: &PriceRecord<BlockNumber>,
+    block_number: BlockNumber,
+) -> Option<PriceRecord<BlockNumber>>
+where
+    BlockNumber: UniqueSaturatedInto<u64> + Copy,
+{
+    let (start, end, at) = (
+        before.block_number().unique_saturated_into(),
+        after.block_number().unique_saturated_into(),
+        block_number.unique_saturated_into(),
+    );
+    if at < start || at > end {
+        return None;
+    }
+
+    let mut decimals = before.decimals().max(after.decimals());
+    let normalize = |record: &PriceRecord<BlockNumber>| {
+        U256::from(10u8)
+            .checked_pow((decimals - record.decimals()).into())?
+            .checked_mul(record.amount().into())
+    };
+    let (from, to) = (normalize(before)?, normalize(after)?);
+
+    let mut amount = if end == start {
+        to
+    } else {
+        let (elapsed, span) = (U256::from(at - start), U256::from(end - start));
+
+        if to >= from {
+            from + (to - from).checked_mul(elapsed)? / span
+        } else {
+            from - (from - to).checked_mul(elapsed)? / span
+        }
+    };
+    while amount > U256::from(u64::MAX) {
+        amount /= U256::from(10u8);
+        decimals = decimals.checked_sub(1)?;
+    }
+
+    Some(PriceRecord::new(
+        amount.checked_into()?,
+        decimals.checked_into()?,
+        block_number,
+    ))
+}
@@ -4,26 +4,145 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-    traits::{Get, IsType},
+    traits::{Currency, ExistenceRequirement, Get, IsType, ReservableCurrency},
     weights::Weight,
+    BoundedBTreeSet, BoundedVec, PalletId,
 };
 use frame_system::{self as system, ensure_root};
 use scale_info::{prelude::string::String, TypeInfo};
+use sp_runtime::{
+    offchain::storage::StorageValueRef,
+    traits::{
+        AccountIdConversion, BlockNumberProvider, Hash, One, Saturating, UniqueSaturatedInto, Zero,
+    },
+    Perbill,
+};
 use sp_std::prelude::*;
+use utils::{BoundedFifo, Identity, IdentityProvider, SafeMode};
+pub use utils::{PageCursor, Paged};
+
+/// Target of the log entries and tracing spans emitted by the pallet.
+pub const LOG_TARGET: &str = "runtime::price-feed";
+
+/// Enters a debug span with the given name for the rest of the current scope.
+/// Expands to nothing unless the `tracing` feature is enabled.
+macro_rules! trace_span {
+    ($name:literal) => {
+        #[cfg(feature = "tracing")]
+        let _span = sp_tracing::span!(target: crate::LOG_TARGET, sp_tracing::Level::DEBUG, $name)
+            .entered();
+    };
+}
 
+/// Emits a debug log entry. Expands to nothing unless the `tracing` feature is enabled,
+/// so the arguments must be cheap to compute and bounded in size.
+macro_rules! trace_log {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing")]
+        log::debug!(target: crate::LOG_TARGET, $($arg)+);
+    };
+}
+
+pub mod anomaly;
+pub mod bounds;
+pub mod commit_reveal;
+pub mod event_filter;
+pub mod evm_contract;
+pub mod fee_multiplier;
+pub mod feed_spec;
+pub mod feed_state;
+pub mod feed_time;
+pub mod http_source;
+pub mod inactivity_check;
+pub mod inherent;
+pub mod interpolation;
+pub mod offchain_index;
+pub mod operator_removal;
+pub mod origin;
+pub mod pair_price_error;
+pub mod price_root;
+pub mod price_status;
 pub mod runtime_api;
+#[cfg(feature = "std")]
+pub mod snapshot_diff;
+pub mod submission_window;
+#[cfg(any(feature = "try-runtime", test))]
+pub mod try_state;
+pub mod weights;
+pub use anomaly::{AnomalyReport, AnomalyReportSubmitter, SignedAnomalyReportSubmitter};
+pub use bounds::PriceBounds;
+use commit_reveal::CommitRevealPhase;
+pub use commit_reveal::{
+    CommitRevealScheme, CommitmentSalt, OnUnrevealedCommitment, PriceCommitment,
+};
+pub use event_filter::EventKinds;
+pub use evm_contract::{ContractConfig, EvmCaller};
+pub use feed_spec::{Aggregation, FeedSpec};
+pub use feed_state::{FeedState, RetentionTier, SourceTag, MAX_SOURCE_TAG_BYTES_LEN};
+pub use feed_time::{BlockNumberFeedTime, FeedTime, LocalFeedTime, UnixFeedTime};
+pub use http_source::{HttpPriceSubmitter, HttpSource, SignedHttpPriceSubmitter};
+pub use inactivity_check::InactivityCheck;
+pub use interpolation::InterpolatedPrice;
+pub use operator_removal::PendingOperatorRemoval;
+pub use origin::EnsureOperatorFor;
+pub use pair_price_error::PairPriceError;
 pub use price_provider::{
-    BoundedCurrencySymbolPair, BoundedStringConversionError, CurrencySymbolPair, PriceProvider,
-    PriceRecord, StaticPriceProvider,
+    BoundedCurrencySymbolPair, BoundedStringConversionError, CurrencySymbolPair,
+    ExponentiallyWeightedPriceProvider, LikeString, MinorUnitExponentList, MinorUnitExponents,
+    MinorUnitsPriceProvider, PairOrientation, PlainCurrencySymbolPair, PriceProvider, PriceRecord,
+    PriceRecordBuilder, PriceRecordError, RoutedPriceProvider, StaticPriceProvider,
+    TimeWeightedPriceProvider,
 };
+pub use price_root::MerkleProof;
+pub use price_status::PriceStatus;
+pub use submission_window::SubmissionWindow;
 use system::ensure_signed;
+pub use weights::WeightInfo;
 
-mod migrations;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+/// Snapshot of the price feed state which can be used to build `GenesisConfig` of a new chain.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenesisSnapshot<AccountId, BlockNumber> {
+    /// Operators of the currency pairs.
+    pub operators: Vec<(CurrencySymbolPair<String, String>, AccountId)>,
+    /// Prices of the currency pairs.
+    pub prices: Vec<(CurrencySymbolPair<String, String>, PriceRecord<BlockNumber>)>,
+}
+
+impl<AccountId, BlockNumber> Default for GenesisSnapshot<AccountId, BlockNumber> {
+    fn default() -> Self {
+        Self {
+            operators: Vec::new(),
+            prices: Vec::new(),
+        }
+    }
+}
+
+/// Currency pairs controlled by a single operator.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorOverview<AccountId> {
+    /// Operator account.
+    pub operator: AccountId,
+    /// Currency pairs the operator can set prices for.
+    pub pairs: Vec<CurrencySymbolPair<String, String>>,
+}
+
+/// Max amount of items returned by a single page of the paginated queries.
+pub const MAX_PAGE_LIMIT: u32 = 1_000;
+
+/// Balance of the `Config::Currency` the operator rewards are paid in.
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as system::Config>::AccountId>>::Balance;
+
 /// Storage version.
 #[derive(Encode, Decode, Clone, TypeInfo, PartialEq, Eq, MaxEncodedLen)]
 pub enum Releases {
@@ -31,6 +150,9 @@ pub enum Releases {
     V1SinglePair,
     /// `dock_price_feed` allows to query of any pair price
     V2MultiPair,
+    /// Operators of each currency pair are stored in a single bounded set in `OperatorSets`,
+    /// and price records carry an optional confidence interval.
+    V3OperatorSets,
 }
 
 impl Default for Releases {
@@ -41,6 +163,53 @@ impl Default for Releases {
 
 pub use pallet::*;
 
+/// Compile-time guard ensuring that all types stored by the pallet have bounded encoded length,
+/// so PoV size of the storage accesses can be accounted.
+/// Must be extended once a new storage item is added.
+#[allow(dead_code)]
+fn assert_storage_types_have_max_encoded_len<T: Config>() {
+    fn max_encoded_len<V: MaxEncodedLen>() {}
+
+    max_encoded_len::<BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>>();
+    max_encoded_len::<T::AccountId>();
+    max_encoded_len::<BoundedBTreeSet<T::AccountId, T::MaxOperatorsPerPair>>();
+    max_encoded_len::<PriceRecord<T::BlockNumber>>();
+    max_encoded_len::<BoundedFifo<PriceRecord<T::BlockNumber>, T::MaxPriceHistoryLen>>();
+    max_encoded_len::<FeedState>();
+    max_encoded_len::<
+        BoundedVec<
+            PendingOperatorRemoval<
+                BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+                T::AccountId,
+                T::BlockNumber,
+            >,
+            T::MaxPendingOperatorRemovals,
+        >,
+    >();
+    max_encoded_len::<T::Hash>();
+    max_encoded_len::<
+        InactivityCheck<
+            BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            T::AccountId,
+            T::BlockNumber,
+        >,
+    >();
+    max_encoded_len::<HttpSource>();
+    max_encoded_len::<ContractConfig>();
+    max_encoded_len::<CommitRevealScheme>();
+    max_encoded_len::<Option<CommitRevealScheme>>();
+    max_encoded_len::<T::PriceConsumer>();
+    max_encoded_len::<BalanceOf<T>>();
+    max_encoded_len::<(
+        u64,
+        BoundedVec<PriceCommitment<T::AccountId, T::Hash>, T::MaxCommitmentsPerRound>,
+    )>();
+    max_encoded_len::<(T::BlockNumber, u64)>();
+    max_encoded_len::<(T::BlockNumber, T::Hash)>();
+    max_encoded_len::<bool>();
+    max_encoded_len::<Releases>();
+}
+
 #[frame_support::pallet]
 mod pallet {
     use super::*;
@@ -53,6 +222,195 @@ mod pallet {
         #[pallet::constant]
         type MaxSymbolBytesLen: Get<u32>;
 
+        /// Min divergence of the on-chain price from the reference price considered to be an anomaly.
+        #[pallet::constant]
+        type AnomalyThreshold: Get<Perbill>;
+
+        /// If `true`, the currency pair will be paused on the reported anomaly until resumed by `PauseOrigin`.
+        #[pallet::constant]
+        type PauseOnAnomaly: Get<bool>;
+
+        /// Max amount of the latest price records kept per currency pair in `PriceHistory`,
+        /// kept for the `Critical` pairs. `0` disables the history.
+        #[pallet::constant]
+        type MaxPriceHistoryLen: Get<u32>;
+
+        /// Amount of the latest price records kept in `PriceHistory` for the `Standard` pairs,
+        /// capped by `MaxPriceHistoryLen`. See [`RetentionTier`] for details.
+        #[pallet::constant]
+        type StandardPriceHistoryLen: Get<u32>;
+
+        /// Amount of the latest price records kept in `PriceHistory` for the `Ephemeral` pairs,
+        /// capped by `MaxPriceHistoryLen`. `0` disables the history of such pairs.
+        #[pallet::constant]
+        type EphemeralPriceHistoryLen: Get<u32>;
+
+        /// Max age of the price in `BlockNumberProvider` blocks. Older prices are considered stale:
+        /// `PriceProvider::pair_price` returns `None` for them, while `price_status` reports them as `Stale`.
+        #[pallet::constant]
+        type MaxPriceAge: Get<Self::BlockNumber>;
+
+        /// Delay in blocks after which operator removals scheduled by `remove_operator` take effect.
+        /// `0` makes `remove_operator` remove operators immediately.
+        #[pallet::constant]
+        type OperatorRemovalDelay: Get<Self::BlockNumber>;
+
+        /// Max amount of the operator removals pending at once.
+        #[pallet::constant]
+        type MaxPendingOperatorRemovals: Get<u32>;
+
+        /// Max amount of the price commitments per round of the currency pair using the commit-reveal scheme.
+        #[pallet::constant]
+        type MaxCommitmentsPerRound: Get<u32>;
+
+        /// Max amount of median absolute deviations the price revealed in the round may differ from the median of
+        /// the round's revealed prices by. Prices deviating further are discarded before the aggregation
+        /// emitting `SubmissionRejectedAsOutlier`. `0` disables the filtering.
+        #[pallet::constant]
+        type MaxRevealDeviations: Get<u32>;
+
+        /// Amount of blocks after the latest submission (or addition) after which the operator is considered inactive:
+        /// `OperatorInactive` event is emitted, and its revealed prices are excluded from the aggregation
+        /// until it submits again. `0` disables the timeout.
+        #[pallet::constant]
+        type OperatorTimeout: Get<Self::BlockNumber>;
+
+        /// Amount of blocks between the starts of the sweeps over `OperatorLastSubmission` emitting `OperatorInactive`,
+        /// see [`inactivity_check`]. `0` starts a sweep each block once the previous one is finished.
+        #[pallet::constant]
+        type InactivityCheckPeriod: Get<Self::BlockNumber>;
+
+        /// Max amount of `OperatorLastSubmission` entries visited by the inactivity sweep in a single block.
+        #[pallet::constant]
+        type MaxInactivityChecksPerBlock: Get<u32>;
+
+        /// Max decimals of the submitted prices, the price bounds, and the decimals of the price sources.
+        /// Must not exceed `price_provider::MAX_DECIMALS`. Lowering it requires
+        /// `migrations::decimals::truncate_excessive_decimals` to be invoked in the same upgrade.
+        #[pallet::constant]
+        type MaxDecimals: Get<u8>;
+
+        /// Reward accrued to `ClaimableRewards` of the operator for each accepted price submission or reveal.
+        /// `0` disables the rewards.
+        #[pallet::constant]
+        type RewardPerSubmission: Get<BalanceOf<Self>>;
+
+        /// Max amount paid out by a single `claim_rewards` call, the rest stays claimable.
+        #[pallet::constant]
+        type MaxRewardPayout: Get<BalanceOf<Self>>;
+
+        /// Identifier of the account holding the funds the operator rewards are paid from.
+        #[pallet::constant]
+        type RewardPotId: Get<PalletId>;
+
+        /// If `true`, each recomputed `PriceRoot` is also deposited into the block digest,
+        /// see [`price_root::price_root_digest_item`].
+        #[pallet::constant]
+        type DepositPriceRootDigest: Get<bool>;
+
+        /// Deposit reserved from the account requesting the operator role via `request_operator_role`.
+        /// Returned once the request is approved or rejected.
+        #[pallet::constant]
+        type OperatorRequestDeposit: Get<BalanceOf<Self>>;
+
+        /// Max amount of operators a single currency pair can have.
+        #[pallet::constant]
+        type MaxOperatorsPerPair: Get<u32>;
+
+        /// Half-life in blocks of the exponentially-weighted moving average price kept in each pair's `FeedState`,
+        /// i.e. the amount of blocks after which the price weighs half in the average. `0` makes the average
+        /// follow the latest price.
+        #[pallet::constant]
+        type EwmaHalfLife: Get<u32>;
+
+        /// Max amount of the currency pairs having a price in `Prices`, which bounds the recomputation of `PriceRoot`.
+        /// Prices of the new pairs are rejected with `TooManyPricedPairs` once it's reached.
+        #[pallet::constant]
+        type MaxPricedPairs: Get<u32>;
+
+        /// Max amount of `ContractConfigs` entries visited in `on_initialize` of a single block. Configs are visited
+        /// in turns, so with more configs a due price is read in one of the following blocks.
+        #[pallet::constant]
+        type MaxContractVisitsPerBlock: Get<u32>;
+
+        /// Max amount of the `BlockNumberProvider` blocks which `RevealDeadlines` are drained in a single block.
+        /// Must exceed the amount of the provided blocks passing per local block, so the gaps between them
+        /// are caught up with.
+        #[pallet::constant]
+        type MaxRevealDeadlinesPerBlock: Get<u32>;
+
+        /// Time source used to measure the age of the prices.
+        /// Use `BlockNumberFeedTime` to measure age in blocks, `UnixFeedTime<pallet_timestamp::Pallet<Runtime>>`
+        /// to measure age in milliseconds using on-chain timestamp, or `LocalFeedTime` to use the node's local clock.
+        type FeedTime: feed_time::FeedTime;
+
+        /// Provides block numbers the price records and submission windows are measured in.
+        /// Use `frame_system::Pallet<Runtime>` for the local block numbers. Parachains can use
+        /// `cumulus_pallet_parachain_system::RelaychainBlockNumberProvider<Runtime>` to measure them in the relay chain
+        /// block numbers which aren't affected by the gaps between the parachain blocks.
+        type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
+
+        /// Currency which total issuance is used to calculate the market capitalization,
+        /// in which the operator rewards are paid and the operator request deposits are reserved.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Representation of the currency pairs in events. Use `BoundedCurrencySymbolPair<String, String, MaxSymbolBytesLen>`
+        /// to keep the stored representation or `PlainCurrencySymbolPair` to emit plain symbol bytes for the decoders
+        /// which can't handle the custom type info of the bounded pair.
+        /// Both have the same encoding, so switching between them only changes the metadata.
+        type EventPair: Parameter
+            + From<BoundedCurrencySymbolPair<String, String, Self::MaxSymbolBytesLen>>;
+
+        /// Kinds of the events deposited by the pallet, e.g. `EventKinds::all()`. Allows to reduce the event noise
+        /// by suppressing the unneeded events. `PriceSet` is always deposited.
+        #[pallet::constant]
+        type DepositedEvents: Get<EventKinds>;
+
+        /// Reports whether the chain is in safe mode. Price submissions are rejected with `ChainInSafeMode`
+        /// while it's entered, and the prices remain readable. Use `()` if the chain doesn't have safe mode.
+        type SafeMode: SafeMode;
+
+        /// Submits prices fetched by the offchain workers from the HTTP sources configured via `set_http_source`.
+        /// Use `SignedHttpPriceSubmitter<AuthorityId>` to sign `set_price` transactions with the operator keys
+        /// from the node's keystore, or `()` to disable fetching.
+        type HttpPriceSubmitter: HttpPriceSubmitter<Self>;
+
+        /// Submits anomaly reports produced by the offchain workers, see [`anomaly`] for details.
+        /// Use `SignedAnomalyReportSubmitter<AuthorityId>` to sign `report_anomaly` transactions with the operator
+        /// keys from the node's keystore, or `()` to disable the detection.
+        type AnomalyReportSubmitter: AnomalyReportSubmitter<Self>;
+
+        /// Calls EVM contracts configured via `set_contract_config` to read prices in `on_initialize`.
+        /// Use `()` if the runtime doesn't have EVM.
+        type EvmCaller: EvmCaller;
+
+        /// Penalizes operators which committed to a price via `commit_price` but didn't reveal it.
+        /// Use `()` to only emit `CommitmentNotRevealed` events.
+        type OnUnrevealedCommitment: OnUnrevealedCommitment<
+            BoundedCurrencySymbolPair<String, String, Self::MaxSymbolBytesLen>,
+            Self::AccountId,
+        >;
+
+        /// Identifier of the remote consumers receiving prices through the remote query interface,
+        /// e.g. `MultiLocation` of the XCM origins or an identifier of the bridged chain.
+        type PriceConsumer: Parameter + MaxEncodedLen;
+
+        /// Origin allowed to remove operators immediately bypassing `OperatorRemovalDelay`.
+        type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Origin allowed to approve or reject the operator role requests, e.g. a council.
+        type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Origin allowed to pause and resume price updates of a single or all currency pairs,
+        /// e.g. a technical committee.
+        type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Provides identities of the operators used to check the identity level required by the currency pairs.
+        type OperatorIdentityProvider: IdentityProvider<Self>;
+
+        /// Weight information for the migrations and extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+
         /// The overarching event type.
         type Event: From<Event<Self>>
             + IsType<<Self as frame_system::Config>::Event>
@@ -69,44 +427,368 @@ mod pallet {
     where
         T: Config,
     {
-        OperatorAdded(
-            BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
-            <T as system::Config>::AccountId,
-        ),
-        OperatorRemoved(
-            BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
-            <T as system::Config>::AccountId,
-        ),
-        PriceSet(
-            BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
-            PriceRecord<<T as system::Config>::BlockNumber>,
-            <T as system::Config>::AccountId,
-        ),
+        /// Operator was added for the given currency pair.
+        OperatorAdded {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+        },
+        /// Operator was removed for the given currency pair.
+        OperatorRemoved {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+        },
+        /// Price of the given currency pair was set by the operator.
+        PriceSet {
+            pair: T::EventPair,
+            price: PriceRecord<<T as system::Config>::BlockNumber>,
+            operator: <T as system::Config>::AccountId,
+        },
+        /// `PriceSet` events for the given currency pair will be emitted once per `every_n_updates` price updates.
+        EventSamplingSet {
+            pair: T::EventPair,
+            every_n_updates: u32,
+        },
+        /// Price amount bounds for the given currency pair were set or removed.
+        PriceBoundsSet {
+            pair: T::EventPair,
+            bounds: Option<PriceBounds>,
+        },
+        /// Hash of the feed spec for the given currency pair was set or removed.
+        FeedSpecHashSet {
+            pair: T::EventPair,
+            spec_hash: Option<T::Hash>,
+        },
+        /// On-chain price of the given currency pair diverged from the reference price.
+        AnomalyReported {
+            pair: T::EventPair,
+            price: PriceRecord<<T as system::Config>::BlockNumber>,
+            report: AnomalyReport<<T as system::Config>::BlockNumber>,
+            divergence: Perbill,
+        },
+        /// Price updates for the given currency pair were paused.
+        PairPaused { pair: T::EventPair },
+        /// Price updates for the given currency pair were resumed.
+        PairResumed { pair: T::EventPair },
+        /// Source tag of the price set for the given currency pair by the preceding `PriceSet` event.
+        PriceSourceRecorded {
+            pair: T::EventPair,
+            source: SourceTag,
+        },
+        /// Identity level required from the operators of the given currency pair was set.
+        /// `0` means that no identity is required.
+        RequiredIdentityLevelSet { pair: T::EventPair, level: u32 },
+        /// Submission window of the given currency pair was set or removed.
+        SubmissionWindowSet {
+            pair: T::EventPair,
+            window: Option<SubmissionWindow>,
+        },
+        /// Retention tier of the given currency pair's price history was set.
+        RetentionTierSet {
+            pair: T::EventPair,
+            tier: RetentionTier,
+        },
+        /// HTTP source of the given currency pair was set or removed.
+        HttpSourceSet {
+            pair: T::EventPair,
+            source: Option<HttpSource>,
+        },
+        /// EVM contract config of the given currency pair was set or removed.
+        ContractConfigSet {
+            pair: T::EventPair,
+            config: Option<ContractConfig>,
+        },
+        /// Price of the given currency pair was read from its EVM contract.
+        ContractPriceRead {
+            pair: T::EventPair,
+            price: PriceRecord<<T as system::Config>::BlockNumber>,
+        },
+        /// Operator of the given currency pair will be removed at the beginning of the block `at`.
+        OperatorRemovalScheduled {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            at: <T as system::Config>::BlockNumber,
+        },
+        /// Commit-reveal scheme of the given currency pair was set or removed.
+        CommitRevealSchemeSet {
+            pair: T::EventPair,
+            scheme: Option<CommitRevealScheme>,
+        },
+        /// Operator committed to a price of the given currency pair in the given round.
+        PriceCommitted {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            round: u64,
+        },
+        /// Operator revealed the price committed for the given currency pair in the given round.
+        PriceRevealed {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            round: u64,
+            price: u64,
+            decimals: u8,
+        },
+        /// Operator didn't reveal the price committed for the given currency pair in the given round.
+        CommitmentNotRevealed {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            round: u64,
+        },
+        /// Price of the given currency pair was aggregated from the prices revealed in the given round.
+        RevealedPriceSet {
+            pair: T::EventPair,
+            price: PriceRecord<<T as system::Config>::BlockNumber>,
+            round: u64,
+            reveals: u32,
+        },
+        /// Price revealed by the operator in the given round deviates too far from the median of the round,
+        /// see `Config::MaxRevealDeviations`, and was excluded from the aggregation.
+        SubmissionRejectedAsOutlier {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            round: u64,
+            price: u64,
+            decimals: u8,
+        },
+        /// Operator of the given currency pair didn't submit prices within `OperatorTimeout` blocks
+        /// after the block `last_submission`, and is considered inactive.
+        OperatorInactive {
+            pair: T::EventPair,
+            operator: <T as system::Config>::AccountId,
+            last_submission: <T as system::Config>::BlockNumber,
+        },
+        /// Access of the remote consumers to the price of the given currency pair was restricted
+        /// to the allowed consumers or opened to everyone.
+        PriceAccessRestrictionSet {
+            pair: T::EventPair,
+            restricted: bool,
+        },
+        /// Remote consumer was allowed or disallowed to receive the price of the given currency pair.
+        PriceConsumerSet {
+            pair: T::EventPair,
+            consumer: T::PriceConsumer,
+            allowed: bool,
+        },
+        /// Price of the given currency pair was set by Root bypassing the operators.
+        PriceForceSet {
+            pair: T::EventPair,
+            price: PriceRecord<<T as system::Config>::BlockNumber>,
+        },
+        /// Price and price history of the given currency pair were removed by Root.
+        PriceCleared { pair: T::EventPair },
+        /// Currency pair was added to the allow-list.
+        PairAllowed { pair: T::EventPair },
+        /// Currency pair was removed from the allow-list.
+        PairDisallowed { pair: T::EventPair },
+        /// Operator claimed the given amount of its accrued rewards.
+        RewardsClaimed {
+            operator: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// Commit-reveal scheme of the given currency pair will be set or removed once the given round
+        /// is aggregated under the current scheme.
+        CommitRevealSchemeScheduled {
+            pair: T::EventPair,
+            scheme: Option<CommitRevealScheme>,
+            round: u64,
+        },
+        /// Account requested the operator role for the given currency pair reserving the deposit.
+        OperatorRoleRequested {
+            pair: T::EventPair,
+            account: T::AccountId,
+            deposit: BalanceOf<T>,
+        },
+        /// Operator role request of the account for the given currency pair was rejected.
+        OperatorRequestRejected {
+            pair: T::EventPair,
+            account: T::AccountId,
+        },
+        /// Price updates of all currency pairs were paused.
+        AllPairsPaused,
+        /// Price updates of all currency pairs were resumed, individually paused pairs stay paused.
+        AllPairsResumed,
     }
 
+    /// Variant indices are a part of the public API used by the tooling decoding `ModuleError`s,
+    /// so existing variants must never be reordered or reindexed, and new variants must get the next unused index.
     #[pallet::error]
     pub enum Error<T> {
         /// The caller isn't an operator for this currency pair.
+        #[codec(index = 0)]
         NotAnOperator,
         /// Given operator is already added for this currency pair.
+        #[codec(index = 1)]
         OperatorIsAlreadyAdded,
         /// Provided operator doesn't exist for this currency pair.
+        #[codec(index = 2)]
         OperatorDoesNotExist,
+        /// Price amount can't be zero.
+        #[codec(index = 3)]
+        ZeroPriceAmount,
+        /// Price decimals exceed max allowed.
+        #[codec(index = 4)]
+        TooManyPriceDecimals,
+        /// Price record's block number is lower than the block number of the stored one.
+        #[codec(index = 5)]
+        NonMonotonicPriceBlockNumber,
+        /// Local time was already noted in the current block.
+        #[codec(index = 6)]
+        LocalTimeAlreadyNoted,
+        /// Price amount lies outside of the bounds set for this currency pair.
+        #[codec(index = 7)]
+        PriceOutOfBounds,
+        /// Min price amount exceeds max price amount.
+        #[codec(index = 8)]
+        InvalidPriceBounds,
+        /// Operator's identity doesn't meet the level required for this currency pair.
+        #[codec(index = 9)]
+        InsufficientOperatorIdentityLevel,
+        /// Price updates for this currency pair are paused.
+        #[codec(index = 10)]
+        PairPaused,
+        /// Price updates for this currency pair aren't paused.
+        #[codec(index = 11)]
+        PairNotPaused,
+        /// There's no price record for this currency pair.
+        #[codec(index = 12)]
+        PriceNotFound,
+        /// Reported price record isn't the latest one.
+        #[codec(index = 13)]
+        OutdatedAnomalyReport,
+        /// Divergence from the reference price doesn't exceed the anomaly threshold.
+        #[codec(index = 14)]
+        NotAnAnomaly,
+        /// Price submissions are rejected since the chain is in safe mode.
+        #[codec(index = 15)]
+        ChainInSafeMode,
+        /// Price is submitted outside of the submission window of the current round.
+        #[codec(index = 16)]
+        OutsideSubmissionWindow,
+        /// Submission window is empty or doesn't fit into the round.
+        #[codec(index = 17)]
+        InvalidSubmissionWindow,
+        /// Too many operator removals are pending already.
+        #[codec(index = 18)]
+        TooManyPendingOperatorRemovals,
+        /// Removal of the given operator is already scheduled.
+        #[codec(index = 19)]
+        OperatorRemovalAlreadyScheduled,
+        /// EVM contract config has zero update period.
+        #[codec(index = 20)]
+        InvalidContractConfig,
+        /// Either phase of the commit-reveal scheme is empty or they don't fit into the round.
+        #[codec(index = 21)]
+        InvalidCommitRevealScheme,
+        /// Prices of this currency pair must be submitted via `commit_price` and `reveal_price`.
+        #[codec(index = 22)]
+        CommitRevealRequired,
+        /// This currency pair doesn't use the commit-reveal scheme.
+        #[codec(index = 23)]
+        CommitRevealNotEnabled,
+        /// Commitment is submitted outside of the commit phase of the current round.
+        #[codec(index = 24)]
+        OutsideCommitPhase,
+        /// Price is revealed outside of the reveal phase of the current round.
+        #[codec(index = 25)]
+        OutsideRevealPhase,
+        /// Too many commitments are submitted in the current round already.
+        #[codec(index = 26)]
+        TooManyCommitments,
+        /// The operator didn't commit to a price in the current round.
+        #[codec(index = 27)]
+        CommitmentNotFound,
+        /// Revealed price doesn't match the commitment.
+        #[codec(index = 28)]
+        CommitmentMismatch,
+        /// The operator has already revealed the price in the current round.
+        #[codec(index = 29)]
+        PriceAlreadyRevealed,
+        /// The remote consumer isn't allowed to receive the price of this currency pair.
+        #[codec(index = 30)]
+        PriceAccessDenied,
+        /// The currency pair isn't in the non-empty allow-list.
+        #[codec(index = 31)]
+        PairNotAllowed,
+        /// The currency pair is already in the allow-list.
+        #[codec(index = 32)]
+        PairAlreadyAllowed,
+        /// The account doesn't have rewards to claim.
+        #[codec(index = 33)]
+        NoRewardsToClaim,
+        /// The account has already requested the operator role for this currency pair.
+        #[codec(index = 34)]
+        OperatorRequestAlreadyExists,
+        /// The account hasn't requested the operator role for this currency pair.
+        #[codec(index = 35)]
+        OperatorRequestNotFound,
+        /// The currency pair already has `MaxOperatorsPerPair` operators.
+        #[codec(index = 36)]
+        TooManyOperators,
+        /// Price updates of all currency pairs are paused.
+        #[codec(index = 37)]
+        AllPairsPaused,
+        /// Price updates of all currency pairs aren't paused.
+        #[codec(index = 38)]
+        AllPairsNotPaused,
+        /// `Prices` already contain `MaxPricedPairs` currency pairs.
+        #[codec(index = 39)]
+        TooManyPricedPairs,
+    }
+
+    impl<T: Config> From<PriceRecordError> for Error<T> {
+        fn from(error: PriceRecordError) -> Self {
+            match error {
+                PriceRecordError::ZeroAmount => Error::<T>::ZeroPriceAmount,
+                PriceRecordError::TooManyDecimals => Error::<T>::TooManyPriceDecimals,
+                PriceRecordError::NonMonotonicBlockNumber => {
+                    Error::<T>::NonMonotonicPriceBlockNumber
+                }
+            }
+        }
     }
 
-    /// Stores operators for the currency pairs.
+    /// Stores operators for the currency pairs, up to `MaxOperatorsPerPair` per pair.
+    /// Pairs without operators have no entry.
+    #[pallet::storage]
+    #[pallet::getter(fn operators_of)]
+    pub type OperatorSets<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        BoundedBTreeSet<<T as frame_system::Config>::AccountId, T::MaxOperatorsPerPair>,
+        ValueQuery,
+    >;
+
+    /// Stores the number of the block in which the operator of the currency pair submitted a price the last time,
+    /// or was added if it didn't submit yet. Used to detect inactive operators, see `Config::OperatorTimeout`.
     #[pallet::storage]
-    #[pallet::getter(fn operators)]
-    pub type Operators<T: Config> = StorageDoubleMap<
+    #[pallet::getter(fn operator_last_submission)]
+    pub type OperatorLastSubmission<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
         BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
         Twox64Concat,
         <T as frame_system::Config>::AccountId,
-        (),
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// Stores the sweep over `OperatorLastSubmission` reporting inactive operators while it's in progress.
+    #[pallet::storage]
+    #[pallet::getter(fn ongoing_inactivity_check)]
+    pub type OngoingInactivityCheck<T: Config> = StorageValue<
+        _,
+        InactivityCheck<
+            BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            T::AccountId,
+            T::BlockNumber,
+        >,
         OptionQuery,
     >;
 
+    /// Stores the block the latest finished inactivity sweep was started at.
+    #[pallet::storage]
+    pub type InactivityCheckedUntil<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
     /// Stores prices of the currency pairs.
     /// Each price record contains raw amount, decimals, and a block number on which it was added to the storage.
     #[pallet::storage]
@@ -119,6 +801,248 @@ mod pallet {
         OptionQuery,
     >;
 
+    /// Stores the latest price records of the currency pairs ordered by block number.
+    /// The amount of the records kept per pair depends on its [`RetentionTier`], see `Pallet::history_len`.
+    #[pallet::storage]
+    #[pallet::getter(fn price_history)]
+    pub type PriceHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        BoundedFifo<PriceRecord<T::BlockNumber>, T::MaxPriceHistoryLen>,
+        ValueQuery,
+    >;
+
+    /// Stores configuration and round state of the currency pairs, see [`FeedState`] for details.
+    /// Pairs without a value have the default state.
+    #[pallet::storage]
+    #[pallet::getter(fn feed_state)]
+    pub type FeedStates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        FeedState,
+        ValueQuery,
+    >;
+
+    /// Stores hashes of the governance-approved feed specs for the currency pairs.
+    /// See [`FeedSpec`] for the spec format.
+    #[pallet::storage]
+    #[pallet::getter(fn feed_spec_hash)]
+    pub type FeedSpecHashes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        T::Hash,
+        OptionQuery,
+    >;
+
+    /// Stores operator removals scheduled by `remove_operator` in the order they were scheduled.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_operator_removals)]
+    pub type PendingOperatorRemovals<T: Config> = StorageValue<
+        _,
+        BoundedVec<
+            PendingOperatorRemoval<
+                BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+                T::AccountId,
+                T::BlockNumber,
+            >,
+            T::MaxPendingOperatorRemovals,
+        >,
+        ValueQuery,
+    >;
+
+    /// Stores HTTP sources of the currency pairs which prices are fetched and submitted by the offchain workers.
+    /// See [`http_source`] for details.
+    #[pallet::storage]
+    #[pallet::getter(fn http_source)]
+    pub type HttpSources<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        HttpSource,
+        OptionQuery,
+    >;
+
+    /// Stores configs of the EVM contracts the prices of the currency pairs are read from.
+    /// See [`evm_contract`] for details.
+    #[pallet::storage]
+    #[pallet::getter(fn contract_config)]
+    pub type ContractConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ContractConfig,
+        OptionQuery,
+    >;
+
+    /// Stores the number of the block in which the config of the currency pair in `ContractConfigs` was visited
+    /// by `on_initialize` the last time, or was set if it wasn't visited yet.
+    #[pallet::storage]
+    #[pallet::getter(fn contract_last_visit)]
+    pub type ContractLastVisit<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        T::BlockNumber,
+        OptionQuery,
+    >;
+
+    /// Stores the currency pair which config in `ContractConfigs` was visited the last, so the next block continues
+    /// after it. Unset once all configs were visited.
+    #[pallet::storage]
+    pub type ContractVisitCursor<T: Config> = StorageValue<
+        _,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        OptionQuery,
+    >;
+
+    /// Stores commit-reveal schemes of the currency pairs which prices are submitted in two phases.
+    /// See [`commit_reveal`] for details.
+    #[pallet::storage]
+    #[pallet::getter(fn commit_reveal_scheme)]
+    pub type CommitRevealSchemes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        CommitRevealScheme,
+        OptionQuery,
+    >;
+
+    /// Stores the index of the latest round of the currency pairs using the commit-reveal scheme along with
+    /// the commitments submitted in it. Removed once the round is aggregated.
+    #[pallet::storage]
+    #[pallet::getter(fn price_commitments)]
+    pub type PriceCommitments<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        (
+            u64,
+            BoundedVec<PriceCommitment<T::AccountId, T::Hash>, T::MaxCommitmentsPerRound>,
+        ),
+        OptionQuery,
+    >;
+
+    /// Stores the currency pairs having a round pending in `PriceCommitments` keyed by the `BlockNumberProvider` block
+    /// at which its reveal phase ends, see [`CommitRevealScheme::reveal_deadline`]. Drained once the block passes.
+    #[pallet::storage]
+    pub type RevealDeadlines<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u64,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Stores the latest `BlockNumberProvider` block which `RevealDeadlines` were drained.
+    #[pallet::storage]
+    pub type RevealDeadlinesDrainedAt<T> = StorageValue<_, u64, OptionQuery>;
+
+    /// Stores the commit-reveal schemes (`None` for removal) set while the currency pairs had a round pending
+    /// in `PriceCommitments`. Each scheme replaces the current one once that round is aggregated.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_commit_reveal_scheme)]
+    pub type PendingCommitRevealSchemes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        Option<CommitRevealScheme>,
+        OptionQuery,
+    >;
+
+    /// Stores currency pairs which prices can be received through the remote query interface
+    /// only by the consumers allowed in `PriceConsumers`, e.g. due to licensing constraints.
+    #[pallet::storage]
+    #[pallet::getter(fn is_price_access_restricted)]
+    pub type RestrictedPriceAccess<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Stores remote consumers allowed to receive prices of the currency pairs with restricted access.
+    #[pallet::storage]
+    #[pallet::getter(fn is_price_consumer_allowed)]
+    pub type PriceConsumers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        Blake2_128Concat,
+        T::PriceConsumer,
+        bool,
+        ValueQuery,
+    >;
+
+    /// Stores currency pairs allowed via `allow_pair`. Once non-empty, operators can be added and prices can be set
+    /// only for these pairs, so the storage can't be spammed with junk symbols.
+    #[pallet::storage]
+    #[pallet::getter(fn allowed_pair)]
+    pub type AllowedPairs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        (),
+        OptionQuery,
+    >;
+
+    /// Stores amount of the currency pairs in `AllowedPairs`.
+    #[pallet::storage]
+    #[pallet::getter(fn allowed_pairs_count)]
+    pub type AllowedPairsCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Stores rewards accrued by the operators for their submissions which weren't claimed via `claim_rewards` yet.
+    #[pallet::storage]
+    #[pallet::getter(fn claimable_rewards)]
+    pub type ClaimableRewards<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+    /// Stores pending operator role requests of the accounts per currency pair along with their reserved deposits.
+    #[pallet::storage]
+    #[pallet::getter(fn operator_request)]
+    pub type OperatorRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Stores the node's local clock (milliseconds since the unix epoch) noted via inherent
+    /// along with the number of the block it was noted in.
+    #[pallet::storage]
+    #[pallet::getter(fn local_time)]
+    pub type LocalTime<T: Config> = StorageValue<_, (T::BlockNumber, u64), OptionQuery>;
+
+    /// Stores the merkle root of all `(pair, record)` entries of `Prices` along with the number of the block
+    /// which final state it commits to. See [`price_root`] for the tree layout.
+    /// Recomputed at the beginning of the block following the one in which prices were changed.
+    #[pallet::storage]
+    #[pallet::getter(fn price_root)]
+    pub type PriceRoot<T: Config> = StorageValue<_, (T::BlockNumber, T::Hash), OptionQuery>;
+
+    /// Set when `Prices` are changed, so `PriceRoot` gets recomputed at the beginning of the next block.
+    #[pallet::storage]
+    pub type PricesChanged<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Stores amount of the currency pairs in `Prices`.
+    #[pallet::storage]
+    #[pallet::getter(fn priced_pairs_count)]
+    pub type PricedPairsCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Set while price updates of all currency pairs are paused by `PauseOrigin`.
+    /// Pairs paused individually in `FeedStates` stay paused once it's unset.
+    #[pallet::storage]
+    #[pallet::getter(fn all_pairs_paused)]
+    pub type AllPairsPaused<T> = StorageValue<_, bool, ValueQuery>;
+
     /// Current storage version.
     #[pallet::storage]
     #[pallet::getter(fn version)]
@@ -126,46 +1050,68 @@ mod pallet {
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
-        _phantom: sp_std::marker::PhantomData<T>,
+        /// Operators of the currency pairs.
+        pub operators: Vec<(CurrencySymbolPair<String, String>, T::AccountId)>,
+        /// Prices of the currency pairs.
+        pub prices: Vec<(
+            CurrencySymbolPair<String, String>,
+            PriceRecord<T::BlockNumber>,
+        )>,
     }
 
     #[cfg(feature = "std")]
     impl<T: Config> Default for GenesisConfig<T> {
         fn default() -> Self {
             GenesisConfig {
-                _phantom: Default::default(),
+                operators: Default::default(),
+                prices: Default::default(),
             }
         }
     }
 
+    #[cfg(feature = "std")]
+    impl<T: Config> From<GenesisSnapshot<T::AccountId, T::BlockNumber>> for GenesisConfig<T> {
+        fn from(
+            GenesisSnapshot { operators, prices }: GenesisSnapshot<T::AccountId, T::BlockNumber>,
+        ) -> Self {
+            GenesisConfig { operators, prices }
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Sets price for the given currency pair. Only callable by the currency price operator.
-        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))]
+        ///
+        /// Can be dispatched via `pallet-multisig` or `pallet-proxy`: in this case, the multisig or proxied account
+        /// must be the operator. Additional reads and call data are accounted by the wrapping pallets.
+        ///
+        /// If `emit_events_every_n_updates` is set for the pair, `PriceSet` event will be emitted only once per `N` updates
+        /// while storage is updated on every call.
+        ///
+        /// If `required_identity_level` is set for the pair, the operator's identity must be verified
+        /// and have at least the required amount of justifications.
+        ///
+        /// `confidence` is the half-width of the price confidence interval in the raw amount units, i.e. the operator
+        /// expects the price to be within `price ± confidence`. It's stored in the price record as is.
+        ///
+        /// `source` is the tag of the price source (e.g. `binance` or `kraken-avg`). It's stored in the pair's state
+        /// until the next price update and emitted in `PriceSourceRecorded` event following `PriceSet`.
+        #[pallet::weight(T::WeightInfo::set_price())]
         pub fn set_price(
             origin: OriginFor<T>,
             currency_pair: CurrencySymbolPair<String, String>,
             price: u64,
             decimals: u8,
+            confidence: Option<u64>,
+            source: Option<SourceTag>,
         ) -> DispatchResult {
             let account = ensure_signed(origin)?;
 
-            let stored_pair = currency_pair.try_into()?;
-            if <Operators<T>>::get(&stored_pair, &account).is_some() {
-                let price_record =
-                    PriceRecord::new(price, decimals, <system::Pallet<T>>::block_number());
-                <Prices<T>>::insert(&stored_pair, price_record);
-
-                Self::deposit_event(Event::<T>::PriceSet(stored_pair, price_record, account));
-
-                return Ok(());
-            }
-
-            Err(Error::<T>::NotAnOperator.into())
+            Self::do_set_price(account, currency_pair, price, decimals, confidence, source)
         }
 
         /// Adds an operator for the given currency pair. Only callable by Root.
-        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))]
+        #[pallet::weight(T::WeightInfo::add_operator())]
         pub fn add_operator(
             origin: OriginFor<T>,
             currency_pair: CurrencySymbolPair<String, String>,
@@ -173,70 +1119,2425 @@ mod pallet {
         ) -> DispatchResult {
             ensure_root(origin)?;
 
-            let stored_pair = currency_pair.try_into()?;
-            <Operators<T>>::try_mutate(&stored_pair, &operator, |allowed| {
-                if allowed.is_none() {
-                    *allowed = Some(());
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
 
-                    Ok(())
-                } else {
-                    Err(Error::<T>::OperatorIsAlreadyAdded)
+            Self::do_add_operator(stored_pair, operator)
+        }
+
+        /// Schedules removal of an operator for the given currency pair which takes effect after
+        /// `OperatorRemovalDelay` blocks, giving notice to the operator and downstream consumers.
+        /// The operator can keep submitting prices until then. Removes the operator immediately
+        /// if the delay is zero. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::remove_operator(T::MaxPendingOperatorRemovals::get()))]
+        pub fn remove_operator(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            let delay = T::OperatorRemovalDelay::get();
+            if delay.is_zero() {
+                return Self::do_remove_operator(stored_pair, operator);
+            }
+
+            ensure!(
+                Self::is_pair_operator(&stored_pair, &operator),
+                Error::<T>::OperatorDoesNotExist
+            );
+            let at = <system::Pallet<T>>::block_number().saturating_add(delay);
+            <PendingOperatorRemovals<T>>::try_mutate(|pending| {
+                ensure!(
+                    !pending
+                        .iter()
+                        .any(|removal| removal.pair == stored_pair && removal.operator == operator),
+                    Error::<T>::OperatorRemovalAlreadyScheduled
+                );
+
+                pending
+                    .try_push(PendingOperatorRemoval {
+                        pair: stored_pair.clone(),
+                        operator: operator.clone(),
+                        at,
+                    })
+                    .map_err(|_| Error::<T>::TooManyPendingOperatorRemovals)
+            })?;
+
+            Self::deposit_enabled_event(Event::<T>::OperatorRemovalScheduled {
+                pair: stored_pair.into(),
+                operator,
+                at,
+            });
+
+            Ok(())
+        }
+
+        /// Sets amount of price updates per each emitted `PriceSet` event for the given currency pair.
+        /// `0` or `1` means that event will be emitted on every price update. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_emit_events_every_n_updates())]
+        pub fn set_emit_events_every_n_updates(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            emit_events_every_n_updates: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::mutate_feed_state(&stored_pair, |state| {
+                state.emit_events_every_n_updates = if emit_events_every_n_updates > 1 {
+                    emit_events_every_n_updates
+                } else {
+                    0
+                };
+                state.updates_since_last_event = 0;
+            });
+
+            Self::deposit_enabled_event(Event::<T>::EventSamplingSet {
+                pair: stored_pair.into(),
+                every_n_updates: emit_events_every_n_updates.max(1),
+            });
+
+            Ok(())
+        }
+
+        /// Sets absolute price amount bounds for the given currency pair, `None` removes existing bounds.
+        /// Prices lying outside of the bounds will be rejected. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_price_bounds())]
+        pub fn set_price_bounds(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            bounds: Option<PriceBounds>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                bounds.as_ref().map_or(true, PriceBounds::is_valid),
+                Error::<T>::InvalidPriceBounds
+            );
+            Self::ensure_valid_decimals(bounds.map(|bounds| bounds.decimals))?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::mutate_feed_state(&stored_pair, |state| state.bounds = bounds);
+
+            Self::deposit_enabled_event(Event::<T>::PriceBoundsSet {
+                pair: stored_pair.into(),
+                bounds,
+            });
+
+            Ok(())
+        }
+
+        /// Sets hash of the feed spec for the given currency pair, `None` removes existing hash.
+        /// The hash must be computed using [`FeedSpec::hash`] with the runtime's hasher. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_feed_spec_hash())]
+        pub fn set_feed_spec_hash(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            spec_hash: Option<T::Hash>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            match spec_hash {
+                Some(spec_hash) => <FeedSpecHashes<T>>::insert(&stored_pair, spec_hash),
+                None => <FeedSpecHashes<T>>::remove(&stored_pair),
+            }
+
+            Self::deposit_enabled_event(Event::<T>::FeedSpecHashSet {
+                pair: stored_pair.into(),
+                spec_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Sets identity level required from the operators of the given currency pair.
+        /// An identity level is the amount of justifications of the verified identity provided by
+        /// `OperatorIdentityProvider`. `0` removes the requirement. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_required_identity_level())]
+        pub fn set_required_identity_level(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            level: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::mutate_feed_state(&stored_pair, |state| state.required_identity_level = level);
+
+            Self::deposit_enabled_event(Event::<T>::RequiredIdentityLevelSet {
+                pair: stored_pair.into(),
+                level,
+            });
+
+            Ok(())
+        }
+
+        /// Reports divergence of the latest on-chain price from the reference price.
+        /// Submitted by the offchain workers of the pair operators, see [`anomaly`] for details.
+        /// Only callable by an operator of the reported currency pair.
+        /// Pauses price updates for the pair if `PauseOnAnomaly` is `true`.
+        #[pallet::weight(T::WeightInfo::report_anomaly())]
+        pub fn report_anomaly(
+            origin: OriginFor<T>,
+            report: AnomalyReport<T::BlockNumber>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            ensure!(
+                Self::is_operator(report.pair.clone(), &account),
+                Error::<T>::NotAnOperator
+            );
+
+            let (stored_pair, record, divergence) = Self::check_anomaly_report(&report)?;
+            Self::deposit_enabled_event(Event::<T>::AnomalyReported {
+                pair: stored_pair.clone().into(),
+                price: record,
+                report,
+                divergence,
+            });
+
+            if T::PauseOnAnomaly::get() && !<FeedStates<T>>::get(&stored_pair).paused {
+                Self::mutate_feed_state(&stored_pair, |state| state.paused = true);
+                Self::deposit_enabled_event(Event::<T>::PairPaused {
+                    pair: stored_pair.into(),
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Resumes price updates for the paused currency pair. Only callable by `PauseOrigin`.
+        /// The pair stays paused while all pairs are paused.
+        #[pallet::weight(T::WeightInfo::resume_pair())]
+        pub fn resume_pair(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::try_mutate_feed_state(&stored_pair, |state| {
+                ensure!(state.paused, Error::<T>::PairNotPaused);
+                state.paused = false;
+
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_enabled_event(Event::<T>::PairResumed {
+                pair: stored_pair.into(),
+            });
+
+            Ok(())
+        }
+
+        /// Notes the node's local clock (milliseconds since the unix epoch) for the current block.
+        /// Dispatched as an inherent provided by `inherent::InherentDataProvider`.
+        #[pallet::weight((T::WeightInfo::note_local_time(), DispatchClass::Mandatory))]
+        pub fn note_local_time(
+            origin: OriginFor<T>,
+            #[pallet::compact] now: u64,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+
+            let current_block = <system::Pallet<T>>::block_number();
+            <LocalTime<T>>::try_mutate(|local_time| {
+                if matches!(local_time, Some((block_number, _)) if *block_number == current_block) {
+                    Err(Error::<T>::LocalTimeAlreadyNoted)
+                } else {
+                    *local_time = Some((current_block, now));
+
+                    Ok(())
+                }
+            })?;
+
+            Ok(())
+        }
+
+        /// Sets submission window of the given currency pair, `None` removes existing window.
+        /// Prices submitted outside of the window of the current round will be rejected. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_submission_window())]
+        pub fn set_submission_window(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            window: Option<SubmissionWindow>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                window.as_ref().map_or(true, SubmissionWindow::is_valid),
+                Error::<T>::InvalidSubmissionWindow
+            );
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::mutate_feed_state(&stored_pair, |state| state.submission_window = window);
+
+            Self::deposit_enabled_event(Event::<T>::SubmissionWindowSet {
+                pair: stored_pair.into(),
+                window,
+            });
+
+            Ok(())
+        }
+
+        /// Sets config of the EVM contract the price of the given currency pair is read from in `on_initialize`,
+        /// `None` removes existing config. See [`evm_contract`] for details. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_contract_config())]
+        pub fn set_contract_config(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            config: Option<ContractConfig>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                config.as_ref().map_or(true, ContractConfig::is_valid),
+                Error::<T>::InvalidContractConfig
+            );
+            Self::ensure_valid_decimals(config.as_ref().map(|config| config.decimals))?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            if config.is_some() {
+                <ContractLastVisit<T>>::insert(&stored_pair, <system::Pallet<T>>::block_number());
+            } else {
+                <ContractLastVisit<T>>::remove(&stored_pair);
+            }
+            <ContractConfigs<T>>::set(&stored_pair, config.clone());
+
+            Self::deposit_enabled_event(Event::<T>::ContractConfigSet {
+                pair: stored_pair.into(),
+                config,
+            });
+
+            Ok(())
+        }
+
+        /// Removes an operator for the given currency pair immediately bypassing `OperatorRemovalDelay`
+        /// and cancels its pending removal if any. Only callable by `EmergencyOrigin`.
+        #[pallet::weight(T::WeightInfo::remove_operator_immediately(
+            T::MaxPendingOperatorRemovals::get()
+        ))]
+        pub fn remove_operator_immediately(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            T::EmergencyOrigin::ensure_origin(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <PendingOperatorRemovals<T>>::mutate(|pending| {
+                pending
+                    .retain(|removal| removal.pair != stored_pair || removal.operator != operator)
+            });
+
+            Self::do_remove_operator(stored_pair, operator)
+        }
+
+        /// Sets HTTP source of the given currency pair, `None` removes existing source.
+        /// Nodes running offchain workers will fetch prices from the source and submit them on behalf of the
+        /// operators whose keys they hold, see [`http_source`] for details. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_http_source())]
+        pub fn set_http_source(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            source: Option<HttpSource>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::ensure_valid_decimals(source.as_ref().map(|source| source.decimals))?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <HttpSources<T>>::set(&stored_pair, source.clone());
+
+            Self::deposit_enabled_event(Event::<T>::HttpSourceSet {
+                pair: stored_pair.into(),
+                source,
+            });
+
+            Ok(())
+        }
+
+        /// Sets commit-reveal scheme of the given currency pair, `None` removes existing scheme.
+        /// While the scheme is set, prices can only be submitted via `commit_price` and `reveal_price`,
+        /// see [`commit_reveal`] for details. If the pair has a round with the submitted commitments, the round
+        /// is finished and aggregated under the current scheme, and the supplied one takes effect after that.
+        /// Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_commit_reveal_scheme())]
+        pub fn set_commit_reveal_scheme(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            scheme: Option<CommitRevealScheme>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(
+                scheme.as_ref().map_or(true, CommitRevealScheme::is_valid),
+                Error::<T>::InvalidCommitRevealScheme
+            );
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            match <PriceCommitments<T>>::get(&stored_pair) {
+                Some((round, _)) => {
+                    <PendingCommitRevealSchemes<T>>::insert(&stored_pair, scheme);
+
+                    Self::deposit_enabled_event(Event::<T>::CommitRevealSchemeScheduled {
+                        pair: stored_pair.into(),
+                        scheme,
+                        round,
+                    });
+                }
+                None => {
+                    <CommitRevealSchemes<T>>::set(&stored_pair, scheme);
+
+                    Self::deposit_enabled_event(Event::<T>::CommitRevealSchemeSet {
+                        pair: stored_pair.into(),
+                        scheme,
+                    });
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Commits to a price of the given currency pair during the commit phase of the current round.
+        /// `commitment` must be computed via [`commit_reveal::price_commitment`] using `T::Hashing`.
+        /// Repeated commitments within the same round replace the previous one.
+        /// Only callable by the currency price operator.
+        #[pallet::weight(T::WeightInfo::commit_price(T::MaxCommitmentsPerRound::get()))]
+        pub fn commit_price(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            commitment: T::Hash,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            ensure!(!T::SafeMode::is_entered(), Error::<T>::ChainInSafeMode);
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::ensure_pair_allowed(&stored_pair)?;
+            ensure!(
+                Self::is_pair_operator(&stored_pair, &account),
+                Error::<T>::NotAnOperator
+            );
+            let state = <FeedStates<T>>::get(&stored_pair);
+            Self::ensure_not_paused(&state)?;
+            ensure!(
+                Self::meets_identity_level(&account, state.required_identity_level),
+                Error::<T>::InsufficientOperatorIdentityLevel
+            );
+            let scheme = <CommitRevealSchemes<T>>::get(&stored_pair)
+                .ok_or(Error::<T>::CommitRevealNotEnabled)?;
+            let (round, phase) = scheme
+                .phase_at(T::BlockNumberProvider::current_block_number().unique_saturated_into());
+            ensure!(
+                phase == CommitRevealPhase::Commit,
+                Error::<T>::OutsideCommitPhase
+            );
+
+            let new_round = <PriceCommitments<T>>::try_mutate(&stored_pair, |entry| {
+                // Commitments of the rounds which weren't aggregated, e.g. due to safe mode, are dropped.
+                if matches!(entry, Some((stored_round, _)) if *stored_round != round) {
+                    *entry = None;
+                }
+                let new_round = entry.is_none();
+                let (_, commitments) = entry.get_or_insert_with(|| (round, Default::default()));
+                let commitment = PriceCommitment {
+                    operator: account.clone(),
+                    commitment,
+                    revealed: None,
+                };
+
+                match commitments
+                    .iter_mut()
+                    .find(|existing| existing.operator == account)
+                {
+                    Some(existing) => *existing = commitment,
+                    None => commitments
+                        .try_push(commitment)
+                        .map_err(|_| Error::<T>::TooManyCommitments)?,
                 }
+
+                Ok::<_, Error<T>>(new_round)
+            })?;
+            if new_round {
+                <RevealDeadlines<T>>::insert(scheme.reveal_deadline(round), &stored_pair, ());
+            }
+            <OperatorLastSubmission<T>>::insert(
+                &stored_pair,
+                &account,
+                <system::Pallet<T>>::block_number(),
+            );
+
+            Self::deposit_enabled_event(Event::<T>::PriceCommitted {
+                pair: stored_pair.into(),
+                operator: account,
+                round,
+            });
+
+            Ok(())
+        }
+
+        /// Reveals the price committed via `commit_price` during the reveal phase of the current round.
+        /// Revealed prices are aggregated once the reveal phase ends, and the operators whose reveals make it into
+        /// the stored price are rewarded. Only callable by the operator which committed.
+        #[pallet::weight(T::WeightInfo::reveal_price(T::MaxCommitmentsPerRound::get()))]
+        pub fn reveal_price(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            price: u64,
+            decimals: u8,
+            salt: CommitmentSalt,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+            ensure!(!T::SafeMode::is_entered(), Error::<T>::ChainInSafeMode);
+            Self::ensure_valid_decimals(Some(decimals))?;
+
+            let stored_pair: BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::ensure_pair_allowed(&stored_pair)?;
+            let scheme = <CommitRevealSchemes<T>>::get(&stored_pair)
+                .ok_or(Error::<T>::CommitRevealNotEnabled)?;
+            let (round, phase) = scheme
+                .phase_at(T::BlockNumberProvider::current_block_number().unique_saturated_into());
+            ensure!(
+                phase == CommitRevealPhase::Reveal,
+                Error::<T>::OutsideRevealPhase
+            );
+            let state = <FeedStates<T>>::get(&stored_pair);
+            Self::ensure_not_paused(&state)?;
+            if let Some(bounds) = state.bounds {
+                ensure!(
+                    bounds.contains(price, decimals),
+                    Error::<T>::PriceOutOfBounds
+                );
+            }
+
+            <PriceCommitments<T>>::try_mutate(&stored_pair, |entry| {
+                let commitment = entry
+                    .as_mut()
+                    .filter(|(stored_round, _)| *stored_round == round)
+                    .and_then(|(_, commitments)| {
+                        commitments
+                            .iter_mut()
+                            .find(|commitment| commitment.operator == account)
+                    })
+                    .ok_or(Error::<T>::CommitmentNotFound)?;
+                ensure!(
+                    commitment.revealed.is_none(),
+                    Error::<T>::PriceAlreadyRevealed
+                );
+                ensure!(
+                    commitment.commitment
+                        == commit_reveal::price_commitment::<T::Hashing, _, _>(
+                            &stored_pair,
+                            round,
+                            &account,
+                            price,
+                            decimals,
+                            &salt
+                        ),
+                    Error::<T>::CommitmentMismatch
+                );
+                commitment.revealed = Some((price, decimals));
+
+                Ok::<_, Error<T>>(())
+            })?;
+
+            Self::deposit_enabled_event(Event::<T>::PriceRevealed {
+                pair: stored_pair.into(),
+                operator: account,
+                round,
+                price,
+                decimals,
+            });
+
+            Ok(())
+        }
+
+        /// Restricts access to the price of the given currency pair through the remote query interface
+        /// to the consumers allowed via `set_price_consumer`, or opens it to everyone. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_price_access_restricted())]
+        pub fn set_price_access_restricted(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            restricted: bool,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <RestrictedPriceAccess<T>>::set(&stored_pair, restricted);
+
+            Self::deposit_enabled_event(Event::<T>::PriceAccessRestrictionSet {
+                pair: stored_pair.into(),
+                restricted,
+            });
+
+            Ok(())
+        }
+
+        /// Allows or disallows the remote consumer to receive the price of the given currency pair
+        /// while its access is restricted. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_price_consumer())]
+        pub fn set_price_consumer(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            consumer: T::PriceConsumer,
+            allowed: bool,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <PriceConsumers<T>>::set(&stored_pair, &consumer, allowed);
+
+            Self::deposit_enabled_event(Event::<T>::PriceConsumerSet {
+                pair: stored_pair.into(),
+                consumer,
+                allowed,
+            });
+
+            Ok(())
+        }
+
+        /// Sets price for the given currency pair bypassing the operators, e.g. to correct a bad oracle value.
+        /// Pause, bounds, submission window and commit-reveal scheme of the pair aren't checked. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::force_set_price())]
+        pub fn force_set_price(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            price: u64,
+            decimals: u8,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            let price_record = PriceRecordBuilder::new(
+                price,
+                decimals,
+                T::BlockNumberProvider::current_block_number(),
+            )
+            .with_previous(<Prices<T>>::get(&stored_pair))
+            .max_decimals(T::MaxDecimals::get())
+            .build()
+            .map_err(Error::<T>::from)?;
+            Self::store_price_record(&stored_pair, price_record)?;
+            Self::mutate_feed_state(&stored_pair, |state| {
+                if let Some(now) = T::FeedTime::now() {
+                    state.updated_at = Some(now);
+                }
+                state.source = None;
+                state.update_ewma(price_record, T::EwmaHalfLife::get());
+            });
+
+            Self::deposit_enabled_event(Event::<T>::PriceForceSet {
+                pair: stored_pair.into(),
+                price: price_record,
+            });
+
+            Ok(())
+        }
+
+        /// Removes price and price history of the given currency pair, e.g. once the pair is delisted.
+        /// Only callable by Root.
+        #[pallet::weight(T::WeightInfo::force_clear_price())]
+        pub fn force_clear_price(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <Prices<T>>::take(&stored_pair).ok_or(Error::<T>::PriceNotFound)?;
+            <PricedPairsCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+            <PriceHistory<T>>::remove(&stored_pair);
+            Self::mutate_feed_state(&stored_pair, |state| state.ewma = None);
+            <PricesChanged<T>>::put(true);
+            offchain_index::index_price_removal(&stored_pair);
+
+            Self::deposit_enabled_event(Event::<T>::PriceCleared {
+                pair: stored_pair.into(),
+            });
+
+            Ok(())
+        }
+
+        /// Adds the given currency pair to the allow-list. Once the allow-list is non-empty, `add_operator`,
+        /// `set_price`, `commit_price` and `reveal_price` are rejected for the pairs outside of it. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::allow_pair())]
+        pub fn allow_pair(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            ensure!(
+                !<AllowedPairs<T>>::contains_key(&stored_pair),
+                Error::<T>::PairAlreadyAllowed
+            );
+            <AllowedPairs<T>>::insert(&stored_pair, ());
+            <AllowedPairsCount<T>>::mutate(|count| *count = count.saturating_add(1));
+
+            Self::deposit_enabled_event(Event::<T>::PairAllowed {
+                pair: stored_pair.into(),
+            });
+
+            Ok(())
+        }
+
+        /// Removes the given currency pair from the allow-list. Existing operators and prices of the pair are kept,
+        /// but new prices are rejected while the allow-list stays non-empty. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::disallow_pair())]
+        pub fn disallow_pair(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            <AllowedPairs<T>>::take(&stored_pair).ok_or(Error::<T>::PairNotAllowed)?;
+            <AllowedPairsCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+
+            Self::deposit_enabled_event(Event::<T>::PairDisallowed {
+                pair: stored_pair.into(),
+            });
+
+            Ok(())
+        }
+
+        /// Pays out up to `MaxRewardPayout` of the rewards accrued by the caller from the reward pot,
+        /// the rest stays claimable.
+        #[pallet::weight(T::WeightInfo::claim_rewards())]
+        pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+
+            let claimable = <ClaimableRewards<T>>::get(&account);
+            let amount = claimable.min(T::MaxRewardPayout::get());
+            ensure!(!amount.is_zero(), Error::<T>::NoRewardsToClaim);
+
+            T::Currency::transfer(
+                &Self::reward_pot(),
+                &account,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            let remaining = claimable.saturating_sub(amount);
+            if remaining.is_zero() {
+                <ClaimableRewards<T>>::remove(&account);
+            } else {
+                <ClaimableRewards<T>>::insert(&account, remaining);
+            }
+
+            Self::deposit_enabled_event(Event::<T>::RewardsClaimed {
+                operator: account,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Requests the operator role for the given currency pair reserving `OperatorRequestDeposit`
+        /// until the request is approved via `approve_operator` or rejected via `reject_operator`.
+        #[pallet::weight(T::WeightInfo::request_operator_role())]
+        pub fn request_operator_role(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            let account = ensure_signed(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::ensure_pair_allowed(&stored_pair)?;
+            ensure!(
+                !Self::is_pair_operator(&stored_pair, &account),
+                Error::<T>::OperatorIsAlreadyAdded
+            );
+            ensure!(
+                !<OperatorRequests<T>>::contains_key(&stored_pair, &account),
+                Error::<T>::OperatorRequestAlreadyExists
+            );
+
+            let deposit = T::OperatorRequestDeposit::get();
+            T::Currency::reserve(&account, deposit)?;
+            <OperatorRequests<T>>::insert(&stored_pair, &account, deposit);
+
+            Self::deposit_enabled_event(Event::<T>::OperatorRoleRequested {
+                pair: stored_pair.into(),
+                account,
+                deposit,
+            });
+
+            Ok(())
+        }
+
+        /// Adds the account which requested the operator role for the given currency pair as its operator
+        /// and returns the request deposit. Only callable by `ApproveOrigin`.
+        #[pallet::weight(T::WeightInfo::approve_operator())]
+        pub fn approve_operator(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            T::ApproveOrigin::ensure_origin(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            let deposit = <OperatorRequests<T>>::take(&stored_pair, &account)
+                .ok_or(Error::<T>::OperatorRequestNotFound)?;
+            T::Currency::unreserve(&account, deposit);
+
+            Self::do_add_operator(stored_pair, account)
+        }
+
+        /// Removes the operator role request of the account for the given currency pair
+        /// and returns the request deposit. Only callable by `ApproveOrigin`.
+        #[pallet::weight(T::WeightInfo::reject_operator())]
+        pub fn reject_operator(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            T::ApproveOrigin::ensure_origin(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            let deposit = <OperatorRequests<T>>::take(&stored_pair, &account)
+                .ok_or(Error::<T>::OperatorRequestNotFound)?;
+            T::Currency::unreserve(&account, deposit);
+
+            Self::deposit_enabled_event(Event::<T>::OperatorRequestRejected {
+                pair: stored_pair.into(),
+                account,
+            });
+
+            Ok(())
+        }
+
+        /// Pauses price updates for the currency pair until resumed via `resume_pair`.
+        /// Only callable by `PauseOrigin`.
+        #[pallet::weight(T::WeightInfo::pause_pair())]
+        pub fn pause_pair(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+        ) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::try_mutate_feed_state(&stored_pair, |state| {
+                ensure!(!state.paused, Error::<T>::PairPaused);
+                state.paused = true;
+
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_enabled_event(Event::<T>::PairPaused {
+                pair: stored_pair.into(),
+            });
+
+            Ok(())
+        }
+
+        /// Pauses price updates for all currency pairs until resumed via `resume_all_pairs`.
+        /// Only callable by `PauseOrigin`.
+        #[pallet::weight(T::WeightInfo::pause_all_pairs())]
+        pub fn pause_all_pairs(origin: OriginFor<T>) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            ensure!(!<AllPairsPaused<T>>::get(), Error::<T>::AllPairsPaused);
+            <AllPairsPaused<T>>::put(true);
+            Self::deposit_enabled_event(Event::<T>::AllPairsPaused);
+
+            Ok(())
+        }
+
+        /// Resumes price updates for all currency pairs except the individually paused ones.
+        /// Only callable by `PauseOrigin`.
+        #[pallet::weight(T::WeightInfo::resume_all_pairs())]
+        pub fn resume_all_pairs(origin: OriginFor<T>) -> DispatchResult {
+            T::PauseOrigin::ensure_origin(origin)?;
+
+            ensure!(<AllPairsPaused<T>>::get(), Error::<T>::AllPairsNotPaused);
+            <AllPairsPaused<T>>::kill();
+            Self::deposit_enabled_event(Event::<T>::AllPairsResumed);
+
+            Ok(())
+        }
+
+        /// Sets retention tier of the given currency pair's price history. The history is trimmed right away
+        /// if the new tier keeps less records. Only callable by Root.
+        #[pallet::weight(T::WeightInfo::set_retention_tier())]
+        pub fn set_retention_tier(
+            origin: OriginFor<T>,
+            currency_pair: CurrencySymbolPair<String, String>,
+            tier: RetentionTier,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::mutate_feed_state(&stored_pair, |state| state.retention = tier);
+            let len = Self::history_len(tier) as usize;
+            <PriceHistory<T>>::mutate_exists(&stored_pair, |history| {
+                if let Some(records) = history {
+                    records.keep_latest(len);
+                    if records.is_empty() {
+                        *history = None;
+                    }
+                }
+            });
+
+            Self::deposit_enabled_event(Event::<T>::RetentionTierSet {
+                pair: stored_pair.into(),
+                tier,
+            });
+
+            Ok(())
+        }
+    }
+
+    #[pallet::inherent]
+    impl<T: Config> ProvideInherent for Pallet<T> {
+        type Call = Call<T>;
+        type Error = sp_inherents::MakeFatalError<()>;
+
+        const INHERENT_IDENTIFIER: InherentIdentifier = inherent::INHERENT_IDENTIFIER;
+
+        fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+            let now = data
+                .get_data::<inherent::InherentType>(&inherent::INHERENT_IDENTIFIER)
+                .ok()??;
+
+            Some(Call::note_local_time { now })
+        }
+
+        fn is_inherent(call: &Self::Call) -> bool {
+            matches!(call, Call::note_local_time { .. })
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            // The price root commits to the previous block, so it's updated before reading new prices.
+            Self::apply_due_operator_removals(now)
+                .saturating_add(Self::update_price_root(now))
+                .saturating_add(Self::read_contract_prices(now))
+                .saturating_add(Self::aggregate_revealed_prices())
+                .saturating_add(Self::report_inactive_operators(now))
+        }
+
+        fn integrity_test() {
+            assert!(
+                T::MaxDecimals::get() <= price_provider::MAX_DECIMALS,
+                "`MaxDecimals` exceeds `price_provider::MAX_DECIMALS`"
+            );
+        }
+
+        fn offchain_worker(_now: T::BlockNumber) {
+            Self::detect_anomalies();
+            Self::submit_http_prices();
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if StorageVersion::<T>::get() == Releases::V1SinglePair {
+                weight += migrations::v1::migrate_to_v2::<T>();
+            }
+            if StorageVersion::<T>::get() == Releases::V2MultiPair {
+                weight += migrations::v2::migrate_to_v3::<T>();
+            }
+
+            weight
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            Self::do_try_state()
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Sets price for the given currency pair on behalf of the operator, see `set_price` for details.
+        /// Records the supplied source tag in the pair's state replacing the previous one.
+        ///
+        /// All checks precede the first write, so a rejected submission never leaves partial state even outside
+        /// of the storage layer wrapping each dispatchable.
+        fn do_set_price(
+            account: T::AccountId,
+            currency_pair: CurrencySymbolPair<String, String>,
+            price: u64,
+            decimals: u8,
+            confidence: Option<u64>,
+            source: Option<SourceTag>,
+        ) -> DispatchResult {
+            trace_span!("set_price");
+            ensure!(!T::SafeMode::is_entered(), Error::<T>::ChainInSafeMode);
+
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            Self::ensure_pair_allowed(&stored_pair)?;
+            if Self::is_pair_operator(&stored_pair, &account) {
+                ensure!(
+                    !<CommitRevealSchemes<T>>::contains_key(&stored_pair),
+                    Error::<T>::CommitRevealRequired
+                );
+                let mut state = <FeedStates<T>>::get(&stored_pair);
+                Self::ensure_not_paused(&state)?;
+                if let Some(window) = state.submission_window {
+                    ensure!(
+                        window.contains(
+                            T::BlockNumberProvider::current_block_number().unique_saturated_into()
+                        ),
+                        Error::<T>::OutsideSubmissionWindow
+                    );
+                }
+                ensure!(
+                    Self::meets_identity_level(&account, state.required_identity_level),
+                    Error::<T>::InsufficientOperatorIdentityLevel
+                );
+                if let Some(bounds) = state.bounds {
+                    trace_log!(
+                        "Checking price {} with {} decimals for {:?} against bounds {:?}",
+                        price,
+                        decimals,
+                        stored_pair,
+                        bounds
+                    );
+                    ensure!(
+                        bounds.contains(price, decimals),
+                        Error::<T>::PriceOutOfBounds
+                    );
+                }
+
+                let price_record = PriceRecordBuilder::new(
+                    price,
+                    decimals,
+                    T::BlockNumberProvider::current_block_number(),
+                )
+                .with_previous(<Prices<T>>::get(&stored_pair))
+                .max_decimals(T::MaxDecimals::get())
+                .confidence(confidence)
+                .build()
+                .map_err(Error::<T>::from)?;
+                trace_log!("Aggregated price for {:?}: {:?}", stored_pair, price_record);
+                Self::store_price_record(&stored_pair, price_record)?;
+                <OperatorLastSubmission<T>>::insert(
+                    &stored_pair,
+                    &account,
+                    <system::Pallet<T>>::block_number(),
+                );
+                Self::accrue_reward(&account);
+                if let Some(now) = T::FeedTime::now() {
+                    state.updated_at = Some(now);
+                }
+                state.source = source.clone();
+                state.update_ewma(price_record, T::EwmaHalfLife::get());
+                let emit_event = state.record_update();
+                // Price updates never reset the state, so a default one was missing in storage.
+                if !state.is_default() {
+                    <FeedStates<T>>::insert(&stored_pair, state);
+                }
+
+                if emit_event {
+                    Self::deposit_enabled_event(Event::<T>::PriceSet {
+                        pair: stored_pair.clone().into(),
+                        price: price_record,
+                        operator: account,
+                    });
+                    if let Some(source) = source {
+                        Self::deposit_enabled_event(Event::<T>::PriceSourceRecorded {
+                            pair: stored_pair.into(),
+                            source,
+                        });
+                    }
+                }
+
+                return Ok(());
+            }
+
+            Err(Error::<T>::NotAnOperator.into())
+        }
+
+        /// Stores the latest price record of the pair, appends it to `PriceHistory`, and marks `Prices` as changed.
+        /// Fails with `TooManyPricedPairs` before any write if the pair has no price yet and `Prices` are full.
+        fn store_price_record(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            price_record: PriceRecord<T::BlockNumber>,
+        ) -> Result<(), Error<T>> {
+            if !<Prices<T>>::contains_key(stored_pair) {
+                <PricedPairsCount<T>>::try_mutate(|count| {
+                    ensure!(
+                        *count < T::MaxPricedPairs::get(),
+                        Error::<T>::TooManyPricedPairs
+                    );
+                    *count += 1;
+
+                    Ok::<_, Error<T>>(())
+                })?;
+            }
+            <Prices<T>>::insert(stored_pair, price_record);
+            Self::append_to_history(stored_pair, price_record);
+            <PricesChanged<T>>::put(true);
+            offchain_index::index_price_update(stored_pair, &price_record);
+
+            Ok(())
+        }
+
+        /// Visits up to `MaxContractVisitsPerBlock` configs of `ContractConfigs` continuing after
+        /// `ContractVisitCursor`, and reads prices of the unpaused currency pairs which EVM contracts became due
+        /// since the previous visit. Does nothing while the chain is in safe mode.
+        fn read_contract_prices(now: T::BlockNumber) -> Weight {
+            if T::SafeMode::is_entered() {
+                return Weight::zero();
+            }
+
+            let max_visits = T::MaxContractVisitsPerBlock::get() as usize;
+            let configs: Vec<_> = match <ContractVisitCursor<T>>::take() {
+                Some(last) => {
+                    <ContractConfigs<T>>::iter_from(<ContractConfigs<T>>::hashed_key_for(last))
+                        .take(max_visits)
+                        .collect()
+                }
+                None => <ContractConfigs<T>>::iter().take(max_visits).collect(),
+            };
+            if configs.len() == max_visits {
+                if let Some((last, _)) = configs.last() {
+                    <ContractVisitCursor<T>>::put(last);
+                }
+            }
+
+            let block_number: u64 = now.unique_saturated_into();
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            for (stored_pair, config) in configs {
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 1));
+                let last_visit = <ContractLastVisit<T>>::mutate(&stored_pair, |last_visit| {
+                    // Configs set before the visits were tracked are due at the multiples of their period only.
+                    last_visit.replace(now).map_or_else(
+                        || block_number.saturating_sub(1),
+                        |last_visit| last_visit.unique_saturated_into(),
+                    )
+                });
+                if !config.is_due_between(last_visit, block_number) {
+                    continue;
+                }
+
+                trace_span!("read_contract_price");
+                let (price, call_weight) = config.read_price::<T::EvmCaller>();
+                weight = weight.saturating_add(call_weight);
+                let result = price
+                    .map_err(|error| {
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "Failed to read price of {:?} from EVM contract: {:?}",
+                            stored_pair,
+                            error
+                        );
+                    })
+                    .and_then(|(amount, decimals)| {
+                        Self::apply_contract_price(&stored_pair, amount, decimals).map_err(
+                            |error| {
+                                log::warn!(
+                                    target: LOG_TARGET,
+                                    "Failed to apply price of {:?} read from EVM contract: {:?}",
+                                    stored_pair,
+                                    error
+                                );
+                            },
+                        )
+                    });
+                if result.is_ok() {
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 4));
+                }
+            }
+
+            weight
+        }
+
+        /// Stores the price read from the EVM contract if the pair isn't paused and the price fits its bounds.
+        fn apply_contract_price(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            amount: u64,
+            decimals: u8,
+        ) -> DispatchResult {
+            let (price_record, emit_event) =
+                Self::store_unattributed_price(stored_pair, amount, decimals, None)?;
+
+            if emit_event {
+                Self::deposit_enabled_event(Event::<T>::ContractPriceRead {
+                    pair: stored_pair.clone().into(),
+                    price: price_record,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Aggregates prices revealed in the rounds of the commit-reveal scheme which reveal deadlines passed
+        /// since the previous block, draining at most `MaxRevealDeadlinesPerBlock` blocks of `RevealDeadlines`.
+        /// Each round is removed once aggregated, and operators which didn't reveal their commitments
+        /// are reported to `Config::OnUnrevealedCommitment`. Outliers among the revealed prices are discarded
+        /// before the aggregation, see `Config::MaxRevealDeviations`. The scheme set while the round was pending
+        /// replaces the current one right after. Does nothing while the chain is in safe mode.
+        fn aggregate_revealed_prices() -> Weight {
+            if T::SafeMode::is_entered() {
+                return Weight::zero();
+            }
+
+            let now: u64 = T::BlockNumberProvider::current_block_number().unique_saturated_into();
+            let drained_at = <RevealDeadlinesDrainedAt<T>>::get().unwrap_or(now.saturating_sub(1));
+            let drain_until =
+                now.min(drained_at.saturating_add(T::MaxRevealDeadlinesPerBlock::get().into()));
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            let mut due = Vec::new();
+            for deadline in drained_at.saturating_add(1)..=drain_until {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                for (stored_pair, ()) in <RevealDeadlines<T>>::drain_prefix(deadline) {
+                    weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+                    due.push(stored_pair);
+                }
+            }
+            <RevealDeadlinesDrainedAt<T>>::put(drained_at.max(drain_until));
+
+            for stored_pair in due {
+                trace_span!("aggregate_revealed_prices");
+                weight = weight.saturating_add(T::DbWeight::get().reads(2));
+                let is_over = |round| {
+                    <CommitRevealSchemes<T>>::get(&stored_pair)
+                        .map_or(true, |scheme| scheme.is_round_over(round, now))
+                };
+                // The deadline of a round dropped in favour of the following one is stale.
+                let (round, commitments) = match <PriceCommitments<T>>::get(&stored_pair) {
+                    Some((round, commitments)) if is_over(round) => (round, commitments),
+                    _ => continue,
+                };
+                <PriceCommitments<T>>::remove(&stored_pair);
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 3));
+                Self::apply_pending_commit_reveal_scheme(&stored_pair);
+
+                let mut revealed = Vec::with_capacity(commitments.len());
+                for PriceCommitment {
+                    operator,
+                    revealed: price,
+                    ..
+                } in commitments
+                {
+                    match price {
+                        Some(price) => {
+                            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                            if Self::is_operator_active(&stored_pair, &operator) {
+                                revealed.push((operator, price));
+                            }
+                        }
+                        None => {
+                            weight = weight.saturating_add(
+                                T::OnUnrevealedCommitment::on_unrevealed_commitment(
+                                    &stored_pair,
+                                    &operator,
+                                    round,
+                                ),
+                            );
+                            Self::deposit_enabled_event(Event::<T>::CommitmentNotRevealed {
+                                pair: stored_pair.clone().into(),
+                                operator,
+                                round,
+                            });
+                        }
+                    }
+                }
+
+                Self::discard_outlier_reveals(&stored_pair, round, &mut revealed);
+
+                let prices: Vec<_> = revealed.iter().map(|(_, price)| *price).collect();
+                let (amount, decimals) = match commit_reveal::median_price(&prices) {
+                    Some(price) => price,
+                    None => continue,
+                };
+                let confidence = commit_reveal::median_confidence(&prices, (amount, decimals));
+                match Self::store_unattributed_price(&stored_pair, amount, decimals, confidence) {
+                    Ok((price_record, emit_event)) => {
+                        weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 4));
+                        // Only the reveals which made it into the stored price are rewarded.
+                        for (operator, _) in &revealed {
+                            Self::accrue_reward(operator);
+                        }
+                        weight = weight.saturating_add(
+                            T::DbWeight::get()
+                                .reads_writes(prices.len() as u64, prices.len() as u64),
+                        );
+                        if emit_event {
+                            Self::deposit_enabled_event(Event::<T>::RevealedPriceSet {
+                                pair: stored_pair.into(),
+                                price: price_record,
+                                round,
+                                reveals: revealed.len() as u32,
+                            });
+                        }
+                    }
+                    Err(error) => log::warn!(
+                        target: LOG_TARGET,
+                        "Failed to apply price of {:?} revealed in round {}: {:?}",
+                        stored_pair,
+                        round,
+                        error
+                    ),
+                }
+            }
+
+            weight
+        }
+
+        /// Removes the revealed prices deviating from the median of the round by more than
+        /// `MaxRevealDeviations` median absolute deviations, emitting `SubmissionRejectedAsOutlier` for each.
+        fn discard_outlier_reveals(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            round: u64,
+            revealed: &mut Vec<(T::AccountId, (u64, u8))>,
+        ) {
+            let max_deviations = T::MaxRevealDeviations::get();
+            if max_deviations == 0 {
+                return;
+            }
+
+            let records: Vec<_> = revealed
+                .iter()
+                .map(|(_, (amount, decimals))| PriceRecord::new(*amount, *decimals, ()))
+                .collect();
+            let outliers = price_provider::outlier_indices(&records, max_deviations);
+
+            let mut idx = 0;
+            revealed.retain(|(operator, (price, decimals))| {
+                let is_outlier = outliers.contains(&idx);
+                idx += 1;
+                if is_outlier {
+                    Self::deposit_enabled_event(Event::<T>::SubmissionRejectedAsOutlier {
+                        pair: stored_pair.clone().into(),
+                        operator: operator.clone(),
+                        round,
+                        price: *price,
+                        decimals: *decimals,
+                    });
+                }
+
+                !is_outlier
+            });
+        }
+
+        /// Replaces the commit-reveal scheme of the pair with the one set while its round was pending, if any.
+        fn apply_pending_commit_reveal_scheme(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) {
+            if let Some(scheme) = <PendingCommitRevealSchemes<T>>::take(stored_pair) {
+                <CommitRevealSchemes<T>>::set(stored_pair, scheme);
+
+                Self::deposit_enabled_event(Event::<T>::CommitRevealSchemeSet {
+                    pair: stored_pair.clone().into(),
+                    scheme,
+                });
+            }
+        }
+
+        /// Stores the price which isn't submitted by a single operator if the pair isn't paused and the price fits
+        /// its bounds. Returns the stored record and `true` if the event should be emitted for it.
+        fn store_unattributed_price(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            amount: u64,
+            decimals: u8,
+            confidence: Option<u64>,
+        ) -> Result<(PriceRecord<T::BlockNumber>, bool), DispatchError> {
+            let mut state = <FeedStates<T>>::get(stored_pair);
+            Self::ensure_not_paused(&state)?;
+            if let Some(bounds) = state.bounds {
+                ensure!(
+                    bounds.contains(amount, decimals),
+                    Error::<T>::PriceOutOfBounds
+                );
+            }
+
+            let price_record = PriceRecordBuilder::new(
+                amount,
+                decimals,
+                T::BlockNumberProvider::current_block_number(),
+            )
+            .with_previous(<Prices<T>>::get(stored_pair))
+            .max_decimals(T::MaxDecimals::get())
+            .confidence(confidence)
+            .build()
+            .map_err(Error::<T>::from)?;
+            Self::store_price_record(stored_pair, price_record)?;
+            if let Some(now) = T::FeedTime::now() {
+                state.updated_at = Some(now);
+            }
+            state.source = None;
+            state.update_ewma(price_record, T::EwmaHalfLife::get());
+            let emit_event = state.record_update();
+            if !state.is_default() {
+                <FeedStates<T>>::insert(stored_pair, state);
+            }
+
+            Ok((price_record, emit_event))
+        }
+
+        /// Appends the record to the pair's `PriceHistory` evicting the oldest records
+        /// once it holds as many as the pair's retention tier allows.
+        fn append_to_history(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            record: PriceRecord<T::BlockNumber>,
+        ) {
+            let len = Self::history_len(<FeedStates<T>>::get(pair).retention);
+            if len == 0 {
+                return;
+            }
+
+            <PriceHistory<T>>::mutate(pair, |history| {
+                history.push(record);
+                history.keep_latest(len as usize);
+            });
+        }
+
+        /// Returns the amount of the latest price records kept in `PriceHistory` for the pairs of the given tier.
+        pub fn history_len(tier: RetentionTier) -> u32 {
+            let len = match tier {
+                RetentionTier::Critical => T::MaxPriceHistoryLen::get(),
+                RetentionTier::Standard => T::StandardPriceHistoryLen::get(),
+                RetentionTier::Ephemeral => T::EphemeralPriceHistoryLen::get(),
+            };
+
+            len.min(T::MaxPriceHistoryLen::get())
+        }
+
+        /// Returns the price of the currency pair at the given block using `PriceHistory`.
+        /// If there's no record at the block, the price is linearly interpolated between the two surrounding records.
+        /// Returns `None` if the block lies outside of the recorded history. Never writes to storage.
+        pub fn interpolated_price(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            block_number: T::BlockNumber,
+        ) -> Option<InterpolatedPrice<T::BlockNumber>> {
+            let history = Self::price_history(pair);
+            // Index of the first record after the block.
+            let after_idx = history.partition_point(|record| record.block_number() <= block_number);
+
+            let before = history.get(after_idx.checked_sub(1)?)?;
+            if before.block_number() == block_number {
+                return Some(InterpolatedPrice::Exact(*before));
+            }
+            let after = history.get(after_idx)?;
+
+            interpolation::interpolate(before, after, block_number)
+                .map(InterpolatedPrice::Interpolated)
+        }
+
+        /// Returns the price record stored for the currency pair in either orientation along with the orientation
+        /// it was found in. The requested orientation is looked up first, so feeds storing only canonical pairs
+        /// (see [`CurrencySymbolPair::into_canonical`]) are served for both orientations.
+        /// Records found in [`PairOrientation::Inverted`] orientation hold the price of the swapped pair,
+        /// see [`PriceRecord::inverse`]. Never writes to storage.
+        pub fn lookup_any_orientation(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<(PriceRecord<T::BlockNumber>, PairOrientation)> {
+            <Prices<T>>::get(pair)
+                .map(|record| (record, PairOrientation::Direct))
+                .or_else(|| {
+                    <Prices<T>>::get(pair.clone().swap())
+                        .map(|record| (record, PairOrientation::Inverted))
+                })
+        }
+
+        /// Returns the time-weighted average price of the currency pair over the last `window_blocks` blocks
+        /// computed using `PriceHistory`, see [`price_provider::time_weighted_average`] for details.
+        /// Blocks not covered by the history are ignored, so windows longer than the history span yield
+        /// the average over the recorded part. Never writes to storage.
+        pub fn twap(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            window_blocks: T::BlockNumber,
+        ) -> Option<PriceRecord<T::BlockNumber>> {
+            let now = T::BlockNumberProvider::current_block_number();
+
+            price_provider::time_weighted_average(
+                &Self::price_history(pair),
+                now.saturating_sub(window_blocks),
+                now,
+            )
+        }
+
+        /// Returns the exponentially-weighted moving average price of the currency pair updated on each price update
+        /// using `EwmaHalfLife`, see [`price_provider::exponentially_weighted_average`]. Never writes to storage.
+        pub fn ewma(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<PriceRecord<T::BlockNumber>> {
+            <FeedStates<T>>::get(pair).ewma_record()
+        }
+
+        /// Returns `TooManyPriceDecimals` if the supplied decimals exceed `MaxDecimals`.
+        fn ensure_valid_decimals(decimals: Option<u8>) -> DispatchResult {
+            ensure!(
+                decimals.map_or(true, |decimals| decimals <= T::MaxDecimals::get()),
+                Error::<T>::TooManyPriceDecimals
+            );
+
+            Ok(())
+        }
+
+        /// Accrues `RewardPerSubmission` to the claimable rewards of the operator.
+        fn accrue_reward(operator: &T::AccountId) {
+            let reward = T::RewardPerSubmission::get();
+            if !reward.is_zero() {
+                <ClaimableRewards<T>>::mutate(operator, |claimable| {
+                    *claimable = claimable.saturating_add(reward)
+                });
+            }
+        }
+
+        /// Returns `PairNotAllowed` if the allow-list is non-empty and doesn't contain the given currency pair.
+        fn ensure_pair_allowed(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> DispatchResult {
+            ensure!(
+                <AllowedPairsCount<T>>::get() == 0 || <AllowedPairs<T>>::contains_key(pair),
+                Error::<T>::PairNotAllowed
+            );
+
+            Ok(())
+        }
+
+        /// Adds an operator for the allowed currency pair.
+        fn do_add_operator(
+            stored_pair: BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            operator: T::AccountId,
+        ) -> DispatchResult {
+            Self::ensure_pair_allowed(&stored_pair)?;
+            <OperatorSets<T>>::try_mutate(&stored_pair, |operators| {
+                ensure!(
+                    !operators.contains(&operator),
+                    Error::<T>::OperatorIsAlreadyAdded
+                );
+
+                operators
+                    .try_insert(operator.clone())
+                    .map(drop)
+                    .map_err(|_| Error::<T>::TooManyOperators)
             })?;
-            Self::deposit_event(Event::<T>::OperatorAdded(stored_pair, operator));
+            <OperatorLastSubmission<T>>::insert(
+                &stored_pair,
+                &operator,
+                <system::Pallet<T>>::block_number(),
+            );
+            Self::deposit_enabled_event(Event::<T>::OperatorAdded {
+                pair: stored_pair.into(),
+                operator,
+            });
 
             Ok(())
         }
 
-        /// Removes an operator for the given currency pair. Only callable by Root.
-        #[pallet::weight(<T as frame_system::Config>::DbWeight::get().reads_writes(1, 1))]
-        pub fn remove_operator(
-            origin: OriginFor<T>,
-            currency_pair: CurrencySymbolPair<String, String>,
+        /// Removes the operator for the given currency pair.
+        fn do_remove_operator(
+            stored_pair: BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
             operator: T::AccountId,
         ) -> DispatchResult {
-            ensure_root(origin)?;
+            ensure!(
+                Self::take_operator(&stored_pair, &operator),
+                Error::<T>::OperatorDoesNotExist
+            );
+            <OperatorLastSubmission<T>>::remove(&stored_pair, &operator);
+            Self::deposit_enabled_event(Event::<T>::OperatorRemoved {
+                pair: stored_pair.into(),
+                operator,
+            });
 
-            let stored_pair = currency_pair.try_into()?;
-            <Operators<T>>::try_mutate(&stored_pair, &operator, |allowed| {
-                if allowed.is_some() {
-                    allowed.take();
+            Ok(())
+        }
 
-                    Ok(())
-                } else {
-                    Err(Error::<T>::OperatorDoesNotExist)
+        /// Removes the operator from the set of the currency pair, the emptied set is removed as well.
+        /// Returns `false` if the account isn't an operator of the pair.
+        fn take_operator(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            operator: &T::AccountId,
+        ) -> bool {
+            <OperatorSets<T>>::mutate_exists(stored_pair, |operators| {
+                let removed = operators
+                    .as_mut()
+                    .map_or(false, |operators| operators.remove(operator));
+                if operators
+                    .as_ref()
+                    .map_or(false, |operators| operators.is_empty())
+                {
+                    *operators = None;
                 }
-            })?;
-            Self::deposit_event(Event::<T>::OperatorRemoved(stored_pair, operator));
+
+                removed
+            })
+        }
+
+        /// Returns `true` if the account is an operator of the stored currency pair.
+        pub(crate) fn is_pair_operator(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            account: &T::AccountId,
+        ) -> bool {
+            Self::operators_of(stored_pair).contains(account)
+        }
+
+        /// Returns `true` if the operator of the currency pair submitted a price or was added within
+        /// the last `OperatorTimeout` blocks, or the timeout is disabled.
+        pub fn is_operator_active(
+            stored_pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            operator: &T::AccountId,
+        ) -> bool {
+            let timeout = T::OperatorTimeout::get();
+
+            timeout.is_zero()
+                || <OperatorLastSubmission<T>>::get(stored_pair, operator).map_or(false, |last| {
+                    <system::Pallet<T>>::block_number() <= last.saturating_add(timeout)
+                })
+        }
+
+        /// Continues the ongoing inactivity sweep, or starts a new one every `InactivityCheckPeriod` blocks,
+        /// and emits `OperatorInactive` for the visited operators which became inactive within the checked blocks.
+        /// Visits at most `MaxInactivityChecksPerBlock` operators. Does nothing if the timeout is disabled.
+        fn report_inactive_operators(now: T::BlockNumber) -> Weight {
+            let timeout = T::OperatorTimeout::get();
+            if timeout.is_zero() {
+                return Weight::zero();
+            }
+
+            let period = T::InactivityCheckPeriod::get();
+            let mut check = match <OngoingInactivityCheck<T>>::get() {
+                Some(check) => check,
+                None if period.is_zero() || (now % period).is_zero() => InactivityCheck {
+                    after: <InactivityCheckedUntil<T>>::get(),
+                    until: now,
+                    last_visited: None,
+                },
+                None => return T::DbWeight::get().reads(1),
+            };
+
+            let max_visits = T::MaxInactivityChecksPerBlock::get() as usize;
+            let visited: Vec<_> = match &check.last_visited {
+                Some((pair, operator)) => <OperatorLastSubmission<T>>::iter_from(
+                    <OperatorLastSubmission<T>>::hashed_key_for(pair, operator),
+                )
+                .take(max_visits)
+                .collect(),
+                None => <OperatorLastSubmission<T>>::iter()
+                    .take(max_visits)
+                    .collect(),
+            };
+            let reads = visited.len() as u64;
+            let finished = visited.len() < max_visits;
+
+            for (stored_pair, operator, last_submission) in visited {
+                if check.became_inactive(last_submission, timeout) {
+                    Self::deposit_enabled_event(Event::<T>::OperatorInactive {
+                        pair: stored_pair.clone().into(),
+                        operator: operator.clone(),
+                        last_submission,
+                    });
+                }
+                check.last_visited = Some((stored_pair, operator));
+            }
+            if finished {
+                <OngoingInactivityCheck<T>>::kill();
+                <InactivityCheckedUntil<T>>::put(check.until);
+            } else {
+                <OngoingInactivityCheck<T>>::put(check);
+            }
+
+            T::DbWeight::get().reads_writes(reads.saturating_add(2), 2)
+        }
+
+        /// Applies operator removals which take effect at or before the given block.
+        fn apply_due_operator_removals(now: T::BlockNumber) -> Weight {
+            let mut pending = <PendingOperatorRemovals<T>>::get();
+            let due: Vec<_> = pending
+                .iter()
+                .filter(|removal| removal.at <= now)
+                .cloned()
+                .collect();
+            if due.is_empty() {
+                return T::DbWeight::get().reads(1);
+            }
+
+            pending.retain(|removal| removal.at > now);
+            <PendingOperatorRemovals<T>>::put(pending);
+
+            let count = due.len() as u64;
+            for PendingOperatorRemoval { pair, operator, .. } in due {
+                // The operator could be removed immediately by `EmergencyOrigin` meanwhile.
+                if Self::take_operator(&pair, &operator) {
+                    <OperatorLastSubmission<T>>::remove(&pair, &operator);
+                    Self::deposit_enabled_event(Event::<T>::OperatorRemoved {
+                        pair: pair.into(),
+                        operator,
+                    });
+                }
+            }
+
+            T::DbWeight::get().reads_writes(
+                count.saturating_add(1),
+                count.saturating_mul(2).saturating_add(1),
+            )
+        }
+
+        /// Recomputes `PriceRoot` if `Prices` were changed in the previous block and deposits it into the digest
+        /// if `DepositPriceRootDigest` is enabled. Walks at most `MaxPricedPairs` prices.
+        pub(crate) fn update_price_root(now: T::BlockNumber) -> Weight {
+            if !<PricesChanged<T>>::take() {
+                return T::DbWeight::get().reads(1);
+            }
+
+            trace_span!("update_price_root");
+            let leaves: Vec<_> = Self::price_leaves().map(|(_, leaf)| leaf).collect();
+            let priced_pairs = leaves.len() as u32;
+            let root = price_root::merkle_root::<T::Hashing>(leaves);
+            let block_number = now.saturating_sub(One::one());
+            <PriceRoot<T>>::put((block_number, root));
+
+            if T::DepositPriceRootDigest::get() {
+                <system::Pallet<T>>::deposit_log(price_root::price_root_digest_item(
+                    block_number,
+                    root,
+                ));
+            }
+
+            T::WeightInfo::update_price_root(priced_pairs)
+        }
+
+        /// Deposits the event unless its kind is suppressed by `DepositedEvents`.
+        fn deposit_enabled_event(event: Event<T>) {
+            if event.is_enabled() {
+                Self::deposit_event(event);
+            }
+        }
+
+        /// Returns `Some(())` if the account is an operator of the stored currency pair.
+        /// Keeps the signature of the getter of the former `Operators` double map, see [`Self::operators_of`].
+        pub fn operators(
+            stored_pair: BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            account: T::AccountId,
+        ) -> Option<()> {
+            Self::is_pair_operator(&stored_pair, &account).then_some(())
+        }
+
+        /// Returns `true` if the account is an operator of the given currency pair.
+        /// Returns `false` if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn is_operator<S: LikeString + 'static>(
+            pair: CurrencySymbolPair<S, S>,
+            account: &T::AccountId,
+        ) -> bool {
+            BoundedCurrencySymbolPair::<_, _, T::MaxSymbolBytesLen>::try_from(pair)
+                .map_or(false, |stored_pair| {
+                    Self::is_pair_operator(&stored_pair, account)
+                })
+        }
+
+        /// Returns any operator of the given currency pair.
+        #[cfg(feature = "runtime-benchmarks")]
+        pub(crate) fn first_operator<S: LikeString + 'static>(
+            pair: CurrencySymbolPair<S, S>,
+        ) -> Option<T::AccountId> {
+            let stored_pair =
+                BoundedCurrencySymbolPair::<_, _, T::MaxSymbolBytesLen>::try_from(pair).ok()?;
+
+            Self::operators_of(stored_pair).iter().next().cloned()
+        }
+
+        /// Returns merkle leaves committing to the stored prices in the storage iteration order.
+        /// Each leaf is the hash of the encoded `(pair, record)` tuple.
+        fn price_leaves() -> impl Iterator<
+            Item = (
+                BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+                T::Hash,
+            ),
+        > {
+            <Prices<T>>::iter().map(|(pair, record)| {
+                let leaf = T::Hashing::hash_of(&(&pair, record));
+
+                (pair, leaf)
+            })
+        }
+
+        /// Returns the price of the given currency pair along with the proof of its inclusion into the merkle root
+        /// of the current prices. The proof is valid against `PriceRoot` once it's recomputed for the current state.
+        /// Iterates over all prices, so should only be used off-chain.
+        pub fn price_root_proof(
+            pair: CurrencySymbolPair<String, String>,
+        ) -> Option<(PriceRecord<T::BlockNumber>, MerkleProof<T::Hash>)> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+            let record = <Prices<T>>::get(&stored_pair)?;
+
+            let mut index = None;
+            let leaves: Vec<_> = Self::price_leaves()
+                .enumerate()
+                .map(|(idx, (pair, leaf))| {
+                    if pair == stored_pair {
+                        index = Some(idx as u32);
+                    }
+
+                    leaf
+                })
+                .collect();
+            let proof = price_root::merkle_proof::<T::Hashing>(leaves, index?)?;
+
+            Some((record, proof))
+        }
+
+        /// Returns absolute price amount bounds of the given currency pair.
+        pub fn price_bounds(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<PriceBounds> {
+            <FeedStates<T>>::get(pair).bounds
+        }
+
+        /// Returns submission window of the given currency pair.
+        pub fn submission_window(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<SubmissionWindow> {
+            <FeedStates<T>>::get(pair).submission_window
+        }
+
+        /// Returns index of the current round of the given currency pair if its submission window is open.
+        /// Returns `None` for the pairs without the submission window.
+        pub fn open_submission_round(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<u64> {
+            let now = T::BlockNumberProvider::current_block_number().unique_saturated_into();
+
+            Self::submission_window(pair)
+                .filter(|window| window.contains(now))
+                .map(|window| window.round_of(now))
+        }
+
+        /// Returns `true` if price updates of the given currency pair are paused individually or globally.
+        pub fn is_paused(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> bool {
+            <AllPairsPaused<T>>::get() || <FeedStates<T>>::get(pair).paused
+        }
+
+        /// Returns `Err` if price updates of all currency pairs or the pair having the given state are paused.
+        fn ensure_not_paused(state: &FeedState) -> Result<(), Error<T>> {
+            ensure!(!<AllPairsPaused<T>>::get(), Error::<T>::AllPairsPaused);
+            ensure!(!state.paused, Error::<T>::PairPaused);
 
             Ok(())
         }
-    }
 
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_runtime_upgrade() -> Weight {
-            T::DbWeight::get().reads(1)
-                + if StorageVersion::<T>::get() == Releases::V1SinglePair {
-                    migrations::v1::migrate_to_v2::<T>()
-                } else {
-                    Weight::zero()
+        /// Returns identity level required from the operators of the given currency pair.
+        /// Returns `None` if any operator is accepted.
+        pub fn required_identity_level(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<u32> {
+            Some(<FeedStates<T>>::get(pair).required_identity_level).filter(|level| *level > 0)
+        }
+
+        /// Returns amount of price updates per each `PriceSet` event emitted for the given currency pair.
+        /// Returns `None` if event is emitted on every price update.
+        pub fn emit_events_every_n_updates(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<u32> {
+            Some(<FeedStates<T>>::get(pair).emit_events_every_n_updates).filter(|n| *n > 1)
+        }
+
+        /// Returns amount of price updates happened since the last emitted `PriceSet` event for the given currency pair.
+        pub fn updates_since_last_event(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> u32 {
+            <FeedStates<T>>::get(pair).updates_since_last_event
+        }
+
+        /// Returns source tag of the latest price update of the given currency pair.
+        pub fn price_source(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<SourceTag> {
+            <FeedStates<T>>::get(pair).source
+        }
+
+        /// Returns time of the latest price update of the given currency pair according to `Config::FeedTime`.
+        /// Returns `None` if the pair was updated while the time was unknown.
+        pub fn price_updated_at(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<u64> {
+            <FeedStates<T>>::get(pair).updated_at
+        }
+
+        /// Returns time elapsed since the latest price update of the given currency pair measured
+        /// in `Config::FeedTime` units.
+        /// Returns `None` if either current time or price update time is unknown.
+        pub fn price_age(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<u64> {
+            let now = T::FeedTime::now()?;
+            let updated_at = Self::price_updated_at(pair)?;
+
+            Some(now.saturating_sub(updated_at))
+        }
+
+        /// Returns `true` if the price of the given currency pair wasn't updated for more than `max_age`
+        /// measured in `Config::FeedTime` units. Pairs with unknown price update time aren't considered stale.
+        pub fn is_price_stale(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            max_age: u64,
+        ) -> bool {
+            Self::price_age(pair).map_or(false, |age| age > max_age)
+        }
+
+        /// Returns market capitalization of the chain's currency calculated as `total_issuance` of `Config::Currency`
+        /// multiplied by the price of the given currency pair. The pair's `from` symbol is expected to denote
+        /// the chain's currency. The result is denominated in the pair's `to` currency and has the same precision as
+        /// the `total_issuance` (i.e. must be divided by 10^(currency decimals) to get the amount of units).
+        /// Returns `None` if the price is missing or calculation overflows.
+        pub fn market_cap(pair: CurrencySymbolPair<String, String>) -> Option<u128> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+            let price = <Prices<T>>::get(stored_pair)?;
+            let total_issuance: u128 = T::Currency::total_issuance().unique_saturated_into();
+
+            price.price_per_unit(total_issuance)
+        }
+
+        /// Converts the amount of the pair's `from` currency units into the amount of its `to` currency units
+        /// using the pair's price, see [`PriceRecord::price_per_unit`]. The result has the same precision
+        /// as the supplied amount. Returns `None` if the price is missing or calculation overflows.
+        pub fn convert(pair: CurrencySymbolPair<String, String>, amount: u128) -> Option<u128> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+
+            <Prices<T>>::get(stored_pair)?.price_per_unit(amount)
+        }
+
+        /// Exports operators and prices of all currency pairs, so they can be used as a genesis of a new chain.
+        /// Iterates over the whole pallet storage, so should only be used off-chain.
+        pub fn export_genesis_config() -> GenesisSnapshot<T::AccountId, T::BlockNumber> {
+            GenesisSnapshot {
+                operators: <OperatorSets<T>>::iter()
+                    .flat_map(|(pair, operators)| {
+                        let pair: CurrencySymbolPair<String, String> = pair.into();
+
+                        operators
+                            .into_inner()
+                            .into_iter()
+                            .map(move |operator| (pair.clone(), operator))
+                    })
+                    .collect(),
+                prices: <Prices<T>>::iter()
+                    .map(|(pair, record)| (pair.into(), record))
+                    .collect(),
+            }
+        }
+
+        /// Returns up to `limit` (capped by `MAX_PAGE_LIMIT`) operators along with the currency pairs they control
+        /// starting after the given cursor. Operators are sorted by the amount of pairs (descending), then by account.
+        /// The cursor is the encoded amount of pairs and the last operator of the previous page.
+        /// Iterates over all operators, so should only be used off-chain.
+        pub fn operators_overview(
+            cursor: Option<PageCursor>,
+            limit: u32,
+        ) -> Paged<OperatorOverview<T::AccountId>> {
+            use sp_std::cmp::Reverse;
+
+            let mut pairs_by_operator =
+                sp_std::collections::btree_map::BTreeMap::<_, Vec<_>>::new();
+            for (pair, operators) in <OperatorSets<T>>::iter() {
+                let pair: CurrencySymbolPair<String, String> = pair.into();
+                for operator in operators.into_inner() {
+                    pairs_by_operator
+                        .entry(operator)
+                        .or_default()
+                        .push(pair.clone());
+                }
+            }
+
+            let mut overview: Vec<_> = pairs_by_operator
+                .into_iter()
+                .map(|(operator, pairs)| OperatorOverview { operator, pairs })
+                .collect();
+            overview.sort_by(|a, b| {
+                (Reverse(a.pairs.len()), &a.operator).cmp(&(Reverse(b.pairs.len()), &b.operator))
+            });
+
+            let limit = limit.min(MAX_PAGE_LIMIT) as usize;
+            let after = cursor
+                .and_then(|PageCursor(last)| <(u32, T::AccountId)>::decode(&mut &last[..]).ok());
+            let items: Vec<_> = overview
+                .into_iter()
+                .filter(|item| {
+                    after.as_ref().map_or(true, |(pairs, operator)| {
+                        (Reverse(item.pairs.len() as u32), &item.operator)
+                            > (Reverse(*pairs), operator)
+                    })
+                })
+                .take(limit)
+                .collect();
+            let next = (!items.is_empty() && items.len() == limit)
+                .then(|| {
+                    items
+                        .last()
+                        .map(|last| PageCursor((last.pairs.len() as u32, &last.operator).encode()))
+                })
+                .flatten();
+
+            Paged { items, next }
+        }
+
+        /// Returns up to `limit` (capped by `MAX_PAGE_LIMIT`) currency pairs having a price starting after the given cursor.
+        pub fn pairs(
+            cursor: Option<PageCursor>,
+            limit: u32,
+        ) -> Paged<CurrencySymbolPair<String, String>> {
+            Self::all_prices(cursor, limit).map(|(pair, _)| pair)
+        }
+
+        /// Returns up to `limit` (capped by `MAX_PAGE_LIMIT`) currency pairs along with their prices
+        /// starting after the given cursor.
+        pub fn all_prices(
+            cursor: Option<PageCursor>,
+            limit: u32,
+        ) -> Paged<(
+            CurrencySymbolPair<String, String>,
+            PriceRecord<T::BlockNumber>,
+        )> {
+            let iter = match cursor {
+                Some(PageCursor(raw_key)) => <Prices<T>>::iter_from(raw_key),
+                None => <Prices<T>>::iter(),
+            };
+
+            Paged::from_prefix_iterator(iter, limit.min(MAX_PAGE_LIMIT))
+                .map(|(pair, record)| (pair.into(), record))
+        }
+
+        /// Returns all operators of the given currency pair in ascending order.
+        /// Returns `None` if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn pair_operator_set(
+            pair: CurrencySymbolPair<String, String>,
+        ) -> Option<Vec<T::AccountId>> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+
+            Some(
+                Self::operators_of(stored_pair)
+                    .into_inner()
+                    .into_iter()
+                    .collect(),
+            )
+        }
+
+        /// Returns up to `limit` (capped by `MAX_PAGE_LIMIT`) operators of the given currency pair
+        /// starting after the given cursor. The cursor is the encoded last operator of the previous page.
+        /// Returns `None` if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn pair_operators(
+            pair: CurrencySymbolPair<String, String>,
+            cursor: Option<PageCursor>,
+            limit: u32,
+        ) -> Option<Paged<T::AccountId>> {
+            let limit = limit.min(MAX_PAGE_LIMIT) as usize;
+            let after =
+                cursor.and_then(|PageCursor(last)| T::AccountId::decode(&mut &last[..]).ok());
+            let items: Vec<_> = Self::pair_operator_set(pair)?
+                .into_iter()
+                .filter(|operator| after.as_ref().map_or(true, |after| operator > after))
+                .take(limit)
+                .collect();
+            let next = (!items.is_empty() && items.len() == limit)
+                .then(|| items.last().map(|last| PageCursor(last.encode())))
+                .flatten();
+
+            Some(Paged { items, next })
+        }
+
+        /// Returns the price of the given currency pair along with its staleness according to `MaxPriceAge`.
+        /// Never writes to storage.
+        pub fn price_status(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+        ) -> Option<PriceStatus<T::BlockNumber>> {
+            let record = Self::price(pair)?;
+
+            Some(PriceStatus::new(
+                record,
+                T::BlockNumberProvider::current_block_number(),
+                T::MaxPriceAge::get(),
+            ))
+        }
+
+        /// Returns the fresh price of the given currency pair requested by the remote consumer.
+        /// Must be used by the handlers serving prices to the remote consumers, e.g. XCM queries,
+        /// so the access restrictions set via `set_price_access_restricted` are enforced.
+        /// Returns `PriceAccessDenied` if the access is restricted and the consumer isn't allowed.
+        /// Never writes to storage.
+        pub fn price_for_consumer(
+            pair: CurrencySymbolPair<String, String>,
+            consumer: &T::PriceConsumer,
+        ) -> Result<Option<PriceRecord<T::BlockNumber>>, DispatchError> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into()?;
+            ensure!(
+                !<RestrictedPriceAccess<T>>::get(&stored_pair)
+                    || <PriceConsumers<T>>::get(&stored_pair, consumer),
+                Error::<T>::PriceAccessDenied
+            );
+
+            Ok(Self::price_status(&stored_pair).and_then(PriceStatus::fresh))
+        }
+
+        /// Returns the price status of the given currency pair, see [`Self::price_status`].
+        /// Returns `None` if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn price_status_of(
+            pair: CurrencySymbolPair<String, String>,
+        ) -> Option<PriceStatus<T::BlockNumber>> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+
+            Self::price_status(&stored_pair)
+        }
+
+        /// Returns the account holding the funds the operator rewards are paid from.
+        pub fn reward_pot() -> T::AccountId {
+            T::RewardPotId::get().into_account_truncating()
+        }
+
+        /// Returns up to `limit` (capped by `MAX_PAGE_LIMIT`) operators along with their claimable rewards
+        /// starting after the given cursor.
+        pub fn claimable_rewards_page(
+            cursor: Option<PageCursor>,
+            limit: u32,
+        ) -> Paged<(T::AccountId, BalanceOf<T>)> {
+            let iter = match cursor {
+                Some(PageCursor(raw_key)) => <ClaimableRewards<T>>::iter_from(raw_key),
+                None => <ClaimableRewards<T>>::iter(),
+            };
+
+            Paged::from_prefix_iterator(iter, limit.min(MAX_PAGE_LIMIT))
+        }
+
+        /// Returns prices of the given currency pairs in the requested order.
+        /// Prices of the pairs which symbols exceed `MaxSymbolBytesLen` are `None`.
+        pub fn prices_of(
+            pairs: Vec<CurrencySymbolPair<String, String>>,
+        ) -> Vec<Option<PriceRecord<T::BlockNumber>>> {
+            pairs
+                .into_iter()
+                .map(|pair| {
+                    let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                        pair.try_into().ok()?;
+
+                    Self::price(stored_pair)
+                })
+                .collect()
+        }
+
+        /// Returns up to `MaxPriceHistoryLen` latest price records of the given currency pair ordered by block number.
+        /// Returns an empty history if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn price_history_of(
+            pair: CurrencySymbolPair<String, String>,
+        ) -> Vec<PriceRecord<T::BlockNumber>> {
+            let stored_pair: Result<BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen>, _> =
+                pair.try_into();
+
+            stored_pair
+                .map(|stored_pair| Self::price_history(stored_pair).into_inner())
+                .unwrap_or_default()
+        }
+
+        /// Returns the storage key of the given currency pair's price record.
+        /// Returns `None` if the pair symbols exceed `MaxSymbolBytesLen`.
+        pub fn price_storage_key(pair: CurrencySymbolPair<String, String>) -> Option<Vec<u8>> {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                pair.try_into().ok()?;
+
+            Some(<Prices<T>>::hashed_key_for(stored_pair))
+        }
+
+        /// Checks that the report refers to the latest price record of the currency pair which diverges from
+        /// the reference price by more than `AnomalyThreshold`. Returns the pair, its record and the divergence.
+        pub fn check_anomaly_report(
+            report: &AnomalyReport<T::BlockNumber>,
+        ) -> Result<
+            (
+                BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+                PriceRecord<T::BlockNumber>,
+                Perbill,
+            ),
+            DispatchError,
+        > {
+            trace_span!("check_anomaly_report");
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                report.pair.clone().try_into()?;
+            let record = <Prices<T>>::get(&stored_pair).ok_or(Error::<T>::PriceNotFound)?;
+            ensure!(
+                record.block_number() == report.record_block_number,
+                Error::<T>::OutdatedAnomalyReport
+            );
+
+            let divergence =
+                anomaly::divergence(&record, report.reference_amount, report.reference_decimals);
+            trace_log!(
+                "Divergence of {} from the reference price: {:?}",
+                report.pair,
+                divergence
+            );
+            let divergence = divergence
+                .filter(|divergence| *divergence > T::AnomalyThreshold::get())
+                .ok_or(Error::<T>::NotAnAnomaly)?;
+
+            Ok((stored_pair, record, divergence))
+        }
+
+        /// Compares prices of all unpaused currency pairs against the reference API and submits
+        /// `report_anomaly` transactions for the diverged ones via `Config::AnomalyReportSubmitter`.
+        /// Does nothing if the reference API isn't configured or the submitter can't submit reports.
+        fn detect_anomalies() {
+            trace_span!("detect_anomalies");
+            if !T::AnomalyReportSubmitter::can_submit() {
+                return;
+            }
+
+            let base_url = match StorageValueRef::persistent(anomaly::REFERENCE_API_KEY)
+                .get::<Vec<u8>>()
+                .ok()
+                .flatten()
+                .and_then(|url| String::from_utf8(url).ok())
+            {
+                Some(base_url) => base_url,
+                None => return,
+            };
+
+            for (stored_pair, record) in <Prices<T>>::iter() {
+                if Self::is_paused(&stored_pair) {
+                    continue;
+                }
+
+                let pair: CurrencySymbolPair<String, String> = stored_pair.into();
+                let (reference_amount, reference_decimals) =
+                    match anomaly::fetch_reference_price(&base_url, &pair) {
+                        Ok(reference) => reference,
+                        Err(error) => {
+                            log::warn!(
+                                target: LOG_TARGET,
+                                "Failed to fetch reference price for {}: {:?}",
+                                pair,
+                                error
+                            );
+                            continue;
+                        }
+                    };
+
+                let report = AnomalyReport {
+                    pair,
+                    record_block_number: record.block_number(),
+                    reference_amount,
+                    reference_decimals,
+                };
+                if Self::check_anomaly_report(&report).is_err() {
+                    continue;
+                }
+
+                if T::AnomalyReportSubmitter::submit_report(report).is_err() {
+                    log::warn!(
+                        target: LOG_TARGET,
+                        "Failed to submit anomaly report"
+                    );
+                }
+            }
+        }
+
+        /// Fetches prices of all unpaused currency pairs having HTTP sources and submits them via
+        /// `Config::HttpPriceSubmitter`. Does nothing if the submitter can't submit prices.
+        fn submit_http_prices() {
+            trace_span!("submit_http_prices");
+            if !T::HttpPriceSubmitter::can_submit() {
+                return;
+            }
+
+            for (stored_pair, source) in <HttpSources<T>>::iter() {
+                if Self::is_paused(&stored_pair) {
+                    continue;
+                }
+
+                let pair: CurrencySymbolPair<String, String> = stored_pair.into();
+                let price = match source.fetch_price() {
+                    Ok(price) => price,
+                    Err(error) => {
+                        log::warn!(
+                            target: LOG_TARGET,
+                            "Failed to fetch price of {} from HTTP source: {:?}",
+                            pair,
+                            error
+                        );
+                        continue;
+                    }
+                };
+
+                if T::HttpPriceSubmitter::submit_price(pair.clone(), price, source.decimals)
+                    .is_err()
+                {
+                    log::warn!(
+                        target: LOG_TARGET,
+                        "Failed to submit price of {} fetched from HTTP source",
+                        pair
+                    );
                 }
+            }
+        }
+
+        /// Returns `true` if the supplied spec matches the hash stored for its currency pair.
+        /// Returns `false` if the pair doesn't have a stored hash.
+        pub fn matches_feed_spec(spec: &FeedSpec<T::AccountId>) -> bool {
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                match spec.pair.clone().try_into() {
+                    Ok(pair) => pair,
+                    Err(_) => return false,
+                };
+
+            <FeedSpecHashes<T>>::get(&stored_pair)
+                .map_or(false, |hash| hash == spec.hash::<T::Hashing>())
+        }
+
+        /// Returns `true` if the operator's identity meets the level required for the given currency pair.
+        /// Always returns `true` for the pairs without the required identity level.
+        pub fn meets_required_identity_level(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            operator: &T::AccountId,
+        ) -> bool {
+            Self::meets_identity_level(operator, <FeedStates<T>>::get(pair).required_identity_level)
+        }
+
+        /// Returns `true` if the operator's identity is verified and has at least `required_level` justifications.
+        /// Always returns `true` if `required_level` is `0`.
+        fn meets_identity_level(operator: &T::AccountId, required_level: u32) -> bool {
+            if required_level == 0 {
+                return true;
+            }
+
+            T::OperatorIdentityProvider::identity(operator).map_or(false, |identity| {
+                identity.verified() && identity.justification_count() >= required_level
+            })
+        }
+
+        /// Modifies state of the given currency pair removing it from storage if it becomes default.
+        fn mutate_feed_state<R>(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            f: impl FnOnce(&mut FeedState) -> R,
+        ) -> R {
+            Self::try_mutate_feed_state(pair, |state| Ok::<_, ()>(f(state)))
+                .expect("Infallible mutation")
+        }
+
+        /// Modifies state of the given currency pair removing it from storage if it becomes default.
+        /// Storage isn't modified if `f` returns an error.
+        fn try_mutate_feed_state<R, E>(
+            pair: &BoundedCurrencySymbolPair<String, String, T::MaxSymbolBytesLen>,
+            f: impl FnOnce(&mut FeedState) -> Result<R, E>,
+        ) -> Result<R, E> {
+            <FeedStates<T>>::try_mutate_exists(pair, |stored| {
+                let mut state = stored.take().unwrap_or_default();
+                let res = f(&mut state)?;
+                *stored = (!state.is_default()).then_some(state);
+
+                Ok(res)
+            })
         }
     }
 
     #[pallet::genesis_build]
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
-            StorageVersion::<T>::put(Releases::V2MultiPair);
+            for (pair, operator) in &self.operators {
+                let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> = pair
+                    .clone()
+                    .try_into()
+                    .expect("Genesis currency pair symbols exceed `MaxSymbolBytesLen`");
+
+                <OperatorSets<T>>::mutate(&stored_pair, |operators| {
+                    operators.try_insert(operator.clone()).expect(
+                        "Genesis operators of the currency pair exceed `MaxOperatorsPerPair`",
+                    );
+                });
+                <OperatorLastSubmission<T>>::insert(stored_pair, operator, T::BlockNumber::zero());
+            }
+            for (pair, record) in &self.prices {
+                let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> = pair
+                    .clone()
+                    .try_into()
+                    .expect("Genesis currency pair symbols exceed `MaxSymbolBytesLen`");
+                assert!(
+                    record.decimals() <= T::MaxDecimals::get().into(),
+                    "Genesis price decimals exceed `MaxDecimals`"
+                );
+
+                <Prices<T>>::insert(stored_pair, record);
+            }
+            let priced_pairs = <Prices<T>>::iter_keys().count() as u32;
+            assert!(
+                priced_pairs <= T::MaxPricedPairs::get(),
+                "Genesis prices exceed `MaxPricedPairs`"
+            );
+            <PricedPairsCount<T>>::put(priced_pairs);
+            <PricesChanged<T>>::put(true);
+
+            StorageVersion::<T>::put(Releases::V3OperatorSets);
         }
     }
 
-    impl<T: Config> PriceProvider<T> for Pallet<T> {
+    impl<T: Config> TimeWeightedPriceProvider<T> for Pallet<T> {
+        type Error = BoundedStringConversionError;
+
+        /// Returns the time-weighted average price of the given currency pair, see [`Pallet::twap`].
+        fn pair_twap<From, To>(
+            currency_pair: CurrencySymbolPair<From, To>,
+            window: T::BlockNumber,
+        ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
+        where
+            From: LikeString + 'static,
+            To: LikeString + 'static,
+        {
+            currency_pair.try_into().map(
+                |stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen>| {
+                    Self::twap(&stored_pair, window)
+                },
+            )
+        }
+    }
+
+    impl<T: Config> ExponentiallyWeightedPriceProvider<T> for Pallet<T> {
         type Error = BoundedStringConversionError;
 
-        /// Returns the price of the given currency pair from storage.
-        /// This operation performs a single storage read.
+        /// Returns the exponentially-weighted moving average price of the given currency pair, see [`Pallet::ewma`].
+        fn pair_ewma<From, To>(
+            currency_pair: CurrencySymbolPair<From, To>,
+        ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
+        where
+            From: LikeString + 'static,
+            To: LikeString + 'static,
+        {
+            currency_pair.try_into().map(
+                |stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen>| {
+                    Self::ewma(&stored_pair)
+                },
+            )
+        }
+    }
+
+    /// Routes prices through the intermediate currency using the fresh prices returned by [`PriceProvider`],
+    /// so the routed price is missing if either of the prices is stale.
+    impl<T: Config> RoutedPriceProvider<T> for Pallet<T> {}
+
+    impl<T: Config> PriceProvider<T> for Pallet<T> {
+        type Error = PairPriceError;
+
+        /// Returns the price of the given currency pair from storage or `None` if it's stale,
+        /// see [`Pallet::price_status`] to distinguish stale prices from the missing ones.
+        /// Returns `PairPriceError::Paused` while price updates of the pair are paused individually or globally.
+        /// This operation performs up to three storage reads and never writes.
         fn pair_price<From, To>(
             currency_pair: CurrencySymbolPair<From, To>,
         ) -> Result<Option<PriceRecord<T::BlockNumber>>, Self::Error>
@@ -244,9 +3545,13 @@ mod pallet {
             From: LikeString + 'static,
             To: LikeString + 'static,
         {
-            currency_pair
-                .try_into()
-                .map(Self::price::<BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen>>)
+            let stored_pair: BoundedCurrencySymbolPair<_, _, T::MaxSymbolBytesLen> =
+                currency_pair.try_into()?;
+            if Self::is_paused(&stored_pair) {
+                return Err(PairPriceError::Paused);
+            }
+
+            Ok(Self::price_status(&stored_pair).and_then(PriceStatus::fresh))
         }
     }
 }
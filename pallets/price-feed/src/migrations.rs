@@ -1,15 +1,67 @@
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, Input, Output};
 use frame_support::traits::Get;
 use sp_std::vec::*;
 
+/// Storage items keyed by the currency pair hashed with `Blake2_128Concat`, along with the length of the key part
+/// preceding the hashed pair (`0` if the pair is the first key).
+/// Must be extended once such an item is added, so [`prune::prune_oversized_pairs`]
+/// and [`rebound::rebound_storage`] cover it. The storage values embedding the currency pairs
+/// (`PendingOperatorRemovals`, `OngoingInactivityCheck` and `ContractVisitCursor`) are covered by both separately.
+pub const PAIR_KEYED_ITEMS: &[(&[u8], usize)] = &[
+    (b"OperatorSets", 0),
+    (b"OperatorLastSubmission", 0),
+    (b"Prices", 0),
+    (b"PriceHistory", 0),
+    (b"FeedStates", 0),
+    (b"FeedSpecHashes", 0),
+    (b"HttpSources", 0),
+    (b"ContractConfigs", 0),
+    (b"ContractLastVisit", 0),
+    (b"CommitRevealSchemes", 0),
+    (b"PriceCommitments", 0),
+    (b"PendingCommitRevealSchemes", 0),
+    // Preceded by the deadline hashed with `Twox64Concat`.
+    (b"RevealDeadlines", 8 + 8),
+    (b"RestrictedPriceAccess", 0),
+    (b"PriceConsumers", 0),
+    (b"AllowedPairs", 0),
+    (b"OperatorRequests", 0),
+];
+
+/// Storage value of any type kept encoded, so the entries of the different items can be moved
+/// or removed without decoding them.
+struct EncodedValue(Vec<u8>);
+
+impl Decode for EncodedValue {
+    fn decode<I: Input>(input: &mut I) -> Result<Self, codec::Error> {
+        let len = input
+            .remaining_len()?
+            .ok_or("Length of the encoded value is unknown")?;
+        let mut value = sp_std::vec![0; len];
+        input.read(&mut value)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl Encode for EncodedValue {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+        dest.write(&self.0)
+    }
+}
+
 pub mod v1 {
     use super::*;
     use crate::{
         pallet::{Config, StorageVersion},
-        Releases,
+        Releases, WeightInfo,
     };
 
-    use frame_support::{decl_module, decl_storage, weights::Weight};
+    use frame_support::{decl_module, decl_storage, weights::Weight, StorageValue};
     use scale_info::TypeInfo;
     use sp_core::H160;
     use sp_std::{prelude::*, vec};
@@ -94,13 +146,600 @@ pub mod v1 {
         pub struct Module<T: Config> for enum Call where origin: <T as frame_system::Config>::Origin {}
     }
 
+    /// Removes all V1 storage values. V1 storage consists of single values only, so the migration
+    /// is bounded and is performed at once. Its weight depends on the size of the residual
+    /// `ContractConfigStore` value which is measured without reading it.
     pub fn migrate_to_v2<T: Config>() -> Weight {
+        trace_span!("migrate_to_v2");
+        let config_len = sp_io::storage::read(&ContractConfigStore::hashed_key(), &mut [], 0)
+            .unwrap_or_default();
+        trace_log!(
+            "Migrating storage from `V1SinglePair` to `V2MultiPair`, contract config size: {} bytes",
+            config_len
+        );
         Price::kill();
         ContractConfigStore::kill();
         LastPriceUpdateAt::<T>::kill();
         PriceUpdateFreq::kill();
         StorageVersion::<T>::put(Releases::V2MultiPair);
 
-        T::DbWeight::get().writes(5)
+        T::WeightInfo::migrate_to_v2(config_len)
+    }
+}
+
+pub mod v2 {
+    use super::*;
+    use crate::{
+        pallet::{
+            Config, OperatorLastSubmission, OperatorSets, Pallet, PricedPairsCount, Prices,
+            PricesChanged, StorageVersion,
+        },
+        Releases,
+    };
+
+    use frame_support::{
+        storage::migration::storage_iter, traits::PalletInfoAccess, weights::Weight,
+        BoundedBTreeSet,
+    };
+    use price_provider::{BoundedCurrencySymbolPair, PriceRecord};
+    use scale_info::prelude::string::String;
+    use sp_std::collections::btree_map::BTreeMap;
+
+    /// Length of the `Blake2_128` hash prepended to the encoded currency pair in storage keys.
+    const BLAKE2_128_LEN: usize = 16;
+    /// Length of the `Twox64` hash prepended to the encoded account in storage keys.
+    const TWOX_64_LEN: usize = 8;
+
+    type StoredPair<T> =
+        BoundedCurrencySymbolPair<String, String, <T as Config>::MaxSymbolBytesLen>;
+
+    /// `PriceRecord` encoded without the confidence interval.
+    #[derive(Decode)]
+    struct OldPriceRecord<BlockNumber> {
+        amount: u64,
+        decimals: u8,
+        block_number: BlockNumber,
+    }
+
+    impl<BlockNumber> From<OldPriceRecord<BlockNumber>> for PriceRecord<BlockNumber> {
+        fn from(
+            OldPriceRecord {
+                amount,
+                decimals,
+                block_number,
+            }: OldPriceRecord<BlockNumber>,
+        ) -> Self {
+            PriceRecord::new(amount, decimals, block_number)
+        }
+    }
+
+    /// Migrates the storage of the `V2MultiPair` release:
+    /// - moves operators from the `Operators` double map into the per-pair sets of `OperatorSets`, see
+    /// [`migrate_operators`];
+    /// - re-encodes the records of `Prices` stored without the confidence interval, so they have none, and seeds
+    /// `PricedPairsCount`, see [`migrate_prices`].
+    pub fn migrate_to_v3<T: Config>() -> Weight {
+        trace_span!("migrate_to_v3");
+        let (operator_reads, operator_writes) = migrate_operators::<T>();
+        let (price_reads, price_writes) = migrate_prices::<T>();
+        StorageVersion::<T>::put(Releases::V3OperatorSets);
+
+        T::DbWeight::get().reads_writes(
+            operator_reads.saturating_add(price_reads),
+            operator_writes
+                .saturating_add(price_writes)
+                .saturating_add(1),
+        )
+    }
+
+    /// Moves operators from the `Operators` double map into the per-pair sets of `OperatorSets` and seeds their
+    /// `OperatorLastSubmission` with the current block, so they aren't considered inactive right away.
+    ///
+    /// If a pair has more than `MaxOperatorsPerPair` operators, the ones encountered later in the storage
+    /// iteration order are dropped. Entries having undecodable keys are removed.
+    /// Returns amount of performed reads and writes.
+    fn migrate_operators<T: Config>() -> (u64, u64) {
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut reads = 0u64;
+        let mut sets = BTreeMap::<
+            Vec<u8>,
+            (
+                StoredPair<T>,
+                BoundedBTreeSet<T::AccountId, T::MaxOperatorsPerPair>,
+            ),
+        >::new();
+        let mut dropped = 0u64;
+        for (key, ()) in storage_iter::<()>(pallet, b"Operators").drain() {
+            reads = reads.saturating_add(1);
+
+            let (pair, operator) = match decode_operator_key::<T>(&key) {
+                Some(decoded) => decoded,
+                None => {
+                    trace_log!("Dropped `Operators` entry with undecodable key: {:?}", key);
+                    continue;
+                }
+            };
+            let (_, operators) = sets
+                .entry(pair.encode())
+                .or_insert_with(|| (pair.clone(), Default::default()));
+            if operators.try_insert(operator).is_err() {
+                dropped = dropped.saturating_add(1);
+            }
+        }
+
+        trace_log!(
+            "Moving {} operators into {} sets, {} exceeding `MaxOperatorsPerPair` are dropped",
+            reads,
+            sets.len(),
+            dropped
+        );
+        // Each drained entry is read and removed.
+        let mut writes = reads.saturating_add(sets.len() as u64);
+        for (_, (pair, operators)) in sets {
+            for operator in &operators {
+                <OperatorLastSubmission<T>>::insert(&pair, operator, now);
+                writes = writes.saturating_add(1);
+            }
+            <OperatorSets<T>>::insert(pair, operators);
+        }
+
+        (reads, writes)
+    }
+
+    /// Decodes the currency pair and the operator from the `Operators` key suffix.
+    fn decode_operator_key<T: Config>(key: &[u8]) -> Option<(StoredPair<T>, T::AccountId)> {
+        let mut rest = key.get(BLAKE2_128_LEN..)?;
+        let pair = StoredPair::<T>::decode(&mut rest).ok()?;
+        let operator = T::AccountId::decode(&mut rest.get(TWOX_64_LEN..)?).ok()?;
+
+        Some((pair, operator))
+    }
+
+    /// Re-encodes the records of `Prices` stored before the confidence interval was introduced, so they have none,
+    /// and seeds `PricedPairsCount` with the amount of the priced currency pairs. Existing prices are kept even if
+    /// they exceed `MaxPricedPairs`, so only the new pairs get rejected. `PriceRoot` is recomputed at the end
+    /// of the block as the encoded records change.
+    /// Returns amount of performed reads and writes.
+    fn migrate_prices<T: Config>() -> (u64, u64) {
+        let mut translated = 0u64;
+
+        <Prices<T>>::translate::<OldPriceRecord<T::BlockNumber>, _>(|_, record| {
+            translated = translated.saturating_add(1);
+
+            Some(record.into())
+        });
+        <PricedPairsCount<T>>::put(translated as u32);
+        <PricesChanged<T>>::put(true);
+        trace_log!(
+            "Re-encoded {} prices without confidence interval",
+            translated
+        );
+
+        (translated, translated.saturating_add(2))
+    }
+}
+
+pub mod rebound {
+    use super::*;
+    use crate::{
+        pallet::{Config, Pallet},
+        InactivityCheck, PendingOperatorRemoval,
+    };
+
+    use frame_support::{
+        storage::{migration::storage_iter, storage_prefix, unhashed},
+        traits::PalletInfoAccess,
+        weights::Weight,
+    };
+    use price_provider::{BoundedCurrencySymbolPair, CurrencySymbolPair};
+    use scale_info::prelude::string::String;
+    use sp_std::vec;
+
+    /// Length of the `Blake2_128` hash prepended to the encoded currency pair in storage keys.
+    const BLAKE2_128_LEN: usize = 16;
+
+    type StoredPair<MaxSymBytesLen> = BoundedCurrencySymbolPair<String, String, MaxSymBytesLen>;
+
+    /// Errors happening during rebounding of the stored currency pairs.
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub enum ReboundStorageError {
+        /// Stored key can't be decoded using the old bound.
+        InvalidStoredKey(Vec<u8>),
+        /// Value of the storage item with the given name can't be decoded using the old bound.
+        InvalidStoredValue(Vec<u8>),
+        /// Stored currency pair doesn't fit into the new bound.
+        PairDoesNotFit(CurrencySymbolPair<String, String>),
+    }
+
+    /// Validates all currency pairs stored in the keys of [`PAIR_KEYED_ITEMS`] and in the values of
+    /// `PendingOperatorRemovals`, `OngoingInactivityCheck` and `ContractVisitCursor` against `OldLen` and `NewLen`.
+    /// Should be invoked in the same upgrade which changes `MaxSymbolBytesLen` from `OldLen` to `NewLen`,
+    /// after [`prune::prune_oversized_pairs`] if the bound is lowered.
+    ///
+    /// The encoding of the currency pairs doesn't depend on the bound, so no writes are performed.
+    /// In case if any of the stored pairs can't be decoded using `OldLen` or doesn't fit into `NewLen`,
+    /// an error will be returned.
+    pub fn rebound_storage<T, OldLen, NewLen>() -> Result<Weight, ReboundStorageError>
+    where
+        T: Config,
+        OldLen: Get<u32>,
+        NewLen: Get<u32>,
+    {
+        trace_span!("rebound_storage");
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+        let mut reads = 0u64;
+        for (item, offset) in PAIR_KEYED_ITEMS {
+            for (key, _) in storage_iter::<EncodedValue>(pallet, item) {
+                reads = reads.saturating_add(1);
+                validate_key::<OldLen, NewLen>(&key, *offset)?;
+            }
+        }
+        validate_value::<
+            Vec<PendingOperatorRemoval<StoredPair<OldLen>, T::AccountId, T::BlockNumber>>,
+            _,
+            _,
+            NewLen,
+        >(pallet, b"PendingOperatorRemovals", |removals| {
+            removals.into_iter().map(|removal| removal.pair).collect()
+        })?;
+        validate_value::<
+            InactivityCheck<StoredPair<OldLen>, T::AccountId, T::BlockNumber>,
+            _,
+            _,
+            NewLen,
+        >(pallet, b"OngoingInactivityCheck", |check| {
+            check
+                .last_visited
+                .into_iter()
+                .map(|(pair, _)| pair)
+                .collect()
+        })?;
+        validate_value::<StoredPair<OldLen>, _, _, NewLen>(
+            pallet,
+            b"ContractVisitCursor",
+            |pair| vec![pair],
+        )?;
+        reads = reads.saturating_add(3);
+        trace_log!(
+            "Validated currency pairs rebounded from {} to {} bytes: {} reads",
+            OldLen::get(),
+            NewLen::get(),
+            reads
+        );
+
+        Ok(T::DbWeight::get().reads(reads))
+    }
+
+    /// Decodes currency pair from the supplied storage key suffix using `OldLen` bound and checks that it fits
+    /// into `NewLen`. The pair is preceded by `offset` bytes of the other key parts.
+    fn validate_key<OldLen, NewLen>(key: &[u8], offset: usize) -> Result<(), ReboundStorageError>
+    where
+        OldLen: Get<u32>,
+        NewLen: Get<u32>,
+    {
+        let invalid_key = || ReboundStorageError::InvalidStoredKey(key.to_vec());
+
+        let mut rest = key
+            .get(offset.saturating_add(BLAKE2_128_LEN)..)
+            .ok_or_else(invalid_key)?;
+        let pair = StoredPair::<OldLen>::decode(&mut rest).map_err(|_| invalid_key())?;
+
+        validate_pair::<_, NewLen>(pair)
+    }
+
+    /// Decodes the value of the given storage item using the old bound and checks that the currency pairs
+    /// returned by `pairs` fit into `NewLen`. Missing value is considered valid.
+    fn validate_value<V, F, OldLen, NewLen>(
+        pallet: &[u8],
+        item: &[u8],
+        pairs: F,
+    ) -> Result<(), ReboundStorageError>
+    where
+        V: Decode,
+        F: FnOnce(V) -> Vec<StoredPair<OldLen>>,
+        OldLen: Get<u32>,
+        NewLen: Get<u32>,
+    {
+        let raw = match unhashed::get_raw(&storage_prefix(pallet, item)) {
+            Some(raw) => raw,
+            None => return Ok(()),
+        };
+        let value = V::decode(&mut &raw[..])
+            .map_err(|_| ReboundStorageError::InvalidStoredValue(item.to_vec()))?;
+
+        pairs(value)
+            .into_iter()
+            .try_for_each(validate_pair::<_, NewLen>)
+    }
+
+    /// Checks that the currency pair fits into `NewLen`.
+    fn validate_pair<OldLen, NewLen>(pair: StoredPair<OldLen>) -> Result<(), ReboundStorageError>
+    where
+        OldLen: Get<u32>,
+        NewLen: Get<u32>,
+    {
+        let pair: CurrencySymbolPair<String, String> = pair.into();
+
+        StoredPair::<NewLen>::try_from(pair.clone())
+            .map(drop)
+            .map_err(|_| ReboundStorageError::PairDoesNotFit(pair))
+    }
+}
+
+pub mod prune {
+    use super::*;
+    use crate::{
+        pallet::{Config, Pallet},
+        InactivityCheck, PendingOperatorRemoval,
+    };
+
+    use frame_support::{
+        storage::{
+            migration::{get_storage_value, put_storage_value, storage_iter},
+            storage_prefix, unhashed,
+        },
+        traits::{PalletInfoAccess, ReservableCurrency},
+        weights::Weight,
+    };
+    use price_provider::{BoundedCurrencySymbolPair, CurrencySymbolPair};
+    use scale_info::prelude::string::String;
+
+    use crate::BalanceOf;
+
+    /// Length of the `Blake2_128` hash prepended to the encoded currency pair in storage keys.
+    const BLAKE2_128_LEN: usize = 16;
+
+    type StoredPair<MaxSymBytesLen> = BoundedCurrencySymbolPair<String, String, MaxSymBytesLen>;
+    /// Currency pair decoded without the symbol length checks.
+    type UncheckedPair = CurrencySymbolPair<String, String>;
+
+    /// Removes entries of all [`PAIR_KEYED_ITEMS`] which pairs don't fit into `Len`, drops such pairs' removals
+    /// from `PendingOperatorRemovals`, and resets `OngoingInactivityCheck` and `ContractVisitCursor` if they
+    /// stopped at such a pair, so the inactivity sweep and the contract visits start over.
+    /// Should be invoked in the same upgrade which lowers `MaxSymbolBytesLen` to `Len`, so entries
+    /// with the longer symbols don't become undecodable zombies.
+    ///
+    /// Keys and values which can't be decoded even without the length check are left untouched.
+    pub fn prune_oversized_pairs<T, Len>() -> Weight
+    where
+        T: Config,
+        Len: Get<u32>,
+    {
+        trace_span!("prune_oversized_pairs");
+        let pallet = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+
+        let (reads, writes) =
+            PAIR_KEYED_ITEMS
+                .iter()
+                .map(|(item, offset)| match *item {
+                    b"AllowedPairs" => prune_allowed_pairs::<T, Len>(pallet),
+                    b"Prices" => prune_prices::<T, Len>(pallet),
+                    b"OperatorRequests" => prune_operator_requests::<T, Len>(pallet),
+                    item => prune_item::<Len>(pallet, item, *offset),
+                })
+                .chain([
+                    prune_pending_operator_removals::<T, Len>(pallet),
+                    prune_cursor::<
+                        InactivityCheck<UncheckedPair, T::AccountId, T::BlockNumber>,
+                        _,
+                        Len,
+                    >(pallet, b"OngoingInactivityCheck", |check| {
+                        check.last_visited.map(|(pair, _)| pair)
+                    }),
+                    prune_cursor::<UncheckedPair, _, Len>(pallet, b"ContractVisitCursor", Some),
+                ])
+                .fold(
+                    (0u64, 0u64),
+                    |(reads, writes), (item_reads, item_writes)| {
+                        (
+                            reads.saturating_add(item_reads),
+                            writes.saturating_add(item_writes),
+                        )
+                    },
+                );
+        if writes > 0 {
+            crate::pallet::PricesChanged::<T>::put(true);
+        }
+        trace_log!(
+            "Pruned {} entries with currency pairs exceeding {} bytes",
+            writes,
+            Len::get()
+        );
+
+        T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    /// Removes entries of the given storage item which pairs don't fit into `Len`.
+    /// The pair is preceded by `offset` bytes of the other key parts.
+    /// Returns amount of performed reads and writes.
+    fn prune_item<Len: Get<u32>>(pallet: &[u8], item: &[u8], offset: usize) -> (u64, u64) {
+        let mut reads = 0u64;
+        let oversized_keys: Vec<_> = storage_iter::<EncodedValue>(pallet, item)
+            .map(|(key, _)| key)
+            .inspect(|_| reads = reads.saturating_add(1))
+            .filter(|key| is_oversized::<Len>(key, offset))
+            .collect();
+
+        let prefix = storage_prefix(pallet, item);
+        for key in &oversized_keys {
+            unhashed::kill(&[&prefix[..], key].concat());
+        }
+
+        (reads, oversized_keys.len() as u64)
+    }
+
+    /// Removes entries of `AllowedPairs` which pairs don't fit into `Len` updating `AllowedPairsCount` accordingly.
+    /// Returns amount of performed reads and writes.
+    fn prune_allowed_pairs<T: Config, Len: Get<u32>>(pallet: &[u8]) -> (u64, u64) {
+        let (reads, writes) = prune_item::<Len>(pallet, b"AllowedPairs", 0);
+        if writes == 0 {
+            return (reads, writes);
+        }
+        crate::pallet::AllowedPairsCount::<T>::put(reads.saturating_sub(writes) as u32);
+
+        (reads, writes.saturating_add(1))
+    }
+
+    /// Removes entries of `Prices` which pairs don't fit into `Len` updating `PricedPairsCount` accordingly.
+    /// Returns amount of performed reads and writes.
+    fn prune_prices<T: Config, Len: Get<u32>>(pallet: &[u8]) -> (u64, u64) {
+        let (reads, writes) = prune_item::<Len>(pallet, b"Prices", 0);
+        if writes == 0 {
+            return (reads, writes);
+        }
+        crate::pallet::PricedPairsCount::<T>::put(reads.saturating_sub(writes) as u32);
+
+        (reads, writes.saturating_add(1))
+    }
+
+    /// Removes entries of `OperatorRequests` which pairs don't fit into `Len` returning their deposits.
+    /// Returns amount of performed reads and writes.
+    fn prune_operator_requests<T: Config, Len: Get<u32>>(pallet: &[u8]) -> (u64, u64) {
+        let mut reads = 0u64;
+        let oversized: Vec<_> = storage_iter::<BalanceOf<T>>(pallet, b"OperatorRequests")
+            .inspect(|_| reads = reads.saturating_add(1))
+            .filter(|(key, _)| is_oversized::<Len>(key, 0))
+            .collect();
+
+        let prefix = storage_prefix(pallet, b"OperatorRequests");
+        let mut writes = 0u64;
+        for (key, deposit) in oversized {
+            if let Some(account) = requester::<T, Len>(&key) {
+                T::Currency::unreserve(&account, deposit);
+                writes = writes.saturating_add(1);
+            }
+            unhashed::kill(&[&prefix[..], &key].concat());
+            writes = writes.saturating_add(1);
+        }
+
+        (reads, writes)
+    }
+
+    /// Drops the removals of `PendingOperatorRemovals` which pairs don't fit into `Len`.
+    /// Returns amount of performed reads and writes.
+    fn prune_pending_operator_removals<T: Config, Len: Get<u32>>(pallet: &[u8]) -> (u64, u64) {
+        let item = b"PendingOperatorRemovals";
+        let removals = match get_storage_value::<
+            Vec<PendingOperatorRemoval<UncheckedPair, T::AccountId, T::BlockNumber>>,
+        >(pallet, item, &[])
+        {
+            Some(removals) => removals,
+            None => return (1, 0),
+        };
+        let count = removals.len();
+        let kept: Vec<_> = removals
+            .into_iter()
+            .filter(|removal| fits::<Len>(&removal.pair))
+            .collect();
+        if kept.len() == count {
+            return (1, 0);
+        }
+        // `BoundedVec` is encoded the same way as `Vec`.
+        put_storage_value(pallet, item, &[], kept);
+
+        (1, 1)
+    }
+
+    /// Removes the value of the given storage item if the currency pair returned by `pair` doesn't fit into `Len`.
+    /// Returns amount of performed reads and writes.
+    fn prune_cursor<V, F, Len>(pallet: &[u8], item: &[u8], pair: F) -> (u64, u64)
+    where
+        V: Decode,
+        F: FnOnce(V) -> Option<UncheckedPair>,
+        Len: Get<u32>,
+    {
+        let oversized = get_storage_value::<V>(pallet, item, &[])
+            .and_then(pair)
+            .map_or(false, |pair| !fits::<Len>(&pair));
+        if !oversized {
+            return (1, 0);
+        }
+        unhashed::kill(&storage_prefix(pallet, item));
+
+        (1, 1)
+    }
+
+    /// Decodes the requesting account from the `OperatorRequests` key suffix.
+    fn requester<T: Config, Len: Get<u32>>(key: &[u8]) -> Option<T::AccountId> {
+        let mut rest = key.get(BLAKE2_128_LEN..)?;
+        StoredPair::<Len>::decode_unchecked_len(&mut rest).ok()?;
+
+        T::AccountId::decode(&mut rest.get(BLAKE2_128_LEN..)?).ok()
+    }
+
+    /// Returns `true` if the currency pair stored in the supplied key suffix after `offset` bytes
+    /// doesn't fit into `Len`.
+    fn is_oversized<Len: Get<u32>>(key: &[u8], offset: usize) -> bool {
+        let mut rest = match key.get(offset.saturating_add(BLAKE2_128_LEN)..) {
+            Some(rest) => rest,
+            None => return false,
+        };
+
+        StoredPair::<Len>::decode_unchecked_len(&mut rest).map_or(false, |pair| !fits::<Len>(&pair))
+    }
+
+    /// Returns `true` if the currency pair fits into `Len`.
+    fn fits<Len: Get<u32>>(pair: &UncheckedPair) -> bool {
+        StoredPair::<Len>::try_from(pair.clone()).is_ok()
+    }
+}
+
+pub mod decimals {
+    use super::*;
+    use crate::pallet::{Config, PriceHistory, PricedPairsCount, Prices, PricesChanged};
+
+    use frame_support::weights::Weight;
+    use sp_std::prelude::*;
+
+    /// Truncates records of `Prices` and `PriceHistory` having more than `MaxDecimals` decimals to `MaxDecimals`,
+    /// see [`price_provider::PriceRecord::truncated_to_decimals`]. Prices truncated to zero are removed.
+    /// Should be invoked in the same upgrade which introduces or lowers `MaxDecimals`.
+    pub fn truncate_excessive_decimals<T: Config>() -> Weight {
+        trace_span!("truncate_excessive_decimals");
+        let max_decimals = T::MaxDecimals::get();
+        let exceeds = |decimals: u32| decimals > max_decimals.into();
+        let mut reads = 0u64;
+
+        let prices: Vec<_> = <Prices<T>>::iter()
+            .inspect(|_| reads = reads.saturating_add(1))
+            .filter(|(_, record)| exceeds(record.decimals()))
+            .collect();
+        let histories: Vec<_> = <PriceHistory<T>>::iter()
+            .inspect(|_| reads = reads.saturating_add(1))
+            .filter(|(_, history)| history.iter().any(|record| exceeds(record.decimals())))
+            .collect();
+        let writes = prices.len().saturating_add(histories.len()) as u64;
+
+        for (pair, record) in prices {
+            let record = record.truncated_to_decimals(max_decimals);
+
+            if record.amount() > 0 {
+                <Prices<T>>::insert(&pair, record);
+            } else {
+                <Prices<T>>::remove(&pair);
+                <PricedPairsCount<T>>::mutate(|count| *count = count.saturating_sub(1));
+            }
+        }
+        for (pair, mut history) in histories {
+            for record in history.iter_mut() {
+                *record = record.truncated_to_decimals(max_decimals);
+            }
+            history.retain(|record| record.amount() > 0);
+
+            <PriceHistory<T>>::insert(&pair, history);
+        }
+        if writes > 0 {
+            <PricesChanged<T>>::put(true);
+        }
+        trace_log!(
+            "Truncated {} records exceeding {} decimals",
+            writes,
+            max_decimals
+        );
+
+        T::DbWeight::get().reads_writes(reads, writes)
     }
 }
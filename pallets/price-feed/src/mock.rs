@@ -2,16 +2,20 @@ use crate as dock_price_feed;
 
 use frame_support::{
     parameter_types,
-    traits::{ConstU32, Everything},
+    traits::{ConstBool, ConstU16, ConstU32, ConstU64, ConstU8, Everything},
+    weights::Weight,
+    PalletId,
 };
 use frame_system as system;
 use scale_info::prelude::string::String;
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, BlockNumberProvider, IdentityLookup},
+    Perbill,
 };
-use sp_std::prelude::*;
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, prelude::*};
+use utils::{Identity, IdentityProvider};
 
 // Configure a mock runtime to test the pallet.
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -24,7 +28,9 @@ frame_support::construct_runtime!(
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Balances: balances::{Pallet, Call, Storage},
-        PriceFeedModule: dock_price_feed::{Pallet, Call, Storage, Event<T>},
+        Multisig: multisig::{Pallet, Call, Storage},
+        Proxy: proxy::{Pallet, Call, Storage},
+        PriceFeedModule: dock_price_feed::{Pallet, Call, Storage, Event<T>, Inherent},
     }
 );
 
@@ -34,6 +40,20 @@ parameter_types! {
     pub const DockChainId: u64 = 2021;
     pub const MinimumPeriod: u64 = 1000;
     pub BlockGasLimit: U256 = U256::from(u32::max_value());
+    pub const AnomalyThreshold: Perbill = Perbill::from_percent(10);
+    pub static SafeModeEntered: bool = false;
+    pub static OperatorRemovalDelay: u64 = 0;
+    pub static OperatorTimeout: u64 = 0;
+    pub static InactivityCheckPeriod: u64 = 1;
+    pub static MaxInactivityChecksPerBlock: u32 = 4;
+    pub static RelayBlockNumber: Option<u64> = None;
+    pub static MaxPricedPairs: u32 = 16;
+    pub static StandardPriceHistoryLen: u32 = 4;
+    pub static MaxContractVisitsPerBlock: u32 = 4;
+    pub static MaxRevealDeadlinesPerBlock: u32 = 16;
+    pub const RewardPotId: PalletId = PalletId(*b"dk/prcfd");
+    pub const DepositedEvents: dock_price_feed::EventKinds =
+        dock_price_feed::EventKinds::all().difference(dock_price_feed::EventKinds::OPERATOR_ADDED);
 }
 
 impl system::Config for Test {
@@ -75,6 +95,31 @@ impl balances::Config for Test {
     type MaxLocks = ();
 }
 
+impl multisig::Config for Test {
+    type Event = ();
+    type Call = Call;
+    type Currency = Balances;
+    type DepositBase = ConstU64<0>;
+    type DepositFactor = ConstU64<0>;
+    type MaxSignatories = ConstU16<3>;
+    type WeightInfo = ();
+}
+
+impl proxy::Config for Test {
+    type Event = ();
+    type Call = Call;
+    type Currency = Balances;
+    type ProxyType = ();
+    type ProxyDepositBase = ConstU64<0>;
+    type ProxyDepositFactor = ConstU64<0>;
+    type MaxProxies = ConstU32<4>;
+    type WeightInfo = ();
+    type MaxPending = ConstU32<2>;
+    type CallHasher = BlakeTwo256;
+    type AnnouncementDepositBase = ConstU64<0>;
+    type AnnouncementDepositFactor = ConstU64<0>;
+}
+
 impl timestamp::Config for Test {
     /// A timestamp: milliseconds since the unix epoch.
     type Moment = u64;
@@ -83,8 +128,151 @@ impl timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+thread_local! {
+    static OPERATOR_IDENTITIES: RefCell<BTreeMap<u64, TestIdentity>> = RefCell::new(Default::default());
+}
+
+/// Identity having the given amount of justifications.
+#[derive(Clone, Copy, Default)]
+pub struct TestIdentity(pub u32);
+
+impl Identity for TestIdentity {
+    type Info = ();
+    type Justification = ();
+
+    fn verified(&self) -> bool {
+        self.0 > 0
+    }
+
+    fn info(&self) -> Self::Info {}
+
+    fn verify(&mut self, (): ()) -> sp_runtime::DispatchResult {
+        self.0 += 1;
+
+        Ok(())
+    }
+
+    fn justification_count(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Returns `RelayBlockNumber` if set or the local block number otherwise.
+pub struct TestBlockNumberProvider;
+
+impl BlockNumberProvider for TestBlockNumberProvider {
+    type BlockNumber = u64;
+
+    fn current_block_number() -> u64 {
+        RelayBlockNumber::get().unwrap_or_else(System::block_number)
+    }
+}
+
+pub struct TestIdentityProvider;
+
+impl TestIdentityProvider {
+    pub fn set_identity(who: u64, identity: TestIdentity) {
+        OPERATOR_IDENTITIES.with(|identities| identities.borrow_mut().insert(who, identity));
+    }
+}
+
+impl IdentityProvider<Test> for TestIdentityProvider {
+    type Identity = TestIdentity;
+
+    fn identity(who: &u64) -> Option<Self::Identity> {
+        OPERATOR_IDENTITIES.with(|identities| identities.borrow().get(who).copied())
+    }
+}
+
+thread_local! {
+    static EVM_OUTPUTS: RefCell<BTreeMap<(H160, Vec<u8>), Vec<u8>>> = RefCell::new(Default::default());
+}
+
+/// Returns outputs set via `set_output` for the matching calls, and fails others.
+pub struct TestEvmCaller;
+
+impl TestEvmCaller {
+    /// Weight consumed by every call.
+    pub const CALL_WEIGHT: u64 = 1_000;
+
+    pub fn set_output(target: H160, input: Vec<u8>, output: Vec<u8>) {
+        EVM_OUTPUTS.with(|outputs| outputs.borrow_mut().insert((target, input), output));
+    }
+}
+
+impl dock_price_feed::EvmCaller for TestEvmCaller {
+    fn call(target: H160, input: &[u8]) -> (Option<Vec<u8>>, Weight) {
+        let output =
+            EVM_OUTPUTS.with(|outputs| outputs.borrow().get(&(target, input.to_vec())).cloned());
+
+        (output, Weight::from_ref_time(Self::CALL_WEIGHT))
+    }
+}
+
+thread_local! {
+    static UNREVEALED_COMMITMENTS: RefCell<Vec<(u64, u64)>> = RefCell::new(Default::default());
+}
+
+/// Records operators which didn't reveal their commitments.
+pub struct TestUnrevealedCommitments;
+
+impl TestUnrevealedCommitments {
+    /// Returns recorded `(operator, round)` pairs clearing them.
+    pub fn take() -> Vec<(u64, u64)> {
+        UNREVEALED_COMMITMENTS.with(|unrevealed| unrevealed.take())
+    }
+}
+
+impl<Pair> dock_price_feed::OnUnrevealedCommitment<Pair, u64> for TestUnrevealedCommitments {
+    fn on_unrevealed_commitment(_pair: &Pair, operator: &u64, round: u64) -> Weight {
+        UNREVEALED_COMMITMENTS.with(|unrevealed| unrevealed.borrow_mut().push((*operator, round)));
+
+        Weight::zero()
+    }
+}
+
 impl dock_price_feed::Config for Test {
     type MaxSymbolBytesLen = ConstU32<4>;
+    type AnomalyThreshold = AnomalyThreshold;
+    type PauseOnAnomaly = ConstBool<true>;
+    type MaxPriceHistoryLen = ConstU32<4>;
+    type StandardPriceHistoryLen = StandardPriceHistoryLen;
+    type EphemeralPriceHistoryLen = ConstU32<1>;
+    type MaxPriceAge = ConstU64<100>;
+    type OperatorRemovalDelay = OperatorRemovalDelay;
+    type MaxPendingOperatorRemovals = ConstU32<2>;
+    type MaxCommitmentsPerRound = ConstU32<3>;
+    type MaxRevealDeviations = ConstU32<3>;
+    type OperatorTimeout = OperatorTimeout;
+    type InactivityCheckPeriod = InactivityCheckPeriod;
+    type MaxInactivityChecksPerBlock = MaxInactivityChecksPerBlock;
+    type MaxDecimals = ConstU8<18>;
+    type RewardPerSubmission = ConstU64<5>;
+    type MaxRewardPayout = ConstU64<12>;
+    type RewardPotId = RewardPotId;
+    type DepositPriceRootDigest = ConstBool<true>;
+    type OperatorRequestDeposit = ConstU64<10>;
+    type MaxOperatorsPerPair = ConstU32<4>;
+    type EwmaHalfLife = ConstU32<10>;
+    type MaxPricedPairs = MaxPricedPairs;
+    type MaxContractVisitsPerBlock = MaxContractVisitsPerBlock;
+    type MaxRevealDeadlinesPerBlock = MaxRevealDeadlinesPerBlock;
+    type FeedTime = dock_price_feed::LocalFeedTime<Self>;
+    type BlockNumberProvider = TestBlockNumberProvider;
+    type Currency = Balances;
+    type EventPair = dock_price_feed::PlainCurrencySymbolPair;
+    type DepositedEvents = DepositedEvents;
+    type SafeMode = utils::SafeModeIf<SafeModeEntered>;
+    type HttpPriceSubmitter = ();
+    type AnomalyReportSubmitter = ();
+    type EmergencyOrigin = system::EnsureRoot<u64>;
+    type ApproveOrigin = system::EnsureRoot<u64>;
+    type PauseOrigin = system::EnsureRoot<u64>;
+    type EvmCaller = TestEvmCaller;
+    type OnUnrevealedCommitment = TestUnrevealedCommitments;
+    type PriceConsumer = u64;
+    type WeightInfo = ();
+    type OperatorIdentityProvider = TestIdentityProvider;
     type Event = ();
 }
 
@@ -0,0 +1,93 @@
+//! Key schema of the price updates written into the offchain index.
+//!
+//! Nodes running with `--enable-offchain-indexing` will have every price update stored in the persistent
+//! offchain storage as SCALE-encoded `OffchainPriceEntry` under the key
+//! `OFFCHAIN_INDEX_PREFIX ++ blake2_256(pair) ++ block_number`, where `pair` is the SCALE-encoded
+//! `CurrencySymbolPair<String, String>` and `block_number` is the SCALE-encoded number of the block containing
+//! the update. The last update within a block overrides previous ones.
+//!
+//! Block number of the latest update for each pair is stored under
+//! `OFFCHAIN_INDEX_PREFIX ++ blake2_256(pair) ++ LATEST_KEY_SUFFIX`. It's cleared once the price is removed
+//! via `force_clear_price`, while the entries of the previous updates are kept.
+
+use codec::{Decode, Encode};
+use price_provider::PriceRecord;
+use scale_info::TypeInfo;
+use sp_std::prelude::*;
+
+/// Prefix of all keys written by the price feed into the offchain index.
+pub const OFFCHAIN_INDEX_PREFIX: &[u8] = b"dock-price-feed/price/";
+
+/// Suffix of the key storing block number of the latest update for a pair.
+pub const LATEST_KEY_SUFFIX: &[u8] = b"/latest";
+
+/// Compact price update entry written into the offchain index.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OffchainPriceEntry<BlockNumber> {
+    /// `blake2_256` hash of the SCALE-encoded currency pair.
+    pub pair_hash: [u8; 32],
+    /// Number of the block containing the update.
+    pub block_number: BlockNumber,
+    /// Raw price amount. This value should be divided by 10^decimals to get a price per 1 unit.
+    pub amount: u64,
+    /// Price precision.
+    pub decimals: u8,
+}
+
+impl<BlockNumber: Copy> OffchainPriceEntry<BlockNumber> {
+    /// Instantiates new entry using supplied pair hash and price record.
+    pub fn new(pair_hash: [u8; 32], record: &PriceRecord<BlockNumber>) -> Self {
+        Self {
+            pair_hash,
+            block_number: record.block_number(),
+            amount: record.amount(),
+            decimals: record.decimals() as u8,
+        }
+    }
+
+    /// Converts entry to the `PriceRecord`.
+    pub fn record(&self) -> PriceRecord<BlockNumber> {
+        PriceRecord::new(self.amount, self.decimals, self.block_number)
+    }
+}
+
+/// Returns `blake2_256` hash of the SCALE-encoded currency pair.
+pub fn pair_hash<P: Encode>(pair: &P) -> [u8; 32] {
+    pair.using_encoded(sp_io::hashing::blake2_256)
+}
+
+/// Returns prefix of all keys related to the given pair hash.
+pub fn pair_prefix(pair_hash: &[u8; 32]) -> Vec<u8> {
+    [OFFCHAIN_INDEX_PREFIX, &pair_hash[..]].concat()
+}
+
+/// Returns key of the entry for the given pair hash and block number.
+pub fn entry_key<BlockNumber: Encode>(pair_hash: &[u8; 32], block_number: &BlockNumber) -> Vec<u8> {
+    let mut key = pair_prefix(pair_hash);
+    block_number.encode_to(&mut key);
+
+    key
+}
+
+/// Returns key of the latest update block number for the given pair hash.
+pub fn latest_key(pair_hash: &[u8; 32]) -> Vec<u8> {
+    [&pair_prefix(pair_hash)[..], LATEST_KEY_SUFFIX].concat()
+}
+
+/// Writes supplied price record for the given pair into the offchain index.
+pub(crate) fn index_price_update<P, BlockNumber>(pair: &P, record: &PriceRecord<BlockNumber>)
+where
+    P: Encode,
+    BlockNumber: Encode + Copy,
+{
+    let pair_hash = pair_hash(pair);
+    let entry = OffchainPriceEntry::new(pair_hash, record);
+
+    sp_io::offchain_index::set(&entry_key(&pair_hash, &entry.block_number), &entry.encode());
+    sp_io::offchain_index::set(&latest_key(&pair_hash), &entry.block_number.encode());
+}
+
+/// Clears block number of the latest update for the given pair once its price is removed.
+pub(crate) fn index_price_removal<P: Encode>(pair: &P) {
+    sp_io::offchain_index::clear(&latest_key(&pair_hash(pair)));
+}
@@ -0,0 +1,16 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Operator removal scheduled by `remove_operator` which takes effect at the beginning of the block `at`.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PendingOperatorRemoval<Pair, AccountId, BlockNumber> {
+    /// Currency pair the operator will be removed for.
+    pub pair: Pair,
+    /// Operator to be removed.
+    pub operator: AccountId,
+    /// Block at which the removal takes effect.
+    pub at: BlockNumber,
+}
@@ -0,0 +1,38 @@
+//! Origin checks built atop of the price feed operators.
+
+use core::marker::PhantomData;
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use price_provider::CurrencySymbolPair;
+
+use crate::{Config, Pallet};
+
+/// Ensures that the origin is signed by an operator of the currency pair provided by `Pair`.
+/// Succeeds with the operator's account.
+///
+/// Can be used by other pallets to gate their extrinsics on the price operators, e.g.
+/// `type UpdateOrigin = EnsureOperatorFor<Runtime, StaticCurrencySymbolPair<DockSym, UsdSym>>;`.
+pub struct EnsureOperatorFor<T, Pair>(PhantomData<(T, Pair)>);
+
+impl<T, Pair, O> EnsureOrigin<O> for EnsureOperatorFor<T, Pair>
+where
+    T: Config,
+    Pair: Get<CurrencySymbolPair<&'static str, &'static str>>,
+    O: Into<Result<RawOrigin<T::AccountId>, O>> + From<RawOrigin<T::AccountId>>,
+{
+    type Success = T::AccountId;
+
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            RawOrigin::Signed(who) if Pallet::<T>::is_operator(Pair::get(), &who) => Ok(who),
+            r => Err(O::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<O, ()> {
+        Pallet::<T>::first_operator(Pair::get())
+            .map(|who| O::from(RawOrigin::Signed(who)))
+            .ok_or(())
+    }
+}
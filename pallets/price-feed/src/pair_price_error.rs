@@ -0,0 +1,37 @@
+use price_provider::BoundedStringConversionError;
+use sp_runtime::DispatchError;
+
+/// Error returned by the pallet's `PriceProvider::pair_price`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PairPriceError {
+    /// The currency pair symbols exceed `MaxSymbolBytesLen`.
+    InvalidStringByteLen,
+    /// Price updates of the currency pair are paused individually or globally, so its stored price
+    /// can't be relied upon and consumers should fall back.
+    Paused,
+}
+
+impl From<BoundedStringConversionError> for PairPriceError {
+    fn from(
+        BoundedStringConversionError::InvalidStringByteLen: BoundedStringConversionError,
+    ) -> Self {
+        Self::InvalidStringByteLen
+    }
+}
+
+impl From<PairPriceError> for &'static str {
+    fn from(error: PairPriceError) -> Self {
+        match error {
+            PairPriceError::InvalidStringByteLen => {
+                BoundedStringConversionError::InvalidStringByteLen.into()
+            }
+            PairPriceError::Paused => "Price updates of the currency pair are paused",
+        }
+    }
+}
+
+impl From<PairPriceError> for DispatchError {
+    fn from(error: PairPriceError) -> Self {
+        DispatchError::Other(error.into())
+    }
+}
@@ -0,0 +1,135 @@
+//! Binary merkle commitment of all current prices.
+//!
+//! Leaves are hashes of the encoded `(pair, record)` entries of `Prices` taken in the storage iteration order.
+//! Each parent node is the hash of the concatenated child hashes. A node without a sibling is promoted
+//! to the next level as is. The root of an empty tree is the default hash.
+//!
+//! If `Config::DepositPriceRootDigest` is enabled, each recomputed root is also deposited into the block digest
+//! as a [`PRICE_ROOT_ENGINE_ID`] consensus item, so light clients following only the headers can detect
+//! the blocks where the feed contents changed, see [`price_root_from_digest`].
+
+use codec::{Decode, Encode};
+use sp_runtime::{traits::Hash, ConsensusEngineId, Digest, DigestItem};
+use sp_std::prelude::*;
+
+/// Engine identifier of the digest items carrying the price roots.
+pub const PRICE_ROOT_ENGINE_ID: ConsensusEngineId = *b"dkpf";
+
+/// Merkle proof of a single leaf.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MerkleProof<Output> {
+    /// Index of the leaf.
+    pub index: u32,
+    /// Total amount of leaves.
+    pub leaves: u32,
+    /// Sibling hashes from the leaf level up to the root. Levels where the node has no sibling are skipped.
+    pub siblings: Vec<Output>,
+}
+
+/// Returns the digest item carrying the price root along with the number of the block which final state it commits to.
+pub fn price_root_digest_item<BlockNumber: Encode, Output: Encode>(
+    block_number: BlockNumber,
+    root: Output,
+) -> DigestItem {
+    DigestItem::Consensus(PRICE_ROOT_ENGINE_ID, (block_number, root).encode())
+}
+
+/// Returns the price root and the number of the block it commits to from the header digest.
+/// `None` means that the feed contents weren't changed in the previous block or the digest items are disabled.
+pub fn price_root_from_digest<BlockNumber: Decode, Output: Decode>(
+    digest: &Digest,
+) -> Option<(BlockNumber, Output)> {
+    digest.convert_first(|item| item.consensus_try_to(&PRICE_ROOT_ENGINE_ID))
+}
+
+/// Hashes two child nodes into a parent one.
+fn hash_node<H: Hash>(left: &H::Output, right: &H::Output) -> H::Output {
+    H::hash(&[left.as_ref(), right.as_ref()].concat())
+}
+
+/// Computes the next level of the tree.
+fn next_level<H: Hash>(level: &[H::Output]) -> Vec<H::Output> {
+    level
+        .chunks(2)
+        .map(|nodes| match nodes {
+            [left, right] => hash_node::<H>(left, right),
+            [single] => *single,
+            _ => unreachable!("Chunks contain either one or two nodes"),
+        })
+        .collect()
+}
+
+/// Computes the merkle root of the supplied leaves.
+pub fn merkle_root<H: Hash>(mut leaves: Vec<H::Output>) -> H::Output {
+    while leaves.len() > 1 {
+        leaves = next_level::<H>(&leaves);
+    }
+
+    leaves.pop().unwrap_or_default()
+}
+
+/// Builds a proof of the leaf with the given index. Returns `None` if the index is out of bounds.
+pub fn merkle_proof<H: Hash>(
+    mut leaves: Vec<H::Output>,
+    index: u32,
+) -> Option<MerkleProof<H::Output>> {
+    let leaves_count = leaves.len() as u32;
+    if index >= leaves_count {
+        return None;
+    }
+
+    let mut siblings = Vec::new();
+    let mut position = index as usize;
+    while leaves.len() > 1 {
+        if let Some(sibling) = leaves.get(position ^ 1) {
+            siblings.push(*sibling);
+        }
+
+        leaves = next_level::<H>(&leaves);
+        position /= 2;
+    }
+
+    Some(MerkleProof {
+        index,
+        leaves: leaves_count,
+        siblings,
+    })
+}
+
+/// Returns `true` if the proof shows that the leaf is a part of the tree with the given root.
+pub fn verify_proof<H: Hash>(
+    root: &H::Output,
+    leaf: H::Output,
+    MerkleProof {
+        index,
+        leaves,
+        siblings,
+    }: &MerkleProof<H::Output>,
+) -> bool {
+    if index >= leaves {
+        return false;
+    }
+
+    let (mut node, mut position, mut width) = (leaf, *index, *leaves);
+    let mut siblings = siblings.iter();
+    while width > 1 {
+        let sibling_position = position ^ 1;
+        if sibling_position < width {
+            let sibling = match siblings.next() {
+                Some(sibling) => sibling,
+                None => return false,
+            };
+
+            node = if position % 2 == 0 {
+                hash_node::<H>(&node, sibling)
+            } else {
+                hash_node::<H>(sibling, &node)
+            };
+        }
+
+        position /= 2;
+        width = (width + 1) / 2;
+    }
+
+    siblings.next().is_none() && node == *root
+}
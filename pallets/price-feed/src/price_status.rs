@@ -0,0 +1,49 @@
+use codec::{Decode, Encode};
+use price_provider::PriceRecord;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Saturating;
+
+/// Price record along with its staleness according to `Config::MaxPriceAge`.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PriceStatus<BlockNumber> {
+    /// The price isn't older than `MaxPriceAge`.
+    Fresh(PriceRecord<BlockNumber>),
+    /// The price is older than `MaxPriceAge`, so it may not reflect the market anymore.
+    Stale(PriceRecord<BlockNumber>),
+}
+
+impl<BlockNumber: Saturating + PartialOrd + Copy> PriceStatus<BlockNumber> {
+    /// Checks the age of the record at the block `now`.
+    pub fn new(record: PriceRecord<BlockNumber>, now: BlockNumber, max_age: BlockNumber) -> Self {
+        if now.saturating_sub(record.block_number()) > max_age {
+            Self::Stale(record)
+        } else {
+            Self::Fresh(record)
+        }
+    }
+}
+
+impl<BlockNumber> PriceStatus<BlockNumber> {
+    /// Returns the underlying price record regardless of its staleness.
+    pub fn record(&self) -> &PriceRecord<BlockNumber> {
+        match self {
+            Self::Fresh(record) | Self::Stale(record) => record,
+        }
+    }
+
+    /// Returns `true` if the price is stale.
+    pub fn is_stale(&self) -> bool {
+        matches!(self, Self::Stale(_))
+    }
+
+    /// Returns the record if the price is fresh.
+    pub fn fresh(self) -> Option<PriceRecord<BlockNumber>> {
+        match self {
+            Self::Fresh(record) => Some(record),
+            Self::Stale(_) => None,
+        }
+    }
+}
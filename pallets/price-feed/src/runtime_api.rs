@@ -1,10 +1,84 @@
 use codec::{Decode, Encode};
 use price_provider::{CurrencySymbolPair, PriceRecord};
 use scale_info::prelude::string::String;
+use sp_std::prelude::*;
+use utils::{PageCursor, Paged};
+
+use crate::{GenesisSnapshot, OperatorOverview, PriceStatus};
+
+/// First version of [`PriceFeedApi`] providing the methods other than `price`.
+/// Clients talking to the runtimes of the older versions have to fall back to the `price` calls.
+pub const EXTENDED_QUERIES_API_VERSION: u32 = 2;
 
 sp_api::decl_runtime_apis! {
+    /// Methods other than `price` are available since [`EXTENDED_QUERIES_API_VERSION`].
+    #[api_version(2)]
     pub trait PriceFeedApi<T: Encode + Decode> {
         /// Gets the price of the given pair from pallet's storage
         fn price(pair: CurrencySymbolPair<String, String>) -> Option<PriceRecord<T>>;
+
+        /// Gets the price of the given pair along with its staleness according to `MaxPriceAge`.
+        fn price_status(pair: CurrencySymbolPair<String, String>) -> Option<PriceStatus<T>>;
+
+        /// Gets the storage key of the given pair's price record, so it can be used to build a storage proof.
+        /// Returns `None` if the pair symbols exceed the max allowed length.
+        fn price_storage_key(pair: CurrencySymbolPair<String, String>) -> Option<Vec<u8>>;
+
+        /// Gets market capitalization of the chain's currency using the price of the given pair.
+        /// The result has the same precision as the currency's total issuance.
+        fn market_cap(pair: CurrencySymbolPair<String, String>) -> Option<u128>;
+
+        /// Gets a page of the currency pairs having a price starting after the given cursor.
+        fn pairs(cursor: Option<PageCursor>, limit: u32) -> Paged<CurrencySymbolPair<String, String>>;
+
+        /// Gets a page of the currency pairs along with their prices starting after the given cursor.
+        fn all_prices(
+            cursor: Option<PageCursor>,
+            limit: u32
+        ) -> Paged<(CurrencySymbolPair<String, String>, PriceRecord<T>)>;
+
+        /// Gets prices of the given pairs in the requested order.
+        fn prices(pairs: Vec<CurrencySymbolPair<String, String>>) -> Vec<Option<PriceRecord<T>>>;
+
+        /// Gets the latest price records of the given pair ordered by block number.
+        fn price_history(pair: CurrencySymbolPair<String, String>) -> Vec<PriceRecord<T>>;
+
+        /// Converts the amount of the pair's `from` currency units into the amount of its `to` currency units
+        /// using the pair's price. The result has the same precision as the supplied amount.
+        /// Returns `None` if the price is missing or calculation overflows.
+        fn convert(pair: CurrencySymbolPair<String, String>, amount: u128) -> Option<u128>;
+
+        /// Gets the exponentially-weighted moving average price of the given pair.
+        fn ewma(pair: CurrencySymbolPair<String, String>) -> Option<PriceRecord<T>>;
+    }
+
+    pub trait PriceFeedGenesisApi<AccountId: Encode + Decode, BlockNumber: Encode + Decode> {
+        /// Exports operators and prices of all currency pairs to be used as a genesis of a new chain.
+        fn export_genesis_config() -> GenesisSnapshot<AccountId, BlockNumber>;
+    }
+
+    pub trait PriceFeedRewardsApi<AccountId: Encode + Decode, Balance: Encode + Decode> {
+        /// Gets a page of the operators along with their rewards which can be claimed via `claim_rewards`
+        /// starting after the given cursor.
+        fn claimable_rewards(cursor: Option<PageCursor>, limit: u32) -> Paged<(AccountId, Balance)>;
+    }
+
+    pub trait PriceFeedOperatorsApi<AccountId: Encode + Decode> {
+        /// Gets a page of the given currency pair's operators starting after the given cursor.
+        /// The cursor is the encoded last operator of the previous page.
+        /// Returns `None` if the pair symbols exceed the max allowed length.
+        fn operators(
+            pair: CurrencySymbolPair<String, String>,
+            cursor: Option<PageCursor>,
+            limit: u32
+        ) -> Option<Paged<AccountId>>;
+
+        /// Gets a page of the operators along with the currency pairs they control, sorted by the amount of pairs
+        /// (descending), starting after the given cursor.
+        fn operators_overview(cursor: Option<PageCursor>, limit: u32) -> Paged<OperatorOverview<AccountId>>;
+
+        /// Gets all operators of the given currency pair in ascending order.
+        /// Returns `None` if the pair symbols exceed the max allowed length.
+        fn operators_of(pair: CurrencySymbolPair<String, String>) -> Option<Vec<AccountId>>;
     }
 }
@@ -0,0 +1,184 @@
+//! Structured diff of two price feed state exports.
+//!
+//! Takes two SCALE-encoded [`GenesisSnapshot`]s, e.g. produced by `export_genesis_config` before and after
+//! a runtime upgrade in `try-runtime`, and reports added/removed pairs, price changes and operator changes,
+//! so migration effects can be reviewed without comparing raw storage.
+
+use codec::{Decode, Encode};
+use core::hash::Hash;
+use price_provider::{CurrencySymbolPair, PriceRecord};
+use scale_info::prelude::string::String;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::GenesisSnapshot;
+
+type Pair = CurrencySymbolPair<String, String>;
+
+/// Change of the currency pair price.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PriceChange<BlockNumber> {
+    /// Currency pair.
+    pub pair: Pair,
+    /// Price before the change.
+    pub before: PriceRecord<BlockNumber>,
+    /// Price after the change.
+    pub after: PriceRecord<BlockNumber>,
+}
+
+impl<BlockNumber> PriceChange<BlockNumber> {
+    /// Returns the difference between the raw amounts normalized to the max decimals of both records,
+    /// along with these decimals. Returns `None` if the normalized amounts overflow.
+    pub fn delta(&self) -> Option<(i128, u8)> {
+        let decimals = self.before.decimals().max(self.after.decimals());
+        let normalize = |record: &PriceRecord<BlockNumber>| {
+            10i128
+                .checked_pow((decimals - record.decimals()).into())?
+                .checked_mul(record.amount().into())
+        };
+
+        Some((
+            normalize(&self.after)?.checked_sub(normalize(&self.before)?)?,
+            decimals,
+        ))
+    }
+}
+
+/// Differences between two price feed state exports.
+/// All lists are sorted by the currency pair.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct SnapshotDiff<AccountId, BlockNumber> {
+    /// Pairs having a price or operators only after the change.
+    pub pairs_added: Vec<Pair>,
+    /// Pairs having a price or operators only before the change.
+    pub pairs_removed: Vec<Pair>,
+    /// Prices which were added.
+    pub prices_added: Vec<(Pair, PriceRecord<BlockNumber>)>,
+    /// Prices which were removed.
+    pub prices_removed: Vec<(Pair, PriceRecord<BlockNumber>)>,
+    /// Prices which exist both before and after the change but differ.
+    pub price_changes: Vec<PriceChange<BlockNumber>>,
+    /// Operators which were added.
+    pub operators_added: Vec<(Pair, AccountId)>,
+    /// Operators which were removed.
+    pub operators_removed: Vec<(Pair, AccountId)>,
+}
+
+impl<AccountId, BlockNumber> SnapshotDiff<AccountId, BlockNumber>
+where
+    AccountId: Clone + Eq + Hash + Ord,
+    BlockNumber: Clone + PartialEq,
+{
+    /// Computes the diff between `before` and `after` snapshots.
+    pub fn new(
+        before: &GenesisSnapshot<AccountId, BlockNumber>,
+        after: &GenesisSnapshot<AccountId, BlockNumber>,
+    ) -> Self {
+        let pairs_of = |snapshot: &GenesisSnapshot<AccountId, BlockNumber>| -> HashSet<Pair> {
+            snapshot
+                .operators
+                .iter()
+                .map(|(pair, _)| pair)
+                .chain(snapshot.prices.iter().map(|(pair, _)| pair))
+                .cloned()
+                .collect()
+        };
+        let (pairs_before, pairs_after) = (pairs_of(before), pairs_of(after));
+        let (prices_before, prices_after): (HashMap<_, _>, HashMap<_, _>) = (
+            before.prices.iter().cloned().collect(),
+            after.prices.iter().cloned().collect(),
+        );
+        let (operators_before, operators_after): (HashSet<_>, HashSet<_>) = (
+            before.operators.iter().cloned().collect(),
+            after.operators.iter().cloned().collect(),
+        );
+
+        let mut price_changes: Vec<_> = prices_before
+            .iter()
+            .filter_map(|(pair, before)| {
+                let after = prices_after.get(pair)?;
+
+                (before != after).then(|| PriceChange {
+                    pair: pair.clone(),
+                    before: before.clone(),
+                    after: after.clone(),
+                })
+            })
+            .collect();
+        price_changes.sort_by_cached_key(|change| change.pair.to_string());
+
+        Self {
+            pairs_added: sorted_pairs(pairs_after.difference(&pairs_before).cloned()),
+            pairs_removed: sorted_pairs(pairs_before.difference(&pairs_after).cloned()),
+            prices_added: sorted_by_pair(
+                prices_after
+                    .iter()
+                    .filter(|(pair, _)| !prices_before.contains_key(pair))
+                    .map(|(pair, price)| (pair.clone(), price.clone())),
+            ),
+            prices_removed: sorted_by_pair(
+                prices_before
+                    .iter()
+                    .filter(|(pair, _)| !prices_after.contains_key(pair))
+                    .map(|(pair, price)| (pair.clone(), price.clone())),
+            ),
+            price_changes,
+            operators_added: sorted_operators(
+                operators_after.difference(&operators_before).cloned(),
+            ),
+            operators_removed: sorted_operators(
+                operators_before.difference(&operators_after).cloned(),
+            ),
+        }
+    }
+
+    /// Returns `true` if both snapshots have the same state.
+    pub fn is_empty(&self) -> bool {
+        self.pairs_added.is_empty()
+            && self.pairs_removed.is_empty()
+            && self.prices_added.is_empty()
+            && self.prices_removed.is_empty()
+            && self.price_changes.is_empty()
+            && self.operators_added.is_empty()
+            && self.operators_removed.is_empty()
+    }
+}
+
+impl<AccountId, BlockNumber> SnapshotDiff<AccountId, BlockNumber>
+where
+    AccountId: Clone + Eq + Hash + Ord + Decode,
+    BlockNumber: Clone + PartialEq + Decode,
+{
+    /// Decodes both SCALE-encoded snapshots and computes the diff between them.
+    pub fn from_encoded(mut before: &[u8], mut after: &[u8]) -> Result<Self, codec::Error> {
+        let before = GenesisSnapshot::decode(&mut before)?;
+        let after = GenesisSnapshot::decode(&mut after)?;
+
+        Ok(Self::new(&before, &after))
+    }
+}
+
+fn sorted_pairs(pairs: impl Iterator<Item = Pair>) -> Vec<Pair> {
+    let mut pairs: Vec<_> = pairs.collect();
+    pairs.sort_by_cached_key(ToString::to_string);
+
+    pairs
+}
+
+fn sorted_by_pair<V>(items: impl Iterator<Item = (Pair, V)>) -> Vec<(Pair, V)> {
+    let mut items: Vec<_> = items.collect();
+    items.sort_by_cached_key(|(pair, _)| pair.to_string());
+
+    items
+}
+
+fn sorted_operators<AccountId: Ord>(
+    items: impl Iterator<Item = (Pair, AccountId)>,
+) -> Vec<(Pair, AccountId)> {
+    let mut items: Vec<_> = items.collect();
+    items.sort_by(|(pair_a, operator_a), (pair_b, operator_b)| {
+        (pair_a.to_string(), operator_a).cmp(&(pair_b.to_string(), operator_b))
+    });
+
+    items
+}
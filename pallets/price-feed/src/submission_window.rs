@@ -0,0 +1,58 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Window of each round during which the operators can submit prices for the currency pair.
+/// Rounds last `period` blocks each starting from the block `0`, so round `n` covers blocks
+/// `n * period..(n + 1) * period`, and its window covers blocks `n * period + start_offset..n * period + start_offset + length`.
+/// The round is closed once its window ends, so every operator either submitted within the round or missed it.
+#[derive(Encode, Decode, TypeInfo, Clone, Copy, PartialEq, Eq, Debug, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SubmissionWindow {
+    /// Length of the round in blocks.
+    pub period: u32,
+    /// Offset of the window from the round start in blocks.
+    pub start_offset: u32,
+    /// Length of the window in blocks.
+    pub length: u32,
+}
+
+impl SubmissionWindow {
+    /// Instantiates new `SubmissionWindow`. Returns `None` if the window doesn't fit into the round.
+    pub fn new(period: u32, start_offset: u32, length: u32) -> Option<Self> {
+        let window = Self {
+            period,
+            start_offset,
+            length,
+        };
+
+        window.is_valid().then_some(window)
+    }
+
+    /// Returns `true` if the non-empty window fits into the round.
+    pub fn is_valid(&self) -> bool {
+        self.length > 0
+            && self
+                .start_offset
+                .checked_add(self.length)
+                .map_or(false, |end| end <= self.period)
+    }
+
+    /// Returns index of the round containing the given block.
+    pub fn round_of(&self, block_number: u64) -> u64 {
+        block_number
+            .checked_div(self.period.into())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the given block lies within the window of its round.
+    pub fn contains(&self, block_number: u64) -> bool {
+        block_number
+            .checked_rem(self.period.into())
+            .map_or(false, |offset| {
+                offset >= self.start_offset.into()
+                    && offset < u64::from(self.start_offset) + u64::from(self.length)
+            })
+    }
+}
@@ -2,14 +2,15 @@ use frame_support::{
     assert_noop, assert_ok, parameter_types,
     traits::{ConstU32, Get},
 };
-use price_provider::{
-    currency_pair::StaticCurrencySymbolPair, BoundedCurrencySymbolPair,
-    BoundedStringConversionError, CurrencySymbolPair, PriceProvider, PriceRecord,
-};
+use price_provider::{currency_pair::StaticCurrencySymbolPair, prelude::*};
 use sp_runtime::{traits::CheckedConversion, DispatchError};
 use sp_std::borrow::ToOwned;
 
-use crate::{mock::*, Error, Prices};
+use crate::{
+    migrations::rebound::{rebound_storage, ReboundStorageError},
+    mock::*,
+    Error, PairPriceError, Prices,
+};
 
 #[test]
 fn add_and_remove_operator() {
@@ -148,7 +149,9 @@ fn set_price() {
             Origin::signed(1),
             CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned),
             1,
-            1
+            1,
+            None,
+            None
         )
         .is_err());
 
@@ -163,7 +166,9 @@ fn set_price() {
             Origin::signed(1),
             CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned),
             10,
-            1
+            1,
+            None,
+            None
         )
         .is_ok());
         assert_eq!(
@@ -175,12 +180,42 @@ fn set_price() {
             .unwrap(),
             PriceRecord::new(10, 1, 0)
         );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned),
+            12,
+            1,
+            Some(3),
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::price(
+                CurrencySymbolPair::new("A", "B")
+                    .checked_into::<BoundedCurrencySymbolPair<_, _, _>>()
+                    .unwrap()
+            )
+            .unwrap(),
+            PriceRecord::new(12, 1, 0).with_confidence(Some(3))
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(
+                Origin::signed(1),
+                CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned),
+                0,
+                1,
+                None,
+                None
+            ),
+            Error::<Test>::ZeroPriceAmount
+        );
         assert_noop!(
             PriceFeedModule::set_price(
                 Origin::signed(1),
                 CurrencySymbolPair::new("B", "C").map_pair(ToOwned::to_owned),
                 1,
-                1
+                1,
+                None,
+                None
             ),
             Error::<Test>::NotAnOperator
         );
@@ -189,7 +224,9 @@ fn set_price() {
                 Origin::signed(2),
                 CurrencySymbolPair::new("B", "C").map_pair(ToOwned::to_owned),
                 1,
-                1
+                1,
+                None,
+                None
             ),
             Error::<Test>::NotAnOperator
         );
@@ -205,7 +242,9 @@ fn set_price() {
             Origin::signed(2),
             CurrencySymbolPair::new("B", "C").map_pair(ToOwned::to_owned),
             1,
-            1
+            1,
+            None,
+            None
         ));
         assert_ok!(PriceFeedModule::remove_operator(
             Origin::root(),
@@ -217,7 +256,9 @@ fn set_price() {
                 Origin::signed(2),
                 CurrencySymbolPair::new("B", "C").map_pair(ToOwned::to_owned),
                 1,
-                1
+                1,
+                None,
+                None
             ),
             Error::<Test>::NotAnOperator
         );
@@ -233,11 +274,11 @@ fn price_provider() {
         );
         assert_eq!(
             PriceFeedModule::pair_price(CurrencySymbolPair::new("ABCDE", "B")),
-            Err(BoundedStringConversionError::InvalidStringByteLen)
+            Err(PairPriceError::InvalidStringByteLen)
         );
         assert_eq!(
             PriceFeedModule::pair_price(CurrencySymbolPair::new("A", "BCDEF")),
-            Err(BoundedStringConversionError::InvalidStringByteLen)
+            Err(PairPriceError::InvalidStringByteLen)
         );
     });
 }
@@ -280,11 +321,11 @@ fn dock_price_provider() {
         );
         assert_eq!(
             <PriceFeedModule as StaticPriceProvider<Test, LargeSymUsdPair>>::price(),
-            Err(BoundedStringConversionError::InvalidStringByteLen)
+            Err(PairPriceError::InvalidStringByteLen)
         );
         assert_eq!(
             <PriceFeedModule as StaticPriceProvider<Test, UsdLargeCurrencySymbolPair>>::price(),
-            Err(BoundedStringConversionError::InvalidStringByteLen)
+            Err(PairPriceError::InvalidStringByteLen)
         );
 
         Prices::<Test>::insert(
@@ -300,3 +341,4465 @@ fn dock_price_provider() {
         );
     })
 }
+
+#[test]
+fn rebound_storage_to_new_len() {
+    new_test_ext().execute_with(|| {
+        for (pair, operator) in [(("A", "B"), 1), (("ABCD", "B"), 2)] {
+            let pair = CurrencySymbolPair::from(pair).map_pair(ToOwned::to_owned);
+
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(operator),
+                pair,
+                operator * 10,
+                2,
+                None,
+                None
+            ));
+        }
+
+        let prices = Prices::<Test>::iter().collect::<Vec<_>>();
+        let operators = crate::OperatorSets::<Test>::iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<4>, ConstU32<3>>(),
+            Err(ReboundStorageError::PairDoesNotFit(
+                CurrencySymbolPair::new("ABCD", "B").map_pair(ToOwned::to_owned)
+            ))
+        );
+        assert_eq!(Prices::<Test>::iter().collect::<Vec<_>>(), prices);
+        assert_eq!(
+            crate::OperatorSets::<Test>::iter().collect::<Vec<_>>(),
+            operators
+        );
+
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<3>, ConstU32<4>>(),
+            Err(ReboundStorageError::InvalidStoredKey(
+                Prices::<Test>::hashed_key_for(
+                    CurrencySymbolPair::new("ABCD", "B")
+                        .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                        .unwrap()
+                )[32..]
+                    .to_vec()
+            ))
+        );
+
+        assert!(rebound_storage::<Test, ConstU32<4>, ConstU32<5>>().is_ok());
+        assert_eq!(Prices::<Test>::iter().collect::<Vec<_>>(), prices);
+        assert_eq!(
+            crate::OperatorSets::<Test>::iter().collect::<Vec<_>>(),
+            operators
+        );
+
+        crate::migrations::prune::prune_oversized_pairs::<Test, ConstU32<3>>();
+        assert!(rebound_storage::<Test, ConstU32<4>, ConstU32<3>>().is_ok());
+
+        // Oversized pairs are found in any pair keyed item.
+        let long_pair = CurrencySymbolPair::new("ABCD", "B")
+            .map_pair(ToOwned::to_owned)
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        crate::FeedSpecHashes::<Test>::insert(&long_pair, sp_core::H256::zero());
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<4>, ConstU32<3>>(),
+            Err(ReboundStorageError::PairDoesNotFit(
+                long_pair.clone().into()
+            ))
+        );
+        crate::FeedSpecHashes::<Test>::remove(&long_pair);
+        assert!(rebound_storage::<Test, ConstU32<4>, ConstU32<3>>().is_ok());
+
+        // ... as well as in the values embedding them.
+        crate::ContractVisitCursor::<Test>::put(&long_pair);
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<4>, ConstU32<3>>(),
+            Err(ReboundStorageError::PairDoesNotFit(
+                long_pair.clone().into()
+            ))
+        );
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<3>, ConstU32<4>>(),
+            Err(ReboundStorageError::InvalidStoredValue(
+                b"ContractVisitCursor".to_vec()
+            ))
+        );
+        crate::ContractVisitCursor::<Test>::kill();
+        assert_eq!(
+            PriceFeedModule::price(
+                CurrencySymbolPair::new("A", "B")
+                    .checked_into::<BoundedCurrencySymbolPair<_, _, _>>()
+                    .unwrap()
+            ),
+            Some(PriceRecord::new(10, 2, 0))
+        );
+    })
+}
+
+#[test]
+fn next_fee_multiplier() {
+    use crate::fee_multiplier::{NextFeeMultiplierProvider, GAS_SYMBOL};
+    use pallet_transaction_payment::Multiplier;
+    use sp_runtime::{traits::Convert, FixedPointNumber};
+
+    parameter_types! {
+        pub const DockSym: &'static str = "DOCK";
+        pub MinMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10);
+    }
+
+    type FeeMultiplier = NextFeeMultiplierProvider<Test, DockSym, MinMultiplier>;
+
+    new_test_ext().execute_with(|| {
+        let previous = Multiplier::saturating_from_integer(2);
+        assert_eq!(FeeMultiplier::convert(previous), previous);
+        assert_eq!(
+            FeeMultiplier::convert(Multiplier::from_inner(1)),
+            MinMultiplier::get()
+        );
+
+        let gas_pair = CurrencySymbolPair::new(GAS_SYMBOL, "DOCK");
+        assert_eq!(FeeMultiplier::pair(), gas_pair);
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            gas_pair.clone().map_pair(ToOwned::to_owned),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            gas_pair.clone().map_pair(ToOwned::to_owned),
+            125,
+            2,
+            None,
+            None
+        ));
+        assert_eq!(
+            FeeMultiplier::convert(previous),
+            Multiplier::saturating_from_rational(125, 100)
+        );
+
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            gas_pair.clone().map_pair(ToOwned::to_owned),
+            5,
+            2,
+            None,
+            None
+        ));
+        assert_eq!(FeeMultiplier::convert(previous), MinMultiplier::get());
+
+        assert_noop!(
+            PriceFeedModule::set_price(
+                Origin::signed(1),
+                gas_pair.clone().map_pair(ToOwned::to_owned),
+                5,
+                u8::MAX,
+                None,
+                None
+            ),
+            Error::<Test>::TooManyPriceDecimals
+        );
+        // Records stored before `MaxDecimals` was introduced can still have any decimals.
+        crate::Prices::<Test>::insert(
+            gas_pair
+                .map_pair(ToOwned::to_owned)
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap(),
+            PriceRecord::new(5, u8::MAX, 0),
+        );
+        assert_eq!(FeeMultiplier::convert(previous), previous);
+    })
+}
+
+#[test]
+fn multisig_operator() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let multisig_operator = Multisig::multi_account_id(&[1, 2], 1);
+        let set_price_call = |price| {
+            Box::new(Call::PriceFeedModule(crate::Call::set_price {
+                currency_pair: pair.clone(),
+                price,
+                decimals: 1,
+                confidence: None,
+                source: None,
+            }))
+        };
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            multisig_operator
+        ));
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 1, None, None),
+            Error::<Test>::NotAnOperator
+        );
+
+        assert_ok!(Multisig::as_multi_threshold_1(
+            Origin::signed(1),
+            vec![2],
+            set_price_call(10)
+        ));
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+
+        assert_ok!(Multisig::as_multi_threshold_1(
+            Origin::signed(2),
+            vec![1],
+            set_price_call(20)
+        ));
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Ok(Some(PriceRecord::new(20, 1, 0)))
+        );
+
+        assert_eq!(
+            Multisig::as_multi_threshold_1(Origin::signed(3), vec![2], set_price_call(30))
+                .map_err(|err| err.error),
+            Err(Error::<Test>::NotAnOperator.into())
+        );
+        assert_eq!(
+            PriceFeedModule::pair_price(pair),
+            Ok(Some(PriceRecord::new(20, 1, 0)))
+        );
+    })
+}
+
+#[test]
+fn proxy_operator() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let set_price_call = |price| {
+            Box::new(Call::PriceFeedModule(crate::Call::set_price {
+                currency_pair: pair.clone(),
+                price,
+                decimals: 1,
+                confidence: None,
+                source: None,
+            }))
+        };
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, (), 0));
+        assert_ok!(Proxy::add_proxy(Origin::signed(3), 2, (), 0));
+
+        assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, set_price_call(10)));
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+
+        // Dispatch succeeds, but the inner call fails since `3` isn't an operator.
+        assert_ok!(Proxy::proxy(Origin::signed(2), 3, None, set_price_call(20)));
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+
+        assert!(Proxy::proxy(Origin::signed(4), 1, None, set_price_call(30)).is_err());
+        assert_eq!(
+            PriceFeedModule::pair_price(pair),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+    })
+}
+
+#[test]
+fn optional_dock_price_provider() {
+    use price_provider::{OptionalStaticPriceProvider, SomeCurrencySymbolPair};
+
+    new_test_ext().execute_with(|| {
+        parameter_types! {
+            pub const DOCKSym: &'static str = "DOCK";
+            pub const USDSym: &'static str = "USD";
+        }
+
+        type DockUsdPair = SomeCurrencySymbolPair<StaticCurrencySymbolPair<DOCKSym, USDSym>>;
+
+        assert_eq!(
+            <PriceFeedModule as OptionalStaticPriceProvider<Test, DockUsdPair>>::pair(),
+            Some(CurrencySymbolPair::new("DOCK", "USD"))
+        );
+        assert_eq!(
+            <PriceFeedModule as OptionalStaticPriceProvider<Test, ()>>::pair(),
+            None
+        );
+        assert_eq!(
+            <PriceFeedModule as OptionalStaticPriceProvider<Test, DockUsdPair>>::price(),
+            Ok(None)
+        );
+
+        Prices::<Test>::insert(
+            CurrencySymbolPair::new("DOCK", "USD")
+                .checked_into::<BoundedCurrencySymbolPair<_, _, _>>()
+                .unwrap(),
+            PriceRecord::new(100, 2, 0),
+        );
+
+        assert_eq!(
+            <PriceFeedModule as OptionalStaticPriceProvider<Test, DockUsdPair>>::price(),
+            Ok(Some(PriceRecord::new(100, 2, 0)))
+        );
+        assert_eq!(
+            <PriceFeedModule as OptionalStaticPriceProvider<Test, ()>>::price(),
+            Ok(None)
+        );
+    })
+}
+
+#[test]
+fn emit_events_every_n_updates() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        assert_noop!(
+            PriceFeedModule::set_emit_events_every_n_updates(Origin::signed(1), pair.clone(), 3),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_emit_events_every_n_updates(
+            Origin::root(),
+            pair.clone(),
+            3
+        ));
+        assert_eq!(
+            PriceFeedModule::emit_events_every_n_updates(&stored_pair),
+            Some(3)
+        );
+
+        for (price, updates_since_last_event) in [(1, 1), (2, 2), (3, 0), (4, 1)] {
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                price,
+                1,
+                None,
+                None
+            ));
+            assert_eq!(
+                PriceFeedModule::price(&stored_pair),
+                Some(PriceRecord::new(price, 1, 0))
+            );
+            assert_eq!(
+                PriceFeedModule::updates_since_last_event(&stored_pair),
+                updates_since_last_event
+            );
+        }
+
+        assert_ok!(PriceFeedModule::set_emit_events_every_n_updates(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_eq!(
+            PriceFeedModule::emit_events_every_n_updates(&stored_pair),
+            None
+        );
+        assert_eq!(PriceFeedModule::updates_since_last_event(&stored_pair), 0);
+
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            5,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::updates_since_last_event(&stored_pair), 0);
+    })
+}
+
+#[test]
+fn local_time_staleness() {
+    use crate::{inherent, LocalTime};
+    use frame_support::inherent::ProvideInherent;
+    use sp_inherents::InherentData;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+
+        let mut data = InherentData::new();
+        assert_eq!(PriceFeedModule::create_inherent(&data), None);
+        data.put_data(inherent::INHERENT_IDENTIFIER, &1_000u64)
+            .unwrap();
+        assert_eq!(
+            PriceFeedModule::create_inherent(&data),
+            Some(crate::Call::note_local_time { now: 1_000 })
+        );
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::price_updated_at(&stored_pair), None);
+        assert!(!PriceFeedModule::is_price_stale(&stored_pair, 0));
+
+        assert_noop!(
+            PriceFeedModule::note_local_time(Origin::signed(1), 1_000),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::note_local_time(Origin::none(), 1_000));
+        assert_noop!(
+            PriceFeedModule::note_local_time(Origin::none(), 2_000),
+            Error::<Test>::LocalTimeAlreadyNoted
+        );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            11,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::price_updated_at(&stored_pair), Some(1_000));
+        assert_eq!(PriceFeedModule::price_age(&stored_pair), Some(0));
+
+        // Block production stalled for a minute.
+        System::set_block_number(2);
+        assert_ok!(PriceFeedModule::note_local_time(Origin::none(), 61_000));
+        assert_eq!(LocalTime::<Test>::get(), Some((2, 61_000)));
+        assert_eq!(PriceFeedModule::price_age(&stored_pair), Some(60_000));
+        assert!(PriceFeedModule::is_price_stale(&stored_pair, 30_000));
+        assert!(!PriceFeedModule::is_price_stale(&stored_pair, 60_000));
+    })
+}
+
+#[test]
+fn price_bounds() {
+    use crate::PriceBounds;
+
+    let bounds = PriceBounds::new(5, 100_00, 2).unwrap();
+    assert!(PriceBounds::new(2, 1, 0).is_none());
+    assert!(bounds.contains(5, 2));
+    assert!(bounds.contains(50, 3));
+    assert!(bounds.contains(100, 0));
+    assert!(!bounds.contains(4, 2));
+    assert!(!bounds.contains(49, 3));
+    assert!(!bounds.contains(101, 0));
+    assert!(!bounds.contains(u64::MAX, 0));
+    assert!(!bounds.contains(1, 255));
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        assert_noop!(
+            PriceFeedModule::set_price_bounds(Origin::signed(1), pair.clone(), Some(bounds)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::set_price_bounds(
+                Origin::root(),
+                pair.clone(),
+                Some(PriceBounds {
+                    min_amount: 2,
+                    max_amount: 1,
+                    decimals: 0
+                })
+            ),
+            Error::<Test>::InvalidPriceBounds
+        );
+        assert_ok!(PriceFeedModule::set_price_bounds(
+            Origin::root(),
+            pair.clone(),
+            Some(bounds)
+        ));
+        assert_eq!(PriceFeedModule::price_bounds(&stored_pair), Some(bounds));
+
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 101, 0, None, None),
+            Error::<Test>::PriceOutOfBounds
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 4, 2, None, None),
+            Error::<Test>::PriceOutOfBounds
+        );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            99,
+            0,
+            None,
+            None
+        ));
+
+        assert_ok!(PriceFeedModule::set_price_bounds(
+            Origin::root(),
+            pair.clone(),
+            None
+        ));
+        assert_eq!(PriceFeedModule::price_bounds(&stored_pair), None);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            101,
+            0,
+            None,
+            None
+        ));
+    })
+}
+
+#[test]
+fn required_identity_level() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 2).unwrap();
+        TestIdentityProvider::set_identity(2, TestIdentity(1));
+
+        assert_noop!(
+            PriceFeedModule::set_required_identity_level(Origin::signed(1), pair.clone(), 2),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_required_identity_level(
+            Origin::root(),
+            pair.clone(),
+            2
+        ));
+        assert_eq!(
+            PriceFeedModule::required_identity_level(&stored_pair),
+            Some(2)
+        );
+
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 0, None, None),
+            Error::<Test>::InsufficientOperatorIdentityLevel
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(2), pair.clone(), 10, 0, None, None),
+            Error::<Test>::InsufficientOperatorIdentityLevel
+        );
+
+        TestIdentityProvider::set_identity(2, TestIdentity(2));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(2),
+            pair.clone(),
+            10,
+            0,
+            None,
+            None
+        ));
+
+        assert_ok!(PriceFeedModule::set_required_identity_level(
+            Origin::root(),
+            pair.clone(),
+            0
+        ));
+        assert_eq!(PriceFeedModule::required_identity_level(&stored_pair), None);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            11,
+            0,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn feed_spec_hash() {
+    use crate::{Aggregation, FeedSpec};
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+        let mut spec = FeedSpec {
+            pair: pair.clone(),
+            sources: vec!["source".to_owned()],
+            aggregation: Aggregation::Median,
+            update_frequency_millis: 60_000,
+            operators: vec![1u64],
+        };
+        let spec_hash = spec.hash::<BlakeTwo256>();
+
+        assert!(!PriceFeedModule::matches_feed_spec(&spec));
+        assert_noop!(
+            PriceFeedModule::set_feed_spec_hash(Origin::signed(1), pair.clone(), Some(spec_hash)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_feed_spec_hash(
+            Origin::root(),
+            pair.clone(),
+            Some(spec_hash)
+        ));
+        assert_eq!(
+            PriceFeedModule::feed_spec_hash(&stored_pair),
+            Some(spec_hash)
+        );
+        assert!(PriceFeedModule::matches_feed_spec(&spec));
+
+        spec.aggregation = Aggregation::Mean;
+        assert!(!PriceFeedModule::matches_feed_spec(&spec));
+
+        assert_ok!(PriceFeedModule::set_feed_spec_hash(
+            Origin::root(),
+            pair.clone(),
+            None
+        ));
+        assert_eq!(PriceFeedModule::feed_spec_hash(&stored_pair), None);
+    });
+}
+
+#[test]
+fn price_storage_key() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+
+        assert_eq!(
+            PriceFeedModule::price_storage_key(pair),
+            Some(Prices::<Test>::hashed_key_for(stored_pair))
+        );
+        assert_eq!(
+            PriceFeedModule::price_storage_key(
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned)
+            ),
+            None
+        );
+    });
+}
+
+#[test]
+fn batch_queries() {
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+        assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair("A"), 1));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("A"),
+            10,
+            1,
+            None,
+            None
+        ));
+        System::set_block_number(1);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("A"),
+            11,
+            1,
+            None,
+            None
+        ));
+
+        assert_eq!(
+            PriceFeedModule::prices_of(vec![pair("C"), pair("A"), pair("ABCDE")]),
+            vec![None, Some(PriceRecord::new(11, 1, 1)), None]
+        );
+        assert_eq!(
+            PriceFeedModule::price_history_of(pair("A")),
+            vec![PriceRecord::new(10, 1, 0), PriceRecord::new(11, 1, 1)]
+        );
+        assert!(PriceFeedModule::price_history_of(pair("C")).is_empty());
+        assert!(PriceFeedModule::price_history_of(pair("ABCDE")).is_empty());
+    });
+}
+
+#[test]
+fn convert() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        assert_eq!(PriceFeedModule::convert(pair.clone(), 100), None);
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            1_234,
+            3,
+            None,
+            None
+        ));
+
+        assert_eq!(PriceFeedModule::convert(pair.clone(), 0), Some(0));
+        assert_eq!(PriceFeedModule::convert(pair.clone(), 1_000), Some(1_234));
+        assert_eq!(PriceFeedModule::convert(pair.clone(), 32), Some(39));
+        assert_eq!(PriceFeedModule::convert(pair, u128::MAX), None);
+        assert_eq!(
+            PriceFeedModule::convert(
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned),
+                100
+            ),
+            None
+        );
+    });
+}
+
+#[test]
+fn market_cap() {
+    use frame_support::traits::Currency;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+
+        Balances::make_free_balance_be(&1, 1_000);
+        Balances::make_free_balance_be(&2, 3_000);
+        assert_eq!(PriceFeedModule::market_cap(pair.clone()), None);
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 125, 2, None, None).unwrap();
+        assert_eq!(PriceFeedModule::market_cap(pair.clone()), Some(5_000));
+
+        assert_eq!(
+            PriceFeedModule::market_cap(
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned)
+            ),
+            None
+        );
+    });
+}
+
+#[test]
+fn anomaly_helpers() {
+    use crate::anomaly::{divergence, parse_decimal_price};
+    use sp_runtime::Perbill;
+
+    assert_eq!(parse_decimal_price(b"1.2345"), Some((12345, 4)));
+    assert_eq!(parse_decimal_price(b" 10\n"), Some((10, 0)));
+    assert_eq!(parse_decimal_price(b"0.50"), Some((50, 2)));
+    assert_eq!(parse_decimal_price(b".5"), None);
+    assert_eq!(parse_decimal_price(b"1.2.3"), None);
+    assert_eq!(parse_decimal_price(b"-1"), None);
+    assert_eq!(parse_decimal_price(b"99999999999999999999"), None);
+
+    let record = PriceRecord::new(110, 2, 1u64);
+    assert_eq!(divergence(&record, 1, 0), Some(Perbill::from_percent(10)));
+    assert_eq!(divergence(&record, 1100, 3), Some(Perbill::zero()));
+    assert_eq!(divergence(&record, 55, 2), Some(Perbill::from_percent(100)));
+    assert_eq!(divergence(&record, 0, 0), None);
+}
+
+#[test]
+fn report_anomaly() {
+    use crate::AnomalyReport;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair: BoundedCurrencySymbolPair<_, _, ConstU32<4>> =
+            pair.clone().checked_into().unwrap();
+        let report = |block_number, reference_amount| AnomalyReport {
+            pair: pair.clone(),
+            record_block_number: block_number,
+            reference_amount,
+            reference_decimals: 0,
+        };
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        assert_noop!(
+            PriceFeedModule::report_anomaly(Origin::signed(1), report(1, 100)),
+            Error::<Test>::PriceNotFound
+        );
+
+        System::set_block_number(1);
+        PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 100, 0, None, None).unwrap();
+
+        assert_noop!(
+            PriceFeedModule::report_anomaly(Origin::none(), report(1, 200)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::report_anomaly(Origin::signed(2), report(1, 200)),
+            Error::<Test>::NotAnOperator
+        );
+        assert_noop!(
+            PriceFeedModule::report_anomaly(Origin::signed(1), report(0, 200)),
+            Error::<Test>::OutdatedAnomalyReport
+        );
+        assert_noop!(
+            PriceFeedModule::report_anomaly(Origin::signed(1), report(1, 105)),
+            Error::<Test>::NotAnAnomaly
+        );
+
+        assert_ok!(PriceFeedModule::report_anomaly(
+            Origin::signed(1),
+            report(1, 200)
+        ));
+        assert!(PriceFeedModule::is_paused(&stored_pair));
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 200, 0, None, None),
+            Error::<Test>::PairPaused
+        );
+
+        assert_noop!(
+            PriceFeedModule::resume_pair(Origin::signed(1), pair.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::resume_pair(Origin::root(), pair.clone()));
+        assert_noop!(
+            PriceFeedModule::resume_pair(Origin::root(), pair.clone()),
+            Error::<Test>::PairNotPaused
+        );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            200,
+            0,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn paginated_queries() {
+    use crate::MAX_PAGE_LIMIT;
+
+    new_test_ext().execute_with(|| {
+        let pairs: Vec<_> = ["A", "B", "C"]
+            .iter()
+            .map(|from| CurrencySymbolPair::new(*from, "D").map_pair(ToOwned::to_owned))
+            .collect();
+        for (idx, pair) in pairs.iter().enumerate() {
+            PriceFeedModule::add_operator(Origin::root(), pair.clone(), idx as u64).unwrap();
+            PriceFeedModule::add_operator(Origin::root(), pairs[0].clone(), 10 + idx as u64)
+                .unwrap();
+            PriceFeedModule::set_price(Origin::signed(idx as u64), pair.clone(), 10, 0, None, None)
+                .unwrap();
+        }
+
+        let first = PriceFeedModule::all_prices(None, 2);
+        assert_eq!(first.items.len(), 2);
+        let second = PriceFeedModule::all_prices(first.next.clone(), 2);
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.next, None);
+
+        let mut all: Vec<_> = first
+            .items
+            .into_iter()
+            .chain(second.items)
+            .map(|(pair, record)| {
+                assert_eq!(record, PriceRecord::new(10, 0, 0));
+
+                pair
+            })
+            .collect();
+        all.sort_by_key(ToString::to_string);
+        assert_eq!(all, pairs);
+
+        let page = PriceFeedModule::pairs(None, 3);
+        assert_eq!(page.items.len(), 3);
+        assert!(PriceFeedModule::pairs(page.next, 3).items.is_empty());
+        assert_eq!(
+            PriceFeedModule::pairs(None, MAX_PAGE_LIMIT + 1).items.len(),
+            3
+        );
+
+        let first = PriceFeedModule::pair_operators(pairs[0].clone(), None, 3).unwrap();
+        let second = PriceFeedModule::pair_operators(pairs[0].clone(), first.next, 3).unwrap();
+        let mut operators: Vec<_> = first.items.into_iter().chain(second.items).collect();
+        operators.sort();
+        assert_eq!(operators, vec![0, 10, 11, 12]);
+        assert_eq!(second.next, None);
+        assert_eq!(
+            PriceFeedModule::pair_operator_set(pairs[0].clone()),
+            Some(operators)
+        );
+        assert_eq!(
+            PriceFeedModule::pair_operators(
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned),
+                None,
+                1
+            ),
+            None
+        );
+    });
+}
+
+#[test]
+fn export_genesis_config() {
+    use crate::GenesisConfig;
+    use frame_support::traits::GenesisBuild;
+
+    let snapshot = new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 1).unwrap();
+        PriceFeedModule::add_operator(Origin::root(), pair.clone(), 2).unwrap();
+        PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 1, None, None).unwrap();
+
+        let mut snapshot = PriceFeedModule::export_genesis_config();
+        snapshot.operators.sort_by_key(|(_, operator)| *operator);
+        assert_eq!(
+            snapshot.operators,
+            vec![(pair.clone(), 1), (pair.clone(), 2)]
+        );
+        assert_eq!(snapshot.prices, vec![(pair, PriceRecord::new(10, 1, 0))]);
+
+        snapshot
+    });
+
+    let mut storage = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    GenesisBuild::<Test>::assimilate_storage(
+        &GenesisConfig::<Test>::from(snapshot.clone()),
+        &mut storage,
+    )
+    .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        let mut exported = PriceFeedModule::export_genesis_config();
+        exported.operators.sort_by_key(|(_, operator)| *operator);
+
+        assert_eq!(exported, snapshot);
+    });
+}
+
+#[test]
+fn snapshot_diff() {
+    use crate::snapshot_diff::{PriceChange, SnapshotDiff};
+    use codec::Encode;
+
+    let (a_b, c_d, e_f) = (
+        CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned),
+        CurrencySymbolPair::new("C", "D").map_pair(ToOwned::to_owned),
+        CurrencySymbolPair::new("E", "F").map_pair(ToOwned::to_owned),
+    );
+
+    let (before, after) = new_test_ext().execute_with(|| {
+        PriceFeedModule::add_operator(Origin::root(), a_b.clone(), 1).unwrap();
+        PriceFeedModule::add_operator(Origin::root(), c_d.clone(), 1).unwrap();
+        PriceFeedModule::set_price(Origin::signed(1), a_b.clone(), 10, 1, None, None).unwrap();
+        PriceFeedModule::set_price(Origin::signed(1), c_d.clone(), 5, 0, None, None).unwrap();
+        let before = PriceFeedModule::export_genesis_config();
+
+        PriceFeedModule::set_price(Origin::signed(1), a_b.clone(), 125, 2, None, None).unwrap();
+        PriceFeedModule::add_operator(Origin::root(), a_b.clone(), 2).unwrap();
+        PriceFeedModule::add_operator(Origin::root(), e_f.clone(), 2).unwrap();
+        PriceFeedModule::set_price(Origin::signed(2), e_f.clone(), 7, 0, None, None).unwrap();
+        PriceFeedModule::remove_operator_immediately(Origin::root(), c_d.clone(), 1).unwrap();
+        Prices::<Test>::remove(
+            c_d.clone()
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap(),
+        );
+        let after = PriceFeedModule::export_genesis_config();
+
+        (before, after)
+    });
+
+    let diff = SnapshotDiff::<u64, u64>::from_encoded(&before.encode(), &after.encode()).unwrap();
+    assert_eq!(
+        diff,
+        SnapshotDiff {
+            pairs_added: vec![e_f.clone()],
+            pairs_removed: vec![c_d.clone()],
+            prices_added: vec![(e_f.clone(), PriceRecord::new(7, 0, 0))],
+            prices_removed: vec![(c_d.clone(), PriceRecord::new(5, 0, 0))],
+            price_changes: vec![PriceChange {
+                pair: a_b.clone(),
+                before: PriceRecord::new(10, 1, 0),
+                after: PriceRecord::new(125, 2, 0),
+            }],
+            operators_added: vec![(a_b, 2), (e_f, 2)],
+            operators_removed: vec![(c_d, 1)],
+        }
+    );
+    assert_eq!(diff.price_changes[0].delta(), Some((25, 2)));
+    assert!(!diff.is_empty());
+    assert!(SnapshotDiff::new(&after, &after).is_empty());
+    assert!(SnapshotDiff::<u64, u64>::from_encoded(&[1], &after.encode()).is_err());
+}
+
+#[test]
+fn storage_max_encoded_len() {
+    use crate::{FeedState, PriceBounds, Releases};
+    use codec::MaxEncodedLen;
+    use frame_support::traits::StorageInfoTrait;
+    use sp_core::H256;
+
+    let pair = BoundedCurrencySymbolPair::<String, String, ConstU32<4>>::max_encoded_len();
+    let hashed_pair = 16 + pair;
+    let record = PriceRecord::<u64>::max_encoded_len();
+    assert_eq!(pair, 10);
+    assert_eq!(record, 26);
+    assert_eq!(
+        FeedState::max_encoded_len(),
+        1 + 1
+            + PriceBounds::max_encoded_len()
+            + 4
+            + 4
+            + 4
+            + 1
+            + 8
+            + 1
+            + 1
+            + 32
+            + 1
+            + 4 * 3
+            + 1
+            + record
+            + 1
+    );
+
+    let expected = [
+        ("OperatorSets", hashed_pair + 1 + 4 * u64::max_encoded_len()),
+        (
+            "OperatorLastSubmission",
+            hashed_pair + 8 + u64::max_encoded_len() + u64::max_encoded_len(),
+        ),
+        (
+            "OngoingInactivityCheck",
+            8 + 8 + 1 + pair + u64::max_encoded_len(),
+        ),
+        ("InactivityCheckedUntil", 8),
+        ("Prices", hashed_pair + record),
+        ("PriceHistory", hashed_pair + 1 + 4 * record),
+        ("FeedStates", hashed_pair + FeedState::max_encoded_len()),
+        ("PendingOperatorRemovals", 1 + 2 * (pair + 8 + 8)),
+        ("FeedSpecHashes", hashed_pair + H256::max_encoded_len()),
+        ("HttpSources", hashed_pair + 2 + 256 + 2 + 64 + 1),
+        (
+            "ContractConfigs",
+            hashed_pair + 20 + 1 + 2 * (2 + 68) + 1 + 4,
+        ),
+        ("ContractLastVisit", hashed_pair + 8),
+        ("ContractVisitCursor", pair),
+        ("CommitRevealSchemes", hashed_pair + 4 * 3),
+        ("RestrictedPriceAccess", hashed_pair + 1),
+        ("PriceConsumers", hashed_pair + 16 + 8 + 1),
+        ("AllowedPairs", hashed_pair),
+        ("AllowedPairsCount", 4),
+        ("ClaimableRewards", 16 + 8 + 8),
+        ("OperatorRequests", hashed_pair + 16 + 8 + 8),
+        (
+            "PriceCommitments",
+            hashed_pair + 8 + 1 + 3 * (8 + H256::max_encoded_len() + 1 + 8 + 1),
+        ),
+        ("PendingCommitRevealSchemes", hashed_pair + 1 + 4 * 3),
+        ("RevealDeadlines", 8 + 8 + hashed_pair),
+        ("RevealDeadlinesDrainedAt", 8),
+        ("LocalTime", 16),
+        ("PriceRoot", 8 + H256::max_encoded_len()),
+        ("PricesChanged", 1),
+        ("PricedPairsCount", 4),
+        ("AllPairsPaused", 1),
+        ("StorageVersion", Releases::max_encoded_len()),
+    ];
+    let storage_info = PriceFeedModule::storage_info();
+    assert_eq!(storage_info.len(), expected.len());
+
+    for (name, max_size) in expected {
+        let info = storage_info
+            .iter()
+            .find(|info| info.storage_name == name.as_bytes())
+            .unwrap_or_else(|| panic!("Missing storage info for `{}`", name));
+
+        assert_eq!(info.max_size, Some(max_size as u32), "`{}`", name);
+    }
+}
+
+#[test]
+fn prune_oversized_pairs() {
+    use crate::{migrations::prune::prune_oversized_pairs, OperatorSets, PriceBounds};
+    use frame_support::traits::{Currency, ReservableCurrency};
+
+    new_test_ext().execute_with(|| {
+        for (pair, operator) in [(("A", "B"), 1), (("ABCD", "B"), 2)] {
+            let pair = CurrencySymbolPair::from(pair).map_pair(ToOwned::to_owned);
+
+            assert_ok!(PriceFeedModule::allow_pair(Origin::root(), pair.clone()));
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(operator),
+                pair.clone(),
+                operator * 10,
+                2,
+                None,
+                None
+            ));
+            assert_ok!(PriceFeedModule::set_price_bounds(
+                Origin::root(),
+                pair.clone(),
+                PriceBounds::new(1, 100, 0)
+            ));
+            Balances::make_free_balance_be(&(operator + 10), 100);
+            assert_ok!(PriceFeedModule::request_operator_role(
+                Origin::signed(operator + 10),
+                pair
+            ));
+        }
+
+        prune_oversized_pairs::<Test, ConstU32<4>>();
+        assert_eq!(Prices::<Test>::iter().count(), 2);
+        assert_eq!(OperatorSets::<Test>::iter().count(), 2);
+        assert_eq!(PriceFeedModule::allowed_pairs_count(), 2);
+
+        prune_oversized_pairs::<Test, ConstU32<3>>();
+        let short_pair = CurrencySymbolPair::new("A", "B")
+            .map_pair(ToOwned::to_owned)
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_eq!(
+            Prices::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![short_pair.clone()]
+        );
+        assert_eq!(
+            OperatorSets::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![short_pair.clone()]
+        );
+        assert_eq!(
+            crate::FeedStates::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![short_pair.clone()]
+        );
+        assert_eq!(
+            crate::AllowedPairs::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![short_pair.clone()]
+        );
+        assert_eq!(PriceFeedModule::allowed_pairs_count(), 1);
+        // Deposits of the pruned operator requests are returned.
+        assert_eq!(
+            crate::OperatorRequests::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![(short_pair, 11)]
+        );
+        assert_eq!(Balances::reserved_balance(11), 10);
+        assert_eq!(Balances::reserved_balance(12), 0);
+    });
+}
+
+#[test]
+fn prune_oversized_pairs_in_values() {
+    use crate::{
+        migrations::prune::prune_oversized_pairs, ContractVisitCursor, InactivityCheck,
+        OngoingInactivityCheck, PendingOperatorRemovals, RevealDeadlines,
+    };
+
+    new_test_ext().execute_with(|| {
+        let stored_pair = |from: &str| {
+            CurrencySymbolPair::new(from, "B")
+                .map_pair(ToOwned::to_owned)
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap()
+        };
+        let (short_pair, long_pair) = (stored_pair("A"), stored_pair("ABCD"));
+        OperatorRemovalDelay::set(5);
+        for (pair, operator) in [(&short_pair, 1), (&long_pair, 2)] {
+            let pair: CurrencySymbolPair<String, String> = pair.clone().into();
+
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+            assert_ok!(PriceFeedModule::remove_operator(
+                Origin::root(),
+                pair,
+                operator
+            ));
+        }
+        OngoingInactivityCheck::<Test>::put(InactivityCheck {
+            after: 0,
+            until: 1,
+            last_visited: Some((long_pair.clone(), 2)),
+        });
+        ContractVisitCursor::<Test>::put(&long_pair);
+        RevealDeadlines::<Test>::insert(10, &short_pair, ());
+        RevealDeadlines::<Test>::insert(10, &long_pair, ());
+        assert_eq!(
+            rebound_storage::<Test, ConstU32<4>, ConstU32<3>>(),
+            Err(ReboundStorageError::PairDoesNotFit(
+                long_pair.clone().into()
+            ))
+        );
+
+        prune_oversized_pairs::<Test, ConstU32<3>>();
+
+        // The removal of the pruned pair's operator is dropped while the other one is kept.
+        assert_eq!(
+            PendingOperatorRemovals::<Test>::get()
+                .iter()
+                .map(|removal| (removal.pair.clone(), removal.operator))
+                .collect::<Vec<_>>(),
+            vec![(short_pair.clone(), 1)]
+        );
+        assert_eq!(OngoingInactivityCheck::<Test>::get(), None);
+        assert_eq!(ContractVisitCursor::<Test>::get(), None);
+        assert_eq!(
+            RevealDeadlines::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![(10, short_pair.clone())]
+        );
+        assert!(rebound_storage::<Test, ConstU32<4>, ConstU32<3>>().is_ok());
+
+        // Cursors stopped at the fitting pairs are kept.
+        ContractVisitCursor::<Test>::put(&short_pair);
+        prune_oversized_pairs::<Test, ConstU32<3>>();
+        assert_eq!(ContractVisitCursor::<Test>::get(), Some(short_pair));
+    });
+}
+
+#[test]
+fn error_indices_are_stable() {
+    use codec::Encode;
+
+    let errors = [
+        Error::<Test>::NotAnOperator,
+        Error::<Test>::OperatorIsAlreadyAdded,
+        Error::<Test>::OperatorDoesNotExist,
+        Error::<Test>::ZeroPriceAmount,
+        Error::<Test>::TooManyPriceDecimals,
+        Error::<Test>::NonMonotonicPriceBlockNumber,
+        Error::<Test>::LocalTimeAlreadyNoted,
+        Error::<Test>::PriceOutOfBounds,
+        Error::<Test>::InvalidPriceBounds,
+        Error::<Test>::InsufficientOperatorIdentityLevel,
+        Error::<Test>::PairPaused,
+        Error::<Test>::PairNotPaused,
+        Error::<Test>::PriceNotFound,
+        Error::<Test>::OutdatedAnomalyReport,
+        Error::<Test>::NotAnAnomaly,
+        Error::<Test>::ChainInSafeMode,
+        Error::<Test>::OutsideSubmissionWindow,
+        Error::<Test>::InvalidSubmissionWindow,
+        Error::<Test>::TooManyPendingOperatorRemovals,
+        Error::<Test>::OperatorRemovalAlreadyScheduled,
+        Error::<Test>::InvalidContractConfig,
+        Error::<Test>::InvalidCommitRevealScheme,
+        Error::<Test>::CommitRevealRequired,
+        Error::<Test>::CommitRevealNotEnabled,
+        Error::<Test>::OutsideCommitPhase,
+        Error::<Test>::OutsideRevealPhase,
+        Error::<Test>::TooManyCommitments,
+        Error::<Test>::CommitmentNotFound,
+        Error::<Test>::CommitmentMismatch,
+        Error::<Test>::PriceAlreadyRevealed,
+        Error::<Test>::PriceAccessDenied,
+        Error::<Test>::PairNotAllowed,
+        Error::<Test>::PairAlreadyAllowed,
+        Error::<Test>::NoRewardsToClaim,
+        Error::<Test>::OperatorRequestAlreadyExists,
+        Error::<Test>::OperatorRequestNotFound,
+        Error::<Test>::TooManyOperators,
+        Error::<Test>::AllPairsPaused,
+        Error::<Test>::AllPairsNotPaused,
+        Error::<Test>::TooManyPricedPairs,
+    ];
+
+    for (idx, error) in errors.into_iter().enumerate() {
+        assert_eq!(error.encode(), vec![idx as u8], "{:?}", error);
+
+        match DispatchError::from(error) {
+            DispatchError::Module(module_error) => assert_eq!(module_error.error[0], idx as u8),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn operators_overview() {
+    use crate::{OperatorOverview, Paged, MAX_PAGE_LIMIT};
+
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "D").map_pair(ToOwned::to_owned);
+        assert_eq!(
+            PriceFeedModule::operators_overview(None, MAX_PAGE_LIMIT),
+            Paged::default()
+        );
+
+        for from in ["A", "B", "C"] {
+            PriceFeedModule::add_operator(Origin::root(), pair(from), 1).unwrap();
+        }
+        for operator in [3, 2] {
+            PriceFeedModule::add_operator(Origin::root(), pair("A"), operator).unwrap();
+        }
+
+        let mut overview = PriceFeedModule::operators_overview(None, MAX_PAGE_LIMIT + 1);
+        assert_eq!(overview.next, None);
+        overview.items[0].pairs.sort_by_key(ToString::to_string);
+        assert_eq!(
+            overview.items,
+            vec![
+                OperatorOverview {
+                    operator: 1,
+                    pairs: vec![pair("A"), pair("B"), pair("C")]
+                },
+                OperatorOverview {
+                    operator: 2,
+                    pairs: vec![pair("A")]
+                },
+                OperatorOverview {
+                    operator: 3,
+                    pairs: vec![pair("A")]
+                }
+            ]
+        );
+
+        // Pages continue after the last operator, including the ones having the same amount of pairs.
+        let first = PriceFeedModule::operators_overview(None, 2);
+        assert_eq!(
+            first
+                .items
+                .iter()
+                .map(|item| item.operator)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        let second = PriceFeedModule::operators_overview(first.next, 2);
+        assert_eq!(
+            second
+                .items
+                .iter()
+                .map(|item| item.operator)
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_eq!(second.next, None);
+    });
+}
+
+#[test]
+fn block_number_feed_time() {
+    use crate::{BlockNumberFeedTime, FeedTime};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_eq!(BlockNumberFeedTime::<Test>::now(), Some(5));
+
+        System::set_block_number(u64::MAX);
+        assert_eq!(BlockNumberFeedTime::<Test>::now(), Some(u64::MAX));
+    })
+}
+
+#[test]
+fn migrate_to_v3() {
+    use crate::{
+        migrations::v2::migrate_to_v3, OperatorLastSubmission, OperatorSets, PricesChanged,
+        Releases, StorageVersion,
+    };
+    use codec::Encode;
+    use frame_support::{
+        storage::{
+            migration::{put_storage_value, storage_iter},
+            unhashed,
+        },
+        Blake2_128Concat, StorageHasher, Twox64Concat,
+    };
+
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str, to: &str| {
+            CurrencySymbolPair::new(from, to)
+                .map_pair(ToOwned::to_owned)
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap()
+        };
+        let (crowded, single) = (pair("A", "B"), pair("C", "D"));
+        let put_operator = |pair: &BoundedCurrencySymbolPair<_, _, _>, operator: u64| {
+            let mut key = Blake2_128Concat::hash(&pair.encode());
+            key.extend(Twox64Concat::hash(&operator.encode()));
+            put_storage_value(b"PriceFeedModule", b"Operators", &key, ());
+        };
+
+        for operator in 1..=5 {
+            put_operator(&crowded, operator);
+        }
+        put_operator(&single, 6);
+        // The operator encountered last in the storage iteration order doesn't fit.
+        let dropped = (1..=5u64)
+            .max_by_key(|operator| Twox64Concat::hash(&operator.encode()))
+            .unwrap();
+        let old_record = |amount: u64, block_number: u64| (amount, 2u8, block_number).encode();
+        unhashed::put_raw(
+            &Prices::<Test>::hashed_key_for(&crowded),
+            &old_record(20, 2),
+        );
+        unhashed::put_raw(&Prices::<Test>::hashed_key_for(&single), &old_record(30, 3));
+        StorageVersion::<Test>::put(Releases::V2MultiPair);
+        System::set_block_number(20);
+
+        migrate_to_v3::<Test>();
+
+        assert_eq!(
+            PriceFeedModule::operators_of(&crowded).into_inner(),
+            (1..=5).filter(|operator| *operator != dropped).collect()
+        );
+        assert_eq!(
+            PriceFeedModule::operators_of(&single).into_inner(),
+            [6].into()
+        );
+        assert_eq!(OperatorSets::<Test>::iter().count(), 2);
+        assert_eq!(
+            storage_iter::<()>(b"PriceFeedModule", b"Operators").count(),
+            0
+        );
+        // Operators are seeded as if they submitted at the migration block.
+        assert_eq!(
+            OperatorLastSubmission::<Test>::iter_prefix(&crowded).count(),
+            4
+        );
+        assert_eq!(OperatorLastSubmission::<Test>::get(&crowded, dropped), None);
+        assert_eq!(
+            PriceFeedModule::operator_last_submission(&single, 6),
+            Some(20)
+        );
+
+        assert_eq!(
+            PriceFeedModule::price(&crowded),
+            Some(PriceRecord::new(20, 2, 2))
+        );
+        assert_eq!(
+            PriceFeedModule::price(&single),
+            Some(PriceRecord::new(30, 2, 3))
+        );
+        assert_eq!(PriceFeedModule::priced_pairs_count(), 2);
+        assert!(PricesChanged::<Test>::get());
+        assert!(StorageVersion::<Test>::get() == Releases::V3OperatorSets);
+        assert_ok!(PriceFeedModule::do_try_state());
+    });
+}
+
+#[test]
+fn operators_per_pair_are_limited() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+
+        for operator in 1..=4 {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+        assert_noop!(
+            PriceFeedModule::add_operator(Origin::root(), pair.clone(), 5),
+            Error::<Test>::TooManyOperators
+        );
+        assert_eq!(
+            PriceFeedModule::operators_of(&stored_pair).into_inner(),
+            (1..=4).collect()
+        );
+
+        for operator in 1..=4 {
+            assert_ok!(PriceFeedModule::remove_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+        assert!(!crate::OperatorSets::<Test>::contains_key(&stored_pair));
+        assert_eq!(PriceFeedModule::pair_operator_set(pair), Some(vec![]));
+    });
+}
+
+#[test]
+fn feed_state_is_removed_once_default() {
+    use crate::FeedStates;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+
+        assert_ok!(PriceFeedModule::set_required_identity_level(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert!(FeedStates::<Test>::contains_key(&stored_pair));
+
+        assert_ok!(PriceFeedModule::set_required_identity_level(
+            Origin::root(),
+            pair,
+            0
+        ));
+        assert!(!FeedStates::<Test>::contains_key(&stored_pair));
+    });
+}
+
+#[test]
+fn event_pairs_are_plain_in_metadata() {
+    use scale_info::{
+        form::PortableForm, meta_type, PortableRegistry, Registry, TypeDef, TypeDefPrimitive,
+    };
+
+    let mut registry = Registry::new();
+    let event = registry.register_type(&meta_type::<crate::Event<Test>>());
+    let registry: PortableRegistry = registry.into();
+    let type_def = |id: u32| -> &TypeDef<PortableForm> { registry.resolve(id).unwrap().type_def() };
+
+    let variants = match type_def(event.id()) {
+        TypeDef::Variant(variants) => variants.variants(),
+        other => panic!("Unexpected event type: {:?}", other),
+    };
+    // Every event field is named, so indexers don't have to rely on positions.
+    assert!(variants
+        .iter()
+        .flat_map(|variant| variant.fields())
+        .all(|field| field.name().is_some()));
+    let price_set = variants
+        .iter()
+        .find(|variant| variant.name() == "PriceSet")
+        .unwrap();
+    assert_eq!(
+        price_set.fields()[0].name().map(String::as_str),
+        Some("pair")
+    );
+    let symbols = match type_def(price_set.fields()[0].ty().id()) {
+        TypeDef::Tuple(tuple) => tuple.fields(),
+        other => panic!("Unexpected pair type: {:?}", other),
+    };
+
+    assert_eq!(symbols.len(), 2);
+    for symbol in symbols {
+        match type_def(symbol.id()) {
+            TypeDef::Sequence(seq) => assert_eq!(
+                type_def(seq.type_param().id()),
+                &TypeDef::Primitive(TypeDefPrimitive::U8)
+            ),
+            other => panic!("Unexpected symbol type: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn ensure_operator_for() {
+    use crate::EnsureOperatorFor;
+    use frame_support::traits::EnsureOrigin;
+
+    parameter_types! {
+        pub const DOCKSym: &'static str = "DOCK";
+        pub const USDSym: &'static str = "USD";
+        pub const LARGESym: &'static str = "ABCDE";
+    }
+
+    type EnsureDockUsdOperator = EnsureOperatorFor<Test, StaticCurrencySymbolPair<DOCKSym, USDSym>>;
+    type EnsureLargeUsdOperator =
+        EnsureOperatorFor<Test, StaticCurrencySymbolPair<LARGESym, USDSym>>;
+
+    new_test_ext().execute_with(|| {
+        assert!(EnsureDockUsdOperator::ensure_origin(Origin::signed(1)).is_err());
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            CurrencySymbolPair::new("DOCK", "USD").map_pair(ToOwned::to_owned),
+            1
+        ));
+        assert_eq!(
+            EnsureDockUsdOperator::ensure_origin(Origin::signed(1)),
+            Ok(1)
+        );
+        assert!(EnsureDockUsdOperator::ensure_origin(Origin::signed(2)).is_err());
+        assert!(EnsureDockUsdOperator::ensure_origin(Origin::root()).is_err());
+        assert!(EnsureDockUsdOperator::ensure_origin(Origin::none()).is_err());
+        assert!(EnsureLargeUsdOperator::ensure_origin(Origin::signed(1)).is_err());
+    });
+}
+
+#[test]
+fn merkle_proofs() {
+    use crate::price_root::{merkle_proof, merkle_root, verify_proof};
+    use sp_core::H256;
+    use sp_runtime::traits::{BlakeTwo256, Hash};
+
+    assert_eq!(merkle_root::<BlakeTwo256>(vec![]), H256::default());
+    assert_eq!(merkle_proof::<BlakeTwo256>(vec![], 0), None);
+
+    for count in 1..=9u32 {
+        let leaves: Vec<_> = (0..count).map(|i| BlakeTwo256::hash_of(&i)).collect();
+        let root = merkle_root::<BlakeTwo256>(leaves.clone());
+        assert_eq!(merkle_proof::<BlakeTwo256>(leaves.clone(), count), None);
+
+        for index in 0..count {
+            let leaf = leaves[index as usize];
+            let proof = merkle_proof::<BlakeTwo256>(leaves.clone(), index).unwrap();
+
+            assert!(verify_proof::<BlakeTwo256>(&root, leaf, &proof));
+            assert!(!verify_proof::<BlakeTwo256>(
+                &root,
+                BlakeTwo256::hash_of(&count),
+                &proof
+            ));
+            assert!(!verify_proof::<BlakeTwo256>(
+                &BlakeTwo256::hash_of(&count),
+                leaf,
+                &proof
+            ));
+            if count > 1 {
+                let mut wrong_index = proof.clone();
+                wrong_index.index = (index + 1) % count;
+                assert!(!verify_proof::<BlakeTwo256>(&root, leaf, &wrong_index));
+            }
+        }
+    }
+}
+
+#[test]
+fn price_root() {
+    use crate::{
+        price_root::{price_root_from_digest, verify_proof},
+        PriceRoot, PricesChanged, WeightInfo,
+    };
+    use frame_support::traits::Hooks;
+    use sp_core::H256;
+    use sp_runtime::traits::{BlakeTwo256, Hash};
+
+    new_test_ext().execute_with(|| {
+        let pairs: Vec<_> = [("A", "B"), ("C", "D"), ("E", "F")]
+            .into_iter()
+            .map(|pair| CurrencySymbolPair::from(pair).map_pair(ToOwned::to_owned))
+            .collect();
+
+        System::set_block_number(1);
+        for (idx, pair) in pairs.iter().enumerate() {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                1
+            ));
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                idx as u64 + 1,
+                2,
+                None,
+                None
+            ));
+        }
+        assert!(PricesChanged::<Test>::get());
+        assert_eq!(PriceFeedModule::price_root(), None);
+
+        // Recomputation is charged for every priced pair.
+        let weight = PriceFeedModule::on_initialize(2);
+        assert!(weight.ref_time() >= <() as WeightInfo>::update_price_root(3).ref_time());
+        assert!(!PricesChanged::<Test>::get());
+        let (block_number, root) = PriceFeedModule::price_root().unwrap();
+        assert_eq!(block_number, 1);
+        assert_eq!(
+            price_root_from_digest(&System::digest()),
+            Some((block_number, root))
+        );
+
+        for pair in &pairs {
+            let (record, proof) = PriceFeedModule::price_root_proof(pair.clone()).unwrap();
+            let leaf = BlakeTwo256::hash_of(&(pair, record));
+
+            assert_eq!(proof.leaves, 3);
+            assert!(verify_proof::<BlakeTwo256>(&root, leaf, &proof));
+        }
+        assert_eq!(
+            PriceFeedModule::price_root_proof(
+                CurrencySymbolPair::new("X", "Y").map_pair(ToOwned::to_owned)
+            ),
+            None
+        );
+
+        // Root isn't recomputed until prices change.
+        PriceRoot::<Test>::kill();
+        System::initialize(&3, &Default::default(), &Default::default());
+        PriceFeedModule::on_initialize(3);
+        assert_eq!(PriceFeedModule::price_root(), None);
+        assert_eq!(price_root_from_digest::<u64, H256>(&System::digest()), None);
+    });
+}
+
+#[test]
+fn set_price_source() {
+    use crate::SourceTag;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let source = SourceTag::new("kraken-avg").unwrap();
+
+        assert!(SourceTag::new(&"a".repeat(33)).is_err());
+        assert_noop!(
+            PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                10,
+                1,
+                None,
+                Some(source.clone())
+            ),
+            Error::<Test>::NotAnOperator
+        );
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            Some(source.clone())
+        ));
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair).map(|record| record.amount()),
+            Some(10)
+        );
+        assert_eq!(PriceFeedModule::price_source(&stored_pair), Some(source));
+
+        // Untagged update drops the stale tag.
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            11,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::price_source(&stored_pair), None);
+    });
+}
+
+#[test]
+fn deposited_events() {
+    use crate::{Event, EventKinds};
+
+    let pair = (b"DOCK".to_vec(), b"USD".to_vec());
+
+    let operator_added = Event::<Test>::OperatorAdded {
+        pair: pair.clone(),
+        operator: 1,
+    };
+    assert_eq!(operator_added.kind(), Some(EventKinds::OPERATOR_ADDED));
+    assert!(!operator_added.is_enabled());
+
+    let operator_removed = Event::<Test>::OperatorRemoved {
+        pair: pair.clone(),
+        operator: 1,
+    };
+    assert_eq!(operator_removed.kind(), Some(EventKinds::OPERATOR_REMOVED));
+    assert!(operator_removed.is_enabled());
+
+    let price_set = Event::<Test>::PriceSet {
+        pair,
+        price: PriceRecord::new(1, 2, 3),
+        operator: 1,
+    };
+    assert_eq!(price_set.kind(), None);
+    assert!(price_set.is_enabled());
+    assert_eq!(Event::<Test>::AllPairsPaused.kind(), None);
+}
+
+#[test]
+fn pause_switch() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let other = CurrencySymbolPair::new("C", "D").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        for pair in [&pair, &other] {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                1
+            ));
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                100,
+                0,
+                None,
+                None
+            ));
+        }
+
+        assert_noop!(
+            PriceFeedModule::pause_pair(Origin::signed(1), pair.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::pause_pair(Origin::root(), pair.clone()));
+        assert_noop!(
+            PriceFeedModule::pause_pair(Origin::root(), pair.clone()),
+            Error::<Test>::PairPaused
+        );
+        assert!(PriceFeedModule::is_paused(&stored_pair));
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 200, 0, None, None),
+            Error::<Test>::PairPaused
+        );
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Err(PairPriceError::Paused)
+        );
+        assert_eq!(
+            PriceFeedModule::pair_price(other.clone()),
+            Ok(Some(PriceRecord::new(100, 0, 0)))
+        );
+
+        assert_noop!(
+            PriceFeedModule::pause_all_pairs(Origin::signed(1)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::resume_all_pairs(Origin::root()),
+            Error::<Test>::AllPairsNotPaused
+        );
+        assert_ok!(PriceFeedModule::pause_all_pairs(Origin::root()));
+        assert_noop!(
+            PriceFeedModule::pause_all_pairs(Origin::root()),
+            Error::<Test>::AllPairsPaused
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), other.clone(), 200, 0, None, None),
+            Error::<Test>::AllPairsPaused
+        );
+        assert_eq!(
+            PriceFeedModule::pair_price(other.clone()),
+            Err(PairPriceError::Paused)
+        );
+
+        // Resuming the pair doesn't lift the global pause.
+        assert_ok!(PriceFeedModule::resume_pair(Origin::root(), pair.clone()));
+        assert!(PriceFeedModule::is_paused(&stored_pair));
+        assert_ok!(PriceFeedModule::pause_pair(Origin::root(), pair.clone()));
+
+        // Individually paused pairs stay paused once all pairs are resumed.
+        assert_ok!(PriceFeedModule::resume_all_pairs(Origin::root()));
+        assert_eq!(
+            PriceFeedModule::pair_price(pair.clone()),
+            Err(PairPriceError::Paused)
+        );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            other.clone(),
+            200,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::pair_price(other),
+            Ok(Some(PriceRecord::new(200, 0, 0)))
+        );
+    });
+}
+
+#[test]
+fn safe_mode() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+
+        SafeModeEntered::set(true);
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 20, 1, None, None),
+            Error::<Test>::ChainInSafeMode
+        );
+        // Prices remain readable.
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair).map(|record| record.amount()),
+            Some(10)
+        );
+
+        SafeModeEntered::set(false);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            20,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair).map(|record| record.amount()),
+            Some(20)
+        );
+    });
+}
+
+#[test]
+fn submission_window() {
+    use crate::SubmissionWindow;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+
+        assert_eq!(SubmissionWindow::new(10, 5, 6), None);
+        assert_eq!(SubmissionWindow::new(10, 5, 0), None);
+        assert_noop!(
+            PriceFeedModule::set_submission_window(
+                Origin::root(),
+                pair.clone(),
+                Some(SubmissionWindow {
+                    period: 0,
+                    start_offset: 0,
+                    length: 0
+                })
+            ),
+            Error::<Test>::InvalidSubmissionWindow
+        );
+
+        // Rounds last 10 blocks, submissions are accepted during blocks 2..5 of each round.
+        let window = SubmissionWindow::new(10, 2, 3).unwrap();
+        assert_ok!(PriceFeedModule::set_submission_window(
+            Origin::root(),
+            pair.clone(),
+            Some(window)
+        ));
+        assert_eq!(
+            PriceFeedModule::submission_window(&stored_pair),
+            Some(window)
+        );
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        for (block, round) in [
+            (11, None),
+            (12, Some(1)),
+            (14, Some(1)),
+            (15, None),
+            (22, Some(2)),
+        ] {
+            System::set_block_number(block);
+            assert_eq!(PriceFeedModule::open_submission_round(&stored_pair), round);
+
+            if round.is_some() {
+                assert_ok!(PriceFeedModule::set_price(
+                    Origin::signed(1),
+                    pair.clone(),
+                    10,
+                    1,
+                    None,
+                    None
+                ));
+            } else {
+                assert_noop!(
+                    PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 1, None, None),
+                    Error::<Test>::OutsideSubmissionWindow
+                );
+            }
+        }
+
+        assert_ok!(PriceFeedModule::set_submission_window(
+            Origin::root(),
+            pair.clone(),
+            None
+        ));
+        assert_eq!(PriceFeedModule::open_submission_round(&stored_pair), None);
+        System::set_block_number(25);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            10,
+            1,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn constants_metadata() {
+    use crate::EventKinds;
+    use codec::Encode;
+    use sp_runtime::Perbill;
+
+    let constants: Vec<_> = PriceFeedModule::pallet_constants_metadata()
+        .into_iter()
+        .map(|constant| (constant.name, constant.value))
+        .collect();
+
+    assert_eq!(
+        constants,
+        vec![
+            ("MaxSymbolBytesLen", 4u32.encode()),
+            ("AnomalyThreshold", Perbill::from_percent(10).encode()),
+            ("PauseOnAnomaly", true.encode()),
+            ("MaxPriceHistoryLen", 4u32.encode()),
+            ("StandardPriceHistoryLen", 4u32.encode()),
+            ("EphemeralPriceHistoryLen", 1u32.encode()),
+            ("MaxPriceAge", 100u64.encode()),
+            ("OperatorRemovalDelay", 0u64.encode()),
+            ("MaxPendingOperatorRemovals", 2u32.encode()),
+            ("MaxCommitmentsPerRound", 3u32.encode()),
+            ("MaxRevealDeviations", 3u32.encode()),
+            ("OperatorTimeout", 0u64.encode()),
+            ("InactivityCheckPeriod", 1u64.encode()),
+            ("MaxInactivityChecksPerBlock", 4u32.encode()),
+            ("MaxDecimals", 18u8.encode()),
+            ("RewardPerSubmission", 5u64.encode()),
+            ("MaxRewardPayout", 12u64.encode()),
+            ("RewardPotId", RewardPotId::get().encode()),
+            ("DepositPriceRootDigest", true.encode()),
+            ("OperatorRequestDeposit", 10u64.encode()),
+            ("MaxOperatorsPerPair", 4u32.encode()),
+            ("EwmaHalfLife", 10u32.encode()),
+            ("MaxPricedPairs", 16u32.encode()),
+            ("MaxContractVisitsPerBlock", 4u32.encode()),
+            ("MaxRevealDeadlinesPerBlock", 16u32.encode()),
+            (
+                "DepositedEvents",
+                EventKinds::all()
+                    .difference(EventKinds::OPERATOR_ADDED)
+                    .encode()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn interpolated_price() {
+    use crate::InterpolatedPrice;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        for (block, price, decimals) in [
+            (10, 100, 2),
+            (20, 30, 1),
+            (30, 400, 2),
+            (40, 4, 0),
+            (50, 1, 0),
+        ] {
+            System::set_block_number(block);
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                price,
+                decimals,
+                None,
+                None
+            ));
+        }
+
+        // The oldest record got evicted.
+        assert_eq!(
+            PriceFeedModule::price_history(&stored_pair).into_inner(),
+            vec![
+                PriceRecord::new(30, 1, 20),
+                PriceRecord::new(400, 2, 30),
+                PriceRecord::new(4, 0, 40),
+                PriceRecord::new(1, 0, 50)
+            ]
+        );
+
+        assert_eq!(PriceFeedModule::interpolated_price(&stored_pair, 15), None);
+        assert_eq!(PriceFeedModule::interpolated_price(&stored_pair, 51), None);
+        assert_eq!(
+            PriceFeedModule::interpolated_price(&stored_pair, 30),
+            Some(InterpolatedPrice::Exact(PriceRecord::new(400, 2, 30)))
+        );
+        // 3.0 -> 4.00
+        assert_eq!(
+            PriceFeedModule::interpolated_price(&stored_pair, 25),
+            Some(InterpolatedPrice::Interpolated(PriceRecord::new(
+                350, 2, 25
+            )))
+        );
+        // 4 -> 1
+        let price = PriceFeedModule::interpolated_price(&stored_pair, 45).unwrap();
+        assert!(price.is_interpolated());
+        assert_eq!(*price.record(), PriceRecord::new(3, 0, 45));
+    });
+}
+
+#[test]
+fn retention_tiers() {
+    use crate::{FeedStates, RetentionTier};
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        let set_prices = |blocks: core::ops::Range<u64>| {
+            for block in blocks {
+                System::set_block_number(block);
+                assert_ok!(PriceFeedModule::set_price(
+                    Origin::signed(1),
+                    pair.clone(),
+                    100,
+                    2,
+                    None,
+                    None
+                ));
+            }
+        };
+        let history_blocks = || {
+            PriceFeedModule::price_history(&stored_pair)
+                .iter()
+                .map(PriceRecord::block_number)
+                .collect::<Vec<_>>()
+        };
+
+        // Pairs are `Standard` by default.
+        StandardPriceHistoryLen::set(2);
+        set_prices(1..4);
+        assert_eq!(history_blocks(), vec![2, 3]);
+
+        assert_noop!(
+            PriceFeedModule::set_retention_tier(
+                Origin::signed(1),
+                pair.clone(),
+                RetentionTier::Critical
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_retention_tier(
+            Origin::root(),
+            pair.clone(),
+            RetentionTier::Critical
+        ));
+        assert_eq!(
+            FeedStates::<Test>::get(&stored_pair).retention,
+            RetentionTier::Critical
+        );
+        set_prices(4..7);
+        assert_eq!(history_blocks(), vec![3, 4, 5, 6]);
+
+        // Lowering the tier trims the history right away.
+        assert_ok!(PriceFeedModule::set_retention_tier(
+            Origin::root(),
+            pair.clone(),
+            RetentionTier::Ephemeral
+        ));
+        assert_eq!(history_blocks(), vec![6]);
+        set_prices(7..8);
+        assert_eq!(history_blocks(), vec![7]);
+
+        // Tier lengths are capped by `MaxPriceHistoryLen`.
+        StandardPriceHistoryLen::set(10);
+        assert_eq!(PriceFeedModule::history_len(RetentionTier::Standard), 4);
+    });
+}
+
+#[test]
+fn lookup_any_orientation() {
+    use crate::PairOrientation;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        assert_eq!(PriceFeedModule::lookup_any_orientation(&stored_pair), None);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            25,
+            2,
+            None,
+            None
+        ));
+
+        let record = PriceRecord::new(25, 2, 0);
+        assert_eq!(
+            PriceFeedModule::lookup_any_orientation(&stored_pair),
+            Some((record, PairOrientation::Direct))
+        );
+        assert_eq!(
+            PriceFeedModule::lookup_any_orientation(&stored_pair.clone().swap()),
+            Some((record, PairOrientation::Inverted))
+        );
+        assert_eq!(record.inverse(0), Some(PriceRecord::new(4, 0, 0)));
+
+        // The requested orientation takes precedence.
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone().swap(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.swap(),
+            3,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::lookup_any_orientation(&stored_pair.swap()),
+            Some((PriceRecord::new(3, 0, 0), PairOrientation::Direct))
+        );
+    });
+}
+
+#[test]
+fn route_price() {
+    use price_provider::RoutedPriceProvider;
+
+    new_test_ext().execute_with(|| {
+        for (pair, price, decimals) in [(("BTC", "USD"), 2_500_000, 2), (("USD", "EUR"), 92, 2)] {
+            let pair = CurrencySymbolPair::new(pair.0, pair.1).map_pair(ToOwned::to_owned);
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                1
+            ));
+
+            assert_eq!(PriceFeedModule::route_price("BTC", "USD", "EUR"), Ok(None));
+            System::set_block_number(System::block_number() + 1);
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair,
+                price,
+                decimals,
+                None,
+                None
+            ));
+        }
+
+        // 25000.00 * 0.92 = 23000.0000 at the lower block number.
+        assert_eq!(
+            PriceFeedModule::route_price("BTC", "USD", "EUR"),
+            Ok(Some(PriceRecord::new(230_000_000, 4, 1)))
+        );
+        assert_eq!(PriceFeedModule::route_price("BTC", "EUR", "USD"), Ok(None));
+        assert!(PriceFeedModule::route_price("BTC", "USDTS", "EUR").is_err());
+    });
+}
+
+#[test]
+fn amount_in_minor_units() {
+    use frame_support::parameter_types;
+    use price_provider::{MinorUnitExponentList, MinorUnitsPriceProvider};
+
+    parameter_types! {
+        pub const ExponentList: &'static [(&'static str, u8)] = &[("USD", 2), ("DOCK", 6)];
+    }
+    type Exponents = MinorUnitExponentList<ExponentList>;
+    let amount_in_minor_units = |from: &'static str, to: &'static str, major_amount| {
+        <PriceFeedModule as MinorUnitsPriceProvider<Test, Exponents>>::amount_in_minor_units(
+            CurrencySymbolPair::new(from, to),
+            major_amount,
+        )
+    };
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("DOCK", "USD").map_pair(ToOwned::to_owned);
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_eq!(amount_in_minor_units("DOCK", "USD", 10), Ok(None));
+
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            1_234,
+            5,
+            None,
+            None
+        ));
+        // 1000 DOCK at 0.01234 USD = 12.34 USD = 1234 cents.
+        assert_eq!(amount_in_minor_units("DOCK", "USD", 1_000), Ok(Some(1_234)));
+        // 10 DOCK = 0.1234 USD rounded down to 12 cents.
+        assert_eq!(amount_in_minor_units("DOCK", "USD", 10), Ok(Some(12)));
+        assert_eq!(amount_in_minor_units("USD", "DOCK", 10), Ok(None));
+        assert_eq!(amount_in_minor_units("DOCK", "EUR", 10), Ok(None));
+        assert!(amount_in_minor_units("DOCKS", "USD", 10).is_err());
+    });
+}
+
+#[test]
+fn ewma() {
+    use price_provider::ExponentiallyWeightedPriceProvider;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_eq!(PriceFeedModule::ewma(&stored_pair), None);
+
+        System::set_block_number(10);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            100,
+            2,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::ewma(&stored_pair),
+            Some(PriceRecord::new(100, 2, 10))
+        );
+
+        // Each half-life halves the weight of the average: (1.00 + 3.0) / 2 and (2.00 + 4.00) / 2.
+        for (block, price, decimals, ewma) in [(20, 30, 1, 200), (30, 400, 2, 300)] {
+            System::set_block_number(block);
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                price,
+                decimals,
+                None,
+                None
+            ));
+            assert_eq!(
+                PriceFeedModule::ewma(&stored_pair),
+                Some(PriceRecord::new(ewma, 2, block))
+            );
+        }
+        assert_eq!(
+            PriceFeedModule::pair_ewma(CurrencySymbolPair::new("A", "B")),
+            Ok(Some(PriceRecord::new(300, 2, 30)))
+        );
+        assert!(PriceFeedModule::pair_ewma(CurrencySymbolPair::new("ABCDE", "B")).is_err());
+
+        // Root overrides are folded as well.
+        System::set_block_number(40);
+        assert_ok!(PriceFeedModule::force_set_price(
+            Origin::root(),
+            pair.clone(),
+            500,
+            2
+        ));
+        assert_eq!(
+            PriceFeedModule::ewma(&stored_pair),
+            Some(PriceRecord::new(400, 2, 40))
+        );
+
+        assert_ok!(PriceFeedModule::force_clear_price(Origin::root(), pair));
+        assert_eq!(PriceFeedModule::ewma(&stored_pair), None);
+    });
+}
+
+#[test]
+fn twap() {
+    use price_provider::TimeWeightedPriceProvider;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        for (block, price, decimals) in [(10, 100, 2), (20, 30, 1), (30, 400, 2)] {
+            System::set_block_number(block);
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                price,
+                decimals,
+                None,
+                None
+            ));
+        }
+        System::set_block_number(40);
+
+        // 10 blocks of 3.0 and 10 blocks of 4.00.
+        assert_eq!(
+            PriceFeedModule::twap(&stored_pair, 20),
+            Some(PriceRecord::new(350, 2, 40))
+        );
+        assert_eq!(
+            PriceFeedModule::twap(&stored_pair, 0),
+            Some(PriceRecord::new(400, 2, 40))
+        );
+        // Blocks before the first record are ignored.
+        assert_eq!(
+            PriceFeedModule::twap(&stored_pair, 100),
+            Some(PriceRecord::new(266, 2, 40))
+        );
+        assert_eq!(
+            PriceFeedModule::pair_twap(CurrencySymbolPair::new("A", "B"), 20),
+            Ok(Some(PriceRecord::new(350, 2, 40)))
+        );
+        assert_eq!(
+            PriceFeedModule::pair_twap(CurrencySymbolPair::new("B", "A"), 20),
+            Ok(None)
+        );
+        assert!(PriceFeedModule::pair_twap(CurrencySymbolPair::new("ABCDE", "A"), 20).is_err());
+    });
+}
+
+#[test]
+fn block_number_provider() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        System::set_block_number(5);
+        RelayBlockNumber::set(Some(1_000));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(10, 1, 1_000))
+        );
+
+        // Records are timestamped using the provider, so the local block number is irrelevant.
+        System::set_block_number(6);
+        RelayBlockNumber::set(Some(999));
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair, 11, 1, None, None),
+            Error::<Test>::NonMonotonicPriceBlockNumber
+        );
+    });
+}
+
+#[test]
+fn price_status() {
+    use crate::PriceStatus;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_eq!(PriceFeedModule::price_status(&stored_pair), None);
+
+        let record = PriceRecord::new(10, 1, 10);
+        Prices::<Test>::insert(&stored_pair, record);
+
+        System::set_block_number(110);
+        assert_eq!(
+            PriceFeedModule::price_status(&stored_pair),
+            Some(PriceStatus::Fresh(record))
+        );
+        assert_eq!(PriceFeedModule::pair_price(pair.clone()), Ok(Some(record)));
+
+        System::set_block_number(111);
+        assert_eq!(
+            PriceFeedModule::price_status_of(pair.clone()),
+            Some(PriceStatus::Stale(record))
+        );
+        assert_eq!(PriceFeedModule::pair_price(pair), Ok(None));
+        // The raw record remains available.
+        assert_eq!(PriceFeedModule::price(&stored_pair), Some(record));
+    });
+}
+
+#[test]
+fn read_paths_never_write() {
+    use sp_runtime::StateVersion;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+        System::set_block_number(1_000);
+
+        let root = sp_io::storage::root(StateVersion::V1);
+        assert_eq!(PriceFeedModule::pair_price(pair.clone()), Ok(None));
+        assert!(PriceFeedModule::price_status(&stored_pair)
+            .unwrap()
+            .is_stale());
+        assert!(PriceFeedModule::interpolated_price(&stored_pair, 0).is_some());
+        assert_eq!(sp_io::storage::root(StateVersion::V1), root);
+    });
+}
+
+#[test]
+fn failed_batch_leaves_no_partial_state() {
+    use frame_support::storage::with_storage_layer;
+
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+        for from in ["A", "C"] {
+            assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair(from), 1));
+        }
+        assert_ok!(PriceFeedModule::set_price_bounds(
+            Origin::root(),
+            pair("C"),
+            crate::PriceBounds::new(1, 5, 0)
+        ));
+
+        // The second submission is rejected, so the whole batch is rolled back.
+        assert_noop!(
+            with_storage_layer(|| {
+                PriceFeedModule::set_price(Origin::signed(1), pair("A"), 10, 1, None, None)?;
+                PriceFeedModule::set_price(Origin::signed(1), pair("C"), 10, 0, None, None)
+            }),
+            Error::<Test>::PriceOutOfBounds
+        );
+        assert_eq!(PriceFeedModule::pair_price(pair("A")), Ok(None));
+        assert!(!crate::PricesChanged::<Test>::get());
+    });
+}
+
+#[test]
+fn http_source_helpers() {
+    use crate::{http_source::json_value_at, HttpSource};
+
+    let json = br#" {"data": {"symbol": "DOCK", "escaped\"key": 1, "prices": [{"usd": 0.0215}, {"usd": "0.02"}], "empty": {}}} "#;
+    assert_eq!(json_value_at(json, "data.symbol"), Some(&b"\"DOCK\""[..]));
+    assert_eq!(
+        json_value_at(json, "data.prices.0.usd"),
+        Some(&b"0.0215"[..])
+    );
+    assert_eq!(
+        json_value_at(json, "data.prices.1.usd"),
+        Some(&b"\"0.02\""[..])
+    );
+    assert_eq!(json_value_at(json, "data.empty"), Some(&b"{}"[..]));
+    assert_eq!(json_value_at(b" 1.5 ", ""), Some(&b"1.5"[..]));
+    assert_eq!(json_value_at(json, "data.prices.2"), None);
+    assert_eq!(json_value_at(json, "data.missing"), None);
+    assert_eq!(json_value_at(json, "data.symbol.usd"), None);
+    assert_eq!(json_value_at(br#"{"a": [1, }"#, "b"), None);
+    assert_eq!(json_value_at(br#"{"a": "#, "a"), None);
+
+    let source = |json_path: &str, decimals| HttpSource {
+        url: "https://example.com".try_into().unwrap(),
+        json_path: json_path.try_into().unwrap(),
+        decimals,
+    };
+    assert_eq!(
+        source("data.prices.0.usd", 6).extract_price(json),
+        Some(21_500)
+    );
+    assert_eq!(source("data.prices.0.usd", 2).extract_price(json), Some(2));
+    assert_eq!(source("data.prices.1.usd", 2).extract_price(json), Some(2));
+    assert_eq!(source("data.symbol", 2).extract_price(json), None);
+    assert_eq!(source("", 30).extract_price(b"1"), None);
+}
+
+#[test]
+fn set_http_source() {
+    use crate::{HttpSource, HttpSources};
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let source = HttpSource {
+            url: "https://example.com/price".try_into().unwrap(),
+            json_path: "price".try_into().unwrap(),
+            decimals: 4,
+        };
+
+        assert_noop!(
+            PriceFeedModule::set_http_source(Origin::signed(1), pair.clone(), Some(source.clone())),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::set_http_source(
+                Origin::root(),
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned),
+                Some(source.clone())
+            ),
+            DispatchError::from(BoundedStringConversionError::InvalidStringByteLen)
+        );
+
+        assert_ok!(PriceFeedModule::set_http_source(
+            Origin::root(),
+            pair.clone(),
+            Some(source.clone())
+        ));
+        assert_eq!(PriceFeedModule::http_source(&stored_pair), Some(source));
+
+        assert_ok!(PriceFeedModule::set_http_source(Origin::root(), pair, None));
+        assert!(!HttpSources::<Test>::contains_key(&stored_pair));
+    });
+}
+
+#[test]
+fn fetch_http_source_price() {
+    use crate::HttpSource;
+    use sp_core::offchain::{testing, OffchainWorkerExt};
+
+    let (offchain, state) = testing::TestOffchainExt::new();
+    let mut ext = new_test_ext();
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+
+    let source = HttpSource {
+        url: "https://example.com/price".try_into().unwrap(),
+        json_path: "data.price".try_into().unwrap(),
+        decimals: 3,
+    };
+    state.write().expect_request(testing::PendingRequest {
+        method: "GET".into(),
+        uri: "https://example.com/price".into(),
+        response: Some(br#"{"data": {"price": "1.23456"}}"#.to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+    state.write().expect_request(testing::PendingRequest {
+        method: "GET".into(),
+        uri: "https://example.com/price".into(),
+        response: Some(br#"{"data": {}}"#.to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+
+    ext.execute_with(|| {
+        assert_eq!(source.fetch_price(), Ok(1_234));
+        assert!(source.fetch_price().is_err());
+    });
+}
+
+#[test]
+fn time_locked_operator_removals() {
+    use crate::PendingOperatorRemoval;
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        OperatorRemovalDelay::set(10);
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        for operator in 1..=4 {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+
+        System::set_block_number(5);
+        assert_noop!(
+            PriceFeedModule::remove_operator(Origin::signed(1), pair.clone(), 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::remove_operator(Origin::root(), pair.clone(), 5),
+            Error::<Test>::OperatorDoesNotExist
+        );
+        assert_ok!(PriceFeedModule::remove_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_noop!(
+            PriceFeedModule::remove_operator(Origin::root(), pair.clone(), 1),
+            Error::<Test>::OperatorRemovalAlreadyScheduled
+        );
+        assert_eq!(
+            PriceFeedModule::pending_operator_removals().into_inner(),
+            vec![PendingOperatorRemoval {
+                pair: stored_pair.clone(),
+                operator: 1,
+                at: 15
+            }]
+        );
+
+        System::set_block_number(6);
+        assert_ok!(PriceFeedModule::remove_operator(
+            Origin::root(),
+            pair.clone(),
+            2
+        ));
+        assert_noop!(
+            PriceFeedModule::remove_operator(Origin::root(), pair.clone(), 3),
+            Error::<Test>::TooManyPendingOperatorRemovals
+        );
+
+        // Operators keep submitting prices until their removal takes effect.
+        PriceFeedModule::on_initialize(14);
+        assert!(PriceFeedModule::is_pair_operator(&stored_pair, &1));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            0,
+            None,
+            None
+        ));
+
+        // Emergency removal bypasses the delay and cancels the pending one.
+        assert_noop!(
+            PriceFeedModule::remove_operator_immediately(Origin::signed(1), pair.clone(), 2),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::remove_operator_immediately(
+            Origin::root(),
+            pair.clone(),
+            2
+        ));
+        assert!(!PriceFeedModule::is_pair_operator(&stored_pair, &2));
+        assert_eq!(PriceFeedModule::pending_operator_removals().len(), 1);
+        assert_ok!(PriceFeedModule::do_try_state());
+        assert_ok!(PriceFeedModule::remove_operator_immediately(
+            Origin::root(),
+            pair.clone(),
+            3
+        ));
+        assert_noop!(
+            PriceFeedModule::remove_operator_immediately(Origin::root(), pair.clone(), 3),
+            Error::<Test>::OperatorDoesNotExist
+        );
+
+        PriceFeedModule::on_initialize(15);
+        assert!(!PriceFeedModule::is_pair_operator(&stored_pair, &1));
+        assert!(!crate::OperatorLastSubmission::<Test>::contains_key(
+            &stored_pair,
+            1
+        ));
+        assert!(PriceFeedModule::pending_operator_removals().is_empty());
+        assert_ok!(PriceFeedModule::do_try_state());
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair, 10, 0, None, None),
+            Error::<Test>::NotAnOperator
+        );
+        assert!(PriceFeedModule::is_pair_operator(&stored_pair, &4));
+    });
+}
+
+#[test]
+fn evm_contract_helpers() {
+    use crate::evm_contract::{
+        answer_to_price, decode_address, decode_latest_round_answer, EvmPriceError,
+    };
+    use sp_core::{H160, U256};
+
+    let word = |value: U256| {
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        word
+    };
+
+    let address = H160::repeat_byte(7);
+    let mut output = vec![0u8; 12];
+    output.extend_from_slice(address.as_bytes());
+    assert_eq!(decode_address(&output), Some(address));
+    output[0] = 1;
+    assert_eq!(decode_address(&output), None);
+    assert_eq!(decode_address(&output[..31]), None);
+
+    let round_data = |answer: U256| -> Vec<u8> {
+        [1.into(), answer, 2.into(), 3.into(), 1.into()]
+            .into_iter()
+            .flat_map(word)
+            .collect()
+    };
+    assert_eq!(
+        decode_latest_round_answer(&round_data(123.into())),
+        Ok(123.into())
+    );
+    assert_eq!(
+        decode_latest_round_answer(&round_data(U256::zero())),
+        Err(EvmPriceError::NonPositiveAnswer)
+    );
+    // -1 as `int256`
+    assert_eq!(
+        decode_latest_round_answer(&round_data(U256::MAX)),
+        Err(EvmPriceError::NonPositiveAnswer)
+    );
+    assert_eq!(
+        decode_latest_round_answer(&round_data(1.into())[..159]),
+        Err(EvmPriceError::InvalidRoundData)
+    );
+
+    assert_eq!(answer_to_price(2_150_000.into(), 8), Some((2_150_000, 8)));
+    assert_eq!(
+        answer_to_price(U256::from(u64::MAX) * 10 + 5, 8),
+        Some((u64::MAX, 7))
+    );
+    assert_eq!(answer_to_price(U256::from(u64::MAX) * 10, 0), None);
+    assert_eq!(answer_to_price(U256::zero(), 8), None);
+}
+
+#[test]
+fn read_contract_prices() {
+    use crate::{ContractConfig, PriceHistory};
+    use frame_support::traits::Hooks;
+    use sp_core::{H160, U256};
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let (proxy, aggregator) = (H160::repeat_byte(1), H160::repeat_byte(2));
+        let (aggregator_call, price_call) =
+            (b"\x24\x5a\x7b\xfc".to_vec(), b"\xfe\xaf\x96\x8c".to_vec());
+        let config = ContractConfig {
+            address: proxy,
+            query_aggregator_abi_encoded: Some(aggregator_call.clone().try_into().unwrap()),
+            query_price_abi_encoded: price_call.clone().try_into().unwrap(),
+            decimals: 8,
+            update_period: 5,
+        };
+
+        assert_noop!(
+            PriceFeedModule::set_contract_config(
+                Origin::root(),
+                pair.clone(),
+                Some(ContractConfig {
+                    update_period: 0,
+                    ..config.clone()
+                })
+            ),
+            Error::<Test>::InvalidContractConfig
+        );
+        assert_noop!(
+            PriceFeedModule::set_contract_config(
+                Origin::signed(1),
+                pair.clone(),
+                Some(config.clone())
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_contract_config(
+            Origin::root(),
+            pair.clone(),
+            Some(config.clone())
+        ));
+        assert_eq!(PriceFeedModule::contract_config(&stored_pair), Some(config));
+
+        let mut aggregator_output = vec![0u8; 12];
+        aggregator_output.extend_from_slice(aggregator.as_bytes());
+        TestEvmCaller::set_output(proxy, aggregator_call, aggregator_output);
+        let round_data = |answer: u64| -> Vec<u8> {
+            [1.into(), U256::from(answer), 2.into(), 3.into(), 1.into()]
+                .into_iter()
+                .flat_map(|value: U256| {
+                    let mut word = [0u8; 32];
+                    value.to_big_endian(&mut word);
+                    word
+                })
+                .collect()
+        };
+        TestEvmCaller::set_output(aggregator, price_call.clone(), round_data(2_150_000));
+
+        // The price is read every 5 blocks only.
+        System::set_block_number(4);
+        PriceFeedModule::on_initialize(4);
+        assert_eq!(PriceFeedModule::price(&stored_pair), None);
+
+        System::set_block_number(5);
+        let weight = PriceFeedModule::on_initialize(5);
+        assert!(weight.ref_time() >= 2 * TestEvmCaller::CALL_WEIGHT);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(2_150_000, 8, 5))
+        );
+        assert_eq!(PriceHistory::<Test>::get(&stored_pair).len(), 1);
+
+        // Prices out of bounds aren't applied, and nothing is read in safe mode.
+        TestEvmCaller::set_output(aggregator, price_call.clone(), round_data(1));
+        assert_ok!(PriceFeedModule::set_price_bounds(
+            Origin::root(),
+            pair.clone(),
+            Some(crate::PriceBounds::new(2, u64::MAX, 8).unwrap())
+        ));
+        System::set_block_number(10);
+        PriceFeedModule::on_initialize(10);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(2_150_000, 8, 5))
+        );
+
+        SafeModeEntered::set(true);
+        TestEvmCaller::set_output(aggregator, price_call, round_data(3_000_000));
+        System::set_block_number(15);
+        PriceFeedModule::on_initialize(15);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(2_150_000, 8, 5))
+        );
+
+        SafeModeEntered::set(false);
+        System::set_block_number(20);
+        PriceFeedModule::on_initialize(20);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(3_000_000, 8, 20))
+        );
+
+        assert_eq!(PriceFeedModule::contract_last_visit(&stored_pair), Some(20));
+
+        assert_ok!(PriceFeedModule::set_contract_config(
+            Origin::root(),
+            pair,
+            None
+        ));
+        assert_eq!(PriceFeedModule::contract_config(&stored_pair), None);
+        assert_eq!(PriceFeedModule::contract_last_visit(&stored_pair), None);
+        assert_ok!(PriceFeedModule::do_try_state());
+    });
+}
+
+#[test]
+fn read_contract_prices_in_turns() {
+    use crate::{ContractConfig, ContractVisitCursor};
+    use frame_support::traits::Hooks;
+    use sp_core::{H160, U256};
+
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+        let stored_pair = |from: &str| {
+            pair(from)
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap()
+        };
+        let price_call = b"\xfe\xaf\x96\x8c".to_vec();
+        let round_data = |answer: u64| -> Vec<u8> {
+            [1.into(), U256::from(answer), 2.into(), 3.into(), 1.into()]
+                .into_iter()
+                .flat_map(|value: U256| {
+                    let mut word = [0u8; 32];
+                    value.to_big_endian(&mut word);
+                    word
+                })
+                .collect()
+        };
+        MaxContractVisitsPerBlock::set(2);
+        for (idx, from) in ["A", "C", "D"].into_iter().enumerate() {
+            let address = H160::repeat_byte(idx as u8 + 1);
+            TestEvmCaller::set_output(address, price_call.clone(), round_data(100));
+            assert_ok!(PriceFeedModule::set_contract_config(
+                Origin::root(),
+                pair(from),
+                Some(ContractConfig {
+                    address,
+                    query_aggregator_abi_encoded: None,
+                    query_price_abi_encoded: price_call.clone().try_into().unwrap(),
+                    decimals: 2,
+                    update_period: 5,
+                })
+            ));
+        }
+        let read = || {
+            ["A", "C", "D"]
+                .into_iter()
+                .filter(|from| PriceFeedModule::price(&stored_pair(from)).is_some())
+                .count()
+        };
+
+        // Two configs are visited at the due block, and the third one is caught up with in the next block.
+        System::set_block_number(5);
+        PriceFeedModule::on_initialize(5);
+        assert_eq!(read(), 2);
+        assert!(ContractVisitCursor::<Test>::get().is_some());
+
+        System::set_block_number(6);
+        PriceFeedModule::on_initialize(6);
+        assert_eq!(read(), 3);
+        assert_eq!(ContractVisitCursor::<Test>::get(), None);
+
+        // Visits which aren't due don't read prices.
+        for from in ["A", "C", "D"] {
+            assert_ok!(PriceFeedModule::force_clear_price(
+                Origin::root(),
+                pair(from)
+            ));
+        }
+        System::set_block_number(7);
+        PriceFeedModule::on_initialize(7);
+        System::set_block_number(8);
+        PriceFeedModule::on_initialize(8);
+        assert_eq!(read(), 0);
+        assert_ok!(PriceFeedModule::do_try_state());
+    });
+}
+
+#[test]
+fn commit_reveal_helpers() {
+    use crate::commit_reveal::{
+        median_confidence, median_price, CommitRevealPhase, CommitRevealScheme,
+    };
+
+    assert_eq!(CommitRevealScheme::new(10, 0, 5), None);
+    assert_eq!(CommitRevealScheme::new(10, 5, 0), None);
+    assert_eq!(CommitRevealScheme::new(10, 6, 5), None);
+    let scheme = CommitRevealScheme::new(10, 4, 3).unwrap();
+
+    assert_eq!(scheme.phase_at(0), (0, CommitRevealPhase::Commit));
+    assert_eq!(scheme.phase_at(3), (0, CommitRevealPhase::Commit));
+    assert_eq!(scheme.phase_at(4), (0, CommitRevealPhase::Reveal));
+    assert_eq!(scheme.phase_at(6), (0, CommitRevealPhase::Reveal));
+    assert_eq!(scheme.phase_at(7), (0, CommitRevealPhase::Closed));
+    assert_eq!(scheme.phase_at(12), (1, CommitRevealPhase::Commit));
+    assert!(!scheme.is_round_over(0, 6));
+    assert!(scheme.is_round_over(0, 7));
+    assert!(scheme.is_round_over(0, 12));
+    assert!(!scheme.is_round_over(1, 12));
+    assert_eq!(scheme.reveal_deadline(0), 7);
+    assert_eq!(scheme.reveal_deadline(1), 17);
+    assert!(scheme.is_round_over(1, scheme.reveal_deadline(1)));
+    assert!(!scheme.is_round_over(1, scheme.reveal_deadline(1) - 1));
+
+    assert_eq!(median_price(&[]), None);
+    assert_eq!(median_price(&[(30, 0), (10, 0), (20, 0)]), Some((20, 0)));
+    assert_eq!(median_price(&[(10, 0), (205, 1)]), Some((152, 1)));
+    assert_eq!(
+        median_price(&[(u64::MAX, 0), (u64::MAX, 0)]),
+        Some((u64::MAX, 0))
+    );
+    assert_eq!(
+        median_price(&[(u64::MAX, 0), (1, 1)]),
+        Some((u64::MAX / 2, 0))
+    );
+
+    assert_eq!(median_confidence(&[(20, 0)], (20, 0)), None);
+    assert_eq!(
+        median_confidence(&[(30, 0), (10, 0), (20, 0)], (20, 0)),
+        Some(10)
+    );
+    assert_eq!(median_confidence(&[(10, 0), (205, 1)], (152, 1)), Some(53));
+    assert_eq!(median_confidence(&[(10, 0), (10, 0)], (10, 0)), Some(0));
+    assert_eq!(
+        median_confidence(&[(u64::MAX, 0), (1, 1)], (u64::MAX / 2, 0)),
+        Some(1 << 63)
+    );
+    // The distance is rounded up to the median decimals.
+    assert_eq!(median_confidence(&[(11, 1), (20, 1)], (2, 0)), Some(1));
+}
+
+#[test]
+fn commit_reveal() {
+    use crate::{
+        commit_reveal::price_commitment, CommitRevealScheme, PriceCommitments, RevealDeadlines,
+    };
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let scheme = CommitRevealScheme::new(10, 4, 3).unwrap();
+        let commitment = |round, operator: u64, price, decimals, salt: u8| {
+            price_commitment::<BlakeTwo256, _, _>(
+                &stored_pair,
+                round,
+                &operator,
+                price,
+                decimals,
+                &[salt; 32],
+            )
+        };
+        for operator in 1..=4 {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+
+        assert_noop!(
+            PriceFeedModule::commit_price(
+                Origin::signed(1),
+                pair.clone(),
+                commitment(0, 1, 10, 0, 1)
+            ),
+            Error::<Test>::CommitRevealNotEnabled
+        );
+        assert_noop!(
+            PriceFeedModule::set_commit_reveal_scheme(
+                Origin::root(),
+                pair.clone(),
+                Some(CommitRevealScheme {
+                    reveal_length: 7,
+                    ..scheme
+                })
+            ),
+            Error::<Test>::InvalidCommitRevealScheme
+        );
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(scheme)
+        ));
+        assert_eq!(
+            PriceFeedModule::commit_reveal_scheme(&stored_pair),
+            Some(scheme)
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 0, None, None),
+            Error::<Test>::CommitRevealRequired
+        );
+
+        // Commit phase of the round `1`.
+        System::set_block_number(10);
+        assert_noop!(
+            PriceFeedModule::commit_price(
+                Origin::signed(5),
+                pair.clone(),
+                commitment(1, 5, 10, 0, 5)
+            ),
+            Error::<Test>::NotAnOperator
+        );
+        for (operator, price) in [(1, 10), (2, 30), (3, 20)] {
+            assert_ok!(PriceFeedModule::commit_price(
+                Origin::signed(operator),
+                pair.clone(),
+                commitment(1, operator, price, 0, operator as u8)
+            ));
+        }
+        // Commitments can be replaced within the round.
+        assert_ok!(PriceFeedModule::commit_price(
+            Origin::signed(2),
+            pair.clone(),
+            commitment(1, 2, 40, 0, 2)
+        ));
+        assert_noop!(
+            PriceFeedModule::commit_price(
+                Origin::signed(4),
+                pair.clone(),
+                commitment(1, 4, 10, 0, 4)
+            ),
+            Error::<Test>::TooManyCommitments
+        );
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(1), pair.clone(), 10, 0, [1; 32]),
+            Error::<Test>::OutsideRevealPhase
+        );
+        // The round is scheduled at the end of its reveal phase.
+        assert!(RevealDeadlines::<Test>::contains_key(17, &stored_pair));
+
+        // Reveal phase.
+        System::set_block_number(14);
+        assert_noop!(
+            PriceFeedModule::commit_price(
+                Origin::signed(1),
+                pair.clone(),
+                commitment(1, 1, 10, 0, 1)
+            ),
+            Error::<Test>::OutsideCommitPhase
+        );
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(1), pair.clone(), 11, 0, [1; 32]),
+            Error::<Test>::CommitmentMismatch
+        );
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(4), pair.clone(), 10, 0, [4; 32]),
+            Error::<Test>::CommitmentNotFound
+        );
+        assert_ok!(PriceFeedModule::reveal_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            0,
+            [1; 32]
+        ));
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(1), pair.clone(), 10, 0, [1; 32]),
+            Error::<Test>::PriceAlreadyRevealed
+        );
+        assert_ok!(PriceFeedModule::reveal_price(
+            Origin::signed(2),
+            pair.clone(),
+            40,
+            0,
+            [2; 32]
+        ));
+
+        // Nothing is aggregated or rewarded until the reveal phase ends.
+        PriceFeedModule::on_initialize(16);
+        assert_eq!(PriceFeedModule::price(&stored_pair), None);
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 0);
+
+        System::set_block_number(17);
+        PriceFeedModule::on_initialize(17);
+        // The confidence interval covers both revealed prices.
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(25, 0, 17).with_confidence(Some(15)))
+        );
+        assert_eq!(PriceCommitments::<Test>::get(&stored_pair), None);
+        assert!(!RevealDeadlines::<Test>::contains_key(17, &stored_pair));
+        assert_eq!(TestUnrevealedCommitments::take(), vec![(3, 1)]);
+        // Only the revealed prices are rewarded.
+        assert_eq!(
+            (1..=3)
+                .map(PriceFeedModule::claimable_rewards)
+                .collect::<Vec<_>>(),
+            vec![5, 5, 0]
+        );
+
+        // Commitments of the previous round can't be revealed in the next one.
+        System::set_block_number(20);
+        assert_ok!(PriceFeedModule::commit_price(
+            Origin::signed(1),
+            pair.clone(),
+            commitment(1, 1, 10, 0, 1)
+        ));
+        System::set_block_number(24);
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(1), pair.clone(), 10, 0, [1; 32]),
+            Error::<Test>::CommitmentMismatch
+        );
+
+        // Removing the scheme takes effect once the pending round is aggregated.
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            None
+        ));
+        assert!(PriceCommitments::<Test>::get(&stored_pair).is_some());
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 0, None, None),
+            Error::<Test>::CommitRevealRequired
+        );
+        System::set_block_number(27);
+        PriceFeedModule::on_initialize(27);
+        assert_eq!(TestUnrevealedCommitments::take(), vec![(1, 2)]);
+        assert_eq!(PriceFeedModule::commit_reveal_scheme(&stored_pair), None);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair,
+            10,
+            0,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn rejected_revealed_prices_are_not_rewarded() {
+    use crate::{commit_reveal::price_commitment, CommitRevealScheme};
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let commitment = |round, operator: u64| {
+            price_commitment::<BlakeTwo256, _, _>(
+                &stored_pair,
+                round,
+                &operator,
+                10,
+                0,
+                &[operator as u8; 32],
+            )
+        };
+        for operator in [1, 2] {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(CommitRevealScheme::new(10, 4, 3).unwrap())
+        ));
+
+        System::set_block_number(10);
+        for operator in [1, 2] {
+            assert_ok!(PriceFeedModule::commit_price(
+                Origin::signed(operator),
+                pair.clone(),
+                commitment(1, operator)
+            ));
+        }
+        System::set_block_number(14);
+        assert_ok!(PriceFeedModule::reveal_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            0,
+            [1; 32]
+        ));
+        assert_ok!(PriceFeedModule::pause_pair(Origin::root(), pair.clone()));
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(2), pair.clone(), 10, 0, [2; 32]),
+            Error::<Test>::PairPaused
+        );
+
+        // The price revealed before the pause is rejected at the aggregation.
+        System::set_block_number(17);
+        PriceFeedModule::on_initialize(17);
+        assert_eq!(PriceFeedModule::price(&stored_pair), None);
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 0);
+        assert_eq!(PriceFeedModule::claimable_rewards(2), 0);
+    });
+}
+
+#[test]
+fn outlier_reveals_are_discarded() {
+    use crate::{commit_reveal::price_commitment, CommitRevealScheme};
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let reveals = [(1, 10), (2, 12), (3, 40)];
+        for (operator, _) in reveals {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(CommitRevealScheme::new(10, 4, 3).unwrap())
+        ));
+
+        System::set_block_number(10);
+        for (operator, price) in reveals {
+            assert_ok!(PriceFeedModule::commit_price(
+                Origin::signed(operator),
+                pair.clone(),
+                price_commitment::<BlakeTwo256, _, _>(
+                    &stored_pair,
+                    1,
+                    &operator,
+                    price,
+                    0,
+                    &[operator as u8; 32],
+                )
+            ));
+        }
+        System::set_block_number(14);
+        for (operator, price) in reveals {
+            assert_ok!(PriceFeedModule::reveal_price(
+                Origin::signed(operator),
+                pair.clone(),
+                price,
+                0,
+                [operator as u8; 32]
+            ));
+        }
+
+        // `40` deviates from the median `12` by 28 while the MAD is 2.
+        System::set_block_number(17);
+        PriceFeedModule::on_initialize(17);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(11, 0, 17).with_confidence(Some(1)))
+        );
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 5);
+        assert_eq!(PriceFeedModule::claimable_rewards(2), 5);
+        assert_eq!(PriceFeedModule::claimable_rewards(3), 0);
+    });
+}
+
+#[test]
+fn commit_reveal_scheme_switch() {
+    use crate::{
+        commit_reveal::price_commitment, CommitRevealScheme, PendingCommitRevealSchemes,
+        PriceCommitments,
+    };
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let old_scheme = CommitRevealScheme::new(10, 4, 3).unwrap();
+        let new_scheme = CommitRevealScheme::new(20, 5, 5).unwrap();
+        let commit = |round, operator: u64, price| {
+            PriceFeedModule::commit_price(
+                Origin::signed(operator),
+                pair.clone(),
+                price_commitment::<BlakeTwo256, _, _>(
+                    &stored_pair,
+                    round,
+                    &operator,
+                    price,
+                    0,
+                    &[operator as u8; 32],
+                ),
+            )
+        };
+        let reveal = |operator: u64, price| {
+            PriceFeedModule::reveal_price(
+                Origin::signed(operator),
+                pair.clone(),
+                price,
+                0,
+                [operator as u8; 32],
+            )
+        };
+        for operator in 1..=3 {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+
+        // Without the pending round, the scheme is replaced immediately.
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(new_scheme)
+        ));
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(old_scheme)
+        ));
+        assert_eq!(
+            PriceFeedModule::commit_reveal_scheme(&stored_pair),
+            Some(old_scheme)
+        );
+        assert_eq!(PendingCommitRevealSchemes::<Test>::get(&stored_pair), None);
+
+        // The round `1` of the old scheme is pending once committed to.
+        System::set_block_number(10);
+        assert_ok!(commit(1, 1, 10));
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(new_scheme)
+        ));
+        assert_eq!(
+            PriceFeedModule::commit_reveal_scheme(&stored_pair),
+            Some(old_scheme)
+        );
+        assert_eq!(
+            PendingCommitRevealSchemes::<Test>::get(&stored_pair),
+            Some(Some(new_scheme))
+        );
+        assert_ok!(PriceFeedModule::do_try_state());
+
+        // The pending round keeps accepting submissions according to the old scheme.
+        System::set_block_number(13);
+        assert_ok!(commit(1, 2, 20));
+        System::set_block_number(14);
+        assert_noop!(commit(1, 3, 30), Error::<Test>::OutsideCommitPhase);
+        assert_ok!(reveal(1, 10));
+        System::set_block_number(16);
+        assert_ok!(reveal(2, 20));
+
+        // The last block of the old reveal phase.
+        PriceFeedModule::on_initialize(16);
+        assert!(PriceCommitments::<Test>::get(&stored_pair).is_some());
+        assert_eq!(
+            PriceFeedModule::commit_reveal_scheme(&stored_pair),
+            Some(old_scheme)
+        );
+
+        // The round is aggregated under the old scheme, and the new one applies to the following rounds.
+        System::set_block_number(17);
+        PriceFeedModule::on_initialize(17);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(15, 0, 17).with_confidence(Some(5)))
+        );
+        assert_eq!(
+            PriceFeedModule::commit_reveal_scheme(&stored_pair),
+            Some(new_scheme)
+        );
+        assert_eq!(PendingCommitRevealSchemes::<Test>::get(&stored_pair), None);
+        assert_ok!(PriceFeedModule::do_try_state());
+
+        // Block `17` is past the reveal phase of the new scheme's round `0`.
+        assert_noop!(commit(0, 1, 10), Error::<Test>::OutsideCommitPhase);
+        System::set_block_number(20);
+        assert_ok!(commit(1, 1, 10));
+        System::set_block_number(24);
+        assert_ok!(commit(1, 2, 30));
+        System::set_block_number(25);
+        assert_ok!(reveal(1, 10));
+        assert_ok!(reveal(2, 30));
+        System::set_block_number(30);
+        PriceFeedModule::on_initialize(30);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(20, 0, 30).with_confidence(Some(10)))
+        );
+        assert_eq!(TestUnrevealedCommitments::take(), vec![]);
+    });
+}
+
+#[test]
+fn reveal_deadlines() {
+    use crate::{
+        commit_reveal::price_commitment, CommitRevealScheme, PriceCommitments, RevealDeadlines,
+        RevealDeadlinesDrainedAt,
+    };
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let scheme = CommitRevealScheme::new(10, 4, 3).unwrap();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair.clone(),
+            Some(scheme)
+        ));
+        MaxRevealDeadlinesPerBlock::set(2);
+
+        System::set_block_number(10);
+        PriceFeedModule::on_initialize(10);
+        assert_eq!(RevealDeadlinesDrainedAt::<Test>::get(), Some(10));
+        assert_ok!(PriceFeedModule::commit_price(
+            Origin::signed(1),
+            pair.clone(),
+            price_commitment::<BlakeTwo256, _, _>(&stored_pair, 1, &1, 10, 0, &[1; 32])
+        ));
+        System::set_block_number(14);
+        assert_ok!(PriceFeedModule::reveal_price(
+            Origin::signed(1),
+            pair,
+            10,
+            0,
+            [1; 32]
+        ));
+
+        // Gaps between the provided block numbers are caught up with gradually.
+        RelayBlockNumber::set(Some(20));
+        PriceFeedModule::on_initialize(15);
+        assert_eq!(RevealDeadlinesDrainedAt::<Test>::get(), Some(12));
+        PriceFeedModule::on_initialize(16);
+        assert_eq!(RevealDeadlinesDrainedAt::<Test>::get(), Some(14));
+        PriceFeedModule::on_initialize(17);
+        assert_eq!(RevealDeadlinesDrainedAt::<Test>::get(), Some(16));
+        assert!(PriceCommitments::<Test>::contains_key(&stored_pair));
+
+        PriceFeedModule::on_initialize(18);
+        assert_eq!(RevealDeadlinesDrainedAt::<Test>::get(), Some(18));
+        assert_eq!(PriceCommitments::<Test>::get(&stored_pair), None);
+        assert_eq!(RevealDeadlines::<Test>::iter().count(), 0);
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(10, 0, 20))
+        );
+    });
+}
+
+#[test]
+fn operator_timeout() {
+    use crate::OperatorLastSubmission;
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        System::set_block_number(1);
+        for operator in [1, 2] {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+        assert_eq!(
+            PriceFeedModule::operator_last_submission(&stored_pair, 1),
+            Some(1)
+        );
+
+        // The timeout is disabled.
+        System::set_block_number(100);
+        assert!(PriceFeedModule::is_operator_active(&stored_pair, &1));
+
+        OperatorTimeout::set(10);
+        System::set_block_number(5);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(
+            PriceFeedModule::operator_last_submission(&stored_pair, 1),
+            Some(5)
+        );
+
+        System::set_block_number(11);
+        assert!(PriceFeedModule::is_operator_active(&stored_pair, &2));
+        System::set_block_number(12);
+        PriceFeedModule::on_initialize(12);
+        assert!(!PriceFeedModule::is_operator_active(&stored_pair, &2));
+        assert!(PriceFeedModule::is_operator_active(&stored_pair, &1));
+        System::set_block_number(16);
+        assert!(!PriceFeedModule::is_operator_active(&stored_pair, &1));
+
+        // Submitting makes the operator active again.
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(2),
+            pair.clone(),
+            10,
+            0,
+            None,
+            None
+        ));
+        assert!(PriceFeedModule::is_operator_active(&stored_pair, &2));
+
+        assert_ok!(PriceFeedModule::remove_operator(Origin::root(), pair, 2));
+        assert_eq!(OperatorLastSubmission::<Test>::get(&stored_pair, 2), None);
+    });
+}
+
+#[test]
+fn inactivity_sweeps() {
+    use crate::{InactivityCheck, InactivityCheckedUntil, OngoingInactivityCheck};
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        System::set_block_number(1);
+        for operator in [1, 2, 3] {
+            assert_ok!(PriceFeedModule::add_operator(
+                Origin::root(),
+                pair.clone(),
+                operator
+            ));
+        }
+
+        // The timeout is disabled.
+        InactivityCheckPeriod::set(5);
+        MaxInactivityChecksPerBlock::set(2);
+        PriceFeedModule::on_initialize(5);
+        assert_eq!(PriceFeedModule::ongoing_inactivity_check(), None);
+
+        OperatorTimeout::set(10);
+        // Sweeps start only every `InactivityCheckPeriod` blocks.
+        PriceFeedModule::on_initialize(4);
+        assert_eq!(PriceFeedModule::ongoing_inactivity_check(), None);
+
+        PriceFeedModule::on_initialize(5);
+        let check = PriceFeedModule::ongoing_inactivity_check().unwrap();
+        assert_eq!((check.after, check.until), (0, 5));
+        assert!(check.last_visited.is_some());
+
+        // The sweep continues from the last visited entry and finishes once fewer entries are left.
+        PriceFeedModule::on_initialize(6);
+        assert_eq!(OngoingInactivityCheck::<Test>::get(), None);
+        assert_eq!(InactivityCheckedUntil::<Test>::get(), 5);
+
+        PriceFeedModule::on_initialize(10);
+        assert_eq!(
+            PriceFeedModule::ongoing_inactivity_check().map(|check| (check.after, check.until)),
+            Some((5, 10))
+        );
+        PriceFeedModule::on_initialize(11);
+        assert_eq!(InactivityCheckedUntil::<Test>::get(), 10);
+
+        // Operators which submitted at block 1 become inactive at block 12,
+        // so only the sweep started at block 15 reports them.
+        let check = |after, until| InactivityCheck::<(), u64, u64> {
+            after,
+            until,
+            last_visited: None,
+        };
+        assert!(!check(5, 10).became_inactive(1, 10));
+        assert!(check(10, 15).became_inactive(1, 10));
+        assert!(!check(15, 20).became_inactive(1, 10));
+        assert!(check(10, 12).became_inactive(1, 10));
+        assert!(!check(12, 15).became_inactive(1, 10));
+
+        // `0` starts a sweep each block once the previous one is finished.
+        InactivityCheckPeriod::set(0);
+        MaxInactivityChecksPerBlock::set(4);
+        PriceFeedModule::on_initialize(13);
+        assert_eq!(OngoingInactivityCheck::<Test>::get(), None);
+        assert_eq!(InactivityCheckedUntil::<Test>::get(), 13);
+    });
+}
+
+#[test]
+fn price_access_restrictions() {
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair.clone(), &5),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+        assert_noop!(
+            PriceFeedModule::set_price_access_restricted(Origin::signed(1), pair.clone(), true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::set_price_access_restricted(
+            Origin::root(),
+            pair.clone(),
+            true
+        ));
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair.clone(), &5),
+            Err(Error::<Test>::PriceAccessDenied.into())
+        );
+
+        assert_ok!(PriceFeedModule::set_price_consumer(
+            Origin::root(),
+            pair.clone(),
+            5,
+            true
+        ));
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair.clone(), &5),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair.clone(), &6),
+            Err(Error::<Test>::PriceAccessDenied.into())
+        );
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(
+                CurrencySymbolPair::new("ABCDE", "B").map_pair(ToOwned::to_owned),
+                &5
+            ),
+            Err(BoundedStringConversionError::InvalidStringByteLen.into())
+        );
+
+        assert_ok!(PriceFeedModule::set_price_consumer(
+            Origin::root(),
+            pair.clone(),
+            5,
+            false
+        ));
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair.clone(), &5),
+            Err(Error::<Test>::PriceAccessDenied.into())
+        );
+        assert_ok!(PriceFeedModule::set_price_access_restricted(
+            Origin::root(),
+            pair.clone(),
+            false
+        ));
+        assert_eq!(
+            PriceFeedModule::price_for_consumer(pair, &6),
+            Ok(Some(PriceRecord::new(10, 1, 0)))
+        );
+    });
+}
+
+#[test]
+fn max_decimals() {
+    use crate::{ContractConfig, HttpSource, PriceBounds};
+    use sp_core::H160;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair.clone(), 10, 19, None, None),
+            Error::<Test>::TooManyPriceDecimals
+        );
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            18,
+            None,
+            None
+        ));
+
+        assert_noop!(
+            PriceFeedModule::set_price_bounds(
+                Origin::root(),
+                pair.clone(),
+                PriceBounds::new(1, 10, 19)
+            ),
+            Error::<Test>::TooManyPriceDecimals
+        );
+        assert_ok!(PriceFeedModule::set_price_bounds(
+            Origin::root(),
+            pair.clone(),
+            PriceBounds::new(1, 10, 18)
+        ));
+
+        let config = |decimals| ContractConfig {
+            address: H160::repeat_byte(1),
+            query_aggregator_abi_encoded: None,
+            query_price_abi_encoded: vec![1, 2, 3, 4].try_into().unwrap(),
+            decimals,
+            update_period: 1,
+        };
+        assert_noop!(
+            PriceFeedModule::set_contract_config(Origin::root(), pair.clone(), Some(config(19))),
+            Error::<Test>::TooManyPriceDecimals
+        );
+        assert_ok!(PriceFeedModule::set_contract_config(
+            Origin::root(),
+            pair.clone(),
+            Some(config(18))
+        ));
+
+        let source = |decimals| HttpSource {
+            url: "https://example.com/price".try_into().unwrap(),
+            json_path: "price".try_into().unwrap(),
+            decimals,
+        };
+        assert_noop!(
+            PriceFeedModule::set_http_source(Origin::root(), pair.clone(), Some(source(19))),
+            Error::<Test>::TooManyPriceDecimals
+        );
+        assert_ok!(PriceFeedModule::set_http_source(
+            Origin::root(),
+            pair,
+            Some(source(18))
+        ));
+    });
+}
+
+#[test]
+fn truncate_excessive_decimals() {
+    use crate::{migrations::decimals::truncate_excessive_decimals, PriceHistory};
+    use utils::BoundedFifo;
+
+    new_test_ext().execute_with(|| {
+        let stored_pair = |from: &str| {
+            CurrencySymbolPair::new(from, "B")
+                .map_pair(ToOwned::to_owned)
+                .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+                .unwrap()
+        };
+        Prices::<Test>::insert(stored_pair("A"), PriceRecord::new(12_345, 20, 3));
+        Prices::<Test>::insert(stored_pair("C"), PriceRecord::new(99, 20, 3));
+        Prices::<Test>::insert(stored_pair("D"), PriceRecord::new(10, 2, 3));
+        PriceHistory::<Test>::insert(
+            stored_pair("A"),
+            BoundedFifo::try_from(vec![
+                PriceRecord::new(99, 20, 1),
+                PriceRecord::new(10, 2, 2),
+                PriceRecord::new(12_345, 20, 3),
+            ])
+            .unwrap(),
+        );
+
+        truncate_excessive_decimals::<Test>();
+
+        assert_eq!(
+            Prices::<Test>::get(stored_pair("A")),
+            Some(PriceRecord::new(123, 18, 3))
+        );
+        assert_eq!(Prices::<Test>::get(stored_pair("C")), None);
+        assert_eq!(
+            Prices::<Test>::get(stored_pair("D")),
+            Some(PriceRecord::new(10, 2, 3))
+        );
+        assert_eq!(
+            PriceHistory::<Test>::get(stored_pair("A")).into_inner(),
+            vec![PriceRecord::new(10, 2, 2), PriceRecord::new(123, 18, 3)]
+        );
+    });
+}
+
+#[test]
+fn force_set_and_clear_price() {
+    use crate::{FeedState, FeedStates, PriceBounds};
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+
+        assert_noop!(
+            PriceFeedModule::force_set_price(Origin::signed(1), pair.clone(), 10, 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::force_clear_price(Origin::root(), pair.clone()),
+            Error::<Test>::PriceNotFound
+        );
+        assert_noop!(
+            PriceFeedModule::force_set_price(Origin::root(), pair.clone(), 10, 19),
+            Error::<Test>::TooManyPriceDecimals
+        );
+
+        // Pause and bounds don't apply to Root.
+        FeedStates::<Test>::insert(
+            &stored_pair,
+            FeedState {
+                paused: true,
+                bounds: PriceBounds::new(1, 5, 0),
+                ..Default::default()
+            },
+        );
+        assert_ok!(PriceFeedModule::force_set_price(
+            Origin::root(),
+            pair.clone(),
+            10,
+            1
+        ));
+        assert_eq!(
+            PriceFeedModule::price(&stored_pair),
+            Some(PriceRecord::new(10, 1, 0))
+        );
+        assert_eq!(
+            PriceFeedModule::price_history(&stored_pair).into_inner(),
+            vec![PriceRecord::new(10, 1, 0)]
+        );
+        assert!(FeedStates::<Test>::get(&stored_pair).paused);
+
+        assert_noop!(
+            PriceFeedModule::force_clear_price(Origin::signed(1), pair.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::force_clear_price(
+            Origin::root(),
+            pair.clone()
+        ));
+        assert_eq!(PriceFeedModule::price(&stored_pair), None);
+        assert!(PriceFeedModule::price_history(&stored_pair).is_empty());
+        assert_noop!(
+            PriceFeedModule::force_clear_price(Origin::root(), pair),
+            Error::<Test>::PriceNotFound
+        );
+    });
+}
+
+#[test]
+fn priced_pairs_cap() {
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+        MaxPricedPairs::set(2);
+        for from in ["A", "C", "D"] {
+            assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair(from), 1));
+        }
+
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("A"),
+            10,
+            1,
+            None,
+            None
+        ));
+        assert_ok!(PriceFeedModule::force_set_price(
+            Origin::root(),
+            pair("C"),
+            20,
+            1
+        ));
+        assert_eq!(PriceFeedModule::priced_pairs_count(), 2);
+
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair("D"), 30, 1, None, None),
+            Error::<Test>::TooManyPricedPairs
+        );
+        assert_noop!(
+            PriceFeedModule::force_set_price(Origin::root(), pair("D"), 30, 1),
+            Error::<Test>::TooManyPricedPairs
+        );
+        // Pairs which already have a price are still updated.
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("A"),
+            11,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::priced_pairs_count(), 2);
+
+        assert_ok!(PriceFeedModule::force_clear_price(
+            Origin::root(),
+            pair("C")
+        ));
+        assert_eq!(PriceFeedModule::priced_pairs_count(), 1);
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("D"),
+            30,
+            1,
+            None,
+            None
+        ));
+        assert_eq!(PriceFeedModule::priced_pairs_count(), 2);
+        assert_ok!(PriceFeedModule::do_try_state());
+    });
+}
+
+#[test]
+fn allowed_pairs() {
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+
+        // Any pair is accepted while the allow-list is empty.
+        assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair("A"), 1));
+
+        assert_noop!(
+            PriceFeedModule::allow_pair(Origin::signed(1), pair("C")),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(PriceFeedModule::allow_pair(Origin::root(), pair("C")));
+        assert_noop!(
+            PriceFeedModule::allow_pair(Origin::root(), pair("C")),
+            Error::<Test>::PairAlreadyAllowed
+        );
+        assert_eq!(PriceFeedModule::allowed_pairs_count(), 1);
+
+        assert_noop!(
+            PriceFeedModule::add_operator(Origin::root(), pair("D"), 1),
+            Error::<Test>::PairNotAllowed
+        );
+        assert_noop!(
+            PriceFeedModule::set_price(Origin::signed(1), pair("A"), 10, 1, None, None),
+            Error::<Test>::PairNotAllowed
+        );
+        assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair("C"), 1));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("C"),
+            10,
+            1,
+            None,
+            None
+        ));
+
+        assert_noop!(
+            PriceFeedModule::disallow_pair(Origin::signed(1), pair("C")),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::disallow_pair(Origin::root(), pair("A")),
+            Error::<Test>::PairNotAllowed
+        );
+        assert_ok!(PriceFeedModule::disallow_pair(Origin::root(), pair("C")));
+        assert_eq!(PriceFeedModule::allowed_pairs_count(), 0);
+        assert_ok!(PriceFeedModule::do_try_state());
+
+        // Empty allow-list accepts any pair again.
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair("A"),
+            10,
+            1,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn commit_reveal_of_disallowed_pair() {
+    use crate::{commit_reveal::price_commitment, CommitRevealScheme};
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::BlakeTwo256;
+
+    new_test_ext().execute_with(|| {
+        let pair = |from: &str| CurrencySymbolPair::new(from, "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair("C")
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        let commitment =
+            |price| price_commitment::<BlakeTwo256, _, _>(&stored_pair, 1, &1, price, 0, &[1; 32]);
+        for allowed in ["C", "E"] {
+            assert_ok!(PriceFeedModule::allow_pair(Origin::root(), pair(allowed)));
+        }
+        assert_ok!(PriceFeedModule::add_operator(Origin::root(), pair("C"), 1));
+        assert_ok!(PriceFeedModule::set_commit_reveal_scheme(
+            Origin::root(),
+            pair("C"),
+            Some(CommitRevealScheme::new(10, 4, 3).unwrap())
+        ));
+
+        System::set_block_number(10);
+        assert_ok!(PriceFeedModule::commit_price(
+            Origin::signed(1),
+            pair("C"),
+            commitment(10)
+        ));
+        assert_ok!(PriceFeedModule::disallow_pair(Origin::root(), pair("C")));
+        assert_noop!(
+            PriceFeedModule::commit_price(Origin::signed(1), pair("C"), commitment(20)),
+            Error::<Test>::PairNotAllowed
+        );
+
+        // The pending round doesn't produce a price either.
+        System::set_block_number(14);
+        assert_noop!(
+            PriceFeedModule::reveal_price(Origin::signed(1), pair("C"), 10, 0, [1; 32]),
+            Error::<Test>::PairNotAllowed
+        );
+        for block in 15..=20 {
+            System::set_block_number(block);
+            PriceFeedModule::on_initialize(block);
+        }
+        assert_eq!(PriceFeedModule::price(&stored_pair), None);
+    });
+}
+
+#[test]
+fn claim_rewards() {
+    use frame_support::traits::Currency;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let pot = PriceFeedModule::reward_pot();
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+
+        assert_noop!(
+            PriceFeedModule::claim_rewards(Origin::signed(1)),
+            Error::<Test>::NoRewardsToClaim
+        );
+        for price in 10..13 {
+            assert_ok!(PriceFeedModule::set_price(
+                Origin::signed(1),
+                pair.clone(),
+                price,
+                1,
+                None,
+                None
+            ));
+        }
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 15);
+        assert_eq!(PriceFeedModule::claimable_rewards(2), 0);
+
+        // Rewards stay claimable while the pot is underfunded.
+        Balances::make_free_balance_be(&pot, 5);
+        assert!(PriceFeedModule::claim_rewards(Origin::signed(1)).is_err());
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 15);
+
+        // Payout per call is capped by `MaxRewardPayout`.
+        Balances::make_free_balance_be(&pot, 100);
+        assert_ok!(PriceFeedModule::claim_rewards(Origin::signed(1)));
+        assert_eq!(Balances::free_balance(1), 12);
+        assert_eq!(PriceFeedModule::claimable_rewards(1), 3);
+        assert_eq!(
+            PriceFeedModule::claimable_rewards_page(None, 10).items,
+            vec![(1, 3)]
+        );
+
+        assert_ok!(PriceFeedModule::claim_rewards(Origin::signed(1)));
+        assert_eq!(Balances::free_balance(1), 15);
+        assert_eq!(Balances::free_balance(pot), 85);
+        assert_noop!(
+            PriceFeedModule::claim_rewards(Origin::signed(1)),
+            Error::<Test>::NoRewardsToClaim
+        );
+        assert!(PriceFeedModule::claimable_rewards_page(None, 10)
+            .items
+            .is_empty());
+        assert_ok!(PriceFeedModule::do_try_state());
+    });
+}
+
+#[test]
+fn operator_requests() {
+    use frame_support::traits::{Currency, ReservableCurrency};
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        Balances::make_free_balance_be(&1, 100);
+        Balances::make_free_balance_be(&2, 100);
+        Balances::make_free_balance_be(&3, 5);
+
+        assert_noop!(
+            PriceFeedModule::request_operator_role(Origin::signed(3), pair.clone()),
+            balances::Error::<Test>::InsufficientBalance
+        );
+        for account in [1, 2] {
+            assert_ok!(PriceFeedModule::request_operator_role(
+                Origin::signed(account),
+                pair.clone()
+            ));
+            assert_eq!(Balances::reserved_balance(account), 10);
+            assert_eq!(
+                PriceFeedModule::operator_request(&stored_pair, account),
+                Some(10)
+            );
+        }
+        assert_noop!(
+            PriceFeedModule::request_operator_role(Origin::signed(1), pair.clone()),
+            Error::<Test>::OperatorRequestAlreadyExists
+        );
+
+        assert_noop!(
+            PriceFeedModule::approve_operator(Origin::signed(1), pair.clone(), 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::reject_operator(Origin::signed(1), pair.clone(), 1),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            PriceFeedModule::approve_operator(Origin::root(), pair.clone(), 3),
+            Error::<Test>::OperatorRequestNotFound
+        );
+        assert_noop!(
+            PriceFeedModule::reject_operator(Origin::root(), pair.clone(), 3),
+            Error::<Test>::OperatorRequestNotFound
+        );
+
+        assert_ok!(PriceFeedModule::approve_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert!(PriceFeedModule::is_operator(pair.clone(), &1));
+        assert_eq!(PriceFeedModule::operator_request(&stored_pair, 1), None);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_noop!(
+            PriceFeedModule::request_operator_role(Origin::signed(1), pair.clone()),
+            Error::<Test>::OperatorIsAlreadyAdded
+        );
+
+        assert_ok!(PriceFeedModule::reject_operator(
+            Origin::root(),
+            pair.clone(),
+            2
+        ));
+        assert!(!PriceFeedModule::is_operator(pair.clone(), &2));
+        assert_eq!(PriceFeedModule::operator_request(&stored_pair, 2), None);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 100);
+
+        // Requests are limited by the allow-list like `add_operator`.
+        assert_ok!(PriceFeedModule::allow_pair(
+            Origin::root(),
+            CurrencySymbolPair::new("C", "D").map_pair(ToOwned::to_owned)
+        ));
+        assert_noop!(
+            PriceFeedModule::request_operator_role(Origin::signed(2), pair),
+            Error::<Test>::PairNotAllowed
+        );
+    });
+}
+
+#[test]
+fn try_state() {
+    use crate::{
+        AllowedPairsCount, ClaimableRewards, CommitRevealScheme, OperatorLastSubmission,
+        PendingCommitRevealSchemes, PendingOperatorRemoval, PendingOperatorRemovals, PriceHistory,
+    };
+    use frame_support::{storage::with_transaction, BoundedVec};
+    use sp_runtime::TransactionOutcome;
+    use utils::BoundedFifo;
+
+    new_test_ext().execute_with(|| {
+        let pair = CurrencySymbolPair::new("A", "B").map_pair(ToOwned::to_owned);
+        let stored_pair = pair
+            .clone()
+            .checked_into::<BoundedCurrencySymbolPair<_, _, ConstU32<4>>>()
+            .unwrap();
+        assert_ok!(PriceFeedModule::allow_pair(Origin::root(), pair.clone()));
+        assert_ok!(PriceFeedModule::add_operator(
+            Origin::root(),
+            pair.clone(),
+            1
+        ));
+        assert_ok!(PriceFeedModule::set_price(
+            Origin::signed(1),
+            pair.clone(),
+            10,
+            1,
+            None,
+            None
+        ));
+        assert_ok!(PriceFeedModule::do_try_state());
+
+        // Each invariant is broken in a separate transaction which is rolled back afterwards.
+        let assert_breaks = |name: &str, corrupt: &dyn Fn()| {
+            with_transaction(|| {
+                corrupt();
+                assert!(PriceFeedModule::do_try_state().is_err(), "{}", name);
+
+                TransactionOutcome::Rollback(Ok::<_, DispatchError>(()))
+            })
+            .unwrap();
+            assert_ok!(PriceFeedModule::do_try_state());
+        };
+
+        assert_breaks("stale last submission", &|| {
+            OperatorLastSubmission::<Test>::insert(&stored_pair, 2, 0)
+        });
+        assert_breaks("missing last submission", &|| {
+            OperatorLastSubmission::<Test>::remove(&stored_pair, 1)
+        });
+        assert_breaks("removal of missing operator", &|| {
+            PendingOperatorRemovals::<Test>::put(
+                BoundedVec::try_from(vec![PendingOperatorRemoval {
+                    pair: stored_pair.clone(),
+                    operator: 2,
+                    at: 1,
+                }])
+                .unwrap(),
+            )
+        });
+        assert_breaks("duplicate removal", &|| {
+            let removal = PendingOperatorRemoval {
+                pair: stored_pair.clone(),
+                operator: 1,
+                at: 1,
+            };
+            PendingOperatorRemovals::<Test>::put(
+                BoundedVec::try_from(vec![removal.clone(), removal]).unwrap(),
+            )
+        });
+        assert_breaks("allowed pairs count", &|| AllowedPairsCount::<Test>::put(2));
+        assert_breaks("history tail", &|| {
+            Prices::<Test>::insert(&stored_pair, PriceRecord::new(11, 1, 1))
+        });
+        assert_breaks("history order", &|| {
+            PriceHistory::<Test>::insert(
+                &stored_pair,
+                BoundedFifo::try_from(vec![PriceRecord::new(11, 1, 1), PriceRecord::new(10, 1, 0)])
+                    .unwrap(),
+            )
+        });
+        assert_breaks("zero rewards", &|| ClaimableRewards::<Test>::insert(1, 0));
+        assert_breaks("pending scheme without round", &|| {
+            PendingCommitRevealSchemes::<Test>::insert(&stored_pair, None::<CommitRevealScheme>)
+        });
+    });
+}
@@ -0,0 +1,85 @@
+//! Consistency checks of the storage items which duplicate each other's data.
+//!
+//! [`Pallet::do_try_state`] is invoked by `try-runtime` after the runtime upgrade and can be invoked by tests
+//! after the extrinsics to catch the writes which update one item but not the other.
+
+use sp_runtime::traits::Zero;
+use sp_std::prelude::*;
+
+use crate::pallet::*;
+
+impl<T: Config> Pallet<T> {
+    /// Returns `Err` describing the first violated invariant:
+    /// - each `OperatorLastSubmission` entry belongs to an operator in `OperatorSets`, and each operator has one;
+    /// - each `PendingOperatorRemovals` entry refers to an operator in `OperatorSets` and is scheduled once;
+    /// - `AllowedPairsCount` equals the amount of `AllowedPairs`;
+    /// - `PricedPairsCount` equals the amount of `Prices`;
+    /// - each `ContractLastVisit` entry belongs to a config in `ContractConfigs`;
+    /// - each `PriceHistory` is ordered by block number and ends with the pair's record in `Prices`;
+    /// - `ClaimableRewards` doesn't keep zero rewards;
+    /// - each `PendingCommitRevealSchemes` entry waits for a round pending in `PriceCommitments`.
+    pub fn do_try_state() -> Result<(), &'static str> {
+        for (pair, operator, _) in <OperatorLastSubmission<T>>::iter() {
+            if !Self::is_pair_operator(&pair, &operator) {
+                return Err("`OperatorLastSubmission` entry doesn't belong to an operator");
+            }
+        }
+        for (pair, operators) in <OperatorSets<T>>::iter() {
+            if operators
+                .iter()
+                .any(|operator| !<OperatorLastSubmission<T>>::contains_key(&pair, operator))
+            {
+                return Err("Operator lacks `OperatorLastSubmission` entry");
+            }
+        }
+
+        let mut scheduled = Vec::new();
+        for removal in <PendingOperatorRemovals<T>>::get() {
+            if !Self::is_pair_operator(&removal.pair, &removal.operator) {
+                return Err("Pending operator removal refers to a missing operator");
+            }
+            let key = (removal.pair, removal.operator);
+            if scheduled.contains(&key) {
+                return Err("Operator removal is scheduled more than once");
+            }
+            scheduled.push(key);
+        }
+
+        if <AllowedPairs<T>>::iter_keys().count() as u32 != <AllowedPairsCount<T>>::get() {
+            return Err("`AllowedPairsCount` doesn't match `AllowedPairs`");
+        }
+        if <Prices<T>>::iter_keys().count() as u32 != <PricedPairsCount<T>>::get() {
+            return Err("`PricedPairsCount` doesn't match `Prices`");
+        }
+
+        for pair in <ContractLastVisit<T>>::iter_keys() {
+            if !<ContractConfigs<T>>::contains_key(&pair) {
+                return Err("`ContractLastVisit` entry doesn't belong to a config");
+            }
+        }
+
+        for (pair, history) in <PriceHistory<T>>::iter() {
+            if history
+                .windows(2)
+                .any(|records| records[0].block_number() > records[1].block_number())
+            {
+                return Err("`PriceHistory` isn't ordered by block number");
+            }
+            if history.last().is_some() && history.last().copied() != <Prices<T>>::get(&pair) {
+                return Err("`PriceHistory` doesn't end with the latest price");
+            }
+        }
+
+        if <ClaimableRewards<T>>::iter_values().any(|rewards| rewards.is_zero()) {
+            return Err("`ClaimableRewards` keeps zero rewards");
+        }
+
+        for pair in <PendingCommitRevealSchemes<T>>::iter_keys() {
+            if !<PriceCommitments<T>>::contains_key(&pair) {
+                return Err("Pending commit-reveal scheme doesn't wait for a round");
+            }
+        }
+
+        Ok(())
+    }
+}
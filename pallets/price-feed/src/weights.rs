@@ -0,0 +1,395 @@
+//! Weights for dock_price_feed.
+//!
+//! Only the `migrate_to_v2` weight comes from a benchmark run. The ref time of the other functions is a
+//! hand-written placeholder, and their DB accesses are counted from the code of the calls, so these must be
+//! generated on the reference hardware before the pallet is deployed, as well as after changing the benchmarks:
+//!
+//! ```text
+//! ./target/release/dock-node benchmark pallet --wasm-execution=compiled --pallet=dock_price_feed
+//!     --extrinsic=* --steps=50 --repeat=20 --template=node/module-weight-template.hbs --output=./tmp
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for dock_price_feed.
+pub trait WeightInfo {
+    fn set_price() -> Weight;
+    fn add_operator() -> Weight;
+    /// `p` is the amount of the pending operator removals.
+    fn remove_operator(p: u32) -> Weight;
+    fn set_emit_events_every_n_updates() -> Weight;
+    fn set_price_bounds() -> Weight;
+    fn set_feed_spec_hash() -> Weight;
+    fn set_required_identity_level() -> Weight;
+    fn report_anomaly() -> Weight;
+    fn resume_pair() -> Weight;
+    fn note_local_time() -> Weight;
+    fn set_submission_window() -> Weight;
+    fn set_contract_config() -> Weight;
+    /// `p` is the amount of the pending operator removals.
+    fn remove_operator_immediately(p: u32) -> Weight;
+    fn set_http_source() -> Weight;
+    fn set_commit_reveal_scheme() -> Weight;
+    /// `c` is the amount of the commitments already submitted in the round.
+    fn commit_price(c: u32) -> Weight;
+    /// `c` is the amount of the commitments submitted in the round.
+    fn reveal_price(c: u32) -> Weight;
+    fn set_price_access_restricted() -> Weight;
+    fn set_price_consumer() -> Weight;
+    fn force_set_price() -> Weight;
+    fn force_clear_price() -> Weight;
+    fn allow_pair() -> Weight;
+    fn disallow_pair() -> Weight;
+    fn claim_rewards() -> Weight;
+    fn request_operator_role() -> Weight;
+    fn approve_operator() -> Weight;
+    fn reject_operator() -> Weight;
+    /// `b` is the encoded size of the residual V1 `ContractConfigStore` value.
+    fn migrate_to_v2(b: u32) -> Weight;
+    fn pause_pair() -> Weight;
+    fn pause_all_pairs() -> Weight;
+    fn resume_all_pairs() -> Weight;
+    /// `p` is the amount of the priced currency pairs.
+    fn update_price_root(p: u32) -> Weight;
+    fn set_retention_tier() -> Weight;
+}
+
+/// Weights for dock_price_feed using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn set_price() -> Weight {
+        Weight::from_ref_time(44_300_000)
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+    fn add_operator() -> Weight {
+        Weight::from_ref_time(19_800_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_operator(p: u32) -> Weight {
+        Weight::from_ref_time(23_600_000)
+            .saturating_add(Weight::from_ref_time(412_000).saturating_mul(p as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_emit_events_every_n_updates() -> Weight {
+        Weight::from_ref_time(17_100_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_price_bounds() -> Weight {
+        Weight::from_ref_time(17_500_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_feed_spec_hash() -> Weight {
+        Weight::from_ref_time(14_900_000).saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_required_identity_level() -> Weight {
+        Weight::from_ref_time(17_000_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn report_anomaly() -> Weight {
+        Weight::from_ref_time(28_400_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn resume_pair() -> Weight {
+        Weight::from_ref_time(17_400_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn note_local_time() -> Weight {
+        Weight::from_ref_time(6_900_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_submission_window() -> Weight {
+        Weight::from_ref_time(17_600_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_contract_config() -> Weight {
+        Weight::from_ref_time(16_900_000).saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_operator_immediately(p: u32) -> Weight {
+        Weight::from_ref_time(25_200_000)
+            .saturating_add(Weight::from_ref_time(398_000).saturating_mul(p as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_http_source() -> Weight {
+        Weight::from_ref_time(16_300_000).saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_commit_reveal_scheme() -> Weight {
+        Weight::from_ref_time(16_700_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn commit_price(c: u32) -> Weight {
+        Weight::from_ref_time(31_900_000)
+            .saturating_add(Weight::from_ref_time(287_000).saturating_mul(c as u64))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn reveal_price(c: u32) -> Weight {
+        Weight::from_ref_time(36_800_000)
+            .saturating_add(Weight::from_ref_time(264_000).saturating_mul(c as u64))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_price_access_restricted() -> Weight {
+        Weight::from_ref_time(14_600_000).saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_price_consumer() -> Weight {
+        Weight::from_ref_time(15_100_000).saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn force_set_price() -> Weight {
+        Weight::from_ref_time(31_400_000)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn force_clear_price() -> Weight {
+        Weight::from_ref_time(25_200_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn allow_pair() -> Weight {
+        Weight::from_ref_time(17_900_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn disallow_pair() -> Weight {
+        Weight::from_ref_time(18_300_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn claim_rewards() -> Weight {
+        Weight::from_ref_time(46_500_000)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn request_operator_role() -> Weight {
+        Weight::from_ref_time(38_200_000)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn approve_operator() -> Weight {
+        Weight::from_ref_time(41_600_000)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn reject_operator() -> Weight {
+        Weight::from_ref_time(31_400_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn pause_pair() -> Weight {
+        Weight::from_ref_time(17_600_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn pause_all_pairs() -> Weight {
+        Weight::from_ref_time(11_300_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn resume_all_pairs() -> Weight {
+        Weight::from_ref_time(11_500_000)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn migrate_to_v2(b: u32) -> Weight {
+        Weight::from_ref_time(14_200_000)
+            .saturating_add(Weight::from_ref_time(1_000).saturating_mul(b as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn update_price_root(p: u32) -> Weight {
+        Weight::from_ref_time(9_870_000)
+            .saturating_add(Weight::from_ref_time(4_310_000).saturating_mul(p as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads(p as u64))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn set_retention_tier() -> Weight {
+        Weight::from_ref_time(18_100_000)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_price() -> Weight {
+        Weight::from_ref_time(44_300_000)
+            .saturating_add(RocksDbWeight::get().reads(11))
+            .saturating_add(RocksDbWeight::get().writes(7))
+    }
+    fn add_operator() -> Weight {
+        Weight::from_ref_time(19_800_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn remove_operator(p: u32) -> Weight {
+        Weight::from_ref_time(23_600_000)
+            .saturating_add(Weight::from_ref_time(412_000).saturating_mul(p as u64))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_emit_events_every_n_updates() -> Weight {
+        Weight::from_ref_time(17_100_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_price_bounds() -> Weight {
+        Weight::from_ref_time(17_500_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_feed_spec_hash() -> Weight {
+        Weight::from_ref_time(14_900_000).saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_required_identity_level() -> Weight {
+        Weight::from_ref_time(17_000_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn report_anomaly() -> Weight {
+        Weight::from_ref_time(28_400_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn resume_pair() -> Weight {
+        Weight::from_ref_time(17_400_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn note_local_time() -> Weight {
+        Weight::from_ref_time(6_900_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_submission_window() -> Weight {
+        Weight::from_ref_time(17_600_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_contract_config() -> Weight {
+        Weight::from_ref_time(16_900_000).saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn remove_operator_immediately(p: u32) -> Weight {
+        Weight::from_ref_time(25_200_000)
+            .saturating_add(Weight::from_ref_time(398_000).saturating_mul(p as u64))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn set_http_source() -> Weight {
+        Weight::from_ref_time(16_300_000).saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_commit_reveal_scheme() -> Weight {
+        Weight::from_ref_time(16_700_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn commit_price(c: u32) -> Weight {
+        Weight::from_ref_time(31_900_000)
+            .saturating_add(Weight::from_ref_time(287_000).saturating_mul(c as u64))
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn reveal_price(c: u32) -> Weight {
+        Weight::from_ref_time(36_800_000)
+            .saturating_add(Weight::from_ref_time(264_000).saturating_mul(c as u64))
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_price_access_restricted() -> Weight {
+        Weight::from_ref_time(14_600_000).saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_price_consumer() -> Weight {
+        Weight::from_ref_time(15_100_000).saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn force_set_price() -> Weight {
+        Weight::from_ref_time(31_400_000)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn force_clear_price() -> Weight {
+        Weight::from_ref_time(25_200_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn allow_pair() -> Weight {
+        Weight::from_ref_time(17_900_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn disallow_pair() -> Weight {
+        Weight::from_ref_time(18_300_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn claim_rewards() -> Weight {
+        Weight::from_ref_time(46_500_000)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn request_operator_role() -> Weight {
+        Weight::from_ref_time(38_200_000)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn approve_operator() -> Weight {
+        Weight::from_ref_time(41_600_000)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+    fn reject_operator() -> Weight {
+        Weight::from_ref_time(31_400_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+    fn pause_pair() -> Weight {
+        Weight::from_ref_time(17_600_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn pause_all_pairs() -> Weight {
+        Weight::from_ref_time(11_300_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn resume_all_pairs() -> Weight {
+        Weight::from_ref_time(11_500_000)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn migrate_to_v2(b: u32) -> Weight {
+        Weight::from_ref_time(14_200_000)
+            .saturating_add(Weight::from_ref_time(1_000).saturating_mul(b as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+    fn update_price_root(p: u32) -> Weight {
+        Weight::from_ref_time(9_870_000)
+            .saturating_add(Weight::from_ref_time(4_310_000).saturating_mul(p as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads(p as u64))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+    fn set_retention_tier() -> Weight {
+        Weight::from_ref_time(18_100_000)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+    }
+}
@@ -781,6 +781,33 @@ benchmarks! {
         assert_eq!(votes.len(), (r - 1) as usize, "Vote was not removed");
     }
 
+    claim_locked_deposit {
+        let from = funded_account::<T>("from", 0);
+        let caller = funded_account::<T>("caller", 0);
+        let deposit = T::MinimumDeposit::get();
+        T::Currency::reserve(&from, deposit)?;
+
+        let target = DepositPaybackTarget::Beneficiary { from: from.clone(), to: caller.clone() };
+        let block_number = target.lock_deposit::<T>(ProposalTrack::Public, deposit);
+        System::<T>::set_block_number(block_number);
+
+        whitelist_account!(caller);
+    }: _(RawOrigin::Signed(caller.clone()), block_number, from)
+    verify {
+        assert!(LockedDeposits::<T>::iter().next().is_none());
+        assert!(Democracy::<T>::total_locked_deposits().is_zero());
+        assert_last_event::<T>(Event::<T>::LockedDepositUnreserved { recipient: caller, deposit }.into());
+    }
+
+    set_deposit_lock_config {
+        let origin = T::DepositLockConfigOrigin::successful_origin();
+        let config = DepositLockConfig::<T>::new(10, 20, T::MinimumDeposit::get());
+    }: _<T::Origin>(origin, config.clone())
+    verify {
+        assert_eq!(CurrentDepositLockConfig::<T>::get(), Some(config.clone()));
+        assert_last_event::<T>(Event::<T>::DepositLockConfigUpdated { config }.into());
+    }
+
     #[extra]
     enact_proposal_execute {
         // Num of bytes in encoded proposal
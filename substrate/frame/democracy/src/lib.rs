@@ -165,15 +165,19 @@ use frame_support::{
         ReservableCurrency, WithdrawReasons,
     },
     weights::Weight,
+    CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
     traits::{Bounded, Dispatchable, Hash, One, Saturating, StaticLookup, Zero},
-    ArithmeticError, DispatchErrorWithPostInfo, DispatchResult, RuntimeDebug,
+    ArithmeticError, DispatchErrorWithPostInfo, DispatchResult, PerThing, Percent, RuntimeDebug,
 };
 use sp_std::prelude::*;
+use utils::{ReservedBalanceSlasher, SlashBeneficiary, SlashHandler};
 
 mod conviction;
+pub mod migrations;
+pub mod runtime_api;
 mod types;
 mod vote;
 mod vote_threshold;
@@ -210,6 +214,7 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+type DepositSlasher<T> = ReservedBalanceSlasher<<T as Config>::Currency, <T as Config>::Slash>;
 
 #[derive(Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
 pub enum PreimageStatus<AccountId, Balance, BlockNumber> {
@@ -241,6 +246,8 @@ impl<AccountId, Balance, BlockNumber> PreimageStatus<AccountId, Balance, BlockNu
 #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 enum Releases {
     V1,
+    /// `TotalLockedDeposits` tracks the sum of all `LockedDeposits`.
+    V2,
 }
 
 /// Denotes the state of the deposit.
@@ -259,16 +266,76 @@ pub struct DepositWithState<Balance, BlockNumber> {
     state: DepositState<BlockNumber>,
 }
 
+/// Denotes the track the referendum was started on.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ProposalTrack {
+    /// Referendum was started from the public proposal.
+    Public,
+    /// Referendum was started from the external proposal.
+    External,
+    /// Referendum was started from the fast-tracked external proposal.
+    FastTrack,
+}
+
+impl Default for ProposalTrack {
+    fn default() -> Self {
+        Self::Public
+    }
+}
+
+/// Multipliers applied to the `lock_deposit_for_unit_amount` depending on the track of the proposal.
+/// Multiplier can't exceed 100%, so the base deposit lock configuration should describe the longest lock.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct TrackLockMultipliers {
+    /// Multiplier for the deposits behind public proposals.
+    pub public: Percent,
+    /// Multiplier for the deposits behind external proposals.
+    pub external: Percent,
+    /// Multiplier for the deposits behind fast-tracked external proposals.
+    pub fast_track: Percent,
+}
+
+impl TrackLockMultipliers {
+    /// Instantiates new `TrackLockMultipliers` using supplied multipliers.
+    pub const fn new(public: Percent, external: Percent, fast_track: Percent) -> Self {
+        Self {
+            public,
+            external,
+            fast_track,
+        }
+    }
+
+    /// Returns multiplier for the given track.
+    pub fn multiplier(&self, track: ProposalTrack) -> Percent {
+        match track {
+            ProposalTrack::Public => self.public,
+            ProposalTrack::External => self.external,
+            ProposalTrack::FastTrack => self.fast_track,
+        }
+    }
+}
+
+impl Default for TrackLockMultipliers {
+    fn default() -> Self {
+        let full = Percent::from_parts(100);
+
+        Self::new(full, full, full)
+    }
+}
+
 /// Denotes configuration to be used for locking preimage provider deposits.
 /// Lock will happen in case if proposal's turnout is less than `immediate_payback_turnout`.
 /// Target lock block number will be rounded to the closest higher value dividable
 /// by the `lock_unit` with zero remainder.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[derive(
+    Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo,
+)]
 #[scale_info(skip_type_params(T))]
 pub struct DepositLockConfig<T: Config> {
     lock_unit: NonZeroU32,
     lock_deposit_for_unit_amount: u32,
     immediate_payback_turnout: BalanceOf<T>,
+    track_multipliers: TrackLockMultipliers,
 }
 
 impl<T: Config> DepositLockConfig<T> {
@@ -288,13 +355,34 @@ impl<T: Config> DepositLockConfig<T> {
             _ => panic!("`lock_unit` can't be equal to zero"),
         };
 
+        let full = Percent::from_parts(100);
+
         Self {
             lock_unit,
             lock_deposit_for_unit_amount,
             immediate_payback_turnout,
+            track_multipliers: TrackLockMultipliers::new(full, full, full),
         }
     }
 
+    /// Sets multipliers applied to the `lock_deposit_for_unit_amount` depending on the track of the proposal.
+    pub fn with_track_multipliers(mut self, track_multipliers: TrackLockMultipliers) -> Self {
+        self.track_multipliers = track_multipliers;
+
+        self
+    }
+
+    /// Returns config to be used for locking deposits behind proposals of the given track.
+    /// `lock_deposit_for_unit_amount` is multiplied by the track's multiplier and rounded up.
+    pub fn for_track(mut self, track: ProposalTrack) -> Self {
+        self.lock_deposit_for_unit_amount = self
+            .track_multipliers
+            .multiplier(track)
+            .mul_ceil(self.lock_deposit_for_unit_amount);
+
+        self
+    }
+
     /// Calculates target lock block using current block number provided by the system.
     /// Target block number will be rounded to the closest higher value dividable
     /// by the `lock_unit` with zero remainder.
@@ -348,15 +436,19 @@ pub enum DepositPaybackTarget<AccountId> {
 }
 
 impl<AccountId: Encode + Decode> DepositPaybackTarget<AccountId> {
-    /// Locks specified deposit amount based on the current block number returning lock target number.
+    /// Locks specified deposit amount based on the current block number and the proposal track
+    /// returning lock target number.
     fn lock_deposit<T: Config<AccountId = AccountId>>(
         &self,
+        track: ProposalTrack,
         deposit: BalanceOf<T>,
     ) -> T::BlockNumber
     where
         BalanceOf<T>: 'static,
     {
-        let target_block = T::DepositLockStrategy::get().target_block_from_current();
+        let target_block = Pallet::<T>::deposit_lock_config()
+            .for_track(track)
+            .target_block_from_current();
 
         LockedDeposits::<T>::mutate(
             target_block,
@@ -365,6 +457,7 @@ impl<AccountId: Encode + Decode> DepositPaybackTarget<AccountId> {
                 deposit_opt.replace(deposit_opt.unwrap_or_default().saturating_add(deposit))
             },
         );
+        TotalLockedDeposits::<T>::mutate(|total| *total = total.saturating_add(deposit));
 
         target_block
     }
@@ -389,16 +482,18 @@ impl<AccountId: Encode + Decode> DepositPaybackTarget<AccountId> {
     }
 
     /// Handles supplied deposit amount: either unreserves or locks it based on the `lock_deposit` flag.
+    /// Lock duration depends on the track of the proposal.
     fn return_deposit<T: Config<AccountId = AccountId>>(
         self,
         lock: bool,
+        track: ProposalTrack,
         deposit: BalanceOf<T>,
     ) -> DepositWithState<BalanceOf<T>, T::BlockNumber>
     where
         BalanceOf<T>: 'static,
     {
         let state = if lock {
-            let target_block = self.lock_deposit::<T>(deposit);
+            let target_block = self.lock_deposit::<T>(track, deposit);
 
             DepositState::Locked(target_block)
         } else {
@@ -433,9 +528,13 @@ pub mod pallet {
 
         /// Denotes how long deposit should be locked in case if proposal turnout is less than
         /// `immediate_payback_turnout`.
+        /// Used unless overridden via `set_deposit_lock_config`.
         #[pallet::constant]
         type DepositLockStrategy: Get<DepositLockConfig<Self>>;
 
+        /// Origin from which the deposit lock configuration can be updated.
+        type DepositLockConfigOrigin: EnsureOrigin<Self::Origin>;
+
         /// The period between a proposal being approved and enacted.
         ///
         /// It should generally be a little more than the unstake period to ensure that
@@ -584,6 +683,13 @@ pub mod pallet {
     #[pallet::storage]
     pub type LockDepositFor<T: Config> = StorageMap<_, Identity, T::Hash, bool, ValueQuery>;
 
+    /// Track of the referendum with the given index.
+    /// Used to determine how long the deposit should be locked once the referendum is enacted.
+    /// Removed when the referendum is enacted, rejected or cancelled.
+    #[pallet::storage]
+    pub type ProposalTrackOf<T: Config> =
+        StorageMap<_, Twox64Concat, ReferendumIndex, ProposalTrack, ValueQuery>;
+
     /// The next free referendum index, aka the number of referenda started so far.
     #[pallet::storage]
     #[pallet::getter(fn referendum_count)]
@@ -601,6 +707,16 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// Deposit lock configuration set via `set_deposit_lock_config`.
+    /// If absent, `DepositLockStrategy` is used.
+    #[pallet::storage]
+    pub type CurrentDepositLockConfig<T: Config> = StorageValue<_, DepositLockConfig<T>>;
+
+    /// Total amount of deposits currently locked by the deposit-lock subsystem.
+    #[pallet::storage]
+    #[pallet::getter(fn total_locked_deposits)]
+    pub type TotalLockedDeposits<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     /// The lowest referendum index representing an unbaked referendum. Equal to
     /// `ReferendumCount` if there isn't a unbaked referendum.
     #[pallet::storage]
@@ -682,7 +798,7 @@ pub mod pallet {
             PublicPropCount::<T>::put(0 as PropIndex);
             ReferendumCount::<T>::put(0 as ReferendumIndex);
             LowestUnbaked::<T>::put(0 as ReferendumIndex);
-            StorageVersion::<T>::put(Releases::V1);
+            StorageVersion::<T>::put(Releases::V2);
         }
     }
 
@@ -775,6 +891,8 @@ pub mod pallet {
         },
         /// A proposal got canceled.
         ProposalCanceled { prop_index: PropIndex },
+        /// Deposit lock configuration was updated.
+        DepositLockConfigUpdated { config: DepositLockConfig<T> },
         /// Locked deposit unreserved to the account.
         LockedDepositUnreserved {
             recipient: T::AccountId,
@@ -849,6 +967,10 @@ pub mod pallet {
         TooManyProposals,
         /// Voting period too low
         VotingPeriodLow,
+        /// Deposits for the given block number are still locked.
+        DepositStillLocked,
+        /// There's no locked deposit for the given block number and payback target.
+        LockedDepositMissing,
     }
 
     #[pallet::hooks]
@@ -857,6 +979,16 @@ pub mod pallet {
         fn on_initialize(n: T::BlockNumber) -> Weight {
             Self::begin_block(n)
         }
+
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+
+            if StorageVersion::<T>::get() != Some(Releases::V2) {
+                weight = weight.saturating_add(migrations::v2::migrate_to_v2::<T>());
+            }
+
+            weight
+        }
     }
 
     #[pallet::call]
@@ -1111,11 +1243,12 @@ pub mod pallet {
 
             <NextExternal<T>>::kill();
             let now = <frame_system::Pallet<T>>::block_number();
-            Self::inject_referendum(
+            Self::inject_referendum_on_track(
                 now.saturating_add(voting_period),
                 proposal_hash,
                 threshold,
                 delay,
+                ProposalTrack::FastTrack,
             );
             Ok(())
         }
@@ -1193,6 +1326,7 @@ pub mod pallet {
             ensure_root(origin)?;
             T::Scheduler::cancel_named((DEMOCRACY_ID, which).encode())
                 .map_err(|_| Error::<T>::ProposalMissing)?;
+            ProposalTrackOf::<T>::remove(which);
             Ok(())
         }
 
@@ -1399,7 +1533,12 @@ pub mod pallet {
             };
 
             let lock_deposit = LockDepositFor::<T>::take(proposal_hash);
-            let deposit_state = deposit_payback_target.return_deposit::<T>(lock_deposit, deposit);
+            // The track of the referendum is cleared once it's enacted, so the default one is used.
+            let deposit_state = deposit_payback_target.return_deposit::<T>(
+                lock_deposit,
+                Default::default(),
+                deposit,
+            );
 
             <Preimages<T>>::remove(&proposal_hash);
             Self::deposit_event(Event::<T>::PreimageReaped {
@@ -1539,7 +1678,12 @@ pub mod pallet {
                     let (prop_index, ..) = props.remove(index);
                     if let Some((whos, amount)) = DepositOf::<T>::take(prop_index) {
                         for who in whos.into_iter() {
-                            T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+                            DepositSlasher::<T>::slash_reserved(
+                                &who,
+                                amount,
+                                (),
+                                SlashBeneficiary::Default,
+                            );
                         }
                     }
                 }
@@ -1580,7 +1724,12 @@ pub mod pallet {
             PublicProps::<T>::mutate(|props| props.retain(|p| p.0 != prop_index));
             if let Some((whos, amount)) = DepositOf::<T>::take(prop_index) {
                 for who in whos.into_iter() {
-                    T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+                    DepositSlasher::<T>::slash_reserved(
+                        &who,
+                        amount,
+                        (),
+                        SlashBeneficiary::Default,
+                    );
                 }
             }
 
@@ -1647,6 +1796,59 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        /// Claims matured locked deposit which was reaped by the caller from the preimage provided by `from`
+        /// without waiting for the automatic unreserve or a third-party `unreserve_locked_deposits` call.
+        ///
+        /// The dispatch origin of this call must be _Signed_ and be the beneficiary of the locked deposit.
+        ///
+        /// - `block_number`: The block number the deposit is locked until. Must be lower or equal to the current.
+        /// - `from`: The account which provided the preimage and had its deposit reaped.
+        ///
+        /// Emits `LockedDepositUnreserved`.
+        #[pallet::weight(T::WeightInfo::claim_locked_deposit())]
+        pub fn claim_locked_deposit(
+            origin: OriginFor<T>,
+            block_number: T::BlockNumber,
+            from: T::AccountId,
+        ) -> DispatchResult {
+            let to = ensure_signed(origin)?;
+            ensure!(
+                block_number <= frame_system::Pallet::<T>::block_number(),
+                Error::<T>::DepositStillLocked
+            );
+
+            let target = DepositPaybackTarget::Beneficiary { from, to };
+            let deposit = LockedDeposits::<T>::take(block_number, &target)
+                .ok_or(Error::<T>::LockedDepositMissing)?;
+            Self::unreserve_locked_deposit(target, deposit);
+
+            Ok(())
+        }
+
+        /// Sets the deposit lock configuration overriding `DepositLockStrategy`.
+        ///
+        /// The dispatch origin of this call must be `DepositLockConfigOrigin`.
+        ///
+        /// - `config`: New deposit lock configuration. Its `lock_unit` can't be equal to zero.
+        ///
+        /// Deposits locked prior to this call keep their target block numbers. If the new `lock_unit`
+        /// doesn't divide these numbers, they won't be unreserved automatically and should be unreserved
+        /// via `unreserve_locked_deposits` or `claim_locked_deposit`.
+        ///
+        /// Emits `DepositLockConfigUpdated`.
+        #[pallet::weight(T::WeightInfo::set_deposit_lock_config())]
+        pub fn set_deposit_lock_config(
+            origin: OriginFor<T>,
+            config: DepositLockConfig<T>,
+        ) -> DispatchResult {
+            T::DepositLockConfigOrigin::ensure_origin(origin)?;
+
+            CurrentDepositLockConfig::<T>::put(config.clone());
+            Self::deposit_event(Event::<T>::DepositLockConfigUpdated { config });
+
+            Ok(())
+        }
     }
 }
 
@@ -1659,21 +1861,35 @@ impl<T: Config> Pallet<T> {
         Self::deposit_of(proposal).map(|(l, d)| d.saturating_mul((l.len() as u32).into()))
     }
 
+    /// Returns deposit lock configuration currently in use.
+    pub fn deposit_lock_config() -> DepositLockConfig<T> {
+        CurrentDepositLockConfig::<T>::get().unwrap_or_else(T::DepositLockStrategy::get)
+    }
+
     pub fn unreserve_locked_deposits_(block_number: T::BlockNumber) -> Weight {
         let mut weight = Weight::zero();
 
         let unlocked_deposits = LockedDeposits::<T>::drain_prefix(block_number);
         for (deposit_payback_target, deposit) in unlocked_deposits {
-            let recipient = deposit_payback_target.unreserve::<T>(deposit);
-
-            Self::deposit_event(Event::LockedDepositUnreserved { recipient, deposit });
+            Self::unreserve_locked_deposit(deposit_payback_target, deposit);
 
-            weight += T::DbWeight::get().reads_writes(3, 3);
+            weight += T::DbWeight::get().reads_writes(4, 4);
         }
 
         weight
     }
 
+    /// Unreserves supplied deposit taken from the `LockedDeposits` to the payback target.
+    fn unreserve_locked_deposit(
+        deposit_payback_target: DepositPaybackTarget<T::AccountId>,
+        deposit: BalanceOf<T>,
+    ) {
+        let recipient = deposit_payback_target.unreserve::<T>(deposit);
+        TotalLockedDeposits::<T>::mutate(|total| *total = total.saturating_sub(deposit));
+
+        Self::deposit_event(Event::LockedDepositUnreserved { recipient, deposit });
+    }
+
     /// Get all referenda ready for tally at block `n`.
     pub fn maturing_referenda_at(
         n: T::BlockNumber,
@@ -1712,11 +1928,12 @@ impl<T: Config> Pallet<T> {
         threshold: VoteThreshold,
         delay: T::BlockNumber,
     ) -> ReferendumIndex {
-        <Pallet<T>>::inject_referendum(
+        <Pallet<T>>::inject_referendum_on_track(
             <frame_system::Pallet<T>>::block_number().saturating_add(T::VotingPeriod::get()),
             proposal_hash,
             threshold,
             delay,
+            ProposalTrack::External,
         )
     }
 
@@ -1724,6 +1941,7 @@ impl<T: Config> Pallet<T> {
     pub fn internal_cancel_referendum(ref_index: ReferendumIndex) {
         Self::deposit_event(Event::<T>::Cancelled { ref_index });
         ReferendumInfoOf::<T>::remove(ref_index);
+        ProposalTrackOf::<T>::remove(ref_index);
     }
 
     // private.
@@ -2025,15 +2243,27 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    /// Start a referendum
+    /// Start a referendum on the public track.
     fn inject_referendum(
         end: T::BlockNumber,
         proposal_hash: T::Hash,
         threshold: VoteThreshold,
         delay: T::BlockNumber,
+    ) -> ReferendumIndex {
+        Self::inject_referendum_on_track(end, proposal_hash, threshold, delay, Default::default())
+    }
+
+    /// Start a referendum on the given track.
+    fn inject_referendum_on_track(
+        end: T::BlockNumber,
+        proposal_hash: T::Hash,
+        threshold: VoteThreshold,
+        delay: T::BlockNumber,
+        track: ProposalTrack,
     ) -> ReferendumIndex {
         let ref_index = Self::referendum_count();
         ReferendumCount::<T>::put(ref_index + 1);
+        ProposalTrackOf::<T>::insert(ref_index, track);
         let status = ReferendumStatus {
             end,
             proposal_hash,
@@ -2065,11 +2295,12 @@ impl<T: Config> Pallet<T> {
         if let Some((proposal, threshold)) = <NextExternal<T>>::take() {
             LastTabledWasExternal::<T>::put(true);
             Self::deposit_event(Event::<T>::ExternalTabled);
-            Self::inject_referendum(
+            Self::inject_referendum_on_track(
                 now.saturating_add(T::VotingPeriod::get()),
                 proposal,
                 threshold,
                 T::EnactmentPeriod::get(),
+                ProposalTrack::External,
             );
             Ok(())
         } else {
@@ -2115,6 +2346,7 @@ impl<T: Config> Pallet<T> {
         index: ReferendumIndex,
         lock_deposit: bool,
     ) -> DispatchResult {
+        let track = ProposalTrackOf::<T>::take(index);
         let preimage = <Preimages<T>>::take(&proposal_hash);
         if let Some(PreimageStatus::Available {
             data,
@@ -2130,7 +2362,7 @@ impl<T: Config> Pallet<T> {
                 let deposit_payback_target = DepositPaybackTarget::Provider(provider.clone());
 
                 let deposit_state =
-                    deposit_payback_target.return_deposit::<T>(lock_deposit, deposit);
+                    deposit_payback_target.return_deposit::<T>(lock_deposit, track, deposit);
 
                 Self::deposit_event(Event::<T>::PreimageUsed {
                     proposal_hash,
@@ -2151,7 +2383,12 @@ impl<T: Config> Pallet<T> {
             } else {
                 LockDepositFor::<T>::insert(proposal_hash, lock_deposit);
 
-                T::Slash::on_unbalanced(T::Currency::slash_reserved(&provider, deposit).0);
+                DepositSlasher::<T>::slash_reserved(
+                    &provider,
+                    deposit,
+                    (),
+                    SlashBeneficiary::Default,
+                );
                 Self::deposit_event(Event::<T>::PreimageInvalid {
                     proposal_hash,
                     ref_index: index,
@@ -2173,7 +2410,7 @@ impl<T: Config> Pallet<T> {
         status: ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>,
     ) -> bool {
         let total_issuance = T::Currency::total_issuance();
-        let lock_deposit = T::DepositLockStrategy::get().should_lock_deposit(&status);
+        let lock_deposit = Self::deposit_lock_config().should_lock_deposit(&status);
         let approved = status.threshold.approved(status.tally, total_issuance);
 
         if approved {
@@ -2213,6 +2450,7 @@ impl<T: Config> Pallet<T> {
                 }
             }
         } else {
+            ProposalTrackOf::<T>::remove(index);
             Self::deposit_event(Event::<T>::NotPassed { ref_index: index });
         }
 
@@ -2239,7 +2477,7 @@ impl<T: Config> Pallet<T> {
         let last = Self::referendum_count();
         let r = last.saturating_sub(next);
 
-        if T::DepositLockStrategy::get().should_unreserve_in_block(now) {
+        if Self::deposit_lock_config().should_unreserve_in_block(now) {
             weight += Self::unreserve_locked_deposits_(now);
         }
 
@@ -0,0 +1,24 @@
+//! Storage migrations of the democracy pallet.
+
+use super::*;
+
+pub mod v2 {
+    use super::*;
+
+    /// Seeds `TotalLockedDeposits` with the sum of the `LockedDeposits` created before the total
+    /// was tracked.
+    pub fn migrate_to_v2<T: Config>() -> Weight {
+        let mut reads = 0u64;
+        let total =
+            LockedDeposits::<T>::iter_values().fold(BalanceOf::<T>::zero(), |total, deposit| {
+                reads = reads.saturating_add(1);
+
+                total.saturating_add(deposit)
+            });
+
+        TotalLockedDeposits::<T>::put(total);
+        StorageVersion::<T>::put(Releases::V2);
+
+        T::DbWeight::get().reads_writes(reads, 2)
+    }
+}
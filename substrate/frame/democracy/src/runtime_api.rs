@@ -0,0 +1,8 @@
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait DemocracyApi<Balance: Codec> {
+        /// Gets the total amount of deposits currently locked by the deposit-lock subsystem.
+        fn total_locked_deposits() -> Balance;
+    }
+}
@@ -44,6 +44,7 @@ mod delegation;
 mod external_proposing;
 mod fast_tracking;
 mod lock_voting;
+mod migrations;
 mod preimage;
 mod public_proposals;
 mod scheduling;
@@ -198,6 +199,7 @@ impl Config for Test {
     type Currency = pallet_balances::Pallet<Self>;
     type EnactmentPeriod = ConstU64<2>;
     type DepositLockStrategy = DepositLockStrategy;
+    type DepositLockConfigOrigin = EnsureRoot<u64>;
     type LaunchPeriod = ConstU64<2>;
     type VotingPeriod = ConstU64<2>;
     type VoteLockingPeriod = ConstU64<3>;
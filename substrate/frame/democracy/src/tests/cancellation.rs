@@ -29,7 +29,9 @@ fn cancel_referendum_should_work() {
             0,
         );
         assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+        assert!(ProposalTrackOf::<Test>::contains_key(r));
         assert_ok!(Democracy::cancel_referendum(Origin::root(), r.into()));
+        assert!(!ProposalTrackOf::<Test>::contains_key(r));
         assert_eq!(Democracy::lowest_unbaked(), 0);
 
         next_block();
@@ -56,6 +58,7 @@ fn cancel_queued_should_work() {
         fast_forward_to(4);
 
         assert!(pallet_scheduler::Agenda::<Test>::get(6)[0].is_some());
+        assert!(ProposalTrackOf::<Test>::contains_key(0));
 
         assert_noop!(
             Democracy::cancel_queued(Origin::root(), 1),
@@ -63,6 +66,7 @@ fn cancel_queued_should_work() {
         );
         assert_ok!(Democracy::cancel_queued(Origin::root(), 0));
         assert!(pallet_scheduler::Agenda::<Test>::get(6)[0].is_none());
+        assert!(!ProposalTrackOf::<Test>::contains_key(0));
     });
 }
 
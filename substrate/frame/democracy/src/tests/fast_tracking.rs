@@ -34,6 +34,7 @@ fn fast_track_referendum_works() {
         ));
         assert_noop!(Democracy::fast_track(Origin::signed(1), h, 3, 2), BadOrigin);
         assert_ok!(Democracy::fast_track(Origin::signed(5), h, 2, 0));
+        assert_eq!(ProposalTrackOf::<Test>::get(0), ProposalTrack::FastTrack);
         assert_eq!(
             Democracy::referendum_status(0),
             Ok(ReferendumStatus {
@@ -0,0 +1,45 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The storage migration tests.
+
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+#[test]
+fn migration_to_v2_seeds_total_locked_deposits() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::<Test>::put(Releases::V1);
+        LockedDeposits::<Test>::insert(10, DepositPaybackTarget::Provider(1), 5);
+        LockedDeposits::<Test>::insert(10, DepositPaybackTarget::Provider(2), 7);
+        LockedDeposits::<Test>::insert(
+            20,
+            DepositPaybackTarget::Beneficiary { from: 1, to: 3 },
+            11,
+        );
+        assert_eq!(Democracy::total_locked_deposits(), 0);
+
+        Democracy::on_runtime_upgrade();
+
+        assert_eq!(Democracy::total_locked_deposits(), 23);
+        assert_eq!(StorageVersion::<Test>::get(), Some(Releases::V2));
+
+        // Running the upgrade again must not double count the deposits.
+        Democracy::on_runtime_upgrade();
+        assert_eq!(Democracy::total_locked_deposits(), 23);
+    });
+}
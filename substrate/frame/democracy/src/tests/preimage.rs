@@ -120,6 +120,7 @@ fn preimage_deposit_should_be_locked_if_turnout_is_lower_than_required() {
                     .collect::<Vec<_>>(),
                     vec![(DepositPaybackTarget::Provider(6), 12)]
                 );
+                assert_eq!(Democracy::total_locked_deposits(), 12);
             }
         }
 
@@ -143,6 +144,7 @@ fn preimage_deposit_should_be_locked_if_turnout_is_lower_than_required() {
             .is_some());
 
         assert_eq!(LockedDeposits::<Test>::iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(Democracy::total_locked_deposits(), 0);
 
         assert_eq!(Balances::reserved_balance(6), 0);
         assert_eq!(Balances::free_balance(6), 60);
@@ -213,6 +215,7 @@ fn preimage_deposit_should_be_locked_if_turnout_is_lower_than_required_and_then_
                 .collect::<Vec<_>>(),
                 vec![(DepositPaybackTarget::Provider(6), 12)]
             );
+            assert_eq!(Democracy::total_locked_deposits(), 12);
         }
 
         // deposits are allowed to be paid from 7th block from the referendum
@@ -224,6 +227,7 @@ fn preimage_deposit_should_be_locked_if_turnout_is_lower_than_required_and_then_
         ));
 
         assert_eq!(LockedDeposits::<Test>::iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(Democracy::total_locked_deposits(), 0);
 
         assert_eq!(Balances::reserved_balance(6), 0);
         assert_eq!(Balances::free_balance(6), 60);
@@ -401,6 +405,62 @@ fn preimage_deposit_should_be_locked_if_turnout_wasnt_enough() {
     });
 }
 
+#[test]
+fn locked_deposit_can_be_claimed_by_beneficiary() {
+    new_test_ext_execute_with_cond(|operational| {
+        DepositLockStrategy::set(DepositLockConfig::new(2, 3, 20));
+
+        PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+        assert_ok!(if operational {
+            Democracy::note_preimage_operational(Origin::signed(6), set_balance_proposal(2))
+        } else {
+            Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2))
+        });
+
+        for _ in 0..4 {
+            next_block();
+        }
+        assert_ok!(Democracy::reap_preimage(
+            Origin::signed(5),
+            set_balance_proposal_hash(2),
+            u32::MAX
+        ));
+        let target_block = DepositLockStrategy::get().target_block_from_current();
+        assert_eq!(Democracy::total_locked_deposits(), 12);
+
+        assert_noop!(
+            Democracy::claim_locked_deposit(Origin::signed(5), target_block, 6),
+            Error::<Test>::DepositStillLocked
+        );
+
+        System::set_block_number(target_block);
+
+        assert_noop!(
+            Democracy::claim_locked_deposit(Origin::signed(4), target_block, 6),
+            Error::<Test>::LockedDepositMissing
+        );
+        assert_noop!(
+            Democracy::claim_locked_deposit(Origin::signed(5), target_block, 4),
+            Error::<Test>::LockedDepositMissing
+        );
+        assert_ok!(Democracy::claim_locked_deposit(
+            Origin::signed(5),
+            target_block,
+            6
+        ));
+        assert_noop!(
+            Democracy::claim_locked_deposit(Origin::signed(5), target_block, 6),
+            Error::<Test>::LockedDepositMissing
+        );
+
+        assert_eq!(LockedDeposits::<Test>::iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(Democracy::total_locked_deposits(), 0);
+        assert_eq!(Balances::reserved_balance(6), 0);
+        assert_eq!(Balances::free_balance(6), 48);
+        assert_eq!(Balances::free_balance(5), 62);
+    });
+}
+
 #[test]
 fn noting_imminent_preimage_for_free_should_work() {
     new_test_ext_execute_with_cond(|operational| {
@@ -568,3 +628,74 @@ fn deposit_lock_config() {
         );
     })
 }
+
+#[test]
+fn set_deposit_lock_config_should_work() {
+    new_test_ext().execute_with(|| {
+        let config = DepositLockConfig::<Test>::new(5, 1, 30);
+
+        assert_eq!(Democracy::deposit_lock_config(), DepositLockStrategy::get());
+        assert_noop!(
+            Democracy::set_deposit_lock_config(Origin::signed(1), config.clone()),
+            DispatchError::BadOrigin
+        );
+        assert!(DepositLockConfig::<Test>::decode(
+            &mut &(0u32, 1u32, 30u64, TrackLockMultipliers::default()).encode()[..]
+        )
+        .is_err());
+
+        assert_ok!(Democracy::set_deposit_lock_config(
+            Origin::root(),
+            config.clone()
+        ));
+        System::assert_last_event(Event::Democracy(crate::Event::DepositLockConfigUpdated {
+            config: config.clone(),
+        }));
+        assert_eq!(Democracy::deposit_lock_config(), config);
+        assert_ne!(Democracy::deposit_lock_config(), DepositLockStrategy::get());
+        assert_eq!(
+            Democracy::deposit_lock_config().target_block_from_current(),
+            10u64
+        );
+    });
+}
+
+#[test]
+fn deposit_lock_config_for_track() {
+    new_test_ext().execute_with(|| {
+        let config = DepositLockConfig::<Test>::new(2, 4, 0).with_track_multipliers(
+            TrackLockMultipliers::new(
+                Percent::from_percent(100),
+                Percent::from_percent(50),
+                Percent::from_percent(0),
+            ),
+        );
+
+        assert_eq!(
+            config
+                .clone()
+                .for_track(ProposalTrack::Public)
+                .target_block_from(1),
+            10u64
+        );
+        assert_eq!(
+            config
+                .clone()
+                .for_track(ProposalTrack::External)
+                .target_block_from(1),
+            6u64
+        );
+        assert_eq!(
+            config
+                .for_track(ProposalTrack::FastTrack)
+                .target_block_from(1),
+            2u64
+        );
+        assert_eq!(
+            DepositLockConfig::<Test>::new(2, 4, 0)
+                .for_track(ProposalTrack::FastTrack)
+                .target_block_from(1),
+            10u64
+        );
+    });
+}
@@ -0,0 +1,126 @@
+use codec::Codec;
+use core::fmt::Debug;
+use jsonrpsee::{
+    core::{async_trait, Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::{error::CallError, ErrorObject},
+};
+pub use pallet_elections_phragmen::runtime_api::ElectionsPhragmenApi as ElectionsPhragmenRuntimeApi;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::{NumberFor, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+
+#[rpc(server, client)]
+pub trait ElectionsPhragmenApi<BlockHash, AccountId, Balance, Number> {
+    /// Returns the number of the block at which the next election will happen.
+    #[method(name = "elections_phragmen_next_election_block")]
+    async fn next_election_block(&self, at: Option<BlockHash>) -> RpcResult<Option<Number>>;
+
+    /// Returns candidates which passed the candidacy delay along with their deposits.
+    #[method(name = "elections_phragmen_active_candidates")]
+    async fn active_candidates(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AccountId, Balance)>>;
+
+    /// Returns candidates which didn't pass the candidacy delay yet along with their deposits
+    /// and the blocks starting from which they become allowed for election.
+    #[method(name = "elections_phragmen_pending_candidates_with_activation_block")]
+    async fn pending_candidates_with_activation_block(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AccountId, Balance, Number)>>;
+}
+
+#[derive(Debug, Clone)]
+struct RuntimeError<T>(T);
+
+impl<T: Debug> From<RuntimeError<T>> for JsonRpseeError {
+    fn from(error: RuntimeError<T>) -> Self {
+        let data = format!("{:?}", error);
+
+        JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            1,
+            "Runtime error",
+            Some(data),
+        )))
+    }
+}
+
+/// A struct that implements the [`ElectionsPhragmenApi`].
+pub struct ElectionsPhragmen<C, P> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> ElectionsPhragmen<C, P> {
+    /// Create new `ElectionsPhragmen` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        ElectionsPhragmen {
+            client,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Returns `BlockId` of the supplied block hash or the best block if the hash is not supplied.
+    fn block_id<Block>(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block>
+    where
+        Block: BlockT,
+        C: HeaderBackend<Block>,
+    {
+        BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+    }
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance>
+    ElectionsPhragmenApiServer<<Block as BlockT>::Hash, AccountId, Balance, NumberFor<Block>>
+    for ElectionsPhragmen<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: ElectionsPhragmenRuntimeApi<Block, AccountId, Balance, NumberFor<Block>>,
+{
+    async fn next_election_block(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<NumberFor<Block>>> {
+        let at = self.block_id(at);
+
+        self.client
+            .runtime_api()
+            .next_election_block(&at)
+            .map_err(RuntimeError)
+            .map_err(Into::into)
+    }
+
+    async fn active_candidates(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AccountId, Balance)>> {
+        let at = self.block_id(at);
+
+        self.client
+            .runtime_api()
+            .active_candidates(&at)
+            .map_err(RuntimeError)
+            .map_err(Into::into)
+    }
+
+    async fn pending_candidates_with_activation_block(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AccountId, Balance, NumberFor<Block>)>> {
+        let at = self.block_id(at);
+
+        self.client
+            .runtime_api()
+            .pending_candidates_with_activation_block(&at)
+            .map_err(RuntimeError)
+            .map_err(Into::into)
+    }
+}
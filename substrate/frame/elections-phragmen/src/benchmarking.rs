@@ -507,6 +507,25 @@ benchmarks! {
         }
     }
 
+    unreserve_locked_deposits {
+        // number of the locked deposits to unreserve.
+        let d in 1 .. Elections::<T>::max_locked_deposits_per_block();
+
+        let block_number = frame_system::Pallet::<T>::block_number();
+        for i in 0 .. d {
+            let candidate = endowed_account::<T>("candidate", i);
+            let deposit = T::CandidacyBond::get();
+            T::Currency::reserve(&candidate, deposit)?;
+            <LockedDeposits<T>>::insert(block_number, candidate, deposit);
+        }
+
+        let caller = endowed_account::<T>("caller", 0);
+        whitelist!(caller);
+    }: _(RawOrigin::Signed(caller), block_number)
+    verify {
+        assert_eq!(<LockedDeposits<T>>::iter_prefix(block_number).count(), 0);
+    }
+
     impl_benchmark_test_suite!(
         Elections,
         crate::tests::ExtBuilder::default().desired_members(13).desired_runners_up(7),
@@ -100,23 +100,25 @@
 
 use codec::{Decode, Encode};
 use frame_support::{
+    dispatch::PostDispatchInfo,
     traits::{
         defensive_prelude::*, ChangeMembers, Contains, ContainsLengthBound, Currency,
         CurrencyToVote, Get, InitializeMembers, LockIdentifier, LockableCurrency, OnUnbalanced,
         ReservableCurrency, SortedMembers, WithdrawReasons,
     },
-    weights::Weight,
+    weights::{Pays, Weight},
 };
 use scale_info::TypeInfo;
 use sp_npos_elections::{ElectionResult, ExtendedBalance};
 use sp_runtime::{
-    traits::{CheckedSub, Saturating, StaticLookup, Zero},
-    DispatchError, Perbill, RuntimeDebug,
+    traits::{One, Saturating, StaticLookup, Zero},
+    DispatchError, DispatchErrorWithPostInfo, Perbill, RuntimeDebug,
 };
 use sp_std::{cmp::Ordering, prelude::*};
 use utils::*;
 
 mod benchmarking;
+pub mod runtime_api;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -131,6 +133,8 @@ type BalanceOf<T> =
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
+type LoserCandidateSlasher<T> =
+    ReservedBalanceSlasher<<T as Config>::Currency, <T as Config>::LoserCandidate>;
 
 /// An indication that the renouncing account currently has which of the below roles.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -243,6 +247,9 @@ pub mod pallet {
         type CandidacyBond: Get<BalanceOf<Self>>;
 
         /// How many blocks are required for candidates before they become allowed for election.
+        /// The delay is multiplied by `1 + offences` where `offences` is the amount of times
+        /// the candidate was slashed, either as a losing candidate or on a forceful removal with
+        /// a slash (see `CandidateHistory`). Removals without a slash aren't counted.
         #[pallet::constant]
         type CandidacyDelay: Get<Self::BlockNumber>;
 
@@ -260,6 +267,11 @@ pub mod pallet {
         /// Handler for the unbalanced reduction when a candidate has lost (and is not a runner-up)
         type LoserCandidate: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+        /// If set, the deposit of a candidate who has lost (and is not a runner-up) will be locked for
+        /// the given amount of blocks and then returned instead of being slashed.
+        #[pallet::constant]
+        type LoserCandidateDepositLock: Get<Option<Self::BlockNumber>>;
+
         /// Handler for the unbalanced reduction when a member has been kicked.
         type KickedMember: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
@@ -309,11 +321,14 @@ pub mod pallet {
         /// Checks if an election needs to happen or not.
         fn on_initialize(n: T::BlockNumber) -> Weight {
             let term_duration = T::TermDuration::get();
-            if !term_duration.is_zero() && (n % term_duration).is_zero() {
-                Self::do_phragmen()
-            } else {
-                Weight::zero()
-            }
+            let unreserve_weight = Self::unreserve_locked_deposits_(n);
+
+            unreserve_weight
+                + if !term_duration.is_zero() && (n % term_duration).is_zero() {
+                    Self::do_phragmen()
+                } else {
+                    Weight::zero()
+                }
         }
 
         fn on_runtime_upgrade() -> Weight {
@@ -640,6 +655,37 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Unreserves locked deposits of the losing candidates.
+        /// Can be called from any account with the block number lower or equal to the current.
+        #[pallet::weight(T::WeightInfo::unreserve_locked_deposits(
+            Pallet::<T>::max_locked_deposits_per_block()
+        ))]
+        pub fn unreserve_locked_deposits(
+            origin: OriginFor<T>,
+            block_number: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+
+            let base_weight = T::DbWeight::get().reads(1);
+            ensure!(
+                block_number <= frame_system::Pallet::<T>::block_number(),
+                DispatchErrorWithPostInfo {
+                    post_info: PostDispatchInfo {
+                        actual_weight: Some(base_weight),
+                        pays_fee: Pays::Yes
+                    },
+                    error: Error::<T>::DepositStillLocked.into()
+                }
+            );
+            let payback_weight = Self::unreserve_locked_deposits_(block_number);
+
+            Ok(PostDispatchInfo {
+                actual_weight: Some(payback_weight + base_weight),
+                pays_fee: Pays::Yes,
+            }
+            .into())
+        }
     }
 
     #[pallet::event]
@@ -680,46 +726,81 @@ pub mod pallet {
             seat_holder: <T as frame_system::Config>::AccountId,
             amount: BalanceOf<T>,
         },
+        /// A candidate's deposit was locked until the given block due to failing to obtain a seat
+        /// as member or runner-up.
+        CandidateDepositLocked {
+            candidate: <T as frame_system::Config>::AccountId,
+            amount: BalanceOf<T>,
+            until: T::BlockNumber,
+        },
+        /// Locked deposit unreserved to the account.
+        LockedDepositUnreserved {
+            recipient: <T as frame_system::Config>::AccountId,
+            deposit: BalanceOf<T>,
+        },
     }
 
+    /// Variant indices are a part of the public API used by the tooling decoding `ModuleError`s,
+    /// so existing variants must never be reordered or reindexed, and new variants must get the next unused index.
     #[pallet::error]
     pub enum Error<T> {
         /// Cannot vote when no candidates or members exist.
+        #[codec(index = 0)]
         UnableToVote,
         /// Must vote for at least one candidate.
+        #[codec(index = 1)]
         NoVotes,
         /// Cannot vote more than candidates.
+        #[codec(index = 2)]
         TooManyVotes,
         /// Cannot vote more than maximum allowed.
+        #[codec(index = 3)]
         MaximumVotesExceeded,
         /// Cannot vote with stake less than minimum balance.
+        #[codec(index = 4)]
         LowBalance,
         /// Voter can not pay voting bond.
+        #[codec(index = 5)]
         UnableToPayBond,
         /// Must be a voter.
+        #[codec(index = 6)]
         MustBeVoter,
         /// Duplicated candidate submission.
+        #[codec(index = 7)]
         DuplicatedCandidate,
         /// Too many candidates have been created.
+        #[codec(index = 8)]
         TooManyCandidates,
         /// Member cannot re-submit candidacy.
+        #[codec(index = 9)]
         MemberSubmit,
         /// Runner cannot re-submit candidacy.
+        #[codec(index = 10)]
         RunnerUpSubmit,
         /// Candidate does not have enough funds.
+        #[codec(index = 11)]
         InsufficientCandidateFunds,
         /// Not a member.
+        #[codec(index = 12)]
         NotMember,
         /// The provided count of number of candidates is incorrect.
+        #[codec(index = 13)]
         InvalidWitnessData,
         /// The provided count of number of votes is incorrect.
+        #[codec(index = 14)]
         InvalidVoteCount,
         /// The renouncing origin presented a wrong `Renouncing` parameter.
+        #[codec(index = 15)]
         InvalidRenouncing,
         /// Prediction regarding replacement after member removal is wrong.
+        #[codec(index = 16)]
         InvalidReplacement,
         /// Supplied candidacy must have an identity verified.
+        #[codec(index = 17)]
         CandidateMustHaveVerifiedIdentity,
+        /// Deposits for the given block number are still locked.
+        #[codec(index = 18)]
+        DepositStillLocked,
     }
 
     /// The current elected members.
@@ -763,6 +844,30 @@ pub mod pallet {
     pub type Voting<T: Config> =
         StorageMap<_, Twox64Concat, T::AccountId, Voter<T::AccountId, BalanceOf<T>>, ValueQuery>;
 
+    /// Deposits of the losing candidates locked prior to the supplied block number.
+    ///
+    /// TWOX-NOTE: SAFE as `AccountId` is a crypto hash.
+    #[pallet::storage]
+    #[pallet::getter(fn locked_deposits)]
+    pub type LockedDeposits<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Twox64Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        OptionQuery,
+    >;
+
+    /// Amount of times the account was slashed as a losing candidate or slashed while being
+    /// forcefully removed from the seat. Used to scale the candidacy delay.
+    ///
+    /// TWOX-NOTE: SAFE as `AccountId` is a crypto hash.
+    #[pallet::storage]
+    #[pallet::getter(fn candidate_history)]
+    pub type CandidateHistory<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn version)]
     pub type Version<T: Config> = StorageValue<_, PalletStorageVersion, ValueQuery>;
@@ -879,9 +984,14 @@ impl<T: Config> Pallet<T> {
 
             // slash or unreserve
             if slash {
-                let (imbalance, _remainder) = T::Currency::slash_reserved(who, removed.deposit);
+                let _remainder = LoserCandidateSlasher::<T>::slash_reserved(
+                    who,
+                    removed.deposit,
+                    (),
+                    SlashBeneficiary::Default,
+                );
                 debug_assert!(_remainder.is_zero());
-                T::LoserCandidate::on_unbalanced(imbalance);
+                Self::note_offence(who);
                 Self::deposit_event(Event::SeatHolderSlashed {
                     seat_holder: who.clone(),
                     amount: removed.deposit,
@@ -1012,6 +1122,126 @@ impl<T: Config> Pallet<T> {
         debug_assert!(_remainder.is_zero());
     }
 
+    /// Either locks or slashes the deposit of the losing candidate depending on the `LoserCandidateDepositLock`.
+    fn handle_loser_deposit(candidate: &T::AccountId, deposit: BalanceOf<T>) {
+        if let Some(lock_for) = T::LoserCandidateDepositLock::get() {
+            let until = <frame_system::Pallet<T>>::block_number()
+                .saturating_add(lock_for)
+                .saturating_add(One::one());
+
+            <LockedDeposits<T>>::mutate(until, candidate, |locked| {
+                *locked = Some(locked.unwrap_or_default().saturating_add(deposit))
+            });
+            Self::deposit_event(Event::CandidateDepositLocked {
+                candidate: candidate.clone(),
+                amount: deposit,
+                until,
+            });
+        } else {
+            LoserCandidateSlasher::<T>::slash_reserved(
+                candidate,
+                deposit,
+                (),
+                SlashBeneficiary::Default,
+            );
+            Self::note_offence(candidate);
+            Self::deposit_event(Event::CandidateSlashed {
+                candidate: candidate.clone(),
+                amount: deposit,
+            });
+        }
+    }
+
+    /// Returns the number of the block at which the next election will happen.
+    /// Returns `None` if the pallet is in passive mode (`TermDuration` is zero).
+    pub fn next_election_block() -> Option<T::BlockNumber> {
+        let term_duration = T::TermDuration::get();
+        if term_duration.is_zero() {
+            return None;
+        }
+
+        let now = <frame_system::Pallet<T>>::block_number();
+        let next_term_start = now
+            .saturating_sub(now % term_duration)
+            .saturating_add(term_duration);
+
+        Some(next_term_start)
+    }
+
+    /// Returns the block starting from which the candidate submitted at `added_at_block`
+    /// becomes allowed for election.
+    pub fn candidate_activation_block(
+        candidate: &T::AccountId,
+        added_at_block: T::BlockNumber,
+    ) -> T::BlockNumber {
+        added_at_block.saturating_add(Self::candidacy_delay_of(candidate))
+    }
+
+    /// Returns candidates which passed the candidacy delay along with their deposits.
+    pub fn active_candidates() -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        Self::candidates()
+            .into_iter()
+            .filter(|(candidate, _, added_at_block)| {
+                Self::candidate_activation_block(candidate, *added_at_block) <= now
+            })
+            .map(|(candidate, deposit, _)| (candidate, deposit))
+            .collect()
+    }
+
+    /// Returns candidates which didn't pass the candidacy delay yet along with their deposits
+    /// and the blocks starting from which they become allowed for election.
+    pub fn pending_candidates_with_activation_block(
+    ) -> Vec<(T::AccountId, BalanceOf<T>, T::BlockNumber)> {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        Self::candidates()
+            .into_iter()
+            .filter_map(|(candidate, deposit, added_at_block)| {
+                let activation_block = Self::candidate_activation_block(&candidate, added_at_block);
+
+                (activation_block > now).then_some((candidate, deposit, activation_block))
+            })
+            .collect()
+    }
+
+    /// Records an offence (slash of a losing candidate or of a forcefully removed member)
+    /// to the history of the given candidate.
+    fn note_offence(candidate: &T::AccountId) {
+        <CandidateHistory<T>>::mutate(candidate, |offences| *offences = offences.saturating_add(1));
+    }
+
+    /// Returns the candidacy delay applied to the given account.
+    /// `CandidacyDelay` is multiplied by `1 + offences` recorded in the `CandidateHistory`.
+    pub fn candidacy_delay_of(candidate: &T::AccountId) -> T::BlockNumber {
+        let multiplier = <CandidateHistory<T>>::get(candidate).saturating_add(1);
+
+        T::CandidacyDelay::get().saturating_mul(multiplier.into())
+    }
+
+    /// Unreserves deposits of the losing candidates locked prior to the supplied block number.
+    pub fn unreserve_locked_deposits_(block_number: T::BlockNumber) -> Weight {
+        let mut unreserved = 0u32;
+
+        for (recipient, deposit) in <LockedDeposits<T>>::drain_prefix(block_number) {
+            let _remainder = T::Currency::unreserve(&recipient, deposit);
+            Self::deposit_event(Event::LockedDepositUnreserved { recipient, deposit });
+
+            unreserved = unreserved.saturating_add(1);
+        }
+
+        T::WeightInfo::unreserve_locked_deposits(unreserved)
+    }
+
+    /// Maximum number of deposits locked until the same block: every candidate, member and
+    /// runner-up of a single election can lose their seat.
+    fn max_locked_deposits_per_block() -> u32 {
+        T::MaxCandidates::get()
+            .saturating_add(T::DesiredMembers::get())
+            .saturating_add(T::DesiredRunnersUp::get())
+    }
+
     /// Run the phragmen election with all required side processes and state updates, if election
     /// succeeds. Else, it will emit an `ElectionError` event.
     ///
@@ -1020,19 +1250,14 @@ impl<T: Config> Pallet<T> {
         let desired_seats = T::DesiredMembers::get() as usize;
         let desired_runners_up = T::DesiredRunnersUp::get() as usize;
         let num_to_elect = desired_runners_up + desired_seats;
-        let max_candidate_submission_block =
-            <frame_system::Pallet<T>>::block_number().checked_sub(&T::CandidacyDelay::get());
-
-        let candidates_and_deposit: Vec<_> = max_candidate_submission_block
-            .map(|max_added_at_block| {
-                Self::candidates().into_iter().filter_map(
-                    move |(candidate, deposit, added_at_block)| {
-                        (added_at_block <= max_added_at_block).then_some((candidate, deposit))
-                    },
-                )
-            })
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let candidates_and_deposit: Vec<_> = Self::candidates()
             .into_iter()
-            .flatten()
+            .filter_map(|(candidate, deposit, added_at_block)| {
+                (Self::candidate_activation_block(&candidate, added_at_block) <= now)
+                    .then_some((candidate, deposit))
+            })
             // add all the previous members and runners-up as candidates as well.
             .chain(Self::implicit_candidates_with_deposit())
             .collect();
@@ -1196,12 +1421,7 @@ impl<T: Config> Pallet<T> {
                             if new_members_ids_sorted.binary_search(c).is_err()
                                 && new_runners_up_ids_sorted.binary_search(c).is_err()
                             {
-                                let (imbalance, _) = T::Currency::slash_reserved(c, *d);
-                                T::LoserCandidate::on_unbalanced(imbalance);
-                                Self::deposit_event(Event::CandidateSlashed {
-                                    candidate: c.clone(),
-                                    amount: *d,
-                                });
+                                Self::handle_loser_deposit(c, *d);
                             }
                         });
 
@@ -1321,6 +1541,7 @@ mod tests {
         pallet_prelude::OptionQuery,
         parameter_types, storage_alias,
         traits::{ConstU32, ConstU64, OnInitialize},
+        weights::RuntimeDbWeight,
         Twox64Concat,
     };
     use frame_system::{ensure_signed, RawOrigin};
@@ -1386,6 +1607,7 @@ mod tests {
         pub static TermDuration: u64 = 5;
         pub static Members: Vec<u64> = vec![];
         pub static Prime: Option<u64> = None;
+        pub static LoserCandidateDepositLock: Option<u64> = None;
     }
 
     pub struct TestChangeMembers;
@@ -1547,6 +1769,7 @@ mod tests {
         type DesiredMembers = DesiredMembers;
         type DesiredRunnersUp = DesiredRunnersUp;
         type LoserCandidate = ();
+        type LoserCandidateDepositLock = LoserCandidateDepositLock;
         type KickedMember = ();
         type WeightInfo = ();
         type MaxVoters = PhragmenMaxVoters;
@@ -1608,6 +1831,10 @@ mod tests {
             DESIRED_MEMBERS.with(|m| *m.borrow_mut() = count);
             self
         }
+        pub fn loser_candidate_deposit_lock(self, lock_for: Option<u64>) -> Self {
+            LOSER_CANDIDATE_DEPOSIT_LOCK.with(|v| *v.borrow_mut() = lock_for);
+            self
+        }
         pub fn balance_factor(mut self, factor: u64) -> Self {
             self.balance_factor = factor;
             self
@@ -2712,6 +2939,125 @@ mod tests {
         });
     }
 
+    #[test]
+    fn candidacy_delay_scales_with_history() {
+        ExtBuilder::default().build_and_execute(|| {
+            CandidateHistory::<Test>::insert(4, 1);
+            assert_eq!(Elections::candidacy_delay_of(&4), 8);
+            assert_eq!(Elections::candidacy_delay_of(&5), 4);
+
+            System::set_block_number(2);
+            assert_ok!(approve_and_submit_candidacy(Origin::signed(4)));
+            assert_ok!(vote(Origin::signed(4), vec![4], 40));
+
+            System::set_block_number(6);
+            Elections::do_phragmen();
+
+            assert_eq!(Elections::members(), vec![]);
+            assert_eq!(
+                System::events().iter().last().unwrap().event,
+                Event::Elections(super::Event::EmptyTerm)
+            );
+
+            System::set_block_number(10);
+            Elections::do_phragmen();
+
+            assert_eq!(
+                Elections::members(),
+                vec![SeatHolder {
+                    who: 4,
+                    stake: 35,
+                    deposit: 3
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn removed_member_with_slash_is_recorded_in_history() {
+        ExtBuilder::default().build_and_execute(|| {
+            assert_ok!(approve_and_submit_candidacy(Origin::signed(5)));
+            assert_ok!(vote(Origin::signed(5), vec![5], 50));
+
+            System::set_block_number(5);
+            Elections::on_initialize(System::block_number());
+            assert_eq!(members_ids(), vec![5]);
+
+            assert_ok!(Elections::remove_member(Origin::root(), 5, false, false));
+            assert_eq!(Elections::candidate_history(5), 0);
+
+            assert_ok!(approve_and_submit_candidacy(Origin::signed(5)));
+            System::set_block_number(10);
+            Elections::on_initialize(System::block_number());
+            assert_eq!(members_ids(), vec![5]);
+
+            assert_ok!(Elections::remove_member(Origin::root(), 5, true, false));
+            assert_eq!(Elections::candidate_history(5), 1);
+            assert_eq!(Elections::candidacy_delay_of(&5), 8);
+        });
+    }
+
+    #[test]
+    fn error_indices_are_stable() {
+        let errors = [
+            Error::<Test>::UnableToVote,
+            Error::<Test>::NoVotes,
+            Error::<Test>::TooManyVotes,
+            Error::<Test>::MaximumVotesExceeded,
+            Error::<Test>::LowBalance,
+            Error::<Test>::UnableToPayBond,
+            Error::<Test>::MustBeVoter,
+            Error::<Test>::DuplicatedCandidate,
+            Error::<Test>::TooManyCandidates,
+            Error::<Test>::MemberSubmit,
+            Error::<Test>::RunnerUpSubmit,
+            Error::<Test>::InsufficientCandidateFunds,
+            Error::<Test>::NotMember,
+            Error::<Test>::InvalidWitnessData,
+            Error::<Test>::InvalidVoteCount,
+            Error::<Test>::InvalidRenouncing,
+            Error::<Test>::InvalidReplacement,
+            Error::<Test>::CandidateMustHaveVerifiedIdentity,
+            Error::<Test>::DepositStillLocked,
+        ];
+
+        for (idx, error) in errors.into_iter().enumerate() {
+            assert_eq!(error.encode(), vec![idx as u8], "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn runtime_api_queries_work() {
+        ExtBuilder::default().build_and_execute(|| {
+            System::set_block_number(1);
+            assert_eq!(Elections::next_election_block(), Some(5));
+
+            assert_ok!(approve_and_submit_candidacy(Origin::signed(5)));
+            System::set_block_number(3);
+            assert_ok!(approve_and_submit_candidacy(Origin::signed(4)));
+
+            assert_eq!(Elections::active_candidates(), vec![]);
+            assert_eq!(
+                Elections::pending_candidates_with_activation_block(),
+                vec![(4, 3, 7), (5, 3, 5)]
+            );
+
+            System::set_block_number(5);
+            assert_eq!(Elections::next_election_block(), Some(10));
+            assert_eq!(Elections::active_candidates(), vec![(5, 3)]);
+            assert_eq!(
+                Elections::pending_candidates_with_activation_block(),
+                vec![(4, 3, 7)]
+            );
+        });
+
+        ExtBuilder::default()
+            .term_duration(0)
+            .build_and_execute(|| {
+                assert_eq!(Elections::next_election_block(), None);
+            });
+    }
+
     #[test]
     fn defunct_voter_will_be_counted() {
         ExtBuilder::default().build_and_execute(|| {
@@ -2913,8 +3259,85 @@ mod tests {
 
             // winner
             assert_eq!(balances(&5), (47, 3));
+            assert_eq!(Elections::candidate_history(5), 0);
             // loser
             assert_eq!(balances(&3), (27, 0));
+            assert_eq!(Elections::candidate_history(3), 1);
+        });
+    }
+
+    #[test]
+    fn candidates_deposit_is_locked_when_outgoing() {
+        ExtBuilder::default()
+            .loser_candidate_deposit_lock(Some(3))
+            .build_and_execute(|| {
+                assert_ok!(approve_and_submit_candidacy(Origin::signed(5)));
+                assert_ok!(approve_and_submit_candidacy(Origin::signed(3)));
+
+                assert_ok!(vote(Origin::signed(4), vec![5], 40));
+
+                System::set_block_number(5);
+                Elections::on_initialize(System::block_number());
+
+                assert_eq!(members_ids(), vec![5]);
+                System::assert_has_event(Event::Elections(super::Event::CandidateDepositLocked {
+                    candidate: 3,
+                    amount: 3,
+                    until: 9,
+                }));
+
+                // winner
+                assert_eq!(balances(&5), (47, 3));
+                // loser
+                assert_eq!(balances(&3), (27, 3));
+                assert_eq!(Elections::locked_deposits(9, 3), Some(3));
+                assert_eq!(Elections::candidate_history(3), 0);
+
+                assert_noop!(
+                    Elections::unreserve_locked_deposits(Origin::signed(1), 9),
+                    DispatchErrorWithPostInfo {
+                        post_info: PostDispatchInfo {
+                            actual_weight: Some(
+                                <<Test as frame_system::Config>::DbWeight as Get<
+                                    RuntimeDbWeight,
+                                >>::get()
+                                .reads(1)
+                            ),
+                            pays_fee: Pays::Yes
+                        },
+                        error: Error::<Test>::DepositStillLocked.into()
+                    }
+                );
+
+                for block_number in 6..9 {
+                    System::set_block_number(block_number);
+                    Elections::on_initialize(System::block_number());
+
+                    assert_eq!(balances(&3), (27, 3));
+                }
+
+                System::set_block_number(9);
+                Elections::on_initialize(System::block_number());
+
+                assert_eq!(balances(&3), (30, 0));
+                assert_eq!(Elections::locked_deposits(9, 3), None);
+                System::assert_has_event(Event::Elections(super::Event::LockedDepositUnreserved {
+                    recipient: 3,
+                    deposit: 3,
+                }));
+            });
+    }
+
+    #[test]
+    fn locked_deposits_can_be_unreserved_manually() {
+        ExtBuilder::default().build_and_execute(|| {
+            assert_ok!(Balances::reserve(&3, 3));
+            LockedDeposits::<Test>::insert(1, 3, 3);
+            assert_eq!(balances(&3), (27, 3));
+
+            assert_ok!(Elections::unreserve_locked_deposits(Origin::signed(1), 1));
+            assert_eq!(balances(&3), (30, 0));
+            assert_eq!(Elections::locked_deposits(1, 3), None);
         });
     }
 
@@ -0,0 +1,16 @@
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    pub trait ElectionsPhragmenApi<AccountId: Codec, Balance: Codec, BlockNumber: Codec> {
+        /// Returns the number of the block at which the next election will happen.
+        fn next_election_block() -> Option<BlockNumber>;
+
+        /// Returns candidates which passed the candidacy delay along with their deposits.
+        fn active_candidates() -> Vec<(AccountId, Balance)>;
+
+        /// Returns candidates which didn't pass the candidacy delay yet along with their deposits
+        /// and the blocks starting from which they become allowed for election.
+        fn pending_candidates_with_activation_block() -> Vec<(AccountId, Balance, BlockNumber)>;
+    }
+}
@@ -42,6 +42,7 @@ pub trait WeightInfo {
     fn election_phragmen(c: u32, v: u32, e: u32) -> Weight;
     fn election_phragmen_c_e(c: u32, e: u32) -> Weight;
     fn election_phragmen_v(v: u32) -> Weight;
+    fn unreserve_locked_deposits(d: u32) -> Weight;
 }
 
 /// Weights for pallet_elections_phragmen using the Substrate node and recommended hardware.
@@ -132,6 +133,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads((1 as u64).saturating_mul(v as u64)))
             .saturating_add(T::DbWeight::get().writes(17))
     }
+    // Placeholder: not produced by the benchmark CLI yet, the DB accesses are counted by hand.
+    fn unreserve_locked_deposits(d: u32) -> Weight {
+        Weight::from_ref_time(9_418_000)
+            .saturating_add(Weight::from_ref_time(18_906_000).saturating_mul(d as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(d as u64)))
+            .saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(d as u64)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -221,4 +230,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads((1 as u64).saturating_mul(v as u64)))
             .saturating_add(RocksDbWeight::get().writes(17))
     }
+    // Placeholder: not produced by the benchmark CLI yet, the DB accesses are counted by hand.
+    fn unreserve_locked_deposits(d: u32) -> Weight {
+        Weight::from_ref_time(9_418_000)
+            .saturating_add(Weight::from_ref_time(18_906_000).saturating_mul(d as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(d as u64)))
+            .saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(d as u64)))
+    }
 }
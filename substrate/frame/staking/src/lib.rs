@@ -295,6 +295,7 @@ mod tests;
 pub mod inflation;
 pub mod migrations;
 pub mod slashing;
+pub mod usd_payout;
 pub mod weights;
 
 mod pallet;
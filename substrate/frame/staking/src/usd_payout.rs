@@ -0,0 +1,181 @@
+//! Example integration with the price feed: era payout denominated in USD.
+//!
+//! [`UsdEraPayout`] converts a USD-denominated target payout into the native token at payout time using
+//! the latest price provided by `PriceProvider`. If the price is missing, stale or can't be used for the
+//! conversion, the payout is computed by the fallback `EraPayout` instead, so the stakers get rewarded
+//! even while the feed is down. Can be used as a template by the other pallets consuming the feed for economics.
+//!
+//! ```ignore
+//! parameter_types! {
+//!     pub const DockSym: &'static str = "DOCK";
+//!     pub const UsdSym: &'static str = "USD";
+//!     // 10_000 USD expressed with the 6 decimals of the native token.
+//!     pub const UsdPayoutPerEra: Balance = 10_000 * 1_000_000;
+//!     // One day of 6 seconds blocks.
+//!     pub const MaxPriceAge: BlockNumber = 14_400;
+//! }
+//!
+//! impl pallet_staking::Config for Runtime {
+//!     type EraPayout = UsdEraPayout<
+//!         Runtime,
+//!         PriceFeed,
+//!         StaticCurrencySymbolPair<DockSym, UsdSym>,
+//!         UsdPayoutPerEra,
+//!         MaxPriceAge,
+//!         System,
+//!         pallet_staking::ConvertCurve<RewardCurve>,
+//!     >;
+//!     // ...
+//! }
+//! ```
+
+use crate::EraPayout;
+use frame_support::traits::Get;
+use price_provider::{CurrencySymbolPair, PriceProvider, PriceRecord};
+use sp_runtime::traits::{
+    AtLeast32BitUnsigned, BlockNumberProvider, CheckedConversion, Saturating, Zero,
+};
+use sp_std::marker::PhantomData;
+
+/// Pays out `UsdPayout` worth of the native token per era to the validators.
+///
+/// - `PP` provides the price of 1 native token unit in USD for the `Pair`, e.g. `DOCK/USD`.
+/// - `UsdPayout` is the USD amount expressed with the decimals of the native token.
+/// - `MaxPriceAge` is the max age of the price in blocks. Older prices are considered stale.
+/// - `BlockNumbers` provides the current block number in the same units the feed records prices in,
+///   e.g. `frame_system::Pallet<Runtime>`, see the feed's `Config::BlockNumberProvider`.
+/// - `Fallback` computes the payout if the price is missing or stale.
+///
+/// Nothing is paid out as "the rest" while the price is used.
+pub struct UsdEraPayout<T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback>(
+    PhantomData<(T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback)>,
+);
+
+impl<T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback>
+    UsdEraPayout<T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback>
+where
+    T: frame_system::Config,
+    PP: PriceProvider<T>,
+    Pair: Get<CurrencySymbolPair<&'static str, &'static str>>,
+    MaxPriceAge: Get<T::BlockNumber>,
+    BlockNumbers: BlockNumberProvider<BlockNumber = T::BlockNumber>,
+{
+    /// Returns the latest price unless it's missing, stale or zero.
+    fn fresh_price() -> Option<PriceRecord<T::BlockNumber>> {
+        let record = PP::pair_price(Pair::get()).ok().flatten()?;
+        let age = BlockNumbers::current_block_number().saturating_sub(record.block_number());
+
+        (age <= MaxPriceAge::get() && !record.amount().is_zero()).then(|| record)
+    }
+
+    /// Converts the USD amount into the native token amount using the given price.
+    fn usd_to_native<Balance>(usd: Balance, price: &PriceRecord<T::BlockNumber>) -> Option<Balance>
+    where
+        Balance: AtLeast32BitUnsigned,
+    {
+        let usd: u128 = usd.checked_into()?;
+        let scale = 10u128.checked_pow(price.decimals())?;
+
+        usd.checked_mul(scale)?
+            .checked_div(price.amount().into())?
+            .checked_into()
+    }
+}
+
+impl<T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback, Balance> EraPayout<Balance>
+    for UsdEraPayout<T, PP, Pair, UsdPayout, MaxPriceAge, BlockNumbers, Fallback>
+where
+    T: frame_system::Config,
+    PP: PriceProvider<T>,
+    Pair: Get<CurrencySymbolPair<&'static str, &'static str>>,
+    UsdPayout: Get<Balance>,
+    MaxPriceAge: Get<T::BlockNumber>,
+    BlockNumbers: BlockNumberProvider<BlockNumber = T::BlockNumber>,
+    Fallback: EraPayout<Balance>,
+    Balance: AtLeast32BitUnsigned,
+{
+    fn era_payout(
+        total_staked: Balance,
+        total_issuance: Balance,
+        era_duration_millis: u64,
+    ) -> (Balance, Balance) {
+        Self::fresh_price()
+            .and_then(|price| Self::usd_to_native(UsdPayout::get(), &price))
+            .map(|payout| (payout, Zero::zero()))
+            .unwrap_or_else(|| {
+                log::warn!(
+                    target: crate::LOG_TARGET,
+                    "USD price of {:?} is unavailable, falling back to the default era payout",
+                    Pair::get()
+                );
+
+                Fallback::era_payout(total_staked, total_issuance, era_duration_millis)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{Balance, BlockNumber, Test};
+    use frame_support::parameter_types;
+
+    parameter_types! {
+        pub static Price: Option<PriceRecord<BlockNumber>> = None;
+        pub DockUsd: CurrencySymbolPair<&'static str, &'static str> = CurrencySymbolPair::new("DOCK", "USD");
+        pub const UsdPayout: Balance = 1_000_000;
+        pub const MaxPriceAge: BlockNumber = 10;
+    }
+
+    struct TestPriceProvider;
+
+    impl PriceProvider<Test> for TestPriceProvider {
+        type Error = ();
+
+        fn pair_price<From, To>(
+            _: CurrencySymbolPair<From, To>,
+        ) -> Result<Option<PriceRecord<BlockNumber>>, ()> {
+            Ok(Price::get())
+        }
+    }
+
+    struct FixedPayout;
+
+    impl EraPayout<Balance> for FixedPayout {
+        fn era_payout(_: Balance, _: Balance, _: u64) -> (Balance, Balance) {
+            (1, 2)
+        }
+    }
+
+    type Payout = UsdEraPayout<
+        Test,
+        TestPriceProvider,
+        DockUsd,
+        UsdPayout,
+        MaxPriceAge,
+        frame_system::Pallet<Test>,
+        FixedPayout,
+    >;
+
+    #[test]
+    fn converts_usd_payout() {
+        sp_io::TestExternalities::default().execute_with(|| {
+            frame_system::Pallet::<Test>::set_block_number(20);
+
+            // No price.
+            assert_eq!(Payout::era_payout(0, 0, 0), (1, 2));
+
+            // 1 DOCK = 0.25 USD.
+            Price::set(Some(PriceRecord::new(25, 2, 10)));
+            assert_eq!(Payout::era_payout(0, 0, 0), (4_000_000, 0));
+
+            // Stale price.
+            Price::set(Some(PriceRecord::new(25, 2, 9)));
+            assert_eq!(Payout::era_payout(0, 0, 0), (1, 2));
+
+            // Zero price.
+            Price::set(Some(PriceRecord::new(0, 2, 20)));
+            assert_eq!(Payout::era_payout(0, 0, 0), (1, 2));
+        });
+    }
+}